@@ -0,0 +1,23 @@
+use std::{path::Path, process::Command};
+
+// Expose the git commit of the build to the version printed by `scouty --version`
+fn main() {
+    // Builds out of a git checkout (e.g. from a tarball) or without git installed
+    // report the commit as unknown
+    let commit = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|commit| commit.trim().to_string())
+        .filter(|commit| !commit.is_empty())
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=SCOUTY_GIT_COMMIT={}", commit);
+    println!("cargo:rerun-if-changed=build.rs");
+    for path in [".git/HEAD", ".git/refs/heads"] {
+        if Path::new(path).exists() {
+            println!("cargo:rerun-if-changed={}", path);
+        }
+    }
+}
@@ -0,0 +1,44 @@
+#!/bin/bash
+#
+# > make a file executable
+# chmod +x ./_catch_up.sh
+#
+# > positional arguments:
+# 1st - Stash
+# 2nd - Identity
+# 3rd - First era missed
+# 4th - Last era missed
+# 5th - Number of eras missed in which the stash was active
+# 6th - Total era points of the eras missed
+# 7th - Estimated total reward (in plancks) of the eras missed, including nominators share
+# 8th - Eras missed in which the stash was slashed (e.g. 1200,1201 or - if none)
+# 9th - Number of eras missed not available anymore (e.g. beyond the history depth)
+#
+# The following arguments depend on exposed flags
+# 10th - Network name (--expose-network flag must be set)
+# 11th - Network token symbol (--expose-network flag must be set)
+# 12th - Network token decimals (--expose-network flag must be set)
+#
+# > Special character '!' controls message visibility on Matrix (Element)
+# Any message that starts with '!' will be sent to Matrix, to the user private room
+# 
+# echo "! This message will be sent to Matrix"
+# echo "This message will NOT be sent to Matrix"
+# 
+# ***** START *****
+#
+echo "! e.g. Write your own script here"
+echo "! --------------------------------"
+echo "! Positional arguments:"
+echo "! 1st - Stash -> $1" 
+echo "! 2nd - Identity -> $2"
+echo "! 3rd - First era missed -> $3"
+echo "! 4th - Last era missed -> $4"
+echo "! 5th - Eras active -> $5"
+echo "! 6th - Total era points -> $6"
+echo "! 7th - Estimated total reward -> $7"
+echo "! 8th - Eras slashed -> $8"
+echo "! 9th - Eras not available -> $9"
+echo "! -------------------------------"
+#
+# ***** END *****
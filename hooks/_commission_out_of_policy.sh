@@ -0,0 +1,40 @@
+#!/bin/bash
+#
+# > make a file executable
+# chmod +x ./_commission_out_of_policy.sh
+#
+# > positional arguments:
+# 1st - Stash
+# 2nd - Identity
+# 3rd - Era
+# 4th - Session
+# 5th - Commission (in parts per billion)
+# 6th - Minimum commission allowed, from the chain (in parts per billion)
+# 7th - Maximum commission allowed, from --max-commission (in parts per billion)
+#
+# The following arguments depend on exposed flags
+# 8th - Network name (--expose-network flag must be set)
+# 9th - Network token symbol (--expose-network flag must be set)
+# 10th - Network token decimals (--expose-network flag must be set)
+#
+# > Special character '!' controls message visibility on Matrix (Element)
+# Any message that starts with '!' will be sent to Matrix, to the user private room
+# 
+# echo "! This message will be sent to Matrix"
+# echo "This message will NOT be sent to Matrix"
+# 
+# ***** START *****
+#
+echo "! e.g. Write your own script here"
+echo "! --------------------------------"
+echo "! Positional arguments:"
+echo "! 1st - Stash -> $1" 
+echo "! 2nd - Identity -> $2"
+echo "! 3rd - Era -> $3"
+echo "! 4th - Session -> $4"
+echo "! 5th - Commission -> $5"
+echo "! 6th - Minimum commission allowed -> $6"
+echo "! 7th - Maximum commission allowed -> $7"
+echo "! -------------------------------"
+#
+# ***** END *****
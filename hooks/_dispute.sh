@@ -0,0 +1,40 @@
+#!/bin/bash
+#
+# > make a file executable
+# chmod +x ./_dispute.sh
+#
+# > positional arguments:
+# 1st - Stash
+# 2nd - Identity
+# 3rd - Era
+# 4th - Session
+# 5th - Session of the candidate disputed
+# 6th - Candidate hash
+# 7th - Vote (for/against)
+#
+# The following arguments depend on exposed flags
+# 8th - Network name (--expose-network flag must be set)
+# 9th - Network token symbol (--expose-network flag must be set)
+# 10th - Network token decimals (--expose-network flag must be set)
+#
+# > Special character '!' controls message visibility on Matrix (Element)
+# Any message that starts with '!' will be sent to Matrix, to the user private room
+# 
+# echo "! This message will be sent to Matrix"
+# echo "This message will NOT be sent to Matrix"
+# 
+# ***** START *****
+#
+echo "! e.g. Write your own script here"
+echo "! --------------------------------"
+echo "! Positional arguments:"
+echo "! 1st - Stash -> $1" 
+echo "! 2nd - Identity -> $2"
+echo "! 3rd - Era -> $3"
+echo "! 4th - Session -> $4"
+echo "! 5th - Session of the candidate disputed -> $5"
+echo "! 6th - Candidate hash -> $6"
+echo "! 7th - Vote -> $7"
+echo "! -------------------------------"
+#
+# ***** END *****
@@ -0,0 +1,50 @@
+#!/bin/bash
+#
+# > make a file executable
+# chmod +x ./_era_paid.sh
+#
+# > positional arguments:
+# 1st - Stash
+# 2nd - Identity
+# 3rd - Era paid
+# 4th - Validator era points
+# 5th - Total era points
+# 6th - Validator estimated reward (in plancks)
+# 7th - Total validators payout (in plancks)
+# 8th - Is reward already claimed? (true/false)
+#
+# The following arguments depend on exposed flags
+# 9th - Network name (--expose-network flag must be set)
+# 10th - Network token symbol (--expose-network flag must be set)
+# 11th - Network token decimals (--expose-network flag must be set)
+#
+# 12th - Stash reward, commission plus own stake share (in plancks)
+# 13th - Stash reward, commission plus own stake share (formatted with token symbol)
+# 14th - Rank by era points, '-' if no points earned
+# 15th - Number of validators that earned era points
+#
+# > Special character '!' controls message visibility on Matrix (Element)
+# Any message that starts with '!' will be sent to Matrix, to the user private room
+# 
+# echo "! This message will be sent to Matrix"
+# echo "This message will NOT be sent to Matrix"
+# 
+# ***** START *****
+#
+echo "! e.g. Write your own script here"
+echo "! --------------------------------"
+echo "! Positional arguments:"
+echo "! 1st - Stash -> $1" 
+echo "! 2nd - Identity -> $2"
+echo "! 3rd - Era paid -> $3"
+echo "! 4th - Validator era points -> $4"
+echo "! 5th - Total era points -> $5"
+echo "! 6th - Validator estimated reward -> $6"
+echo "! 7th - Total validators payout -> $7"
+echo "! 8th - Is reward already claimed? -> $8"
+echo "! 12th - Stash reward -> ${12}"
+echo "! 13th - Stash reward formatted -> ${13}"
+echo "! 14th - Rank by era points -> ${14}"
+echo "! -------------------------------"
+#
+# ***** END *****
@@ -0,0 +1,38 @@
+#!/bin/bash
+#
+# > make a file executable
+# chmod +x ./_low_balance.sh
+#
+# > positional arguments:
+# 1st - Account
+# 2nd - Identity, '-' if not available
+# 3rd - Era
+# 4th - Session
+# 5th - Free balance (in plancks)
+# 6th - Minimum free balance defined (in plancks)
+#
+# The following arguments depend on exposed flags
+# 7th - Network name (--expose-network flag must be set)
+# 8th - Network token symbol (--expose-network flag must be set)
+# 9th - Network token decimals (--expose-network flag must be set)
+#
+# > Special character '!' controls message visibility on Matrix (Element)
+# Any message that starts with '!' will be sent to Matrix, to the user private room
+# 
+# echo "! This message will be sent to Matrix"
+# echo "This message will NOT be sent to Matrix"
+# 
+# ***** START *****
+#
+echo "! e.g. Write your own script here"
+echo "! --------------------------------"
+echo "! Positional arguments:"
+echo "! 1st - Account -> $1" 
+echo "! 2nd - Identity -> $2"
+echo "! 3rd - Era -> $3"
+echo "! 4th - Session -> $4"
+echo "! 5th - Free balance -> $5"
+echo "! 6th - Minimum free balance -> $6"
+echo "! -------------------------------"
+#
+# ***** END *****
@@ -12,22 +12,33 @@
 # 6th - Era
 # 7th - Session
 # 8th - Eras session index [1,2,3,4,5,6]
-# 9th - To Be Defined (TBD)
+# 9th - Event block number
 #
 # The following arguments depend on exposed flags
 # 10th - Network name (--expose-network flag must be set)
 # 11th - Network token symbol (--expose-network flag must be set)
 # 12th - Network token decimals (--expose-network flag must be set)
 
-# 13th - Validator Total stake (--expose-nominators flag must be set)
-# 14th - Validator Own stake (--expose-nominators flag must be set)
-# 15th - Active Nominators stashes [stash_1, stash_2, ..] (--expose-nominators flag must be set)
-# 16th - Active Nominators stakes [stake_1, stake_2, ..] (--expose-nominators flag must be set)
+# 13th - Projected APR (same calculation as in polkadot.js) (--expose-nominators flag must be set)
+# 14th - Validator Total stake (--expose-nominators flag must be set)
+# 15th - Validator Own stake (--expose-nominators flag must be set)
+# 16th - Active Nominators stashes [stash_1, stash_2, ..] (--expose-nominators flag must be set)
+# 17th - Active Nominators stakes [stake_1, stake_2, ..] (--expose-nominators flag must be set)
 #
-# 17th - Number of Authored blocks in previous Session (--expose-authored-blocks flag must be set)
-# 18th - Number of Authored blocks in previous 6 Sessions (--expose-authored-blocks flag must be set)
+# 18th - Number of Authored blocks in previous Session (--expose-authored-blocks flag must be set)
+# 19th - Number of Authored blocks in previous 6 Sessions (--expose-authored-blocks flag must be set)
 #
-# 19th - All Nominators stashes [stash_1, stash_2, ..] (--expose-all-nominators flag must be set)
+# 20th - All Nominators stashes [stash_1, stash_2, ..] (--expose-all-nominators flag must be set)
+# 21th - Total nominators stake and Raw distribution stake per nominee [total_nominators_stake, raw_distribution_stake] (--expose-all-nominators flag must be set)
+#
+# 22th - Is Para validator? (true/false) (--expose-para-validator flag must be set)
+# 23th - Number of Para validator times in previous 6 Sessions (--expose-para-validator flag must be set)
+#
+# 24th - Validator era points in previous era (--expose-era-points flag must be set)
+# 25th - Average era points in previous era (--expose-era-points flag must be set)
+#
+# 26th - First session index of the new era
+# 27th - Era start (unix timestamp in seconds)
 #
 # > Special character '!' controls message visibility on Matrix (Element)
 # Any message that starts with '!' will be sent to Matrix, to the user private room
@@ -48,7 +59,7 @@ echo "! 5th - Session keys queued? -> $5"
 echo "! 6th - Era -> $6"
 echo "! 7th - Session -> $7"
 echo "! 8th - Eras session index -> $8"
-echo "! 9th - TBD -> $9"
+echo "! 9th - Event block number -> $9"
 echo "! (10th) - Network name -> ${10}"
 echo "! (11th) - Network token symbol -> ${11}"
 echo "! (12th) - Network token decimals -> ${12}"
@@ -59,6 +70,14 @@ echo "! (16th) - Active Nominators Stake -> ${16}"
 echo "! (17th) - Number of Authored blocks in previous Session -> ${17}"
 echo "! (18th) - Number of Authored blocks in previous 6 Sessions -> ${18}"
 echo "! (19th) - All Nominators -> ${19}"
+echo "! 26th - First session index of the era -> ${26}"
+echo "! 27th - Era start -> ${27}"
+echo "! 28th - Validator own stake in the era, '-' if not active -> ${28}"
+echo "! 29th - Validator total backing stake in the era, '-' if not active -> ${29}"
+echo "! 30th - Number of nominators backing the validator in the era (all exposure pages), '-' if not active -> ${30}"
+echo "! 31st - Own stake difference from the previous era, '-' if not active in the previous era -> ${31}"
+echo "! 32nd - Total backing stake difference from the previous era, '-' if not active in the previous era -> ${32}"
+echo "! 33rd - Nominators difference from the previous era, '-' if not active in the previous era -> ${33}"
 echo "! -------------------------------"
 #
 # ***** END *****
@@ -34,6 +34,12 @@
 # 22th - Is Para validator? (true/false) (--expose-para-validator flag must be set)
 # 23th - Number of Para validator times in previous 6 Sessions (--expose-para-validator flag must be set)
 #
+# 24th - Para validator group index in the session, '-' if not para validator (--expose-para-validator flag must be set)
+# 25th - Implicit backing votes in previous Session, '-' if not para validator (--expose-para-validator flag must be set)
+# 26th - Explicit backing votes in previous Session, '-' if not para validator (--expose-para-validator flag must be set)
+# 27th - Missed backing votes in previous Session, '-' if not para validator (--expose-para-validator flag must be set)
+# 28th - Missed votes ratio in previous Session, '-' if no candidates backed by the group (--expose-para-validator flag must be set)
+#
 # > Special character '!' controls message visibility on Matrix (Element)
 # Any message that starts with '!' will be sent to Matrix, to the user private room
 # 
@@ -64,6 +70,11 @@ echo "! (16th) - Active Nominators Stake -> ${16}"
 echo "! (17th) - Number of Authored blocks in previous Session -> ${17}"
 echo "! (18th) - Number of Authored blocks in previous 6 Sessions -> ${18}"
 echo "! (19th) - All Nominators -> ${19}"
+echo "! (24th) - Para validator group -> ${24}"
+echo "! (25th) - Implicit backing votes -> ${25}"
+echo "! (26th) - Explicit backing votes -> ${26}"
+echo "! (27th) - Missed backing votes -> ${27}"
+echo "! (28th) - Missed votes ratio -> ${28}"
 echo "! -------------------------------"
 if [ "$4" = "true" ]
 then
@@ -0,0 +1,40 @@
+#!/bin/bash
+#
+# > make a file executable
+# chmod +x ./_nomination_inactive.sh
+#
+# > positional arguments:
+# 1st - Nominator
+# 2nd - Identity
+# 3rd - Active era index
+# 4th - Active bonded stake (in plancks)
+# 5th - Number of validators nominated
+# 6th - Validators nominated that are inactive (e.g. ALICE,BOB or - if none)
+# 7th - Warnings of the nominations (e.g. ALICE inactive;BOB raised commission 5.00% -> 10.00%)
+#
+# The following arguments depend on exposed flags
+# 8th - Network name (--expose-network flag must be set)
+# 9th - Network token symbol (--expose-network flag must be set)
+# 10th - Network token decimals (--expose-network flag must be set)
+#
+# > Special character '!' controls message visibility on Matrix (Element)
+# Any message that starts with '!' will be sent to Matrix, to the user private room
+# 
+# echo "! This message will be sent to Matrix"
+# echo "This message will NOT be sent to Matrix"
+# 
+# ***** START *****
+#
+echo "! e.g. Write your own script here"
+echo "! --------------------------------"
+echo "! Positional arguments:"
+echo "! 1st - Nominator -> $1" 
+echo "! 2nd - Identity -> $2"
+echo "! 3rd - Active era -> $3"
+echo "! 4th - Bonded stake -> $4"
+echo "! 5th - Validators nominated -> $5"
+echo "! 6th - Validators inactive -> $6"
+echo "! 7th - Warnings -> $7"
+echo "! -------------------------------"
+#
+# ***** END *****
@@ -0,0 +1,38 @@
+#!/bin/bash
+#
+# > make a file executable
+# chmod +x ./_offence_reported.sh
+#
+# > positional arguments:
+# 1st - Stash
+# 2nd - Identity
+# 3rd - Offence kind (e.g. babe:equivocatio, grandpa:equivoca, im-online:offlin)
+# 4th - Era
+# 5th - Session in which the offence was reported
+# 6th - Offence time slot (0x..)
+#
+# The following arguments depend on exposed flags
+# 7th - Network name (--expose-network flag must be set)
+# 8th - Network token symbol (--expose-network flag must be set)
+# 9th - Network token decimals (--expose-network flag must be set)
+#
+# > Special character '!' controls message visibility on Matrix (Element)
+# Any message that starts with '!' will be sent to Matrix, to the user private room
+# 
+# echo "! This message will be sent to Matrix"
+# echo "This message will NOT be sent to Matrix"
+# 
+# ***** START *****
+#
+echo "! e.g. Write your own script here"
+echo "! --------------------------------"
+echo "! Positional arguments:"
+echo "! 1st - Stash -> $1" 
+echo "! 2nd - Identity -> $2"
+echo "! 3rd - Offence kind -> $3"
+echo "! 4th - Era -> $4"
+echo "! 5th - Session -> $5"
+echo "! 6th - Offence time slot -> $6"
+echo "! -------------------------------"
+#
+# ***** END *****
@@ -0,0 +1,44 @@
+#!/bin/bash
+#
+# > make a file executable
+# chmod +x ./_payee_changed.sh
+#
+# > positional arguments:
+# 1st - Stash
+# 2nd - Identity
+# 3rd - Era
+# 4th - Session
+# 5th - Previous reward destination (Staked, Stash, Controller, Account or None)
+# 6th - Previous reward destination account, '-' if not Account
+# 7th - New reward destination (Staked, Stash, Controller, Account or None)
+# 8th - New reward destination account, '-' if not Account
+# 9th - Identity of the new reward destination account, '-' if not available
+#
+# The following arguments depend on exposed flags
+# 10th - Network name (--expose-network flag must be set)
+# 11th - Network token symbol (--expose-network flag must be set)
+# 12th - Network token decimals (--expose-network flag must be set)
+#
+# > Special character '!' controls message visibility on Matrix (Element)
+# Any message that starts with '!' will be sent to Matrix, to the user private room
+# 
+# echo "! This message will be sent to Matrix"
+# echo "This message will NOT be sent to Matrix"
+# 
+# ***** START *****
+#
+echo "! e.g. Write your own script here"
+echo "! --------------------------------"
+echo "! Positional arguments:"
+echo "! 1st - Stash -> $1" 
+echo "! 2nd - Identity -> $2"
+echo "! 3rd - Era -> $3"
+echo "! 4th - Session -> $4"
+echo "! 5th - Previous reward destination -> $5"
+echo "! 6th - Previous reward destination account -> $6"
+echo "! 7th - New reward destination -> $7"
+echo "! 8th - New reward destination account -> $8"
+echo "! 9th - New reward destination identity -> $9"
+echo "! -------------------------------"
+#
+# ***** END *****
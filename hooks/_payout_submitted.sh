@@ -0,0 +1,42 @@
+#!/bin/bash
+#
+# > make a file executable
+# chmod +x ./_payout_submitted.sh
+#
+# > positional arguments:
+# 1st - Stash
+# 2nd - Identity
+# 3rd - Era paid out
+# 4th - Is payout successful? (true/false)
+# 5th - Block hash in which the payout was finalized, '-' if failed
+# 6th - Fee paid (in plancks)
+# 7th - Rewards paid out to the stash and its nominators (in plancks)
+# 8th - Reason of the failure, '-' if successful
+#
+# The following arguments depend on exposed flags
+# 9th - Network name (--expose-network flag must be set)
+# 10th - Network token symbol (--expose-network flag must be set)
+# 11th - Network token decimals (--expose-network flag must be set)
+#
+# > Special character '!' controls message visibility on Matrix (Element)
+# Any message that starts with '!' will be sent to Matrix, to the user private room
+# 
+# echo "! This message will be sent to Matrix"
+# echo "This message will NOT be sent to Matrix"
+# 
+# ***** START *****
+#
+echo "! e.g. Write your own script here"
+echo "! --------------------------------"
+echo "! Positional arguments:"
+echo "! 1st - Stash -> $1" 
+echo "! 2nd - Identity -> $2"
+echo "! 3rd - Era paid out -> $3"
+echo "! 4th - Is payout successful? -> $4"
+echo "! 5th - Block hash -> $5"
+echo "! 6th - Fee paid -> $6"
+echo "! 7th - Rewards paid out -> $7"
+echo "! 8th - Reason of the failure -> $8"
+echo "! -------------------------------"
+#
+# ***** END *****
@@ -0,0 +1,32 @@
+#!/bin/bash
+#
+# > make a file executable
+# chmod +x ./_runtime_upgrade.sh
+#
+# > positional arguments:
+# 1st - Previous runtime spec version
+# 2nd - New runtime spec version
+# 3rd - Block number at which the runtime upgrade was observed
+#
+# The following arguments depend on exposed flags
+# 4th - Network name (--expose-network flag must be set)
+# 5th - Network token symbol (--expose-network flag must be set)
+# 6th - Network token decimals (--expose-network flag must be set)
+#
+# > Special character '!' controls message visibility on Matrix (Element)
+# Any message that starts with '!' will be sent to Matrix, to the user private room
+# 
+# echo "! This message will be sent to Matrix"
+# echo "This message will NOT be sent to Matrix"
+# 
+# ***** START *****
+#
+echo "! e.g. Write your own script here"
+echo "! --------------------------------"
+echo "! Positional arguments:"
+echo "! 1st - Previous spec version -> $1" 
+echo "! 2nd - New spec version -> $2"
+echo "! 3rd - Block number -> $3"
+echo "! -------------------------------"
+#
+# ***** END *****
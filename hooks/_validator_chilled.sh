@@ -9,11 +9,14 @@
 # 3rd - Queued session keys (0x..)
 # 4th - Is active? (true/false)
 # 5th - Session keys queued? (true/false)
+# 6th - Era
+# 7th - Session
+# 8th - Reason (e.g. chill() called by the controller)
 #
 # The following arguments depend on exposed flags
-# 6th - Network name (--expose-network flag must be set)
-# 7th - Network token symbol (--expose-network flag must be set)
-# 8th - Network token decimals (--expose-network flag must be set)
+# 9th - Network name (--expose-network flag must be set)
+# 10th - Network token symbol (--expose-network flag must be set)
+# 11th - Network token decimals (--expose-network flag must be set)
 #
 # > Special character '!' controls message visibility on Matrix (Element)
 # Any message that starts with '!' will be sent to Matrix, to the user private room
@@ -31,6 +34,9 @@ echo "! 2nd - Identity -> $2"
 echo "! 3rd - Queued session keys -> ${3:0:16}.."
 echo "! 4th - Is active? -> $4"
 echo "! 5th - Session keys queued? -> $5"
+echo "! 6th - Era -> $6"
+echo "! 7th - Session -> $7"
+echo "! 8th - Reason -> $8"
 echo "! -------------------------------"
 #
 # ***** END *****
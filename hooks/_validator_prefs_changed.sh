@@ -0,0 +1,42 @@
+#!/bin/bash
+#
+# > make a file executable
+# chmod +x ./_validator_prefs_changed.sh
+#
+# > positional arguments:
+# 1st - Stash
+# 2nd - Identity
+# 3rd - Era
+# 4th - Session
+# 5th - Previous commission (in parts per billion)
+# 6th - New commission (in parts per billion)
+# 7th - Previously blocked? (true/false)
+# 8th - Blocked? (true/false)
+#
+# The following arguments depend on exposed flags
+# 9th - Network name (--expose-network flag must be set)
+# 10th - Network token symbol (--expose-network flag must be set)
+# 11th - Network token decimals (--expose-network flag must be set)
+#
+# > Special character '!' controls message visibility on Matrix (Element)
+# Any message that starts with '!' will be sent to Matrix, to the user private room
+# 
+# echo "! This message will be sent to Matrix"
+# echo "This message will NOT be sent to Matrix"
+# 
+# ***** START *****
+#
+echo "! e.g. Write your own script here"
+echo "! --------------------------------"
+echo "! Positional arguments:"
+echo "! 1st - Stash -> $1" 
+echo "! 2nd - Identity -> $2"
+echo "! 3rd - Era -> $3"
+echo "! 4th - Session -> $4"
+echo "! 5th - Previous commission -> $5"
+echo "! 6th - New commission -> $6"
+echo "! 7th - Previously blocked? -> $7"
+echo "! 8th - Blocked? -> $8"
+echo "! -------------------------------"
+#
+# ***** END *****
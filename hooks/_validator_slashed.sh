@@ -6,11 +6,12 @@
 # > positional arguments:
 # 1st - Slashed Validator Stash
 # 2nd - Slashed Validator Amount
+# 3rd - Era
 #
 # The following arguments depend on exposed flags
-# 3r - Network name (--expose-network flag must be set)
-# 4th - Network token symbol (--expose-network flag must be set)
-# 5th - Network token decimals (--expose-network flag must be set)
+# 4th - Network name (--expose-network flag must be set)
+# 5th - Network token symbol (--expose-network flag must be set)
+# 6th - Network token decimals (--expose-network flag must be set)
 #
 # > Special character '!' controls message visibility on Matrix (Element)
 # Any message that starts with '!' will be sent to Matrix, to the user private room
@@ -25,6 +26,7 @@ echo "! --------------------------------"
 echo "! Positional arguments:"
 echo "! 1st - Slashed Validator Stash -> $1" 
 echo "! 2nd - Slashed Validator Amount -> $2"
+echo "! 3rd - Era -> $3"
 echo "! -------------------------------"
 #
 # ***** END *****
@@ -15,6 +15,9 @@
 # 7th - Network token symbol (--expose-network flag must be set)
 # 8th - Network token decimals (--expose-network flag must be set)
 #
+# 9th - Reason inferred for the validator being inactive next era
+# (e.g. backing stake 12.3k DOT below minimum elected 14.1k DOT)
+#
 # > Special character '!' controls message visibility on Matrix (Element)
 # Any message that starts with '!' will be sent to Matrix, to the user private room
 # 
@@ -34,6 +37,7 @@
 # echo "! (6th) - Network name -> $6"
 # echo "! (7th) - Network token symbol -> $7"
 # echo "! (8th) - Network token decimals -> $8"
+# echo "! 9th - Reason -> $9"
 # echo "! -------------------------------"
 echo "! 🏎️ Last lap! $2 will be 🔴 next era $4
 #
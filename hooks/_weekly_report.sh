@@ -0,0 +1,47 @@
+#!/bin/bash
+#
+# > make a file executable
+# chmod +x ./_weekly_report.sh
+#
+# > positional arguments:
+# 1st - Stash
+# 2nd - Identity
+# 3rd - Number of eras paid of the week in which the stash was active
+# 4th - Number of eras paid of the week
+# 5th - Total era points of the week
+# 6th - Estimated total reward (in plancks) of the week, including nominators share
+# 7th - Average rank by era points (e.g. 12.5 or - if not ranked)
+# 8th - Number of critical alerts raised of the week
+# 9th - Uptime percentage of scouty itself (e.g. 99.8)
+#
+# The following arguments depend on exposed flags
+# 10th - Network name (--expose-network flag must be set)
+# 11th - Network token symbol (--expose-network flag must be set)
+# 12th - Network token decimals (--expose-network flag must be set)
+#
+# 13th - Start of the week (unix timestamp)
+# 14th - End of the week (unix timestamp)
+#
+# > Special character '!' controls message visibility on Matrix (Element)
+# Any message that starts with '!' will be sent to Matrix, to the user private room
+# 
+# echo "! This message will be sent to Matrix"
+# echo "This message will NOT be sent to Matrix"
+# 
+# ***** START *****
+#
+echo "! e.g. Write your own script here"
+echo "! --------------------------------"
+echo "! Positional arguments:"
+echo "! 1st - Stash -> $1" 
+echo "! 2nd - Identity -> $2"
+echo "! 3rd - Eras active -> $3"
+echo "! 4th - Eras paid -> $4"
+echo "! 5th - Total era points -> $5"
+echo "! 6th - Estimated total reward -> $6"
+echo "! 7th - Average rank -> $7"
+echo "! 8th - Alerts -> $8"
+echo "! 9th - Uptime -> $9"
+echo "! -------------------------------"
+#
+# ***** END *****
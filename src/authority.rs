@@ -27,11 +27,27 @@ use subxt::utils::AccountId32;
 
 pub type AuthorityIndex = u32;
 
+/// Blocks authored by a stash during a full session, compared with the average of
+/// blocks authored per authority in the session
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct AuthoredBlocks {
+    pub session_index: u32,
+    pub count: u32,
+    pub session_avg: f64,
+}
+
 #[derive(Debug, Default)]
 pub struct AuthorityRecords {
     last_block: u32,
     current_session_index: u32,
+    // First session tracked, only partially
+    first_session_index: Option<u32>,
     authorities: Vec<AccountId32>,
+    // Authorities of the current and the previous session
+    session_authorities: Vec<AccountId32>,
+    previous_session_authorities: Vec<AccountId32>,
+    // Blocks finalized in every session
+    session_blocks: BTreeMap<u32, u32>,
     pub records: BTreeMap<String, u32>,
 }
 
@@ -40,7 +56,11 @@ impl AuthorityRecords {
         Self {
             last_block: 0,
             current_session_index: 0,
+            first_session_index: None,
             authorities: vec![],
+            session_authorities: vec![],
+            previous_session_authorities: vec![],
+            session_blocks: BTreeMap::new(),
             records: BTreeMap::new(),
         }
     }
@@ -50,6 +70,15 @@ impl AuthorityRecords {
     }
 
     pub fn set_session(&mut self, new_session_index: u32) {
+        if self.first_session_index.is_none() {
+            self.first_session_index = Some(new_session_index);
+        } else if self.current_session_index != new_session_index {
+            // The authorities are set before the session on a new era
+            self.previous_session_authorities = std::mem::replace(
+                &mut self.session_authorities,
+                self.authorities.clone(),
+            );
+        }
         self.current_session_index = new_session_index;
     }
 
@@ -57,18 +86,28 @@ impl AuthorityRecords {
         self.authorities = new_authorities;
     }
 
+    /// Count the block authored by the authority if it is one of the stashes monitored
+    /// on the chain
     pub fn insert_record(
         &mut self,
+        chain: &str,
         block_number: u32,
         authority: Option<AuthorityIndex>,
     ) -> Result<(), ScoutyError> {
-        let config = CONFIG.clone();
+        let config = CONFIG.current();
         if self.last_block != block_number {
+            *self
+                .session_blocks
+                .entry(self.current_session_index)
+                .or_default() += 1;
+            let current_session_index = self.current_session_index;
+            self.session_blocks
+                .retain(|session_index, _| session_index + 7 > current_session_index);
             if let Some(authority_index) = authority {
                 // Get author stash from authorities set
                 let i: usize = authority_index.try_into().unwrap();
                 if let Some(author_stash) = self.authorities.get(i) {
-                    for stash_str in config.stashes.iter() {
+                    for stash_str in config.validator_stashes(chain).iter() {
                         let stash = AccountId32::from_str(stash_str).map_err(|e| {
                             ScoutyError::Other(format!(
                                 "Invalid SS58 format account: {:?} error: {e:?}",
@@ -130,7 +169,33 @@ impl AuthorityRecords {
                 None => 0,
             };
         }
-        return total;
+        total
+    }
+
+    /// Returns the blocks authored by the stash in the previous session, only defined if
+    /// the stash was an authority and the whole session has been tracked
+    pub fn previous_session_authored_blocks(
+        &self,
+        stash: &AccountId32,
+    ) -> Option<AuthoredBlocks> {
+        let session_index = self.current_session_index.checked_sub(1)?;
+        if self
+            .first_session_index
+            .is_none_or(|first_session_index| session_index <= first_session_index)
+            || !self.previous_session_authorities.contains(stash)
+        {
+            return None;
+        }
+        let blocks = self
+            .session_blocks
+            .get(&session_index)
+            .copied()
+            .unwrap_or_default();
+        Some(AuthoredBlocks {
+            session_index,
+            count: self.previous_session_total(stash),
+            session_avg: blocks as f64 / self.previous_session_authorities.len() as f64,
+        })
     }
 
     fn remove(&mut self, stash: &AccountId32) {
@@ -0,0 +1,224 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::BTreeMap;
+use subxt::utils::AccountId32;
+
+/// Para validator group of a stash in a session
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ParaAssignment {
+    pub para_index: u32,
+    pub group_index: u32,
+    /// Position of the stash in the group, which is also the position of its vote in
+    /// the validator indices of the candidates backed by the group
+    pub group_position: u32,
+}
+
+/// Backing votes of a stash in the candidates backed by its group
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct BackingVotes {
+    pub implicit: u32,
+    pub explicit: u32,
+    pub missed: u32,
+}
+
+impl BackingVotes {
+    pub fn total(&self) -> u32 {
+        self.implicit + self.explicit + self.missed
+    }
+
+    /// Returns the ratio of the votes missed, not defined if the group did not back
+    /// any candidate
+    pub fn missed_ratio(&self) -> Option<f64> {
+        match self.total() {
+            0 => None,
+            total => Some(self.missed as f64 / total as f64),
+        }
+    }
+
+    /// Returns true if the votes missed are above `threshold` (in percentage), a
+    /// threshold of 0 never flags the votes
+    pub fn is_above(&self, threshold: u32) -> bool {
+        threshold > 0
+            && self
+                .missed_ratio()
+                .is_some_and(|ratio| ratio * 100.0 > threshold as f64)
+    }
+}
+
+/// Group and backing votes of a stash during a session
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SessionBacking {
+    pub session_index: u32,
+    pub assignment: ParaAssignment,
+    pub votes: BackingVotes,
+}
+
+/// Backing votes of the stashes predefined that are para validators in the current
+/// session, the records are replaced on every new session
+#[derive(Debug, Default)]
+pub struct BackingRecords {
+    session_index: u32,
+    records: BTreeMap<AccountId32, SessionBacking>,
+}
+
+impl BackingRecords {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Start tracking the stashes assigned to a para validator group in the new session,
+    /// and return the backing of the stashes in the previous session. Nothing is returned
+    /// if the previous session was not tracked (e.g. after a restart).
+    pub fn start_session(
+        &mut self,
+        session_index: u32,
+        assignments: BTreeMap<AccountId32, ParaAssignment>,
+    ) -> BTreeMap<AccountId32, SessionBacking> {
+        if session_index == self.session_index {
+            return BTreeMap::new();
+        }
+        let is_consecutive = self.session_index + 1 == session_index;
+        self.session_index = session_index;
+        let records = std::mem::replace(
+            &mut self.records,
+            assignments
+                .into_iter()
+                .map(|(stash, assignment)| {
+                    (
+                        stash,
+                        SessionBacking {
+                            session_index,
+                            assignment,
+                            ..Default::default()
+                        },
+                    )
+                })
+                .collect(),
+        );
+        if is_consecutive {
+            records
+        } else {
+            BTreeMap::new()
+        }
+    }
+
+    pub fn assignment(&self, stash: &AccountId32) -> Option<ParaAssignment> {
+        self.records.get(stash).map(|backing| backing.assignment)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Track a candidate backed by a group. `voted` tells which members of the group
+    /// voted, in the order of the group, and `explicit` tells whether each of the votes
+    /// cast is an explicit attestation.
+    pub fn track_candidate(
+        &mut self,
+        group_index: u32,
+        voted: &[bool],
+        explicit: &[bool],
+    ) {
+        for backing in self
+            .records
+            .values_mut()
+            .filter(|backing| backing.assignment.group_index == group_index)
+        {
+            let position = backing.assignment.group_position as usize;
+            if !voted.get(position).copied().unwrap_or_default() {
+                backing.votes.missed += 1;
+                continue;
+            }
+            // Votes are listed in the same order as the members that voted
+            let vote_index = voted[..position].iter().filter(|x| **x).count();
+            if explicit.get(vote_index).copied().unwrap_or_default() {
+                backing.votes.explicit += 1;
+            } else {
+                backing.votes.implicit += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assignment(group_index: u32, group_position: u32) -> ParaAssignment {
+        ParaAssignment {
+            para_index: group_index * 5 + group_position,
+            group_index,
+            group_position,
+        }
+    }
+
+    #[test]
+    fn it_tracks_the_backing_votes_of_a_stash() {
+        let stash = AccountId32([1; 32]);
+        let mut records = BackingRecords::new();
+
+        let previous = records
+            .start_session(10, BTreeMap::from([(stash.clone(), assignment(3, 2))]));
+        assert!(previous.is_empty());
+        assert_eq!(records.assignment(&stash), Some(assignment(3, 2)));
+
+        // Candidates backed by the group of the stash
+        records.track_candidate(3, &[true, false, true], &[false, true]);
+        records.track_candidate(3, &[true, true, true], &[false, false, false]);
+        records.track_candidate(3, &[true, true, false], &[true, true]);
+        records.track_candidate(3, &[true, true], &[true, true]);
+        // Candidate backed by another group
+        records.track_candidate(1, &[true, false, false], &[true]);
+
+        let previous = records.start_session(11, BTreeMap::new());
+        let votes = previous[&stash].votes;
+        assert_eq!(
+            votes,
+            BackingVotes {
+                implicit: 1,
+                explicit: 1,
+                missed: 2
+            }
+        );
+        assert_eq!(votes.missed_ratio(), Some(0.5));
+        assert!(votes.is_above(20));
+        assert!(!votes.is_above(50));
+        assert!(!votes.is_above(0));
+        assert!(records.is_empty());
+    }
+
+    #[test]
+    fn it_returns_the_backing_of_consecutive_sessions_only() {
+        let stash = AccountId32([1; 32]);
+        let mut records = BackingRecords::new();
+
+        records.start_session(10, BTreeMap::from([(stash.clone(), assignment(0, 0))]));
+        records.track_candidate(0, &[true], &[true]);
+
+        // A restart within the same session keeps the records
+        assert!(records.start_session(10, BTreeMap::new()).is_empty());
+        assert_eq!(records.assignment(&stash), Some(assignment(0, 0)));
+
+        assert!(records.start_session(12, BTreeMap::new()).is_empty());
+        assert_eq!(BackingVotes::default().missed_ratio(), None);
+    }
+}
@@ -0,0 +1,107 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use rand::Rng;
+use std::time::Duration;
+
+/// Exponential backoff with jitter (e.g. 1s, 2s, 4s.. capped at max interval)
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    attempt: u32,
+    initial_interval: u64,
+    max_interval: u64,
+}
+
+impl Backoff {
+    /// Intervals are defined in seconds
+    pub fn new(initial_interval: u64, max_interval: u64) -> Self {
+        Self {
+            attempt: 0,
+            initial_interval: initial_interval.max(1),
+            max_interval: max_interval.max(initial_interval.max(1)),
+        }
+    }
+
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+    }
+
+    /// Returns the capped exponential interval for the current attempt, without jitter
+    pub fn interval(&self) -> Duration {
+        let factor = 2_u64.saturating_pow(self.attempt.min(32));
+        let secs = self
+            .initial_interval
+            .saturating_mul(factor)
+            .min(self.max_interval);
+        Duration::from_secs(secs)
+    }
+
+    /// Returns the next delay to wait for and increments the attempt counter.
+    /// The delay is randomly picked between half and the full interval so that
+    /// multiple clients do not retry at the same time.
+    pub fn next_delay(&mut self) -> Duration {
+        let interval = self.interval().as_millis() as u64;
+        let half = interval / 2;
+        let delay = half + rand::thread_rng().gen_range(0..=half);
+        self.attempt = self.attempt.saturating_add(1);
+        Duration::from_millis(delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_doubles_the_interval_up_to_max() {
+        let mut backoff = Backoff::new(1, 10);
+        let intervals: Vec<u64> = (0..6)
+            .map(|_| {
+                let interval = backoff.interval().as_secs();
+                backoff.next_delay();
+                interval
+            })
+            .collect();
+        assert_eq!(intervals, vec![1, 2, 4, 8, 10, 10]);
+    }
+
+    #[test]
+    fn it_applies_jitter_within_interval() {
+        let mut backoff = Backoff::new(4, 60);
+        let delay = backoff.next_delay();
+        assert!(delay >= Duration::from_secs(2) && delay <= Duration::from_secs(4));
+    }
+
+    #[test]
+    fn it_resets_the_attempts() {
+        let mut backoff = Backoff::new(1, 60);
+        backoff.next_delay();
+        backoff.next_delay();
+        assert_eq!(backoff.attempt(), 2);
+        backoff.reset();
+        assert_eq!(backoff.attempt(), 0);
+        assert_eq!(backoff.interval(), Duration::from_secs(1));
+    }
+}
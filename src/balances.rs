@@ -0,0 +1,100 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::str::FromStr;
+use subxt::utils::AccountId32;
+
+/// Account whose free balance is watched on a chain, defined as
+/// `chain:account:min_free_balance` (e.g. polkadot:15abc..:10), the minimum free balance
+/// is defined in tokens
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatchedAccount {
+    pub chain: String,
+    pub account: AccountId32,
+    pub min_free_balance: f64,
+}
+
+impl WatchedAccount {
+    /// Parse the watched account, the chain name is matched case insensitive
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let mut fields = value.split(':').map(|field| field.trim());
+        let (chain, account, min_free_balance) =
+            match (fields.next(), fields.next(), fields.next(), fields.next()) {
+                (Some(chain), Some(account), Some(amount), None) if !chain.is_empty() => {
+                    (chain, account, amount)
+                }
+                _ => {
+                    return Err(format!(
+                        "{} is not defined as chain:account:min_free_balance",
+                        value
+                    ))
+                }
+            };
+        let account = AccountId32::from_str(account)
+            .map_err(|_| format!("{} is not a valid SS58 format account", account))?;
+        let min_free_balance = match min_free_balance.parse::<f64>() {
+            Ok(amount) if amount.is_finite() && amount > 0.0 => amount,
+            _ => {
+                return Err(format!(
+                    "{} is not a positive amount of tokens",
+                    min_free_balance
+                ))
+            }
+        };
+        Ok(Self {
+            chain: chain.to_string(),
+            account,
+            min_free_balance,
+        })
+    }
+
+    /// Returns true if the account is watched on the chain
+    pub fn is_watched_on(&self, chain: &str) -> bool {
+        self.chain.eq_ignore_ascii_case(chain)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+    #[test]
+    fn it_parses_the_watched_account() {
+        let watched = WatchedAccount::parse(&format!("polkadot:{}:10.5", ALICE)).unwrap();
+        assert_eq!(watched.chain, "polkadot");
+        assert_eq!(watched.account, AccountId32::from_str(ALICE).unwrap());
+        assert_eq!(watched.min_free_balance, 10.5);
+        assert!(watched.is_watched_on("Polkadot"));
+        assert!(!watched.is_watched_on("Kusama"));
+    }
+
+    #[test]
+    fn it_rejects_invalid_watched_accounts() {
+        assert!(WatchedAccount::parse(ALICE).is_err());
+        assert!(WatchedAccount::parse(&format!(":{}:10", ALICE)).is_err());
+        assert!(WatchedAccount::parse("polkadot:alice:10").is_err());
+        assert!(WatchedAccount::parse(&format!("polkadot:{}:ten", ALICE)).is_err());
+        assert!(WatchedAccount::parse(&format!("polkadot:{}:0", ALICE)).is_err());
+        assert!(WatchedAccount::parse(&format!("polkadot:{}:10:1", ALICE)).is_err());
+    }
+}
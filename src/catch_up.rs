@@ -0,0 +1,159 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::ops::Range;
+
+/// Maximum number of eras looked back at startup, the history depth of the staking
+/// pallet in Polkadot and Kusama
+pub const MAX_LOOKBACK_ERAS: u32 = 84;
+
+/// Eras missed while scouty was down, summarized at startup
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CatchUp {
+    /// Eras queried for the digest
+    pub eras: Range<u32>,
+    /// Eras missed beyond the history depth or the maximum lookback, not queried
+    pub pruned: Range<u32>,
+}
+
+impl CatchUp {
+    /// Returns the eras missed since the era of the last session processed, or within
+    /// the lookback defined without any previous record. The active era is not missed,
+    /// its sessions are processed as usual.
+    pub fn new(
+        last_era_index: Option<u32>,
+        active_era_index: u32,
+        lookback: u32,
+        history_depth: u32,
+    ) -> Self {
+        let start = match last_era_index {
+            Some(last_era_index) => last_era_index.min(active_era_index),
+            None => active_era_index.saturating_sub(lookback),
+        };
+        let oldest = active_era_index
+            .saturating_sub(history_depth.min(MAX_LOOKBACK_ERAS))
+            .max(start);
+        Self {
+            eras: oldest..active_era_index,
+            pruned: start..oldest,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.eras.is_empty() && self.pruned.is_empty()
+    }
+}
+
+/// Summary of a stash in one of the eras missed
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MissedEra {
+    pub era_index: u32,
+    /// False if the era points are not kept on-chain anymore, e.g. pruned
+    pub is_available: bool,
+    pub is_active: bool,
+    pub points: u32,
+    pub era_avg: u32,
+    /// Rank by era points within the validators that earned points in the era
+    pub rank: Option<u32>,
+    /// Estimated reward (in plancks) of the era, including the nominators share
+    pub reward: u128,
+    /// Amount slashed (in plancks) for an offence committed in the era
+    pub slashed: Option<u128>,
+}
+
+impl MissedEra {
+    /// Returns true if the points of an active stash are lower than `threshold` (in
+    /// percentage) of the era average
+    pub fn is_below_average(&self, threshold: u32) -> bool {
+        self.is_active
+            && (self.points as u64) * 100 < (self.era_avg as u64) * (threshold as u64)
+    }
+
+    /// Returns true if the era is worth a line in the digest, i.e. a slash, points
+    /// below average or data not available anymore
+    pub fn is_notable(&self, threshold: u32) -> bool {
+        !self.is_available || self.slashed.is_some() || self.is_below_average(threshold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_catches_up_the_eras_missed_since_the_last_era_processed() {
+        let catch_up = CatchUp::new(Some(1200), 1203, 0, 84);
+        assert_eq!(catch_up.eras, 1200..1203);
+        assert!(catch_up.pruned.is_empty());
+
+        // Restarted within the same era
+        assert!(CatchUp::new(Some(1203), 1203, 0, 84).is_empty());
+
+        // Without any previous record only the lookback is queried
+        assert!(CatchUp::new(None, 1203, 0, 84).is_empty());
+        assert_eq!(CatchUp::new(None, 1203, 4, 84).eras, 1199..1203);
+    }
+
+    #[test]
+    fn it_bounds_the_eras_missed_by_the_history_depth() {
+        let catch_up = CatchUp::new(Some(1000), 1203, 0, 84);
+        assert_eq!(catch_up.eras, 1119..1203);
+        assert_eq!(catch_up.pruned, 1000..1119);
+
+        let catch_up = CatchUp::new(None, 1203, 200, 28);
+        assert_eq!(catch_up.eras, 1175..1203);
+        assert_eq!(catch_up.pruned, 1003..1175);
+
+        let catch_up = CatchUp::new(None, 10, 200, 84);
+        assert_eq!(catch_up.eras, 0..10);
+        assert!(catch_up.pruned.is_empty());
+    }
+
+    #[test]
+    fn it_flags_the_notable_eras_missed() {
+        let era = MissedEra {
+            era_index: 1200,
+            is_available: true,
+            is_active: true,
+            points: 80,
+            era_avg: 100,
+            ..Default::default()
+        };
+        assert!(!era.is_notable(70));
+        assert!(era.is_notable(90));
+        assert!(!era.is_notable(0));
+        assert!(MissedEra {
+            slashed: Some(1),
+            ..era.clone()
+        }
+        .is_notable(70));
+        assert!(MissedEra {
+            is_available: false,
+            ..Default::default()
+        }
+        .is_notable(70));
+        assert!(!MissedEra {
+            is_available: true,
+            ..Default::default()
+        }
+        .is_notable(70));
+    }
+}
@@ -0,0 +1,240 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::errors::ScoutyError;
+use crate::status::ChainStatus;
+use serde::Serialize;
+
+/// Exit code of the one-shot check if every stash is fine
+pub const EXIT_OK: i32 = 0;
+/// Exit code of the one-shot check if any stash needs attention, e.g. not active
+pub const EXIT_ATTENTION: i32 = 1;
+/// Exit code of the one-shot check if the checks could not run, e.g. connection failed
+pub const EXIT_ERROR: i32 = 2;
+
+/// Result of the one-shot check of a predefined stash
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct StashCheck {
+    pub stash: String,
+    pub name: String,
+    pub is_active: bool,
+    /// Session keys are queued, so the stash is active in the next era
+    pub is_active_next_era: bool,
+    pub era_points: u32,
+}
+
+impl StashCheck {
+    pub fn needs_attention(&self) -> bool {
+        !self.is_active || !self.is_active_next_era
+    }
+}
+
+/// Result of the one-shot check of a chain
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Check {
+    pub chain: String,
+    pub session_index: Option<u32>,
+    pub era_index: Option<u32>,
+    pub stashes: Vec<StashCheck>,
+    pub error: Option<String>,
+}
+
+impl Check {
+    /// Build the check from the status updated by the session checks, stashes that
+    /// are not in the status are reported as not active
+    pub fn new(
+        chain: &str,
+        stashes: &[String],
+        status: &ChainStatus,
+        error: Option<ScoutyError>,
+    ) -> Self {
+        let stashes = stashes
+            .iter()
+            .map(|stash| {
+                let s = status.stashes.get(stash).cloned().unwrap_or_default();
+                StashCheck {
+                    stash: stash.to_string(),
+                    name: s.name,
+                    is_active: s.is_active,
+                    is_active_next_era: s.is_queued,
+                    era_points: s.era_points,
+                }
+            })
+            .collect();
+        Self {
+            chain: chain.to_string(),
+            session_index: status.session_index,
+            era_index: status.era_index,
+            stashes,
+            error: error.map(|e| e.to_string()),
+        }
+    }
+
+    /// Build the check of a chain for which the checks could not run at all
+    pub fn failed(chain: &str, error: ScoutyError) -> Self {
+        Self {
+            chain: chain.to_string(),
+            session_index: None,
+            era_index: None,
+            stashes: Vec::new(),
+            error: Some(error.to_string()),
+        }
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        if self.error.is_some() {
+            EXIT_ERROR
+        } else if self.stashes.iter().any(|s| s.needs_attention()) {
+            EXIT_ATTENTION
+        } else {
+            EXIT_OK
+        }
+    }
+
+    /// Returns the human readable results of the check
+    pub fn to_text(&self) -> String {
+        let mut lines = Vec::new();
+        let session = self
+            .session_index
+            .map_or("-".to_string(), |index| index.to_string());
+        let era = self
+            .era_index
+            .map_or("-".to_string(), |index| index.to_string());
+        lines.push(format!(
+            "{} -> session {} * era {}",
+            self.chain, session, era
+        ));
+        for s in self.stashes.iter() {
+            let name = if s.name.is_empty() { "-" } else { &s.name };
+            lines.push(format!(
+                "  {} {} {} -> active: {} * active next era: {} * era points: {}",
+                if s.needs_attention() { "✗" } else { "✓" },
+                name,
+                s.stash,
+                yes_no(s.is_active),
+                yes_no(s.is_active_next_era),
+                s.era_points
+            ));
+        }
+        if let Some(error) = &self.error {
+            lines.push(format!("  error: {}", error));
+        }
+        lines.join("\n")
+    }
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value {
+        "yes"
+    } else {
+        "no"
+    }
+}
+
+#[derive(Serialize)]
+struct Checks<'a> {
+    exit_code: i32,
+    chains: &'a [Check],
+}
+
+/// Returns the exit code of the checks of every chain, the worst one wins
+pub fn exit_code(checks: &[Check]) -> i32 {
+    checks
+        .iter()
+        .map(|c| c.exit_code())
+        .max()
+        .unwrap_or(EXIT_OK)
+}
+
+/// Returns the results of the checks of every chain, as JSON if defined
+pub fn render(checks: &[Check], is_json: bool) -> String {
+    if is_json {
+        let checks = Checks {
+            exit_code: exit_code(checks),
+            chains: checks,
+        };
+        return serde_json::to_string_pretty(&checks).unwrap_or_default();
+    }
+    checks
+        .iter()
+        .map(|c| c.to_text())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::status::StashStatus;
+
+    fn status() -> ChainStatus {
+        let mut status = ChainStatus {
+            session_index: Some(100),
+            era_index: Some(10),
+            ..Default::default()
+        };
+        status.stashes.insert(
+            "stash_1".to_string(),
+            StashStatus {
+                name: "ALICE".to_string(),
+                is_active: true,
+                is_queued: true,
+                era_points: 1200,
+            },
+        );
+        status
+    }
+
+    #[test]
+    fn it_exits_with_the_code_of_the_stashes_checked() {
+        let stashes = vec!["stash_1".to_string()];
+        let check = Check::new("Polkadot", &stashes, &status(), None);
+        assert_eq!(check.exit_code(), EXIT_OK);
+        assert_eq!(check.stashes[0].era_points, 1200);
+
+        // Stashes not found in the status are not active
+        let stashes = vec!["stash_1".to_string(), "stash_2".to_string()];
+        let check = Check::new("Polkadot", &stashes, &status(), None);
+        assert_eq!(check.exit_code(), EXIT_ATTENTION);
+        assert!(!check.stashes[1].is_active_next_era);
+
+        let error = ScoutyError::Other("Connection failed".to_string());
+        let failed = Check::failed("ws://127.0.0.1:9944", error);
+        assert_eq!(failed.exit_code(), EXIT_ERROR);
+        assert_eq!(exit_code(&[check, failed]), EXIT_ERROR);
+        assert_eq!(exit_code(&[]), EXIT_OK);
+    }
+
+    #[test]
+    fn it_renders_the_checks() {
+        let stashes = vec!["stash_1".to_string()];
+        let checks = vec![Check::new("Polkadot", &stashes, &status(), None)];
+        assert_eq!(
+            render(&checks, false),
+            "Polkadot -> session 100 * era 10\n  ✓ ALICE stash_1 -> active: yes * active next era: yes * era points: 1200"
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&render(&checks, true)).unwrap();
+        assert_eq!(json["exit_code"], 0);
+        assert_eq!(json["chains"][0]["stashes"][0]["is_active_next_era"], true);
+        assert_eq!(json["chains"][0]["error"], serde_json::Value::Null);
+    }
+}
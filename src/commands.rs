@@ -0,0 +1,257 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::config::CONFIG;
+use crate::report::Report;
+use crate::status::SharedStatus;
+use chrono::{DateTime, Duration, Utc};
+
+/// Commands the matrix bot replies to, sent as room messages starting with `!`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Help,
+    Status,
+    Era,
+    /// Mute the alerts for the duration defined, e.g. `!mute 2h`
+    Mute(String),
+    Unmute,
+    Unknown(String),
+}
+
+impl Command {
+    /// Parse the command from the body of a room message, messages that do not start
+    /// with `!` are not commands
+    pub fn parse(body: &str) -> Option<Command> {
+        let body = body.trim();
+        if !body.starts_with('!') {
+            return None;
+        }
+        let mut words = body[1..].split_whitespace();
+        let name = words.next().unwrap_or_default();
+        let command = match name.to_lowercase().as_str() {
+            "help" => Command::Help,
+            "status" => Command::Status,
+            "era" => Command::Era,
+            "mute" => Command::Mute(words.next().unwrap_or_default().to_string()),
+            "unmute" => Command::Unmute,
+            _ => Command::Unknown(name.to_string()),
+        };
+        Some(command)
+    }
+
+    /// Run the command and build the reply from the status shared by the runtime
+    /// modules
+    pub fn run(
+        &self,
+        chain: &str,
+        url: &str,
+        shared_status: &SharedStatus,
+        now: DateTime<Utc>,
+    ) -> Report {
+        let mut report = Report::new();
+        match self {
+            Command::Help => {
+                report.add_raw_text("🤖 Available commands:".into());
+                report.add_raw_text(
+                    "<code>!status</code> -> connected chain, node and stashes state"
+                        .into(),
+                );
+                report.add_raw_text(
+                    "<code>!era</code> -> current era progress and era points".into(),
+                );
+                report.add_raw_text(
+                    "<code>!mute 2h</code> -> mute the alerts for a period (e.g. 30m, 2h or 1d)"
+                        .into(),
+                );
+                report.add_raw_text("<code>!unmute</code> -> unmute the alerts".into());
+                report.add_raw_text("<code>!help</code> -> this message".into());
+            }
+            Command::Status => {
+                let status = shared_status.get();
+                report
+                    .add_raw_text(format!("⛓️ <b>{}</b> -> <code>{}</code>", chain, url));
+                report.add_raw_text(format!(
+                    "📦 Last finalized block processed #{}",
+                    status.block_number
+                ));
+                match (status.session_index, status.era_index) {
+                    (Some(session_index), Some(era_index)) => {
+                        report.add_raw_text(format!(
+                            "🕰️ Last session processed {} (era {})",
+                            session_index, era_index
+                        ))
+                    }
+                    _ => report.add_raw_text("🕰️ No session processed yet".into()),
+                }
+                for (stash, s) in status.stashes.iter() {
+                    report.add_raw_text(format!(
+                        "{} <b>{}</b> ({}) -> {}{}",
+                        if s.is_active { "🟢" } else { "🔴" },
+                        s.name,
+                        stash,
+                        if s.is_active { "active" } else { "inactive" },
+                        if s.is_queued {
+                            ", queued next session"
+                        } else {
+                            ""
+                        }
+                    ));
+                }
+                if let Some(until) = status.muted_until.filter(|_| status.is_muted(now)) {
+                    report.add_raw_text(format!(
+                        "🔕 Alerts muted until {}",
+                        until.format("%Y-%m-%d %H:%M:%S UTC")
+                    ));
+                }
+            }
+            Command::Era => {
+                let status = shared_status.get();
+                match status.era_index {
+                    Some(era_index) => {
+                        report.add_raw_text(format!(
+                            "🕰️ Era {} -> session {} of {} ({}% completed)",
+                            era_index,
+                            status.eras_session_index,
+                            status.sessions_per_era,
+                            status.era_progress()
+                        ));
+                        for s in status.stashes.values() {
+                            report.add_raw_text(format!(
+                                "🎲 <b>{}</b> -> {} era points so far",
+                                s.name, s.era_points
+                            ));
+                        }
+                    }
+                    None => report.add_raw_text("🕰️ No era processed yet".into()),
+                }
+            }
+            Command::Mute(duration) => match parse_duration(duration) {
+                Some(duration) => {
+                    let until = now + duration;
+                    shared_status.update(|status| status.muted_until = Some(until));
+                    report.add_raw_text(format!(
+                        "🔕 Alerts muted until {}",
+                        until.format("%Y-%m-%d %H:%M:%S UTC")
+                    ));
+                    if !CONFIG.current().mute_all {
+                        report.add_raw_text(
+                            "🚨 Slashes, offences and disputes are still notified".into(),
+                        );
+                    }
+                    if CONFIG.current().mute_hooks {
+                        report
+                            .add_raw_text("🪝 Hook scripts are not run meanwhile".into());
+                    }
+                }
+                None => report.add_raw_text(format!(
+                    "🤷 Invalid duration <code>{}</code>, try e.g. <code>!mute 2h</code>",
+                    duration
+                )),
+            },
+            Command::Unmute => {
+                let is_muted = shared_status.get().is_muted(now);
+                shared_status.update(|status| status.muted_until = None);
+                if is_muted {
+                    report.add_raw_text("🔔 Alerts unmuted".into());
+                } else {
+                    report.add_raw_text("🔔 Alerts were not muted".into());
+                }
+            }
+            Command::Unknown(name) => {
+                report.add_raw_text(format!(
+                    "🤷 Unknown command <code>!{}</code>, try <code>!help</code>",
+                    name
+                ));
+            }
+        }
+        report
+    }
+}
+
+/// Parse a positive duration with its unit, e.g. 90s, 30m, 2h or 1d
+pub fn parse_duration(duration: &str) -> Option<Duration> {
+    let duration = duration.trim().to_lowercase();
+    if duration.len() < 2 || !duration.is_ascii() {
+        return None;
+    }
+    let (value, unit) = duration.split_at(duration.len() - 1);
+    let value: i64 = value.parse().ok().filter(|value| *value > 0)?;
+    match unit {
+        "s" => Some(Duration::seconds(value)),
+        "m" => Some(Duration::minutes(value)),
+        "h" => Some(Duration::hours(value)),
+        "d" => Some(Duration::days(value)),
+        _ => None,
+    }
+}
+
+/// Returns true if the sender is allowed to run commands
+pub fn is_allowed(sender: &str, allowlist: &[String]) -> bool {
+    allowlist.iter().any(|user| user.trim() == sender)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_the_commands() {
+        assert_eq!(Command::parse("!status"), Some(Command::Status));
+        assert_eq!(Command::parse("  !Era please "), Some(Command::Era));
+        assert_eq!(Command::parse("!help"), Some(Command::Help));
+        assert_eq!(
+            Command::parse("!mute 2h"),
+            Some(Command::Mute("2h".to_string()))
+        );
+        assert_eq!(Command::parse("!unmute"), Some(Command::Unmute));
+        assert_eq!(
+            Command::parse("!foo"),
+            Some(Command::Unknown("foo".to_string()))
+        );
+        assert_eq!(Command::parse("status"), None);
+    }
+
+    #[test]
+    fn it_parses_the_mute_durations() {
+        assert_eq!(parse_duration("90s"), Some(Duration::seconds(90)));
+        assert_eq!(parse_duration("30m"), Some(Duration::minutes(30)));
+        assert_eq!(parse_duration("2H"), Some(Duration::hours(2)));
+        assert_eq!(parse_duration("1d"), Some(Duration::days(1)));
+        assert_eq!(parse_duration("0h"), None);
+        assert_eq!(parse_duration("-2h"), None);
+        assert_eq!(parse_duration("2"), None);
+        assert_eq!(parse_duration("2w"), None);
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("2é"), None);
+    }
+
+    #[test]
+    fn it_only_allows_the_users_of_the_allowlist() {
+        let allowlist = vec![
+            "@alice:matrix.org".to_string(),
+            " @bob:matrix.org".to_string(),
+        ];
+        assert!(is_allowed("@alice:matrix.org", &allowlist));
+        assert!(is_allowed("@bob:matrix.org", &allowlist));
+        assert!(!is_allowed("@mallory:matrix.org", &allowlist));
+        assert!(!is_allowed("@mallory:matrix.org", &[]));
+    }
+}
@@ -30,16 +30,222 @@
 //
 // Set Config struct into a CONFIG lazy_static to avoid multiple processing.
 //
-use clap::{App, Arg};
-use dotenv;
+use crate::balances::WatchedAccount;
+use crate::era_digest::ReportMode;
+use crate::errors::ScoutyError;
+use crate::hooks::{
+    HooksDir, HOOK_CATCH_UP, HOOK_COMMISSION_OUT_OF_POLICY, HOOK_DISPUTE, HOOK_ERA_PAID,
+    HOOK_FINALITY_STALLED, HOOK_INIT, HOOK_LOW_BALANCE, HOOK_NEW_ERA, HOOK_NEW_SESSION,
+    HOOK_NODE_UNHEALTHY, HOOK_NOMINATION_INACTIVE, HOOK_OFFENCE_REPORTED,
+    HOOK_PAYEE_CHANGED, HOOK_PAYOUT_SUBMITTED, HOOK_REFERENDA_SUBMITTED,
+    HOOK_RUNTIME_UPGRADE, HOOK_SESSION_KEYS_MISMATCH, HOOK_VALIDATOR_CHILLED,
+    HOOK_VALIDATOR_DISABLED, HOOK_VALIDATOR_OFFLINE, HOOK_VALIDATOR_OFFLINE_RISK,
+    HOOK_VALIDATOR_PREFS_CHANGED, HOOK_VALIDATOR_SLASHED,
+    HOOK_VALIDATOR_STARTS_ACTIVE_NEXT_ERA, HOOK_VALIDATOR_STARTS_INACTIVE_NEXT_ERA,
+    HOOK_WEEKLY_REPORT,
+};
+use crate::matrix::{MsgType, Severity};
+use crate::nomination::AccountType;
+use crate::pools::MonitoredPool;
+use crate::quiet::QuietHours;
+use crate::smtp::SmtpTls;
+use crate::weekly::WeeklySchedule;
+use clap::{App, Arg, SubCommand};
 use lazy_static::lazy_static;
 use log::info;
-use serde::Deserialize;
-use std::env;
+use serde::{de, Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::Path,
+    str::FromStr,
+    sync::{Arc, RwLock},
+};
+use subxt::{
+    ext::sp_core::crypto::{self, Ss58Codec},
+    utils::AccountId32,
+};
+
+// Default path of the TOML config file, loaded if SCOUTY_CONFIG env var is not set
+pub const DEFAULT_CONFIG_FILE_PATH: &str = "/etc/scouty/scouty.toml";
+
+// Value shown instead of the secrets defined when the config is printed
+const REDACTED: &str = "<redacted>";
+
+// Config fields without a default value
+const REQUIRED_FIELDS: [(&str, &str); 1] = [("substrate_ws_url", "ws://127.0.0.1:9944")];
+
+// Config fields of which at least one has to be defined to have stashes monitored
+const STASHES_FIELDS: [&str; 5] = [
+    "stashes",
+    "stashes_polkadot",
+    "stashes_kusama",
+    "stashes_url",
+    "stashes_path",
+];
+
+// Config fields applied live when the configuration is reloaded, other fields that
+// change are only applied after a restart. Hook paths are also reloadable.
+const RELOADABLE_FIELDS: [&str; 17] = [
+    "stashes",
+    "stashes_polkadot",
+    "stashes_kusama",
+    "hooks_dir",
+    "hooks_dir_polkadot",
+    "hooks_dir_kusama",
+    "hook_timeout",
+    "hook_retries",
+    "hook_retry_delay",
+    "hook_output_to_matrix",
+    "is_short",
+    "matrix_batch_messages",
+    "matrix_mention_users",
+    "slash_summary",
+    "mute_hooks",
+    "mute_all",
+    "offline_risk_threshold",
+];
+
+// Config fields with secrets, that can also be read from the file defined by the same
+// field with the `_file` suffix (e.g. SCOUTY_MATRIX_BOT_PASSWORD_FILE)
+const SECRET_FIELDS: [&str; 9] = [
+    "matrix_bot_password",
+    "matrix_access_token",
+    "webhook_token",
+    "webhook_secret",
+    "telegram_bot_token",
+    "discord_webhook_url",
+    "pagerduty_routing_key",
+    "smtp_password",
+    "healthcheck_ping_url",
+];
 
 // Set Config struct into a CONFIG lazy_static to avoid multiple processing
 lazy_static! {
-    pub static ref CONFIG: Config = get_config();
+    pub static ref CONFIG: LiveConfig = get_config();
+}
+
+/// Fields that changed on a configuration reload
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ConfigReload {
+    /// Fields applied live
+    pub applied: Vec<String>,
+    /// Fields that need a restart to be applied
+    pub ignored: Vec<String>,
+}
+
+/// Websocket endpoints of a chain to be monitored
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainEndpoints {
+    /// Chain the endpoints must be connected to, if defined for a specific chain
+    pub chain: Option<String>,
+    pub urls: Vec<String>,
+}
+
+/// Config currently running, replaced on every reload so that the call sites of CONFIG
+/// get the fields applied live
+pub struct LiveConfig {
+    current: RwLock<Arc<Config>>,
+    /// Stashes loaded from the stashes url and the stashes file, by source, merged
+    /// with the stashes defined locally
+    remote_stashes: RwLock<BTreeMap<String, Vec<String>>>,
+}
+
+impl LiveConfig {
+    fn new(config: Config) -> Self {
+        LiveConfig {
+            current: RwLock::new(Arc::new(config)),
+            remote_stashes: RwLock::new(BTreeMap::new()),
+        }
+    }
+
+    /// Returns the config currently running, the call sites holding it keep it
+    /// unchanged until they get it again
+    pub fn current(&self) -> Arc<Config> {
+        self.current.read().unwrap().clone()
+    }
+
+    /// Replaces the stashes loaded from the source (the stashes url or the stashes
+    /// file), the stashes defined locally are kept, and returns the stashes monitored
+    /// before and after the change
+    pub fn set_remote_stashes(
+        &self,
+        source: &str,
+        stashes: Vec<String>,
+    ) -> (Vec<String>, Vec<String>) {
+        let mut current = self.current.write().unwrap();
+        let before = current.stashes.clone();
+        {
+            let mut remote_stashes = self.remote_stashes.write().unwrap();
+            if remote_stashes.get(source) == Some(&stashes) {
+                return (before.clone(), before);
+            }
+            remote_stashes.insert(source.to_string(), stashes);
+        }
+        let mut config = Config::from_loaded(&current.values, Vec::new())
+            .unwrap_or_else(|_| Config::clone(&current));
+        config.merge_stashes(&self.remote_stashes());
+        let after = config.stashes.clone();
+        *current = Arc::new(config);
+        (before, after)
+    }
+
+    /// Reloads the config file and env vars, and applies live the reloadable fields
+    /// that changed
+    pub fn reload(&self) -> Result<ConfigReload, ScoutyError> {
+        let loaded = Config::load()?;
+        let mut current = self.current.write().unwrap();
+        let (values, reload) = diff_values(&current.values, &loaded.values);
+        if !reload.applied.is_empty() {
+            let mut config = Config::from_loaded(&values, Vec::new())?;
+            config.merge_stashes(&self.remote_stashes());
+            *current = Arc::new(config);
+        }
+        Ok(reload)
+    }
+
+    /// Returns the stashes loaded from every source
+    fn remote_stashes(&self) -> Vec<String> {
+        self.remote_stashes
+            .read()
+            .unwrap()
+            .values()
+            .flatten()
+            .cloned()
+            .collect()
+    }
+}
+
+/// Returns true if the field is applied live when the configuration is reloaded
+fn is_reloadable(field: &str) -> bool {
+    RELOADABLE_FIELDS.contains(&field)
+        || (field.starts_with("hook_") && field.ends_with("_path"))
+}
+
+/// Returns the `running` values with the reloadable fields that changed replaced by the
+/// `loaded` values, alongside the fields that changed
+fn diff_values(
+    running: &BTreeMap<String, String>,
+    loaded: &BTreeMap<String, String>,
+) -> (BTreeMap<String, String>, ConfigReload) {
+    let mut values = running.clone();
+    let mut reload = ConfigReload::default();
+    for field in config_fields().iter() {
+        let value = loaded.get(*field);
+        if running.get(*field) == value {
+            continue;
+        }
+        if !is_reloadable(field) {
+            reload.ignored.push(field.to_string());
+            continue;
+        }
+        match value {
+            Some(value) => values.insert(field.to_string(), value.clone()),
+            None => values.remove(*field),
+        };
+        reload.applied.push(field.to_string());
+    }
+    (values, reload)
 }
 
 /// provides default value for interval if SCOUTY_INTERVAL env var is not set
@@ -52,19 +258,339 @@ fn default_error_interval() -> u64 {
     30
 }
 
-#[derive(Clone, Deserialize, Debug)]
+/// provides default value for the state file path if SCOUTY_STATE_PATH env var is not set
+fn default_state_path() -> String {
+    ".scouty.state".into()
+}
+
+/// provides default value for the number of failed connection attempts before rotating
+/// to the next endpoint if SCOUTY_SUBSTRATE_WS_URL_MAX_ATTEMPTS env var is not set
+fn default_substrate_ws_url_max_attempts() -> u32 {
+    3
+}
+
+/// provides default value (in seconds) for the first reconnection delay if
+/// SCOUTY_RECONNECT_INITIAL_INTERVAL env var is not set
+fn default_reconnect_initial_interval() -> u64 {
+    1
+}
+
+/// provides default value (in seconds) for the maximum reconnection delay if
+/// SCOUTY_RECONNECT_MAX_INTERVAL env var is not set
+fn default_reconnect_max_interval() -> u64 {
+    60
+}
+
+/// provides default value (in seconds) for the websocket connection timeout if
+/// SCOUTY_CONNECTION_TIMEOUT env var is not set
+fn default_connection_timeout() -> u64 {
+    10
+}
+
+/// provides default value (in seconds) for the rpc request timeout if
+/// SCOUTY_REQUEST_TIMEOUT env var is not set
+fn default_request_timeout() -> u64 {
+    30
+}
+
+/// provides default value (in seconds) for the node health check interval if
+/// SCOUTY_HEALTH_CHECK_INTERVAL env var is not set
+fn default_health_check_interval() -> u64 {
+    60
+}
+
+/// provides default value for the minimum number of peers if
+/// SCOUTY_HEALTH_MIN_PEERS env var is not set
+fn default_health_min_peers() -> usize {
+    1
+}
+
+/// provides default value (in seconds) for how long the node is allowed to be syncing
+/// if SCOUTY_HEALTH_SYNCING_GRACE_PERIOD env var is not set
+fn default_health_syncing_grace_period() -> u64 {
+    300
+}
+
+/// provides default value (in blocks) for the maximum lag between best and finalized
+/// blocks if SCOUTY_FINALITY_MAX_LAG env var is not set
+fn default_finality_max_lag() -> u32 {
+    10
+}
+
+/// provides default value (in seconds) for how long finality is allowed to lag
+/// if SCOUTY_FINALITY_MAX_LAG_DURATION env var is not set
+fn default_finality_max_lag_duration() -> u64 {
+    120
+}
+
+/// provides default value (in percentage) of the session elapsed before verifying
+/// validators heartbeats if SCOUTY_OFFLINE_RISK_THRESHOLD env var is not set
+fn default_offline_risk_threshold() -> u32 {
+    80
+}
+
+/// provides default value (in percentage) of the maximum commission allowed if
+/// SCOUTY_MAX_COMMISSION env var is not set
+fn default_max_commission() -> f64 {
+    100.0
+}
+
+/// provides default value for the number of eras after which an unclaimed era is
+/// alerted if SCOUTY_UNCLAIMED_ERAS_THRESHOLD env var is not set
+fn default_unclaimed_eras_threshold() -> u32 {
+    60
+}
+
+/// provides default value for the number of the last eras verified for unclaimed
+/// rewards if SCOUTY_PAYOUT_ERAS_DEPTH env var is not set
+fn default_payout_eras_depth() -> u32 {
+    8
+}
+
+/// provides default value for the maximum number of eras paid out after every era
+/// paid if SCOUTY_PAYOUT_MAX_ERAS env var is not set
+fn default_payout_max_eras() -> u32 {
+    4
+}
+
+/// provides default value (in seconds) for how long a hook script is allowed to run
+/// if SCOUTY_HOOK_TIMEOUT env var is not set
+fn default_hook_timeout() -> u64 {
+    300
+}
+
+/// provides default value (in seconds) to wait before retrying a failed hook script
+/// if SCOUTY_HOOK_RETRY_DELAY env var is not set
+fn default_hook_retry_delay() -> u64 {
+    10
+}
+
+/// provides default value for the matrix session file path if SCOUTY_MATRIX_SESSION_PATH
+/// env var is not set
+fn default_matrix_session_path() -> String {
+    ".scouty.matrix.session".into()
+}
+
+/// provides default value for the number of times a rate limited matrix message is
+/// retried if SCOUTY_MATRIX_RATE_LIMIT_RETRIES env var is not set
+fn default_matrix_rate_limit_retries() -> u32 {
+    3
+}
+
+/// provides default value for the number of matrix messages retained to be retried
+/// if SCOUTY_MATRIX_RETRY_QUEUE_SIZE env var is not set
+fn default_matrix_retry_queue_size() -> usize {
+    100
+}
+
+/// provides default value (in seconds) for how long a matrix message is retried
+/// if SCOUTY_MATRIX_RETRY_MAX_AGE env var is not set
+fn default_matrix_retry_max_age() -> u64 {
+    3600
+}
+
+/// provides default value for the port of the SMTP server
+/// if SCOUTY_SMTP_PORT env var is not set
+fn default_smtp_port() -> u16 {
+    587
+}
+
+/// provides default value for the minimum severity of the events that are emailed
+/// if SCOUTY_SMTP_MIN_SEVERITY env var is not set
+fn default_smtp_min_severity() -> Severity {
+    Severity::Critical
+}
+
+/// provides default value (in megabytes) for the size at which the events file is
+/// rotated if SCOUTY_EVENTS_FILE_MAX_SIZE env var is not set
+fn default_events_file_max_size() -> u64 {
+    10
+}
+
+/// provides default value for the minimum severity of the events that page
+/// if SCOUTY_PAGERDUTY_MIN_SEVERITY env var is not set
+fn default_pagerduty_min_severity() -> Severity {
+    Severity::Critical
+}
+
+/// provides default value (in megabytes) for the size at which the hook history log
+/// is rotated if SCOUTY_HOOK_LOG_MAX_SIZE env var is not set
+fn default_hook_log_max_size() -> u64 {
+    10
+}
+
+/// provides default value (in seconds) for how often the stashes are fetched again
+/// from the stashes url if SCOUTY_STASHES_URL_INTERVAL env var is not set
+fn default_stashes_url_interval() -> u64 {
+    3600
+}
+
+/// provides default value for the timezone of the quiet hours
+/// if SCOUTY_QUIET_HOURS_TIMEZONE env var is not set
+fn default_quiet_hours_timezone() -> String {
+    "UTC".to_string()
+}
+
+/// provides default value for the timezone of the weekly report
+/// if SCOUTY_WEEKLY_REPORT_TIMEZONE env var is not set
+fn default_weekly_report_timezone() -> String {
+    "UTC".to_string()
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
 pub struct Config {
+    #[allow(dead_code)]
     #[serde(default = "default_interval")]
     pub interval: u64,
     #[serde(default = "default_error_interval")]
     pub error_interval: u64,
+    #[serde(default = "default_state_path")]
+    pub state_path: String,
     pub substrate_ws_url: String,
+    #[serde(default)]
+    pub substrate_ws_urls_polkadot: String,
+    #[serde(default)]
+    pub substrate_ws_urls_kusama: String,
+    #[serde(default = "default_substrate_ws_url_max_attempts")]
+    pub substrate_ws_url_max_attempts: u32,
+    #[serde(default = "default_reconnect_initial_interval")]
+    pub reconnect_initial_interval: u64,
+    #[serde(default = "default_reconnect_max_interval")]
+    pub reconnect_max_interval: u64,
+    #[serde(default = "default_connection_timeout")]
+    pub connection_timeout: u64,
+    #[serde(default = "default_request_timeout")]
+    pub request_timeout: u64,
+    // node health check configuration
+    #[serde(default = "default_health_check_interval")]
+    pub health_check_interval: u64,
+    #[serde(default = "default_health_min_peers")]
+    pub health_min_peers: usize,
+    #[serde(default = "default_health_syncing_grace_period")]
+    pub health_syncing_grace_period: u64,
+    #[serde(default)]
+    pub node_syncing_wait_disabled: bool,
+    #[serde(default)]
+    pub generic_runtime: bool,
+    #[serde(default)]
+    pub session_keys_check_disabled: bool,
+    // finality lag configuration
+    #[serde(default = "default_finality_max_lag")]
+    pub finality_max_lag: u32,
+    #[serde(default = "default_finality_max_lag_duration")]
+    pub finality_max_lag_duration: u64,
+    // offline risk configuration
+    #[serde(default = "default_offline_risk_threshold")]
+    pub offline_risk_threshold: u32,
+    // era points configuration
+    #[serde(default)]
+    pub era_points_threshold: u32,
+    // backing votes configuration
+    #[serde(default)]
+    pub missed_votes_threshold: u32,
+    // stake margin configuration
+    #[serde(default)]
+    pub stake_margin_threshold: u32,
+    // nominator changes configuration
+    #[serde(default)]
+    pub nominator_amount_threshold: f64,
+    #[serde(default)]
+    pub backing_drop_threshold: u32,
+    // commission policy configuration
+    #[serde(default = "default_max_commission")]
+    pub max_commission: f64,
+    // payout configuration
+    #[serde(default)]
+    pub payout_chains: Vec<String>,
+    #[serde(default)]
+    pub payout_seed_path: String,
+    #[serde(default = "default_payout_eras_depth")]
+    pub payout_eras_depth: u32,
+    #[serde(default = "default_payout_max_eras")]
+    pub payout_max_eras: u32,
+    #[serde(default = "default_unclaimed_eras_threshold")]
+    pub unclaimed_eras_threshold: u32,
+    #[serde(default)]
+    pub catch_up_disabled: bool,
+    #[serde(default)]
+    pub catch_up_lookback_eras: u32,
+    #[serde(default)]
+    pub catch_up_hooks_enabled: bool,
+    // watched balances configuration
+    #[serde(default)]
+    pub watch_balance_accounts: Vec<String>,
+    // account types configuration
+    #[serde(default)]
+    pub account_types: Vec<String>,
+    // nomination pools configuration
+    #[serde(default)]
+    pub pools: Vec<String>,
+    #[serde(default)]
     pub stashes: Vec<String>,
+    // chain specific stashes configuration
+    #[serde(default)]
+    pub stashes_polkadot: Vec<String>,
+    #[serde(default)]
+    pub stashes_kusama: Vec<String>,
+    // remote stashes configuration
+    #[serde(default)]
+    pub stashes_url: String,
+    #[serde(default = "default_stashes_url_interval")]
+    pub stashes_url_interval: u64,
+    #[serde(default)]
+    pub stashes_path: String,
+    #[serde(default)]
+    pub stash_prefix_mismatch_allowed: bool,
     #[serde(default)]
     pub is_debug: bool,
     #[serde(default)]
     pub is_short: bool,
+    #[serde(default)]
+    pub dry_run: bool,
+    // one-shot check configuration
+    #[serde(default)]
+    pub oneshot: bool,
+    #[serde(default)]
+    pub oneshot_json: bool,
+    #[serde(default)]
+    pub oneshot_quiet: bool,
+    #[serde(default)]
+    pub test_matrix: bool,
+    #[serde(default)]
+    pub test_hook: String,
+    #[serde(default)]
+    pub test_hook_args: Vec<String>,
+    #[serde(default)]
+    pub print_config: bool,
     // hooks configuration
+    #[serde(default = "default_hook_timeout")]
+    pub hook_timeout: u64,
+    #[serde(default)]
+    pub hook_retries: u32,
+    #[serde(default = "default_hook_retry_delay")]
+    pub hook_retry_delay: u64,
+    #[serde(default)]
+    pub hooks_blocking: bool,
+    #[serde(default)]
+    pub max_concurrent_hooks: usize,
+    #[serde(default)]
+    pub hook_overlap_skip: bool,
+    #[serde(default)]
+    pub hook_log_path: String,
+    #[serde(default = "default_hook_log_max_size")]
+    pub hook_log_max_size: u64,
+    #[serde(default)]
+    pub hook_output_to_matrix: bool,
+    #[serde(default)]
+    pub hooks_strict: bool,
+    #[serde(default)]
+    pub hooks_dir: String,
+    #[serde(default)]
+    pub hooks_dir_polkadot: String,
+    #[serde(default)]
+    pub hooks_dir_kusama: String,
+    #[serde(default)]
+    pub slash_summary: bool,
     #[serde(default)]
     pub hook_init_path: String,
     #[serde(default)]
@@ -72,6 +598,8 @@ pub struct Config {
     #[serde(default)]
     pub hook_new_era_path: String,
     #[serde(default)]
+    pub hook_era_paid_path: String,
+    #[serde(default)]
     pub hook_validator_starts_active_next_era_path: String,
     #[serde(default)]
     pub hook_validator_starts_inactive_next_era_path: String,
@@ -82,7 +610,39 @@ pub struct Config {
     #[serde(default)]
     pub hook_validator_offline_path: String,
     #[serde(default)]
+    pub hook_validator_offline_risk_path: String,
+    #[serde(default)]
+    pub hook_session_keys_mismatch_path: String,
+    #[serde(default)]
+    pub hook_validator_prefs_changed_path: String,
+    #[serde(default)]
+    pub hook_dispute_path: String,
+    #[serde(default)]
     pub hook_referenda_submitted_path: String,
+    #[serde(default)]
+    pub hook_node_unhealthy_path: String,
+    #[serde(default)]
+    pub hook_finality_stalled_path: String,
+    #[serde(default)]
+    pub hook_runtime_upgrade_path: String,
+    #[serde(default)]
+    pub hook_payout_submitted_path: String,
+    #[serde(default)]
+    pub hook_commission_out_of_policy_path: String,
+    #[serde(default)]
+    pub hook_validator_disabled_path: String,
+    #[serde(default)]
+    pub hook_payee_changed_path: String,
+    #[serde(default)]
+    pub hook_low_balance_path: String,
+    #[serde(default)]
+    pub hook_offence_reported_path: String,
+    #[serde(default)]
+    pub hook_catch_up_path: String,
+    #[serde(default)]
+    pub hook_weekly_report_path: String,
+    #[serde(default)]
+    pub hook_nomination_inactive_path: String,
     // matrix configuration
     #[serde(default)]
     pub matrix_user: String,
@@ -91,9 +651,105 @@ pub struct Config {
     #[serde(default)]
     pub matrix_bot_password: String,
     #[serde(default)]
+    pub matrix_access_token: String,
+    #[serde(default = "default_matrix_session_path")]
+    pub matrix_session_path: String,
+    #[serde(default)]
     pub matrix_disabled: bool,
     #[serde(default)]
     pub matrix_bot_display_name_disabled: bool,
+    #[serde(default)]
+    pub matrix_room_info: String,
+    #[serde(default)]
+    pub matrix_room_critical: String,
+    #[serde(default = "default_matrix_rate_limit_retries")]
+    pub matrix_rate_limit_retries: u32,
+    #[serde(default = "default_matrix_retry_queue_size")]
+    pub matrix_retry_queue_size: usize,
+    #[serde(default = "default_matrix_retry_max_age")]
+    pub matrix_retry_max_age: u64,
+    #[serde(default)]
+    pub matrix_mention_users: Vec<String>,
+    #[serde(default)]
+    pub matrix_msgtype: MsgType,
+    #[serde(default)]
+    pub matrix_msgtype_critical: Option<MsgType>,
+    #[serde(default)]
+    pub templates_path: String,
+    #[serde(default)]
+    pub block_explorer_url: String,
+    #[serde(default)]
+    pub matrix_batch_messages: bool,
+    #[serde(default)]
+    pub report_mode: ReportMode,
+    #[serde(default)]
+    pub notify_on_change_only: bool,
+    #[serde(default)]
+    pub notify_era_heartbeat: bool,
+    #[serde(default)]
+    pub hooks_on_change_only: bool,
+    #[serde(default)]
+    pub webhook_url: String,
+    #[serde(default)]
+    pub webhook_token: String,
+    #[serde(default)]
+    pub webhook_secret: String,
+    #[serde(default)]
+    pub telegram_bot_token: String,
+    #[serde(default)]
+    pub telegram_chat_id: String,
+    #[serde(default)]
+    pub discord_webhook_url: String,
+    #[serde(default)]
+    pub pagerduty_routing_key: String,
+    #[serde(default = "default_pagerduty_min_severity")]
+    pub pagerduty_min_severity: Severity,
+    #[serde(default)]
+    pub smtp_host: String,
+    #[serde(default = "default_smtp_port")]
+    pub smtp_port: u16,
+    #[serde(default)]
+    pub smtp_tls: SmtpTls,
+    #[serde(default)]
+    pub smtp_username: String,
+    #[serde(default)]
+    pub smtp_password: String,
+    #[serde(default)]
+    pub smtp_from: String,
+    #[serde(default)]
+    pub smtp_to: String,
+    #[serde(default = "default_smtp_min_severity")]
+    pub smtp_min_severity: Severity,
+    #[serde(default)]
+    pub prometheus_port: u16,
+    #[serde(default)]
+    pub events_file_path: String,
+    #[serde(default = "default_events_file_max_size")]
+    pub events_file_max_size: u64,
+    #[serde(default)]
+    pub events_file_fsync_critical: bool,
+    #[serde(default)]
+    pub healthcheck_ping_url: String,
+    #[serde(default)]
+    pub healthcheck_ping_fail: bool,
+    #[serde(default)]
+    pub matrix_commands_enabled: bool,
+    #[serde(default)]
+    pub matrix_commands_users: Vec<String>,
+    #[serde(default)]
+    pub mute_hooks: bool,
+    #[serde(default)]
+    pub mute_all: bool,
+    // quiet hours configuration
+    #[serde(default)]
+    pub quiet_hours: String,
+    #[serde(default = "default_quiet_hours_timezone")]
+    pub quiet_hours_timezone: String,
+    // weekly report configuration
+    #[serde(default)]
+    pub weekly_report: String,
+    #[serde(default = "default_weekly_report_timezone")]
+    pub weekly_report_timezone: String,
     // chain settings exposure
     #[serde(default)]
     pub expose_network: bool,
@@ -109,83 +765,1390 @@ pub struct Config {
     pub expose_era_points: bool,
     #[serde(default)]
     pub expose_all: bool,
+    #[serde(default)]
+    pub config_reload_announce: bool,
+    // Values of the fields as they were loaded, used to diff the config on reload
+    #[serde(skip)]
+    values: BTreeMap<String, String>,
 }
 
-/// Inject dotenv and env vars into the Config struct
-fn get_config() -> Config {
-    // Define CLI flags with clap
-    let matches = App::new(env!("CARGO_PKG_NAME"))
-    .version(env!("CARGO_PKG_VERSION"))
-    .author(env!("CARGO_PKG_AUTHORS"))
-    .about(env!("CARGO_PKG_DESCRIPTION"))
-    .arg(
-      Arg::with_name("CHAIN")
-          .index(1)
-          .possible_values(&["westend", "kusama", "polkadot"])
-          .help(
-            "Sets the substrate-based chain for which 'scouty' will try to connect",
-          )
-    )
-    .arg(
-      Arg::with_name("debug")
-        .long("debug")
-        .help("Prints debug information verbosely."))
-    .arg(
-      Arg::with_name("matrix-user")
-        .long("matrix-user")
-        .takes_value(true)
-        .help("Your regular matrix user. e.g. '@your-regular-matrix-account:matrix.org' this user account will receive notifications from your other 'Scouty Bot' matrix account."))
-    .arg(
-          Arg::with_name("matrix-bot-user")
-            .long("matrix-bot-user")
-            .takes_value(true)
-            .help("Your new 'Scouty Bot' matrix user. e.g. '@your-own-scouty-bot-account:matrix.org' this user account will be your 'Scouty Bot' which will be responsible to send messages/notifications to your private 'Scouty Bot' room."))
-    .arg(
-      Arg::with_name("matrix-bot-password")
-        .long("matrix-bot-password")
-        .takes_value(true)
-        .help("Password for the 'Scouty Bot' matrix user sign in."))
-    .arg(
-      Arg::with_name("disable-matrix")
-        .long("disable-matrix")
-        .help(
-          "Disable matrix bot for 'scouty'. (e.g. with this flag active 'scouty' will not send messages/notifications to your private 'Scouty Bot' room) (https://matrix.org/)",
-        ),
-    )
-    .arg(
-      Arg::with_name("disable-matrix-bot-display-name")
-        .long("disable-matrix-bot-display-name")
-        .help(
-          "Disable matrix bot display name update for 'scouty'. (e.g. with this flag active 'scouty' will not change the matrix bot user display name)",
-        ),
-      )
-    .arg(
-      Arg::with_name("short")
-        .long("short")
-        .help("Display only essential information (e.g. with this flag active 'scouty' will hide certain sections in a message)"))
-    .arg(
-      Arg::with_name("error-interval")
-        .long("error-interval")
-        .takes_value(true)
-        .default_value("30")
-        .help("Interval value (in minutes) from which 'scouty' will restart again in case of a critical error."))
-    .arg(
-      Arg::with_name("stashes")
-        .short("s")
-        .long("stashes")
-        .takes_value(true)
-        .help(
-          "Validator stash addresses for which 'scouty' will take a particular eye. If needed specify more than one (e.g. stash_1,stash_2,stash_3).",
-        ),
-    )
-    .arg(
-      Arg::with_name("substrate-ws-url")
-        .short("w")
-        .long("substrate-ws-url")
-        .takes_value(true)
-        .help(
-          "Substrate websocket endpoint for which 'scouty' will try to connect. (e.g. wss://kusama-rpc.polkadot.io) (NOTE: substrate_ws_url takes precedence than <CHAIN> argument)",
-        ),
+impl Config {
+    /// Loads the config from the TOML file defined by `SCOUTY_CONFIG`, or from the
+    /// default config file path if it exists, with the environment variables
+    /// overriding the values of the file
+    pub fn load() -> Result<Config, ScoutyError> {
+        let path = match env::var("SCOUTY_CONFIG") {
+            Ok(path) => Some(path),
+            Err(_) if Path::new(DEFAULT_CONFIG_FILE_PATH).exists() => {
+                Some(DEFAULT_CONFIG_FILE_PATH.to_string())
+            }
+            Err(_) => None,
+        };
+        let file = match &path {
+            Some(path) => {
+                info!("Loading configuration from {} file", path);
+                Some(fs::read_to_string(path).map_err(|e| {
+                    ScoutyError::ConfigInvalid(vec![format!("{}: {}", path, e)])
+                })?)
+            }
+            None => None,
+        };
+        Config::load_from(file.as_deref(), env::vars())
+    }
+
+    /// Loads the config from the TOML `file` contents and the `SCOUTY_` prefixed `vars`,
+    /// the vars override the values of the file. Every field that is unknown, missing
+    /// or invalid is reported in a single error.
+    pub fn load_from<I>(file: Option<&str>, vars: I) -> Result<Config, ScoutyError>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let (values, errors) = values_from(file, vars)?;
+        Config::from_loaded(&values, errors)
+    }
+
+    /// Deserialize the config from the values loaded, the `errors` found while loading
+    /// the values are reported alongside the fields that are missing or invalid
+    fn from_loaded(
+        values: &BTreeMap<String, String>,
+        mut errors: Vec<String>,
+    ) -> Result<Config, ScoutyError> {
+        if STASHES_FIELDS
+            .iter()
+            .all(|field| values.get(*field).is_none_or(|value| value.is_empty()))
+        {
+            errors.push("stashes: missing field".to_string());
+        }
+        let mut config = match from_values(values) {
+            Ok(config) => config,
+            Err(e) => {
+                let invalid = invalid_fields(config_fields(), values);
+                if invalid.is_empty() {
+                    errors.push(e.to_string());
+                }
+                errors.extend(invalid);
+                return Err(ScoutyError::ConfigInvalid(errors));
+            }
+        };
+        config.normalize_stashes();
+        config.discover_hooks();
+        config.values = values.clone();
+
+        errors.extend(
+            config
+                .invalid_stashes()
+                .iter()
+                .map(|stash| format!("stashes: invalid SS58 format account {}", stash)),
+        );
+        if config
+            .substrate_ws_urls_by_chain()
+            .iter()
+            .any(|endpoints| endpoints.urls.is_empty())
+        {
+            errors.push("substrate_ws_url: no websocket endpoint defined".to_string());
+        }
+        if let Err(e) = config.quiet_hours() {
+            errors.push(format!("quiet_hours: {}", e));
+        }
+        if let Err(e) = config.weekly_report() {
+            errors.push(format!("weekly_report: {}", e));
+        }
+        if !config.payout_chains.is_empty() && config.payout_seed_path.is_empty() {
+            errors.push("payout_seed_path: missing field".to_string());
+        }
+        errors.extend(
+            config
+                .watch_balance_accounts
+                .iter()
+                .filter_map(|value| WatchedAccount::parse(value).err())
+                .map(|e| format!("watch_balance_accounts: {}", e)),
+        );
+        errors.extend(
+            config
+                .account_types
+                .iter()
+                .filter_map(|value| AccountType::parse(value).err())
+                .map(|e| format!("account_types: {}", e)),
+        );
+        errors.extend(
+            config
+                .pools
+                .iter()
+                .filter_map(|value| MonitoredPool::parse(value).err())
+                .map(|e| format!("pools: {}", e)),
+        );
+        if !errors.is_empty() {
+            return Err(ScoutyError::ConfigInvalid(errors));
+        }
+        Ok(config)
+    }
+    /// Returns the list of substrate websocket endpoints defined in `substrate_ws_url`.
+    /// The first endpoint is the primary one, the remaining are used as fallbacks.
+    pub fn substrate_ws_urls(&self) -> Vec<String> {
+        split_urls(&self.substrate_ws_url)
+    }
+
+    /// Returns the substrate websocket endpoints of each chain to be monitored. Chain
+    /// specific endpoints take precedence over `substrate_ws_url`, and must be connected
+    /// to the chain they are defined for.
+    pub fn substrate_ws_urls_by_chain(&self) -> Vec<ChainEndpoints> {
+        let chains: Vec<ChainEndpoints> = [
+            ("Polkadot", &self.substrate_ws_urls_polkadot),
+            ("Kusama", &self.substrate_ws_urls_kusama),
+        ]
+        .iter()
+        .map(|(chain, urls)| ChainEndpoints {
+            chain: Some(chain.to_string()),
+            urls: split_urls(urls),
+        })
+        .filter(|endpoints| !endpoints.urls.is_empty())
+        .collect();
+
+        if chains.is_empty() {
+            return vec![ChainEndpoints {
+                chain: None,
+                urls: self.substrate_ws_urls(),
+            }];
+        }
+        chains
+    }
+
+    /// Returns true if the payouts of the unclaimed eras are submitted for the chain
+    pub fn is_payout_enabled(&self, chain: &str) -> bool {
+        self.payout_chains
+            .iter()
+            .any(|name| name.trim().eq_ignore_ascii_case(chain))
+    }
+
+    /// Returns the accounts whose free balance is watched on the chain
+    pub fn watched_accounts(&self, chain: &str) -> Vec<WatchedAccount> {
+        self.watch_balance_accounts
+            .iter()
+            .filter_map(|value| WatchedAccount::parse(value).ok())
+            .filter(|watched| watched.is_watched_on(chain))
+            .collect()
+    }
+
+    /// Returns the type of the account, accounts without a type defined are validator
+    /// stashes
+    pub fn account_type(&self, account: &str) -> AccountType {
+        let account = match AccountId32::from_str(account) {
+            Ok(account) => account,
+            Err(_) => return AccountType::default(),
+        };
+        self.account_types
+            .iter()
+            .filter_map(|value| AccountType::parse(value).ok())
+            .find(|(typed, _)| typed == &account)
+            .map(|(_, account_type)| account_type)
+            .unwrap_or_default()
+    }
+
+    /// Returns the stashes monitored on the chain, the stashes defined for the chain
+    /// take precedence over the stashes defined for every chain
+    pub fn chain_stashes(&self, chain: &str) -> &[String] {
+        let stashes = match chain.to_lowercase().as_str() {
+            "polkadot" => &self.stashes_polkadot,
+            "kusama" => &self.stashes_kusama,
+            _ => &self.stashes,
+        };
+        if stashes.is_empty() {
+            return &self.stashes;
+        }
+        stashes
+    }
+
+    /// Returns the directory of the hook scripts of the chain, if defined for the chain
+    pub fn chain_hooks_dir(&self, chain: &str) -> Option<&str> {
+        let hooks_dir = match chain.to_lowercase().as_str() {
+            "polkadot" => &self.hooks_dir_polkadot,
+            "kusama" => &self.hooks_dir_kusama,
+            _ => return None,
+        };
+        Some(hooks_dir.as_str()).filter(|hooks_dir| !hooks_dir.is_empty())
+    }
+
+    /// Returns the stashes monitored on the chain as validators
+    pub fn validator_stashes(&self, chain: &str) -> Vec<String> {
+        self.stashes_of_type(chain, AccountType::Validator)
+    }
+
+    /// Returns the stashes monitored on the chain as nominators
+    pub fn nominator_stashes(&self, chain: &str) -> Vec<String> {
+        self.stashes_of_type(chain, AccountType::Nominator)
+    }
+
+    fn stashes_of_type(&self, chain: &str, account_type: AccountType) -> Vec<String> {
+        self.chain_stashes(chain)
+            .iter()
+            .filter(|stash| self.account_type(stash) == account_type)
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the nomination pools monitored
+    pub fn pools(&self) -> Vec<MonitoredPool> {
+        self.pools
+            .iter()
+            .filter_map(|value| MonitoredPool::parse(value).ok())
+            .collect()
+    }
+
+    /// Returns true if more than one chain is being monitored
+    pub fn is_multi_chain(&self) -> bool {
+        self.substrate_ws_urls_by_chain().len() > 1
+    }
+
+    /// Returns the matrix users allowed to run bot commands, by default only the
+    /// regular matrix user
+    pub fn matrix_commands_allowlist(&self) -> Vec<String> {
+        if self.matrix_commands_users.is_empty() {
+            return vec![self.matrix_user.clone()];
+        }
+        self.matrix_commands_users.clone()
+    }
+
+    /// Returns the matrix message type of the severity, by default critical messages
+    /// are sent with the same message type as routine messages
+    pub fn matrix_msgtype_of(&self, severity: Severity) -> MsgType {
+        match severity {
+            Severity::Critical => {
+                self.matrix_msgtype_critical.unwrap_or(self.matrix_msgtype)
+            }
+            Severity::Info => self.matrix_msgtype,
+        }
+    }
+
+    /// Returns the name and the path of every hook script
+    pub fn hook_paths_mut(&mut self) -> Vec<(&'static str, &mut String)> {
+        vec![
+            (HOOK_INIT, &mut self.hook_init_path),
+            (HOOK_NEW_SESSION, &mut self.hook_new_session_path),
+            (HOOK_NEW_ERA, &mut self.hook_new_era_path),
+            (HOOK_ERA_PAID, &mut self.hook_era_paid_path),
+            (
+                HOOK_VALIDATOR_STARTS_ACTIVE_NEXT_ERA,
+                &mut self.hook_validator_starts_active_next_era_path,
+            ),
+            (
+                HOOK_VALIDATOR_STARTS_INACTIVE_NEXT_ERA,
+                &mut self.hook_validator_starts_inactive_next_era_path,
+            ),
+            (
+                HOOK_VALIDATOR_SLASHED,
+                &mut self.hook_validator_slashed_path,
+            ),
+            (
+                HOOK_VALIDATOR_CHILLED,
+                &mut self.hook_validator_chilled_path,
+            ),
+            (
+                HOOK_VALIDATOR_OFFLINE,
+                &mut self.hook_validator_offline_path,
+            ),
+            (
+                HOOK_VALIDATOR_OFFLINE_RISK,
+                &mut self.hook_validator_offline_risk_path,
+            ),
+            (
+                HOOK_SESSION_KEYS_MISMATCH,
+                &mut self.hook_session_keys_mismatch_path,
+            ),
+            (
+                HOOK_VALIDATOR_PREFS_CHANGED,
+                &mut self.hook_validator_prefs_changed_path,
+            ),
+            (HOOK_DISPUTE, &mut self.hook_dispute_path),
+            (
+                HOOK_REFERENDA_SUBMITTED,
+                &mut self.hook_referenda_submitted_path,
+            ),
+            (HOOK_NODE_UNHEALTHY, &mut self.hook_node_unhealthy_path),
+            (HOOK_FINALITY_STALLED, &mut self.hook_finality_stalled_path),
+            (HOOK_RUNTIME_UPGRADE, &mut self.hook_runtime_upgrade_path),
+            (HOOK_PAYOUT_SUBMITTED, &mut self.hook_payout_submitted_path),
+            (
+                HOOK_COMMISSION_OUT_OF_POLICY,
+                &mut self.hook_commission_out_of_policy_path,
+            ),
+            (
+                HOOK_VALIDATOR_DISABLED,
+                &mut self.hook_validator_disabled_path,
+            ),
+            (HOOK_PAYEE_CHANGED, &mut self.hook_payee_changed_path),
+            (HOOK_LOW_BALANCE, &mut self.hook_low_balance_path),
+            (HOOK_OFFENCE_REPORTED, &mut self.hook_offence_reported_path),
+            (HOOK_CATCH_UP, &mut self.hook_catch_up_path),
+            (HOOK_WEEKLY_REPORT, &mut self.hook_weekly_report_path),
+            (
+                HOOK_NOMINATION_INACTIVE,
+                &mut self.hook_nomination_inactive_path,
+            ),
+        ]
+    }
+
+    /// Trims the stashes predefined and removes the empty and repeated ones, so that
+    /// every stash is only monitored once
+    fn normalize_stashes(&mut self) {
+        for stashes in [
+            &mut self.stashes,
+            &mut self.stashes_polkadot,
+            &mut self.stashes_kusama,
+        ] {
+            let mut normalized: Vec<String> = Vec::new();
+            for stash in stashes.iter().map(|s| s.trim()) {
+                if !stash.is_empty() && !normalized.iter().any(|s| s == stash) {
+                    normalized.push(stash.to_string());
+                }
+            }
+            *stashes = normalized;
+        }
+    }
+
+    /// Returns the fully-resolved config, after the file, env vars and defaults are
+    /// merged and the hooks discovered, as `key=value` lines sorted by key with the
+    /// secrets redacted
+    pub fn dump(&self) -> Vec<String> {
+        let fields = match serde_json::to_value(self) {
+            Ok(serde_json::Value::Object(fields)) => fields,
+            _ => return Vec::new(),
+        };
+        let mut lines: Vec<String> = fields
+            .iter()
+            .map(|(field, value)| {
+                let value = match value {
+                    serde_json::Value::String(value) if value.is_empty() => String::new(),
+                    _ if SECRET_FIELDS.contains(&field.as_str()) => REDACTED.to_string(),
+                    serde_json::Value::String(value) => value.to_string(),
+                    serde_json::Value::Array(values) => values
+                        .iter()
+                        .map(|value| value.as_str().unwrap_or_default().to_string())
+                        .collect::<Vec<String>>()
+                        .join(","),
+                    serde_json::Value::Null => String::new(),
+                    value => value.to_string(),
+                };
+                format!("{}={}", field, value)
+            })
+            .collect();
+        lines.sort();
+        lines
+    }
+
+    /// Append the stashes that are not defined yet, e.g. the stashes fetched from the
+    /// stashes url
+    fn merge_stashes(&mut self, stashes: &[String]) {
+        self.stashes.extend(stashes.iter().cloned());
+        self.normalize_stashes();
+    }
+
+    /// Returns the stashes predefined that are not a valid SS58 address
+    fn invalid_stashes(&self) -> Vec<String> {
+        self.stashes
+            .iter()
+            .chain(self.stashes_polkadot.iter())
+            .chain(self.stashes_kusama.iter())
+            .filter(|stash| AccountId32::from_str(stash).is_err())
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the quiet hours defined, if any
+    pub fn quiet_hours(&self) -> Result<Option<QuietHours>, String> {
+        if self.quiet_hours.trim().is_empty() {
+            return Ok(None);
+        }
+        QuietHours::parse(&self.quiet_hours, &self.quiet_hours_timezone).map(Some)
+    }
+
+    /// Returns the weekday and time of the weekly report, if any
+    pub fn weekly_report(&self) -> Result<Option<WeeklySchedule>, String> {
+        if self.weekly_report.trim().is_empty() {
+            return Ok(None);
+        }
+        WeeklySchedule::parse(&self.weekly_report, &self.weekly_report_timezone).map(Some)
+    }
+
+    /// Returns the stashes monitored on the chain whose SS58 prefix does not match the
+    /// prefix of the connected chain, e.g. a Kusama address monitored on Polkadot
+    pub fn stash_prefix_mismatches(&self, chain: &str, chain_prefix: u16) -> Vec<String> {
+        self.chain_stashes(chain)
+            .iter()
+            .filter_map(
+                |stash| match crypto::AccountId32::from_ss58check_with_version(stash) {
+                    Ok((_, format)) if format.prefix() == chain_prefix => None,
+                    Ok((_, format)) => Some(format!(
+                        "stashes: {} has SS58 prefix {} but the chain connected expects {}",
+                        stash,
+                        format.prefix(),
+                        chain_prefix
+                    )),
+                    Err(e) => Some(format!("stashes: {} is invalid {:?}", stash, e)),
+                },
+            )
+            .collect()
+    }
+
+    /// Sets the hook paths not defined explicitly to the scripts discovered in
+    /// `hooks_dir`. Errors reading the directory are reported when hooks are verified.
+    fn discover_hooks(&mut self) {
+        if self.hooks_dir.is_empty() {
+            return;
+        }
+        if let Ok(hooks_dir) = HooksDir::discover(&self.hooks_dir) {
+            for (name, path) in self.hook_paths_mut() {
+                if path.is_empty() {
+                    if let Some(filename) = hooks_dir.discovered.get(name) {
+                        *path = filename.to_string();
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns the values of the fields defined in the TOML `file` contents and by the
+/// `SCOUTY_` prefixed `vars`, alongside the unknown or invalid fields of the file
+fn values_from<I>(
+    file: Option<&str>,
+    vars: I,
+) -> Result<(BTreeMap<String, String>, Vec<String>), ScoutyError>
+where
+    I: IntoIterator<Item = (String, String)>,
+{
+    let fields = config_fields();
+    let mut errors: Vec<String> = Vec::new();
+    let mut values: BTreeMap<String, String> = BTreeMap::new();
+
+    if let Some(file) = file {
+        let table = file
+            .parse::<toml::Table>()
+            .map_err(|e| ScoutyError::ConfigInvalid(vec![e.to_string()]))?;
+        for (key, value) in table.iter() {
+            if !fields.contains(&key.as_str()) && !is_secret_file_field(key) {
+                errors.push(format!("{}: unknown field", key));
+                continue;
+            }
+            match toml_to_env_value(value) {
+                Ok(value) => {
+                    values.insert(key.to_string(), value);
+                }
+                Err(e) => errors.push(format!("{}: {}", key, e)),
+            }
+        }
+    }
+
+    for (key, value) in vars {
+        if let Some(field) = key.strip_prefix("SCOUTY_") {
+            values.insert(field.to_lowercase(), value);
+        }
+    }
+    errors.extend(read_secret_files(&mut values));
+    Ok((values, errors))
+}
+
+/// Returns true if the field defines the file of a secret field
+fn is_secret_file_field(field: &str) -> bool {
+    field
+        .strip_suffix("_file")
+        .is_some_and(|field| SECRET_FIELDS.contains(&field))
+}
+
+/// Replace the value of the secret fields by the trimmed contents of their `_file`
+/// field if defined, which takes precedence over the value defined directly. The
+/// secrets are never included in the errors returned.
+fn read_secret_files(values: &mut BTreeMap<String, String>) -> Vec<String> {
+    let mut errors = Vec::new();
+    for field in SECRET_FIELDS.iter() {
+        let file_field = format!("{}_file", field);
+        let path = match values.remove(&file_field) {
+            Some(path) if !path.trim().is_empty() => path,
+            _ => continue,
+        };
+        match fs::read_to_string(path.trim()) {
+            Ok(secret) => {
+                values.insert(field.to_string(), secret.trim().to_string());
+            }
+            Err(e) => errors.push(format!(
+                "{}: {} could not be read -> {}",
+                file_field, path, e
+            )),
+        }
+    }
+    errors
+}
+
+/// Deserialize the config from the values of the fields, as if they were defined by
+/// `SCOUTY_` prefixed env vars
+fn from_values(values: &BTreeMap<String, String>) -> Result<Config, envy::Error> {
+    envy::prefixed("SCOUTY_").from_iter(values.iter().map(|(field, value)| {
+        (format!("SCOUTY_{}", field.to_uppercase()), value.clone())
+    }))
+}
+
+/// Returns the fields that are missing or fail to deserialize on their own, so that all
+/// of them are reported at once instead of only the first one
+fn invalid_fields(fields: &[&str], values: &BTreeMap<String, String>) -> Vec<String> {
+    let mut invalid = Vec::new();
+    // Required fields are set with a placeholder to verify every other field
+    let mut required: BTreeMap<String, String> = BTreeMap::new();
+    for (field, placeholder) in REQUIRED_FIELDS.iter() {
+        match values.get(*field) {
+            Some(value) => required.insert(field.to_string(), value.clone()),
+            None => {
+                invalid.push(format!("{}: missing field", field));
+                required.insert(field.to_string(), placeholder.to_string())
+            }
+        };
+    }
+    for (field, value) in values.iter() {
+        if !fields.contains(&field.as_str()) {
+            continue;
+        }
+        let mut probe = required.clone();
+        probe.insert(field.to_string(), value.clone());
+        if let Err(e) = from_values(&probe) {
+            invalid.push(format!("{}: {}", field, e));
+        }
+    }
+    invalid
+}
+
+/// Returns the names of the fields of the Config struct, used to report the unknown
+/// fields defined in the config file
+fn config_fields() -> &'static [&'static str] {
+    struct FieldsDeserializer<'a>(&'a mut &'static [&'static str]);
+
+    impl<'de, 'a> de::Deserializer<'de> for FieldsDeserializer<'a> {
+        type Error = de::value::Error;
+
+        fn deserialize_any<V: de::Visitor<'de>>(
+            self,
+            _visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            Err(de::Error::custom("config is not a struct"))
+        }
+
+        fn deserialize_struct<V: de::Visitor<'de>>(
+            self,
+            _name: &'static str,
+            fields: &'static [&'static str],
+            _visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            *self.0 = fields;
+            Err(de::Error::custom("config fields collected"))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map enum identifier ignored_any
+        }
+    }
+
+    let mut fields: &'static [&'static str] = &[];
+    let _ = Config::deserialize(FieldsDeserializer(&mut fields));
+    fields
+}
+
+/// Returns the value of the config file as it would be defined by an env var, arrays
+/// are comma separated
+fn toml_to_env_value(value: &toml::Value) -> Result<String, String> {
+    match value {
+        toml::Value::String(value) => Ok(value.to_string()),
+        toml::Value::Integer(value) => Ok(value.to_string()),
+        toml::Value::Float(value) => Ok(value.to_string()),
+        toml::Value::Boolean(value) => Ok(value.to_string()),
+        toml::Value::Datetime(value) => Ok(value.to_string()),
+        toml::Value::Array(values) => values
+            .iter()
+            .map(toml_to_env_value)
+            .collect::<Result<Vec<String>, String>>()
+            .map(|values| values.join(",")),
+        toml::Value::Table(_) => Err("tables are not supported".to_string()),
+    }
+}
+
+fn split_urls(urls: &str) -> Vec<String> {
+    urls.split(',')
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty())
+        .collect()
+}
+
+/// Inject dotenv and env vars into the Config struct
+fn get_config() -> LiveConfig {
+    // Define CLI flags with clap
+    let matches = App::new(env!("CARGO_PKG_NAME"))
+    .version(concat!(env!("CARGO_PKG_VERSION"), " (", env!("SCOUTY_GIT_COMMIT"), ")"))
+    .author(env!("CARGO_PKG_AUTHORS"))
+    .about(env!("CARGO_PKG_DESCRIPTION"))
+    .after_help("Every option is also available through the environment variable shown next to it, options defined in the command line take precedence.")
+    .subcommand(
+      SubCommand::with_name("run")
+        .about("Subscribe on-chain events and run the hooks and notifications of the stashes predefined (default)"),
+    )
+    .subcommand(
+      SubCommand::with_name("check")
+        .about("Run the checks of a new session once for the current session, print the results and exit with 0 if every stash is fine, 1 if any stash needs attention or 2 on error [env: SCOUTY_ONESHOT]")
+        .arg(
+          Arg::with_name("json")
+            .long("json")
+            .help("Print the results of the checks as JSON. [env: SCOUTY_ONESHOT_JSON]"))
+        .arg(
+          Arg::with_name("quiet")
+            .long("quiet")
+            .help("Do not send the matrix message of the session checks. [env: SCOUTY_ONESHOT_QUIET]")),
+    )
+    .subcommand(
+      SubCommand::with_name("config")
+        .about("Print the fully-resolved configuration with the secrets redacted, and the chain and ss58 prefix of every endpoint connected [env: SCOUTY_PRINT_CONFIG]"),
+    )
+    .subcommand(
+      SubCommand::with_name("test-matrix")
+        .about("Send a test message to the private room and to every matrix room configured, print the homeserver response and exit with 1 if any room failed [env: SCOUTY_TEST_MATRIX]"),
+    )
+    .subcommand(
+      SubCommand::with_name("test-hook")
+        .about("Run the hook script configured with synthetic arguments and payload, and exit with the exit code of the script [env: SCOUTY_TEST_HOOK]")
+        .arg(
+          Arg::with_name("NAME")
+            .index(1)
+            .required(true)
+            .help("The hook to run, by its script name or a unique suffix of it (e.g. new_session, active_next_era or validator_offline)"))
+        .arg(
+          Arg::with_name("args")
+            .long("args")
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1)
+            .help("Override the synthetic fields with key=value pairs, one of chain, stash, identity, queued_session_keys, is_active, is_queued, era, session, eras_session or block (e.g. --args stash=<stash> --args era=1234). [env: SCOUTY_TEST_HOOK_ARGS]")),
+    )
+    .arg(
+      Arg::with_name("CHAIN")
+          .index(1)
+          .possible_values(&["westend", "kusama", "polkadot"])
+          .help(
+            "Sets the substrate-based chain for which 'scouty' will try to connect",
+          )
+    )
+    .arg(
+      Arg::with_name("debug")
+        .long("debug")
+        .help("Prints debug information verbosely. [env: SCOUTY_IS_DEBUG]"))
+    .arg(
+      Arg::with_name("matrix-user")
+        .long("matrix-user")
+        .takes_value(true)
+        .help("Your regular matrix user. e.g. '@your-regular-matrix-account:matrix.org' this user account will receive notifications from your other 'Scouty Bot' matrix account. [env: SCOUTY_MATRIX_USER]"))
+    .arg(
+          Arg::with_name("matrix-bot-user")
+            .long("matrix-bot-user")
+            .takes_value(true)
+            .help("Your new 'Scouty Bot' matrix user. e.g. '@your-own-scouty-bot-account:matrix.org' this user account will be your 'Scouty Bot' which will be responsible to send messages/notifications to your private 'Scouty Bot' room. [env: SCOUTY_MATRIX_BOT_USER]"))
+    .arg(
+      Arg::with_name("matrix-bot-password")
+        .long("matrix-bot-password")
+        .takes_value(true)
+        .help("Password for the 'Scouty Bot' matrix user sign in. [env: SCOUTY_MATRIX_BOT_PASSWORD]"))
+    .arg(
+      Arg::with_name("matrix-access-token")
+        .long("matrix-access-token")
+        .takes_value(true)
+        .help("Access token of the 'Scouty Bot' matrix user, used instead of the password sign in. (e.g. for homeservers that enforce SSO) [env: SCOUTY_MATRIX_ACCESS_TOKEN]"))
+    .arg(
+      Arg::with_name("matrix-session-path")
+        .long("matrix-session-path")
+        .takes_value(true)
+        .value_name("FILE")
+        .help("Sets a custom matrix session file path. The session file keeps the access token and device of the 'Scouty Bot' password sign in between restarts, so that a new device is not created on every restart. (default: .scouty.matrix.session) [env: SCOUTY_MATRIX_SESSION_PATH]"))
+    .arg(
+      Arg::with_name("disable-matrix")
+        .long("disable-matrix")
+        .help(
+          "Disable matrix bot for 'scouty'. (e.g. with this flag active 'scouty' will not send messages/notifications to your private 'Scouty Bot' room) (https://matrix.org/) [env: SCOUTY_MATRIX_DISABLED]",
+        ),
+    )
+    .arg(
+      Arg::with_name("disable-matrix-bot-display-name")
+        .long("disable-matrix-bot-display-name")
+        .help(
+          "Disable matrix bot display name update for 'scouty'. (e.g. with this flag active 'scouty' will not change the matrix bot user display name) [env: SCOUTY_MATRIX_BOT_DISPLAY_NAME_DISABLED]",
+        ),
+      )
+    .arg(
+      Arg::with_name("matrix-room-info")
+        .long("matrix-room-info")
+        .takes_value(true)
+        .value_name("ROOM")
+        .help(
+          "Room id or alias where routine messages (e.g. session summaries) are sent. (e.g. '#scouty-firehose:matrix.org', by default messages are sent to the private 'Scouty Bot' room) [env: SCOUTY_MATRIX_ROOM_INFO]",
+        ),
+    )
+    .arg(
+      Arg::with_name("matrix-room-critical")
+        .long("matrix-room-critical")
+        .takes_value(true)
+        .value_name("ROOM")
+        .help(
+          "Room id or alias where critical messages (e.g. validator inactive next era, slashes or errors) are sent. (e.g. '#scouty-alerts:matrix.org', by default messages are sent to the private 'Scouty Bot' room) [env: SCOUTY_MATRIX_ROOM_CRITICAL]",
+        ),
+    )
+    .arg(
+      Arg::with_name("matrix-rate-limit-retries")
+        .long("matrix-rate-limit-retries")
+        .takes_value(true)
+        .help(
+          "Number of times a matrix message is retried when the homeserver rate limits it, waiting the time requested by the homeserver between retries. (default: 3 retries) [env: SCOUTY_MATRIX_RATE_LIMIT_RETRIES]",
+        ),
+    )
+    .arg(
+      Arg::with_name("matrix-retry-queue-size")
+        .long("matrix-retry-queue-size")
+        .takes_value(true)
+        .help(
+          "Maximum number of matrix messages retained to be retried while the homeserver is unreachable, the oldest message is dropped when the queue is full. (default: 100 messages, set 0 to disable) [env: SCOUTY_MATRIX_RETRY_QUEUE_SIZE]",
+        ),
+    )
+    .arg(
+      Arg::with_name("matrix-retry-max-age")
+        .long("matrix-retry-max-age")
+        .takes_value(true)
+        .help(
+          "Maximum time (in seconds) a matrix message is retried, stale messages are dropped from the retry queue. (default: 3600 seconds) [env: SCOUTY_MATRIX_RETRY_MAX_AGE]",
+        ),
+    )
+    .arg(
+      Arg::with_name("templates-path")
+        .long("templates-path")
+        .takes_value(true)
+        .value_name("FILE")
+        .help(
+          "Sets the path of the JSON file with the templates of the matrix messages by event (e.g. new_session, inactive_next_era or error_hold), events without a template keep the built-in messages. [env: SCOUTY_TEMPLATES_PATH]",
+        ),
+    )
+    .arg(
+      Arg::with_name("block-explorer-url")
+        .long("block-explorer-url")
+        .takes_value(true)
+        .help(
+          "Block explorer url to which the stashes are linked in the matrix messages, with {chain} and {address} replaced (e.g. https://{chain}.statescan.io/#/accounts/{address}). (default: subscan) [env: SCOUTY_BLOCK_EXPLORER_URL]",
+        ),
+    )
+    .arg(
+      Arg::with_name("matrix-mention-users")
+        .long("matrix-mention-users")
+        .takes_value(true)
+        .help(
+          "Matrix users mentioned on critical messages (e.g. validator inactive next era, slashes or errors), so that their clients notify them. If needed specify more than one (e.g. '@ops:matrix.org,@alice:matrix.org') [env: SCOUTY_MATRIX_MENTION_USERS]",
+        ),
+    )
+    .arg(
+      Arg::with_name("matrix-msgtype")
+        .long("matrix-msgtype")
+        .takes_value(true)
+        .possible_values(&["text", "notice"])
+        .help(
+          "Message type of the matrix messages sent. Bots conventionally send 'notice' messages, so that other bots and bridges ignore them and clients render them dimmed [default: text] [env: SCOUTY_MATRIX_MSGTYPE]",
+        ),
+    )
+    .arg(
+      Arg::with_name("matrix-msgtype-critical")
+        .long("matrix-msgtype-critical")
+        .takes_value(true)
+        .possible_values(&["text", "notice"])
+        .help(
+          "Message type of the critical matrix messages (e.g. validator inactive next era, slashes or errors), so that critical messages can still notify while routine messages are sent as 'notice' [default: same as --matrix-msgtype] [env: SCOUTY_MATRIX_MSGTYPE_CRITICAL]",
+        ),
+    )
+    .arg(
+      Arg::with_name("webhook-url")
+        .long("webhook-url")
+        .takes_value(true)
+        .help(
+          "Url where a JSON body is posted for every event reported (e.g. event, chain, stashes, session and era indices, severity, message and timestamp) [env: SCOUTY_WEBHOOK_URL]",
+        ),
+    )
+    .arg(
+      Arg::with_name("webhook-token")
+        .long("webhook-token")
+        .takes_value(true)
+        .help("Bearer token sent in the authorization header of the webhook requests [env: SCOUTY_WEBHOOK_TOKEN]"),
+    )
+    .arg(
+      Arg::with_name("webhook-secret")
+        .long("webhook-secret")
+        .takes_value(true)
+        .help(
+          "Secret used to sign the body of the webhook requests, the HMAC-SHA256 signature is sent in the 'X-Scouty-Signature' header so that receivers can verify it [env: SCOUTY_WEBHOOK_SECRET]",
+        ),
+    )
+    .arg(
+      Arg::with_name("telegram-bot-token")
+        .long("telegram-bot-token")
+        .takes_value(true)
+        .help("Telegram bot token, messages are also sent to the telegram chat defined if set [env: SCOUTY_TELEGRAM_BOT_TOKEN]"),
+    )
+    .arg(
+      Arg::with_name("telegram-chat-id")
+        .long("telegram-chat-id")
+        .takes_value(true)
+        .help("Telegram chat id (e.g. '-1001234567890') or channel username (e.g. '@scouty_alerts') where the messages are sent [env: SCOUTY_TELEGRAM_CHAT_ID]"),
+    )
+    .arg(
+      Arg::with_name("discord-webhook-url")
+        .long("discord-webhook-url")
+        .takes_value(true)
+        .help("Discord webhook url, messages are also posted as embeds to the discord channel of the webhook if set [env: SCOUTY_DISCORD_WEBHOOK_URL]"),
+    )
+    .arg(
+      Arg::with_name("pagerduty-routing-key")
+        .long("pagerduty-routing-key")
+        .takes_value(true)
+        .help("PagerDuty integration routing key, events at or above the minimum severity are also sent to PagerDuty with the Events API v2 if set [env: SCOUTY_PAGERDUTY_ROUTING_KEY]"),
+    )
+    .arg(
+      Arg::with_name("pagerduty-min-severity")
+        .long("pagerduty-min-severity")
+        .takes_value(true)
+        .possible_values(&["info", "critical"])
+        .help("Minimum severity of the events that page, other events are ignored by PagerDuty [default: critical] [env: SCOUTY_PAGERDUTY_MIN_SEVERITY]"),
+    )
+    .arg(
+      Arg::with_name("smtp-host")
+        .long("smtp-host")
+        .takes_value(true)
+        .help("Host of the SMTP server, events at or above the minimum severity are also emailed if set [env: SCOUTY_SMTP_HOST]"),
+    )
+    .arg(
+      Arg::with_name("smtp-port")
+        .long("smtp-port")
+        .takes_value(true)
+        .help("Port of the SMTP server [default: 587] [env: SCOUTY_SMTP_PORT]"),
+    )
+    .arg(
+      Arg::with_name("smtp-tls")
+        .long("smtp-tls")
+        .takes_value(true)
+        .possible_values(&["starttls", "tls"])
+        .help("How the connection to the SMTP server is secured, upgraded with STARTTLS or with implicit TLS [default: starttls] [env: SCOUTY_SMTP_TLS]"),
+    )
+    .arg(
+      Arg::with_name("smtp-username")
+        .long("smtp-username")
+        .takes_value(true)
+        .help("Username to authenticate with the SMTP server, no authentication if not set [env: SCOUTY_SMTP_USERNAME]"),
+    )
+    .arg(
+      Arg::with_name("smtp-password")
+        .long("smtp-password")
+        .takes_value(true)
+        .help("Password to authenticate with the SMTP server [env: SCOUTY_SMTP_PASSWORD]"),
+    )
+    .arg(
+      Arg::with_name("smtp-from")
+        .long("smtp-from")
+        .takes_value(true)
+        .help("Address the emails are sent from, e.g. 'Scouty <scouty@example.com>' [env: SCOUTY_SMTP_FROM]"),
+    )
+    .arg(
+      Arg::with_name("smtp-to")
+        .long("smtp-to")
+        .takes_value(true)
+        .help("Comma separated addresses the emails are sent to [env: SCOUTY_SMTP_TO]"),
+    )
+    .arg(
+      Arg::with_name("smtp-min-severity")
+        .long("smtp-min-severity")
+        .takes_value(true)
+        .possible_values(&["info", "critical"])
+        .help("Minimum severity of the events that are emailed, other events are not emailed [default: critical] [env: SCOUTY_SMTP_MIN_SEVERITY]"),
+    )
+    .arg(
+      Arg::with_name("prometheus-port")
+        .long("prometheus-port")
+        .takes_value(true)
+        .help("Port where the prometheus metrics are served at /metrics, metrics are not served if not set [env: SCOUTY_PROMETHEUS_PORT]"),
+    )
+    .arg(
+      Arg::with_name("healthcheck-ping-url")
+        .long("healthcheck-ping-url")
+        .takes_value(true)
+        .help("Url pinged every time a session is processed (e.g. 'https://hc-ping.com/<uuid>'), so that an external uptime service notices if 'scouty' stops [env: SCOUTY_HEALTHCHECK_PING_URL]"),
+    )
+    .arg(
+      Arg::with_name("healthcheck-ping-fail")
+        .long("healthcheck-ping-fail")
+        .help("Ping the healthcheck url with '/fail' appended when the subscription fails and 'scouty' restarts it [env: SCOUTY_HEALTHCHECK_PING_FAIL]"),
+    )
+    .arg(
+      Arg::with_name("events-file-path")
+        .long("events-file-path")
+        .takes_value(true)
+        .help("Path of the events file, where a JSON line is appended for every event reported, so that another process can tail the file and forward the alerts [env: SCOUTY_EVENTS_FILE_PATH]"),
+    )
+    .arg(
+      Arg::with_name("events-file-max-size")
+        .long("events-file-max-size")
+        .takes_value(true)
+        .help("Size (in megabytes) at which the events file is rotated. (default: 10 megabytes) [env: SCOUTY_EVENTS_FILE_MAX_SIZE]"),
+    )
+    .arg(
+      Arg::with_name("events-file-fsync-critical")
+        .long("events-file-fsync-critical")
+        .help("Sync the events file to disk straight away after every critical event is appended [env: SCOUTY_EVENTS_FILE_FSYNC_CRITICAL]"),
+    )
+    .arg(
+      Arg::with_name("config-reload-announce")
+        .long("config-reload-announce")
+        .help("Send a message with the fields applied and the fields that need a restart every time the configuration is reloaded on SIGHUP [env: SCOUTY_CONFIG_RELOAD_ANNOUNCE]"),
+    )
+    .arg(
+      Arg::with_name("matrix-batch-messages")
+        .long("matrix-batch-messages")
+        .help(
+          "Send the new session message of all the stashes predefined as a single table (e.g. with this flag active 'scouty' sends one row per stash with its era points and queued keys, and a separate critical message for every stash that starts inactive next era) [env: SCOUTY_MATRIX_BATCH_MESSAGES]",
+        ),
+    )
+    .arg(
+      Arg::with_name("report-mode")
+        .long("report-mode")
+        .takes_value(true)
+        .possible_values(&["session", "era"])
+        .help("How often the stashes are reported, a message at every new session or a single digest at every new era with the sessions, era points, blocks authored, backing votes, stake changes and warnings of the era. The stashes that start inactive next era and the slashes are still sent straight away [default: session] [env: SCOUTY_REPORT_MODE]"),
+    )
+    .arg(
+      Arg::with_name("notify-on-change-only")
+        .long("notify-on-change-only")
+        .help(
+          "Only send the new session message of the stashes whose state changed since the last one notified, i.e. active now, active next era or queued session keys (e.g. with this flag active 'scouty' does not send the same 'active next era' message every session). Stashes with a warning are still notified [env: SCOUTY_NOTIFY_ON_CHANGE_ONLY]",
+        ),
+    )
+    .arg(
+      Arg::with_name("notify-era-heartbeat")
+        .long("notify-era-heartbeat")
+        .help(
+          "Send a summary of all the stashes predefined once per era at the first session, even if nothing changed, to know that 'scouty' is still alive while only the changes are notified [env: SCOUTY_NOTIFY_ERA_HEARTBEAT]",
+        ),
+    )
+    .arg(
+      Arg::with_name("hooks-on-change-only")
+        .long("hooks-on-change-only")
+        .help(
+          "Only run the new session hook for the stashes whose state changed since the last session, i.e. active now, active next era or queued session keys [env: SCOUTY_HOOKS_ON_CHANGE_ONLY]",
+        ),
+    )
+    .arg(
+      Arg::with_name("enable-matrix-commands")
+        .long("enable-matrix-commands")
+        .help(
+          "Enable the matrix bot commands (e.g. with this flag active 'scouty' replies to '!status', '!era' and '!help' sent to the 'Scouty Bot' rooms) [env: SCOUTY_MATRIX_COMMANDS_ENABLED]",
+        ),
+    )
+    .arg(
+      Arg::with_name("matrix-commands-users")
+        .long("matrix-commands-users")
+        .takes_value(true)
+        .help(
+          "Matrix users allowed to run the bot commands, commands from other users are ignored. If needed specify more than one (e.g. '@alice:matrix.org,@bob:matrix.org', by default only the regular matrix user) [env: SCOUTY_MATRIX_COMMANDS_USERS]",
+        ),
+    )
+    .arg(
+      Arg::with_name("mute-hooks")
+        .long("mute-hooks")
+        .help(
+          "Do not run the hook scripts while alerts are muted with the '!mute' matrix bot command, except for slashes and disputes unless '--mute-all' is set. [env: SCOUTY_MUTE_HOOKS]",
+        ),
+    )
+    .arg(
+      Arg::with_name("mute-all")
+        .long("mute-all")
+        .help(
+          "Mute also slashes, offences and disputes with the '!mute' matrix bot command. (e.g. without this flag these events are still notified while alerts are muted) [env: SCOUTY_MUTE_ALL]",
+        ),
+    )
+    .arg(
+      Arg::with_name("quiet-hours")
+        .long("quiet-hours")
+        .takes_value(true)
+        .help(
+          "Daily period during which informational messages are not sent but summarized in a digest sent once the period ends, warnings and critical messages are still sent straight away (e.g. 23:00-07:00). [env: SCOUTY_QUIET_HOURS]",
+        ),
+    )
+    .arg(
+      Arg::with_name("quiet-hours-timezone")
+        .long("quiet-hours-timezone")
+        .takes_value(true)
+        .help(
+          "Timezone of the quiet hours (e.g. Europe/Lisbon), by default UTC. [env: SCOUTY_QUIET_HOURS_TIMEZONE]",
+        ),
+    )
+    .arg(
+      Arg::with_name("weekly-report")
+        .long("weekly-report")
+        .takes_value(true)
+        .help(
+          "Weekday and time at which a weekly summary of the stashes is sent, with the eras active, era points, estimated rewards, average rank and alerts raised of the week, and the uptime of 'scouty' itself (e.g. mon 09:00). [env: SCOUTY_WEEKLY_REPORT]",
+        ),
+    )
+    .arg(
+      Arg::with_name("weekly-report-timezone")
+        .long("weekly-report-timezone")
+        .takes_value(true)
+        .help(
+          "Timezone of the weekly report (e.g. Europe/Lisbon), by default UTC. [env: SCOUTY_WEEKLY_REPORT_TIMEZONE]",
+        ),
+    )
+    .arg(
+      Arg::with_name("short")
+        .long("short")
+        .help("Display only essential information (e.g. with this flag active 'scouty' will hide certain sections in a message) [env: SCOUTY_IS_SHORT]"))
+    .arg(
+      Arg::with_name("dry-run")
+        .long("dry-run")
+        .help("Run 'scouty' without side effects (e.g. with this flag active 'scouty' will only log the hook scripts it would run and the matrix messages it would send) [env: SCOUTY_DRY_RUN]"))
+    .arg(
+      Arg::with_name("error-interval")
+        .long("error-interval")
+        .takes_value(true)
+        .default_value("30")
+        .help("Interval value (in minutes) from which 'scouty' will restart again in case of a critical error. [env: SCOUTY_ERROR_INTERVAL]"))
+    .arg(
+      Arg::with_name("stashes")
+        .short("s")
+        .long("stashes")
+        .visible_alias("stash")
+        .takes_value(true)
+        .multiple(true)
+        .number_of_values(1)
+        .help(
+          "Validator stash addresses for which 'scouty' will take a particular eye. If needed specify more than one, comma separated or repeating the option (e.g. stash_1,stash_2,stash_3 or -s stash_1 -s stash_2). [env: SCOUTY_STASHES]",
+        ),
+    )
+    .arg(
+      Arg::with_name("stashes-url")
+        .long("stashes-url")
+        .takes_value(true)
+        .help(
+          "Url of a JSON (or newline-delimited) list of validator stash addresses to be monitored alongside the stashes defined (e.g. the Thousand Validators candidates endpoint). [env: SCOUTY_STASHES_URL]",
+        ),
+    )
+    .arg(
+      Arg::with_name("stashes-url-interval")
+        .long("stashes-url-interval")
+        .takes_value(true)
+        .help(
+          "Interval (in seconds) to fetch the stashes url again, by default 3600 seconds (set 0 to only fetch it at startup). [env: SCOUTY_STASHES_URL_INTERVAL]",
+        ),
+    )
+    .arg(
+      Arg::with_name("stashes-path")
+        .long("stashes-path")
+        .takes_value(true)
+        .value_name("FILE")
+        .help(
+          "Sets the path of a file with one validator stash address per line, monitored alongside the stashes defined. The file is watched for changes, so that stashes can be added or removed without a restart. [env: SCOUTY_STASHES_PATH]",
+        ),
+    )
+    .arg(
+      Arg::with_name("stash-prefix-mismatch-allowed")
+        .long("stash-prefix-mismatch-allowed")
+        .help("Allow stash addresses with a SS58 prefix other than the prefix of the chain connected (e.g. generic prefix 42 addresses). By default 'scouty' stops straight away if the prefix of one of the stashes does not match. [env: SCOUTY_STASH_PREFIX_MISMATCH_ALLOWED]"),
+    )
+    .arg(
+      Arg::with_name("substrate-ws-url")
+        .short("w")
+        .long("substrate-ws-url")
+        .visible_alias("ws-url")
+        .takes_value(true)
+        .help(
+          "Substrate websocket endpoint for which 'scouty' will try to connect. (e.g. wss://kusama-rpc.polkadot.io) If needed specify more than one, the first endpoint is the primary and the following ones are used as fallbacks (e.g. ws://127.0.0.1:9944,wss://kusama-rpc.polkadot.io). (NOTE: substrate_ws_url takes precedence than <CHAIN> argument) [env: SCOUTY_SUBSTRATE_WS_URL]",
+        ),
+    )
+    .arg(
+      Arg::with_name("substrate-ws-urls-polkadot")
+        .long("substrate-ws-urls-polkadot")
+        .takes_value(true)
+        .help(
+          "Polkadot websocket endpoints for which 'scouty' will try to connect, so that Polkadot and Kusama can be monitored from a single process. (NOTE: chain specific endpoints take precedence than substrate_ws_url) [env: SCOUTY_SUBSTRATE_WS_URLS_POLKADOT]",
+        ),
+    )
+    .arg(
+      Arg::with_name("substrate-ws-urls-kusama")
+        .long("substrate-ws-urls-kusama")
+        .takes_value(true)
+        .help(
+          "Kusama websocket endpoints for which 'scouty' will try to connect, so that Polkadot and Kusama can be monitored from a single process. (NOTE: chain specific endpoints take precedence than substrate_ws_url) [env: SCOUTY_SUBSTRATE_WS_URLS_KUSAMA]",
+        ),
+    )
+    .arg(
+      Arg::with_name("stashes-polkadot")
+        .long("stashes-polkadot")
+        .takes_value(true)
+        .help(
+          "Validator stash addresses monitored on Polkadot only, comma separated. (NOTE: chain specific stashes take precedence than stashes) [env: SCOUTY_STASHES_POLKADOT]",
+        ),
+    )
+    .arg(
+      Arg::with_name("stashes-kusama")
+        .long("stashes-kusama")
+        .takes_value(true)
+        .help(
+          "Validator stash addresses monitored on Kusama only, comma separated. (NOTE: chain specific stashes take precedence than stashes) [env: SCOUTY_STASHES_KUSAMA]",
+        ),
+    )
+    .arg(
+      Arg::with_name("substrate-ws-url-max-attempts")
+        .long("substrate-ws-url-max-attempts")
+        .takes_value(true)
+        .help(
+          "Number of failed connection attempts to the same websocket endpoint before 'scouty' rotates to the next fallback endpoint. [env: SCOUTY_SUBSTRATE_WS_URL_MAX_ATTEMPTS]",
+        ),
+    )
+    .arg(
+      Arg::with_name("reconnect-initial-interval")
+        .long("reconnect-initial-interval")
+        .takes_value(true)
+        .help(
+          "Interval value (in seconds) that 'scouty' waits before the first reconnection attempt. The interval doubles (with jitter) on every failed attempt. [env: SCOUTY_RECONNECT_INITIAL_INTERVAL]",
+        ),
+    )
+    .arg(
+      Arg::with_name("reconnect-max-interval")
+        .long("reconnect-max-interval")
+        .takes_value(true)
+        .help(
+          "Maximum interval value (in seconds) that 'scouty' waits between reconnection attempts. [env: SCOUTY_RECONNECT_MAX_INTERVAL]",
+        ),
+    )
+    .arg(
+      Arg::with_name("connection-timeout")
+        .long("connection-timeout")
+        .takes_value(true)
+        .help(
+          "Timeout value (in seconds) for 'scouty' to establish the websocket connection to the substrate node. (default: 10 seconds) [env: SCOUTY_CONNECTION_TIMEOUT]",
+        ),
+    )
+    .arg(
+      Arg::with_name("request-timeout")
+        .long("request-timeout")
+        .takes_value(true)
+        .help(
+          "Timeout value (in seconds) for every rpc request (e.g. storage queries) made to the substrate node. (default: 30 seconds) [env: SCOUTY_REQUEST_TIMEOUT]",
+        ),
+    )
+    .arg(
+      Arg::with_name("health-check-interval")
+        .long("health-check-interval")
+        .takes_value(true)
+        .help(
+          "Interval value (in seconds) from which 'scouty' checks the substrate node health. (default: 60 seconds, set 0 to disable) [env: SCOUTY_HEALTH_CHECK_INTERVAL]",
+        ),
+    )
+    .arg(
+      Arg::with_name("health-min-peers")
+        .long("health-min-peers")
+        .takes_value(true)
+        .help(
+          "Minimum number of peers below which the substrate node is considered unhealthy. (default: 1) [env: SCOUTY_HEALTH_MIN_PEERS]",
+        ),
+    )
+    .arg(
+      Arg::with_name("health-syncing-grace-period")
+        .long("health-syncing-grace-period")
+        .takes_value(true)
+        .help(
+          "Period (in seconds) that the substrate node is allowed to be syncing before it is considered unhealthy. (default: 300 seconds) [env: SCOUTY_HEALTH_SYNCING_GRACE_PERIOD]",
+        ),
+    )
+    .arg(
+      Arg::with_name("finality-max-lag")
+        .long("finality-max-lag")
+        .takes_value(true)
+        .help(
+          "Maximum number of blocks that the finalized block is allowed to lag behind the best block. (default: 10 blocks, set 0 to disable) [env: SCOUTY_FINALITY_MAX_LAG]",
+        ),
+    )
+    .arg(
+      Arg::with_name("offline-risk-threshold")
+        .long("offline-risk-threshold")
+        .takes_value(true)
+        .help(
+          "Percentage of the session elapsed after which the validators heartbeats and authored blocks are verified. (default: 80%, set 0 to disable) [env: SCOUTY_OFFLINE_RISK_THRESHOLD]",
+        ),
+    )
+    .arg(
+      Arg::with_name("era-points-threshold")
+        .long("era-points-threshold")
+        .takes_value(true)
+        .help(
+          "Percentage of the era average per active validator below which the era points of a stash are flagged as a critical message, once below for more than one consecutive session. (default: 0, disabled) [env: SCOUTY_ERA_POINTS_THRESHOLD]",
+        ),
+    )
+    .arg(
+      Arg::with_name("missed-votes-threshold")
+        .long("missed-votes-threshold")
+        .takes_value(true)
+        .help(
+          "Percentage of the backing votes missed by a para validator in a session above which the session message is sent as a critical message. (default: 0, disabled) [env: SCOUTY_MISSED_VOTES_THRESHOLD]",
+        ),
+    )
+    .arg(
+      Arg::with_name("stake-margin-threshold")
+        .long("stake-margin-threshold")
+        .takes_value(true)
+        .help(
+          "Percentage above the minimum elected stake below which the total backing of an active stash is flagged as a critical message, once per era. (default: 0, disabled) [env: SCOUTY_STAKE_MARGIN_THRESHOLD]",
+        ),
+    )
+    .arg(
+      Arg::with_name("nominator-amount-threshold")
+        .long("nominator-amount-threshold")
+        .takes_value(true)
+        .help(
+          "Stake (in tokens) from which a nominator leaving a stash between eras is sent as a critical message, and a nominator arriving as an info message. (default: 0, disabled) [env: SCOUTY_NOMINATOR_AMOUNT_THRESHOLD]",
+        ),
+    )
+    .arg(
+      Arg::with_name("backing-drop-threshold")
+        .long("backing-drop-threshold")
+        .takes_value(true)
+        .help(
+          "Percentage by which the total backing of a stash has to drop between eras to be sent as a critical message. (default: 0, disabled) [env: SCOUTY_BACKING_DROP_THRESHOLD]",
+        ),
+    )
+    .arg(
+      Arg::with_name("max-commission")
+        .long("max-commission")
+        .takes_value(true)
+        .help(
+          "Maximum commission (in percentage) allowed by the policy of the stashes predefined, e.g. the limit of the Thousand Validators programme. The minimum commission allowed is read from the chain. (default: 100) [env: SCOUTY_MAX_COMMISSION]",
+        ),
+    )
+    .arg(
+      Arg::with_name("payout-chains")
+        .long("payout-chains")
+        .takes_value(true)
+        .help(
+          "Chains for which the payouts of the unclaimed eras of the stashes predefined are submitted after every era paid. If needed specify more than one (e.g. polkadot,kusama). (default: disabled) [env: SCOUTY_PAYOUT_CHAINS]",
+        ),
+    )
+    .arg(
+      Arg::with_name("payout-seed-path")
+        .long("payout-seed-path")
+        .takes_value(true)
+        .value_name("FILE")
+        .help(
+          "Sets the path of the file with the secret seed of the account that signs the payouts. The seed is never logged. [env: SCOUTY_PAYOUT_SEED_PATH]",
+        ),
+    )
+    .arg(
+      Arg::with_name("payout-eras-depth")
+        .long("payout-eras-depth")
+        .takes_value(true)
+        .help(
+          "Number of the last eras verified for unclaimed rewards. (default: 8) [env: SCOUTY_PAYOUT_ERAS_DEPTH]",
+        ),
+    )
+    .arg(
+      Arg::with_name("payout-max-eras")
+        .long("payout-max-eras")
+        .takes_value(true)
+        .help(
+          "Maximum number of eras paid out after every era paid. (default: 4) [env: SCOUTY_PAYOUT_MAX_ERAS]",
+        ),
+    )
+    .arg(
+      Arg::with_name("unclaimed-eras-threshold")
+        .long("unclaimed-eras-threshold")
+        .takes_value(true)
+        .help(
+          "Number of eras after which the rewards not claimed yet of a stash are sent as a critical message, before they expire. (default: 60, set 0 to disable) [env: SCOUTY_UNCLAIMED_ERAS_THRESHOLD]",
+        ),
+    )
+    .arg(
+      Arg::with_name("disable-catch-up")
+        .long("disable-catch-up")
+        .help(
+          "Disable the digest sent at startup with the era points, active status, rewards and slashes of the stashes in the eras missed while 'scouty' was down. [env: SCOUTY_CATCH_UP_DISABLED]",
+        ),
+    )
+    .arg(
+      Arg::with_name("catch-up-lookback-eras")
+        .long("catch-up-lookback-eras")
+        .takes_value(true)
+        .help(
+          "Number of eras summarized at startup if the last session processed is not known, e.g. on the first run. The eras summarized are bounded by the history depth, with a maximum of 84 eras. (default: 0) [env: SCOUTY_CATCH_UP_LOOKBACK_ERAS]",
+        ),
+    )
+    .arg(
+      Arg::with_name("enable-catch-up-hooks")
+        .long("enable-catch-up-hooks")
+        .help(
+          "Run the catch up hook for every stash with the eras missed while 'scouty' was down. By default no hook is run for the conditions that are already stale. [env: SCOUTY_CATCH_UP_HOOKS_ENABLED]",
+        ),
+    )
+    .arg(
+      Arg::with_name("watch-balance-accounts")
+        .long("watch-balance-accounts")
+        .takes_value(true)
+        .help(
+          "Accounts whose free balance is verified at every new session, e.g. the account that signs the payouts, defined as chain:account:min_free_balance (in tokens). A critical message is sent when the free balance drops below the minimum, and again only after it recovers. If needed specify more than one (e.g. polkadot:15abc..:10,kusama:Habc..:1). (default: disabled) [env: SCOUTY_WATCH_BALANCE_ACCOUNTS]",
+        ),
+    )
+    .arg(
+      Arg::with_name("account-types")
+        .long("account-types")
+        .takes_value(true)
+        .help(
+          "Type of the stashes predefined, defined as account:type with the type validator or nominator. The nominations of the nominator accounts are verified at every new era and summarized in a message with warnings for the validators nominated that are inactive, oversubscribed, slashed or raised commission, no validator hook is run for them. If needed specify more than one (e.g. 15abc..:nominator,16abc..:nominator). (default: validator) [env: SCOUTY_ACCOUNT_TYPES]",
+        ),
+    )
+    .arg(
+      Arg::with_name("pools")
+        .long("pools")
+        .takes_value(true)
+        .help(
+          "Nomination pools verified at every new era, defined by their pool id or as pool_id:member to also report the pending rewards of a member account. The nominations of the bonded account of the pool are summarized in a message, and a critical message is sent when the pool is blocked, destroyed or its commission changes. If needed specify more than one (e.g. 12,34:15abc..). (default: disabled) [env: SCOUTY_POOLS]",
+        ),
+    )
+    .arg(
+      Arg::with_name("finality-max-lag-duration")
+        .long("finality-max-lag-duration")
+        .takes_value(true)
+        .help(
+          "Period (in seconds) that finality is allowed to lag more than the maximum number of blocks before it is considered stalled. (default: 120 seconds) [env: SCOUTY_FINALITY_MAX_LAG_DURATION]",
+        ),
+    )
+    .arg(
+      Arg::with_name("disable-node-syncing-wait")
+        .long("disable-node-syncing-wait")
+        .help(
+          "Disable the wait for the substrate node to be fully synced before 'scouty' subscribes to on-chain events. (e.g. with this flag active 'scouty' will also monitor nodes that are still syncing) [env: SCOUTY_NODE_SYNCING_WAIT_DISABLED]",
+        ),
+    )
+    .arg(
+      Arg::with_name("disable-session-keys-check")
+        .long("disable-session-keys-check")
+        .help(
+          "Disable the verification that the substrate node owns the next session keys of the stashes predefined. (e.g. with this flag active 'scouty' can be connected to a node other than the validator node itself) [env: SCOUTY_SESSION_KEYS_CHECK_DISABLED]",
+        ),
+    )
+    .arg(
+      Arg::with_name("generic-runtime")
+        .long("generic-runtime")
+        .help(
+          "Enable the generic runtime for substrate-based chains that are not natively supported but use the standard session and staking pallets. (e.g. with this flag active 'scouty' will only run the new session and starts active/inactive next era hooks for those chains) [env: SCOUTY_GENERIC_RUNTIME]",
+        ),
+    )
+    .arg(
+      Arg::with_name("state-path")
+        .long("state-path")
+        .takes_value(true)
+        .value_name("FILE")
+        .help(
+          "Sets a custom state file path. The state file keeps track of the hooks already run between restarts (e.g. the last era for which the new era hook has been run, the last session and era paid processed), so that they are not notified again after a restart. [env: SCOUTY_STATE_PATH]",
+        ),
     )
     .arg(
       Arg::with_name("config-path")
@@ -195,128 +2158,401 @@ fn get_config() -> Config {
         .value_name("FILE")
         .default_value(".env")
         .help(
-          "Sets a custom config file path. The config file contains 'scouty' configuration variables.",
+          "Sets a custom config file path. The config file contains 'scouty' configuration variables. [env: SCOUTY_CONFIG_FILENAME]",
+        ),
+    )
+    .arg(
+      Arg::with_name("config")
+        .long("config")
+        .takes_value(true)
+        .value_name("FILE")
+        .help(
+          "Sets the TOML config file path, environment variables override the values of the file. (default: /etc/scouty/scouty.toml if it exists) [env: SCOUTY_CONFIG]",
         ),
     )
     .arg(
       Arg::with_name("expose-network")
         .long("expose-network")
         .help(
-          "Expose the network name, token symbol and token decimal under new positional arguments for each hook.",
+          "Expose the network name, token symbol and token decimal under new positional arguments for each hook. [env: SCOUTY_EXPOSE_NETWORK]",
         ),
       )
     .arg(
       Arg::with_name("expose-nominators")
         .long("expose-nominators")
         .help(
-          "Expose ACTIVE nominator details under new positional arguments for some of the hooks. Note: `scouty` only look after active nominators for each validator stash predefined.",
+          "Expose ACTIVE nominator details under new positional arguments for some of the hooks. Note: `scouty` only look after active nominators for each validator stash predefined. [env: SCOUTY_EXPOSE_NOMINATORS]",
         ),
       )
     .arg(
         Arg::with_name("expose-authored-blocks")
           .long("expose-authored-blocks")
           .help(
-            "Expose the number of blocks authored by each validator stash predefined.",
+            "Expose the number of blocks authored by each validator stash predefined. [env: SCOUTY_EXPOSE_AUTHORED_BLOCKS]",
           ),
         )
     .arg(
       Arg::with_name("expose-all-nominators")
         .long("expose-all-nominators")
         .help(
-          "Expose ALL nominator details under new positional arguments for some of the hooks. Note: `scouty` only look after all nominators for each validator stash predefined.",
+          "Expose ALL nominator details under new positional arguments for some of the hooks. Note: `scouty` only look after all nominators for each validator stash predefined. [env: SCOUTY_EXPOSE_ALL_NOMINATORS]",
         ),
       )
     .arg(
       Arg::with_name("expose-para-validator")
         .long("expose-para-validator")
         .help(
-          "Expose the para validator details under new positional arguments for some of the hooks.",
+          "Expose the para validator details under new positional arguments for some of the hooks. [env: SCOUTY_EXPOSE_PARA_VALIDATOR]",
+        ),
+      )
+    .arg(
+      Arg::with_name("expose-era-points")
+        .long("expose-era-points")
+        .help(
+          "Expose the era points details under new positional arguments for the `_new_era` hook. [env: SCOUTY_EXPOSE_ERA_POINTS]",
+        ),
+      )
+    .arg(
+      Arg::with_name("expose-all")
+        .long("expose-all")
+        .help(
+          "Expose all positional arguments for some of the hooks. Note: Each hook bash script describes which data is available through the positional arguments. [env: SCOUTY_EXPOSE_ALL]",
+        ),
+      )
+    .arg(
+      Arg::with_name("hook-timeout")
+        .long("hook-timeout")
+        .takes_value(true)
+        .help(
+          "Timeout value (in seconds) for every hook script to finish, otherwise the script and any process spawned by it are killed. (default: 300 seconds, set 0 to disable) [env: SCOUTY_HOOK_TIMEOUT]",
+        ),
+    )
+    .arg(
+      Arg::with_name("hook-retries")
+        .long("hook-retries")
+        .takes_value(true)
+        .help(
+          "Number of times a hook script is retried if it exits with error. Hook scripts killed on timeout or that exit with code 75 are not retried. (default: 0) [env: SCOUTY_HOOK_RETRIES]",
+        ),
+    )
+    .arg(
+      Arg::with_name("hook-retry-delay")
+        .long("hook-retry-delay")
+        .takes_value(true)
+        .help(
+          "Delay value (in seconds) between retries of a failed hook script. (default: 10 seconds) [env: SCOUTY_HOOK_RETRY_DELAY]",
+        ),
+    )
+    .arg(
+      Arg::with_name("hooks-blocking")
+        .long("hooks-blocking")
+        .help(
+          "Run hook scripts one at a time while processing on-chain events. (e.g. with this flag active 'scouty' waits for each hook script to finish and includes its output in the same message, otherwise hook scripts run in the background) [env: SCOUTY_HOOKS_BLOCKING]",
+        ),
+    )
+    .arg(
+      Arg::with_name("max-concurrent-hooks")
+        .long("max-concurrent-hooks")
+        .takes_value(true)
+        .help(
+          "Maximum number of hook scripts running in the background at the same time. (default: 0, unlimited) [env: SCOUTY_MAX_CONCURRENT_HOOKS]",
+        ),
+    )
+    .arg(
+      Arg::with_name("hook-overlap-skip")
+        .long("hook-overlap-skip")
+        .help(
+          "Skip a hook script if the same hook is still running for the same stash. (e.g. by default the hook script is queued and runs once the previous one finishes) [env: SCOUTY_HOOK_OVERLAP_SKIP]",
+        ),
+    )
+    .arg(
+      Arg::with_name("hook-log-path")
+        .long("hook-log-path")
+        .takes_value(true)
+        .value_name("FILE")
+        .help(
+          "Sets the path for the hook history log, where a JSON line is appended for every hook script run. (e.g. /var/log/scouty/hooks.jsonl) [env: SCOUTY_HOOK_LOG_PATH]",
+        ),
+    )
+    .arg(
+      Arg::with_name("hook-log-max-size")
+        .long("hook-log-max-size")
+        .takes_value(true)
+        .help(
+          "Size (in megabytes) at which the hook history log is rotated. (default: 10 megabytes) [env: SCOUTY_HOOK_LOG_MAX_SIZE]",
+        ),
+    )
+    .arg(
+      Arg::with_name("hook-output-to-matrix")
+        .long("hook-output-to-matrix")
+        .help(
+          "Include the full output of the hook scripts in the matrix message. (e.g. by default only the output lines starting with the special character '!' are included) [env: SCOUTY_HOOK_OUTPUT_TO_MATRIX]",
+        ),
+    )
+    .arg(
+      Arg::with_name("hooks-strict")
+        .long("hooks-strict")
+        .help(
+          "Stop 'scouty' at startup if any hook script configured is not defined, not a file or not executable. (e.g. by default only a warning is logged for these hook scripts) [env: SCOUTY_HOOKS_STRICT]",
+        ),
+    )
+    .arg(
+      Arg::with_name("hooks-dir")
+        .long("hooks-dir")
+        .takes_value(true)
+        .value_name("DIR")
+        .help(
+          "Sets the directory where hook scripts are discovered by their conventional names (e.g. _new_session.sh, new_era or validator_offline.sh). Hook paths set explicitly take precedence over the scripts discovered. [env: SCOUTY_HOOKS_DIR]",
+        ),
+    )
+    .arg(
+      Arg::with_name("hooks-dir-polkadot")
+        .long("hooks-dir-polkadot")
+        .takes_value(true)
+        .value_name("DIR")
+        .help(
+          "Sets the directory where the hook scripts run for Polkadot only are discovered by their conventional names. The hooks not found in the directory fall back to the hooks defined for every chain. [env: SCOUTY_HOOKS_DIR_POLKADOT]",
+        ),
+    )
+    .arg(
+      Arg::with_name("hooks-dir-kusama")
+        .long("hooks-dir-kusama")
+        .takes_value(true)
+        .value_name("DIR")
+        .help(
+          "Sets the directory where the hook scripts run for Kusama only are discovered by their conventional names. The hooks not found in the directory fall back to the hooks defined for every chain. [env: SCOUTY_HOOKS_DIR_KUSAMA]",
+        ),
+    )
+    .arg(
+      Arg::with_name("slash-summary")
+        .long("slash-summary")
+        .help(
+          "Send a summary message when other validators than the stashes predefined are slashed or reported for an offence. (e.g. 3 validators slashed in era 1234) [env: SCOUTY_SLASH_SUMMARY]",
+        ),
+    )
+    .arg(
+        Arg::with_name("hook-init-path")
+          .long("hook-init-path")
+          .takes_value(true)
+          .value_name("FILE")
+          .help(
+            "Sets the path for the script that is called every time `scouty` starts. Here is a good place for try out new things and test new scripts. [env: SCOUTY_HOOK_INIT_PATH]",
+          ),
+      )
+    .arg(
+      Arg::with_name("hook-new-session-path")
+        .long("hook-new-session-path")
+        .takes_value(true)
+        .value_name("FILE")
+        .help(
+          "Sets the path for the script that is called every new session. [env: SCOUTY_HOOK_NEW_SESSION_PATH]",
+        ),
+    )
+    .arg(
+      Arg::with_name("hook-new-era-path")
+        .long("hook-new-era-path")
+        .takes_value(true)
+        .value_name("FILE")
+        .help(
+          "Sets the path for the script that is called every new era. [env: SCOUTY_HOOK_NEW_ERA_PATH]",
+        ),
+    )
+    .arg(
+      Arg::with_name("hook-era-paid-path")
+        .long("hook-era-paid-path")
+        .takes_value(true)
+        .value_name("FILE")
+        .help(
+          "Sets the path for the script that is called every time an era is paid. [env: SCOUTY_HOOK_ERA_PAID_PATH]",
+        ),
+    )
+    .arg(
+      Arg::with_name("hook-validator-starts-active-next-era-path")
+        .long("hook-validator-starts-active-next-era-path")
+        .takes_value(true)
+        .value_name("FILE")
+        .help(
+          "Sets the path for the script that is called on the last session of an era, if the stash is NOT ACTIVE and keys are QUEUED for the next Session/Era. [env: SCOUTY_HOOK_VALIDATOR_STARTS_ACTIVE_NEXT_ERA_PATH]",
+        ),
+    )
+    .arg(
+      Arg::with_name("hook-validator-starts-inactive-next-era-path")
+        .long("hook-validator-starts-inactive-next-era-path")
+        .takes_value(true)
+        .value_name("FILE")
+        .help(
+          "Sets the path for the script that is called on the last session of an era, if the stash is ACTIVE and keys are NOT QUEUED for the next Session/Era. [env: SCOUTY_HOOK_VALIDATOR_STARTS_INACTIVE_NEXT_ERA_PATH]",
+        ),
+    )
+    .arg(
+      Arg::with_name("hook-validator-slashed-path")
+        .long("hook-validator-slashed-path")
+        .takes_value(true)
+        .value_name("FILE")
+        .help(
+          "Sets the path for the script that is called every time a Slash occurred on the network. [env: SCOUTY_HOOK_VALIDATOR_SLASHED_PATH]",
+        ),
+    )
+    .arg(
+      Arg::with_name("hook-validator-chilled-path")
+        .long("hook-validator-chilled-path")
+        .takes_value(true)
+        .value_name("FILE")
+        .help(
+          "Sets the path for the script that is called every time one of the Validator stashes defined is chilled. [env: SCOUTY_HOOK_VALIDATOR_CHILLED_PATH]",
+        ),
+    )
+    .arg(
+      Arg::with_name("hook-validator-offline-path")
+        .long("hook-validator-offline-path")
+        .takes_value(true)
+        .value_name("FILE")
+        .help(
+          "Sets the path for the script that is called every time one of the Validator stashes defined is offline at the end of a session. [env: SCOUTY_HOOK_VALIDATOR_OFFLINE_PATH]",
+        ),
+    )
+    .arg(
+      Arg::with_name("hook-validator-offline-risk-path")
+        .long("hook-validator-offline-risk-path")
+        .takes_value(true)
+        .value_name("FILE")
+        .help(
+          "Sets the path for the script that is called every time one of the Validator stashes defined has not sent a heartbeat nor authored a block late in a session. [env: SCOUTY_HOOK_VALIDATOR_OFFLINE_RISK_PATH]",
+        ),
+    )
+    .arg(
+      Arg::with_name("hook-session-keys-mismatch-path")
+        .long("hook-session-keys-mismatch-path")
+        .takes_value(true)
+        .value_name("FILE")
+        .help(
+          "Sets the path for the script that is called every time the substrate node stops owning the next session keys of one of the Validator stashes defined. [env: SCOUTY_HOOK_SESSION_KEYS_MISMATCH_PATH]",
+        ),
+    )
+    .arg(
+      Arg::with_name("hook-validator-prefs-changed-path")
+        .long("hook-validator-prefs-changed-path")
+        .takes_value(true)
+        .value_name("FILE")
+        .help(
+          "Sets the path for the script that is called every time the commission or the blocked preference of one of the Validator stashes defined is changed. [env: SCOUTY_HOOK_VALIDATOR_PREFS_CHANGED_PATH]",
+        ),
+    )
+    .arg(
+      Arg::with_name("hook-dispute-path")
+        .long("hook-dispute-path")
+        .takes_value(true)
+        .value_name("FILE")
+        .help(
+          "Sets the path for the script that is called every time a dispute vote of one of the Validator stashes defined is included on-chain. [env: SCOUTY_HOOK_DISPUTE_PATH]",
+        ),
+    )
+    .arg(
+      Arg::with_name("hook-node-unhealthy-path")
+        .long("hook-node-unhealthy-path")
+        .takes_value(true)
+        .value_name("FILE")
+        .help(
+          "Sets the path for the script that is called every time the substrate node becomes unhealthy (e.g. low number of peers or syncing for too long). [env: SCOUTY_HOOK_NODE_UNHEALTHY_PATH]",
+        ),
+    )
+    .arg(
+      Arg::with_name("hook-finality-stalled-path")
+        .long("hook-finality-stalled-path")
+        .takes_value(true)
+        .value_name("FILE")
+        .help(
+          "Sets the path for the script that is called every time finality lags behind the best block for too long. [env: SCOUTY_HOOK_FINALITY_STALLED_PATH]",
+        ),
+    )
+    .arg(
+      Arg::with_name("hook-runtime-upgrade-path")
+        .long("hook-runtime-upgrade-path")
+        .takes_value(true)
+        .value_name("FILE")
+        .help(
+          "Sets the path for the script that is called every time the runtime is upgraded. [env: SCOUTY_HOOK_RUNTIME_UPGRADE_PATH]",
         ),
-      )
+    )
     .arg(
-      Arg::with_name("expose-era-points")
-        .long("expose-era-points")
+      Arg::with_name("hook-commission-out-of-policy-path")
+        .long("hook-commission-out-of-policy-path")
+        .takes_value(true)
+        .value_name("FILE")
         .help(
-          "Expose the era points details under new positional arguments for the `_new_era` hook.",
+          "Sets the path for the script that is called every time the commission of one of the Validator stashes defined falls outside the range allowed by the policy. [env: SCOUTY_HOOK_COMMISSION_OUT_OF_POLICY_PATH]",
         ),
-      )
+    )
     .arg(
-      Arg::with_name("expose-all")
-        .long("expose-all")
+      Arg::with_name("hook-validator-disabled-path")
+        .long("hook-validator-disabled-path")
+        .takes_value(true)
+        .value_name("FILE")
         .help(
-          "Expose all positional arguments for some of the hooks. Note: Each hook bash script describes which data is available through the positional arguments.",
+          "Sets the path for the script that is called every time one of the Validator stashes defined is disabled on-chain for the remainder of the session. [env: SCOUTY_HOOK_VALIDATOR_DISABLED_PATH]",
         ),
-      )
-    .arg(
-        Arg::with_name("hook-init-path")
-          .long("hook-init-path")
-          .takes_value(true)
-          .value_name("FILE")
-          .help(
-            "Sets the path for the script that is called every time `scouty` starts. Here is a good place for try out new things and test new scripts.",
-          ),
-      )
+    )
     .arg(
-      Arg::with_name("hook-new-session-path")
-        .long("hook-new-session-path")
+      Arg::with_name("hook-payee-changed-path")
+        .long("hook-payee-changed-path")
         .takes_value(true)
         .value_name("FILE")
         .help(
-          "Sets the path for the script that is called every new session.",
+          "Sets the path for the script that is called every time the reward destination of one of the Validator stashes defined changes. [env: SCOUTY_HOOK_PAYEE_CHANGED_PATH]",
         ),
     )
     .arg(
-      Arg::with_name("hook-new-era-path")
-        .long("hook-new-era-path")
+      Arg::with_name("hook-low-balance-path")
+        .long("hook-low-balance-path")
         .takes_value(true)
         .value_name("FILE")
         .help(
-          "Sets the path for the script that is called every new era.",
+          "Sets the path for the script that is called every time the free balance of one of the accounts watched drops below the minimum defined. [env: SCOUTY_HOOK_LOW_BALANCE_PATH]",
         ),
     )
     .arg(
-      Arg::with_name("hook-validator-starts-active-next-era-path")
-        .long("hook-validator-starts-active-next-era-path")
+      Arg::with_name("hook-offence-reported-path")
+        .long("hook-offence-reported-path")
         .takes_value(true)
         .value_name("FILE")
         .help(
-          "Sets the path for the script that is called on the last session of an era, if the stash is NOT ACTIVE and keys are QUEUED for the next Session/Era.",
+          "Sets the path for the script that is called every time an offence (e.g. a BABE or GRANDPA equivocation) is reported against one of the Validator stashes defined. [env: SCOUTY_HOOK_OFFENCE_REPORTED_PATH]",
         ),
     )
     .arg(
-      Arg::with_name("hook-validator-starts-inactive-next-era-path")
-        .long("hook-validator-starts-inactive-next-era-path")
+      Arg::with_name("hook-catch-up-path")
+        .long("hook-catch-up-path")
         .takes_value(true)
         .value_name("FILE")
         .help(
-          "Sets the path for the script that is called on the last session of an era, if the stash is ACTIVE and keys are NOT QUEUED for the next Session/Era.",
+          "Sets the path for the script that is called at startup for every one of the Validator stashes defined with the eras missed while 'scouty' was down, only if --enable-catch-up-hooks is set. [env: SCOUTY_HOOK_CATCH_UP_PATH]",
         ),
     )
     .arg(
-      Arg::with_name("hook-validator-slashed-path")
-        .long("hook-validator-slashed-path")
+      Arg::with_name("hook-weekly-report-path")
+        .long("hook-weekly-report-path")
         .takes_value(true)
         .value_name("FILE")
         .help(
-          "Sets the path for the script that is called every time a Slash occurred on the network.",
+          "Sets the path for the script that is called for every one of the Validator stashes defined when the weekly report is sent, with the stats of the week. [env: SCOUTY_HOOK_WEEKLY_REPORT_PATH]",
         ),
     )
     .arg(
-      Arg::with_name("hook-validator-chilled-path")
-        .long("hook-validator-chilled-path")
+      Arg::with_name("hook-nomination-inactive-path")
+        .long("hook-nomination-inactive-path")
         .takes_value(true)
         .value_name("FILE")
         .help(
-          "Sets the path for the script that is called every time one of the Validator stashes defined is chilled.",
+          "Sets the path for the script that is called every new era for every one of the nominator accounts defined whose stake is not applied to any of the validators nominated. [env: SCOUTY_HOOK_NOMINATION_INACTIVE_PATH]",
         ),
     )
     .arg(
-      Arg::with_name("hook-validator-offline-path")
-        .long("hook-validator-offline-path")
+      Arg::with_name("hook-payout-submitted-path")
+        .long("hook-payout-submitted-path")
         .takes_value(true)
         .value_name("FILE")
         .help(
-          "Sets the path for the script that is called every time one of the Validator stashes defined is offline at the end of a session.",
+          "Sets the path for the script that is called every time a payout of an unclaimed era is submitted. [env: SCOUTY_HOOK_PAYOUT_SUBMITTED_PATH]",
         ),
     )
     .get_matches();
@@ -324,17 +2560,21 @@ fn get_config() -> Config {
     // Try to load configuration from file first
     let config_path = matches.value_of("config-path").unwrap_or(".env");
 
-    match dotenv::from_filename(&config_path).ok() {
+    match dotenv::from_filename(config_path).ok() {
         Some(_) => info!("Loading configuration from {} file", &config_path),
         None => {
             let config_path =
                 env::var("SCOUTY_CONFIG_FILENAME").unwrap_or(".env".to_string());
-            if let Some(_) = dotenv::from_filename(&config_path).ok() {
+            if dotenv::from_filename(&config_path).is_ok() {
                 info!("Loading configuration from {} file", &config_path);
             }
         }
     }
 
+    if let Some(config) = matches.value_of("config") {
+        env::set_var("SCOUTY_CONFIG", config);
+    }
+
     match matches.value_of("CHAIN") {
         Some("westend") => {
             env::set_var(
@@ -358,85 +2598,472 @@ fn get_config() -> Config {
         }
     }
 
-    if let Some(stashes) = matches.value_of("stashes") {
-        env::set_var("SCOUTY_STASHES", stashes);
+    if matches.is_present("stash-prefix-mismatch-allowed") {
+        env::set_var("SCOUTY_STASH_PREFIX_MISMATCH_ALLOWED", "true");
+    }
+
+    if let Some(stashes) = matches.values_of("stashes") {
+        env::set_var("SCOUTY_STASHES", stashes.collect::<Vec<&str>>().join(","));
+    }
+
+    if let Some(stashes_url) = matches.value_of("stashes-url") {
+        env::set_var("SCOUTY_STASHES_URL", stashes_url);
+    }
+
+    if let Some(stashes_url_interval) = matches.value_of("stashes-url-interval") {
+        env::set_var("SCOUTY_STASHES_URL_INTERVAL", stashes_url_interval);
+    }
+
+    if let Some(stashes_path) = matches.value_of("stashes-path") {
+        env::set_var("SCOUTY_STASHES_PATH", stashes_path);
     }
 
     if let Some(substrate_ws_url) = matches.value_of("substrate-ws-url") {
         env::set_var("SCOUTY_SUBSTRATE_WS_URL", substrate_ws_url);
     }
 
-    if matches.is_present("debug") {
-        env::set_var("SCOUTY_IS_DEBUG", "true");
+    if let Some(substrate_ws_urls_polkadot) =
+        matches.value_of("substrate-ws-urls-polkadot")
+    {
+        env::set_var(
+            "SCOUTY_SUBSTRATE_WS_URLS_POLKADOT",
+            substrate_ws_urls_polkadot,
+        );
+    }
+
+    if let Some(substrate_ws_urls_kusama) = matches.value_of("substrate-ws-urls-kusama") {
+        env::set_var("SCOUTY_SUBSTRATE_WS_URLS_KUSAMA", substrate_ws_urls_kusama);
+    }
+
+    if let Some(stashes_polkadot) = matches.value_of("stashes-polkadot") {
+        env::set_var("SCOUTY_STASHES_POLKADOT", stashes_polkadot);
+    }
+
+    if let Some(stashes_kusama) = matches.value_of("stashes-kusama") {
+        env::set_var("SCOUTY_STASHES_KUSAMA", stashes_kusama);
+    }
+
+    if let Some(substrate_ws_url_max_attempts) =
+        matches.value_of("substrate-ws-url-max-attempts")
+    {
+        env::set_var(
+            "SCOUTY_SUBSTRATE_WS_URL_MAX_ATTEMPTS",
+            substrate_ws_url_max_attempts,
+        );
+    }
+
+    if let Some(reconnect_initial_interval) =
+        matches.value_of("reconnect-initial-interval")
+    {
+        env::set_var(
+            "SCOUTY_RECONNECT_INITIAL_INTERVAL",
+            reconnect_initial_interval,
+        );
+    }
+
+    if let Some(reconnect_max_interval) = matches.value_of("reconnect-max-interval") {
+        env::set_var("SCOUTY_RECONNECT_MAX_INTERVAL", reconnect_max_interval);
+    }
+
+    if let Some(connection_timeout) = matches.value_of("connection-timeout") {
+        env::set_var("SCOUTY_CONNECTION_TIMEOUT", connection_timeout);
+    }
+
+    if let Some(request_timeout) = matches.value_of("request-timeout") {
+        env::set_var("SCOUTY_REQUEST_TIMEOUT", request_timeout);
+    }
+
+    if let Some(health_check_interval) = matches.value_of("health-check-interval") {
+        env::set_var("SCOUTY_HEALTH_CHECK_INTERVAL", health_check_interval);
+    }
+
+    if let Some(health_min_peers) = matches.value_of("health-min-peers") {
+        env::set_var("SCOUTY_HEALTH_MIN_PEERS", health_min_peers);
+    }
+
+    if let Some(health_syncing_grace_period) =
+        matches.value_of("health-syncing-grace-period")
+    {
+        env::set_var(
+            "SCOUTY_HEALTH_SYNCING_GRACE_PERIOD",
+            health_syncing_grace_period,
+        );
+    }
+
+    if let Some(finality_max_lag) = matches.value_of("finality-max-lag") {
+        env::set_var("SCOUTY_FINALITY_MAX_LAG", finality_max_lag);
+    }
+
+    if let Some(offline_risk_threshold) = matches.value_of("offline-risk-threshold") {
+        env::set_var("SCOUTY_OFFLINE_RISK_THRESHOLD", offline_risk_threshold);
+    }
+
+    if let Some(era_points_threshold) = matches.value_of("era-points-threshold") {
+        env::set_var("SCOUTY_ERA_POINTS_THRESHOLD", era_points_threshold);
+    }
+
+    if let Some(missed_votes_threshold) = matches.value_of("missed-votes-threshold") {
+        env::set_var("SCOUTY_MISSED_VOTES_THRESHOLD", missed_votes_threshold);
+    }
+
+    if let Some(stake_margin_threshold) = matches.value_of("stake-margin-threshold") {
+        env::set_var("SCOUTY_STAKE_MARGIN_THRESHOLD", stake_margin_threshold);
+    }
+
+    if let Some(nominator_amount_threshold) =
+        matches.value_of("nominator-amount-threshold")
+    {
+        env::set_var(
+            "SCOUTY_NOMINATOR_AMOUNT_THRESHOLD",
+            nominator_amount_threshold,
+        );
+    }
+
+    if let Some(backing_drop_threshold) = matches.value_of("backing-drop-threshold") {
+        env::set_var("SCOUTY_BACKING_DROP_THRESHOLD", backing_drop_threshold);
+    }
+
+    if let Some(max_commission) = matches.value_of("max-commission") {
+        env::set_var("SCOUTY_MAX_COMMISSION", max_commission);
+    }
+
+    if let Some(payout_chains) = matches.value_of("payout-chains") {
+        env::set_var("SCOUTY_PAYOUT_CHAINS", payout_chains);
+    }
+
+    if let Some(payout_seed_path) = matches.value_of("payout-seed-path") {
+        env::set_var("SCOUTY_PAYOUT_SEED_PATH", payout_seed_path);
+    }
+
+    if let Some(payout_eras_depth) = matches.value_of("payout-eras-depth") {
+        env::set_var("SCOUTY_PAYOUT_ERAS_DEPTH", payout_eras_depth);
+    }
+
+    if let Some(payout_max_eras) = matches.value_of("payout-max-eras") {
+        env::set_var("SCOUTY_PAYOUT_MAX_ERAS", payout_max_eras);
+    }
+
+    if let Some(unclaimed_eras_threshold) = matches.value_of("unclaimed-eras-threshold") {
+        env::set_var("SCOUTY_UNCLAIMED_ERAS_THRESHOLD", unclaimed_eras_threshold);
+    }
+
+    if matches.is_present("disable-catch-up") {
+        env::set_var("SCOUTY_CATCH_UP_DISABLED", "true");
+    }
+
+    if let Some(catch_up_lookback_eras) = matches.value_of("catch-up-lookback-eras") {
+        env::set_var("SCOUTY_CATCH_UP_LOOKBACK_ERAS", catch_up_lookback_eras);
+    }
+
+    if matches.is_present("enable-catch-up-hooks") {
+        env::set_var("SCOUTY_CATCH_UP_HOOKS_ENABLED", "true");
+    }
+
+    if let Some(watch_balance_accounts) = matches.value_of("watch-balance-accounts") {
+        env::set_var("SCOUTY_WATCH_BALANCE_ACCOUNTS", watch_balance_accounts);
+    }
+
+    if let Some(account_types) = matches.value_of("account-types") {
+        env::set_var("SCOUTY_ACCOUNT_TYPES", account_types);
+    }
+
+    if let Some(pools) = matches.value_of("pools") {
+        env::set_var("SCOUTY_POOLS", pools);
+    }
+
+    if let Some(finality_max_lag_duration) = matches.value_of("finality-max-lag-duration")
+    {
+        env::set_var(
+            "SCOUTY_FINALITY_MAX_LAG_DURATION",
+            finality_max_lag_duration,
+        );
+    }
+
+    if let Some(state_path) = matches.value_of("state-path") {
+        env::set_var("SCOUTY_STATE_PATH", state_path);
+    }
+
+    if matches.is_present("debug") {
+        env::set_var("SCOUTY_IS_DEBUG", "true");
+    }
+
+    if matches.is_present("short") {
+        env::set_var("SCOUTY_IS_SHORT", "true");
+    }
+
+    if matches.is_present("dry-run") {
+        env::set_var("SCOUTY_DRY_RUN", "true");
+    }
+
+    if let Some(check) = matches.subcommand_matches("check") {
+        env::set_var("SCOUTY_ONESHOT", "true");
+
+        if check.is_present("json") {
+            env::set_var("SCOUTY_ONESHOT_JSON", "true");
+        }
+
+        if check.is_present("quiet") {
+            env::set_var("SCOUTY_ONESHOT_QUIET", "true");
+        }
+    }
+
+    if matches.subcommand_matches("config").is_some() {
+        env::set_var("SCOUTY_PRINT_CONFIG", "true");
+    }
+
+    if matches.subcommand_matches("test-matrix").is_some() {
+        env::set_var("SCOUTY_TEST_MATRIX", "true");
+    }
+
+    if let Some(test_hook) = matches.subcommand_matches("test-hook") {
+        if let Some(name) = test_hook.value_of("NAME") {
+            env::set_var("SCOUTY_TEST_HOOK", name);
+        }
+
+        if let Some(args) = test_hook.values_of("args") {
+            env::set_var(
+                "SCOUTY_TEST_HOOK_ARGS",
+                args.collect::<Vec<&str>>().join(","),
+            );
+        }
+    }
+
+    if let Some(hook_timeout) = matches.value_of("hook-timeout") {
+        env::set_var("SCOUTY_HOOK_TIMEOUT", hook_timeout);
+    }
+
+    if let Some(hook_retries) = matches.value_of("hook-retries") {
+        env::set_var("SCOUTY_HOOK_RETRIES", hook_retries);
+    }
+
+    if let Some(hook_retry_delay) = matches.value_of("hook-retry-delay") {
+        env::set_var("SCOUTY_HOOK_RETRY_DELAY", hook_retry_delay);
+    }
+
+    if matches.is_present("hooks-blocking") {
+        env::set_var("SCOUTY_HOOKS_BLOCKING", "true");
+    }
+
+    if let Some(max_concurrent_hooks) = matches.value_of("max-concurrent-hooks") {
+        env::set_var("SCOUTY_MAX_CONCURRENT_HOOKS", max_concurrent_hooks);
+    }
+
+    if matches.is_present("hook-overlap-skip") {
+        env::set_var("SCOUTY_HOOK_OVERLAP_SKIP", "true");
+    }
+
+    if let Some(hook_log_path) = matches.value_of("hook-log-path") {
+        env::set_var("SCOUTY_HOOK_LOG_PATH", hook_log_path);
+    }
+
+    if let Some(hook_log_max_size) = matches.value_of("hook-log-max-size") {
+        env::set_var("SCOUTY_HOOK_LOG_MAX_SIZE", hook_log_max_size);
+    }
+
+    if matches.is_present("hook-output-to-matrix") {
+        env::set_var("SCOUTY_HOOK_OUTPUT_TO_MATRIX", "true");
+    }
+
+    if matches.is_present("hooks-strict") {
+        env::set_var("SCOUTY_HOOKS_STRICT", "true");
+    }
+
+    if let Some(hooks_dir) = matches.value_of("hooks-dir") {
+        env::set_var("SCOUTY_HOOKS_DIR", hooks_dir);
+    }
+
+    if let Some(hooks_dir_polkadot) = matches.value_of("hooks-dir-polkadot") {
+        env::set_var("SCOUTY_HOOKS_DIR_POLKADOT", hooks_dir_polkadot);
+    }
+
+    if let Some(hooks_dir_kusama) = matches.value_of("hooks-dir-kusama") {
+        env::set_var("SCOUTY_HOOKS_DIR_KUSAMA", hooks_dir_kusama);
+    }
+
+    if matches.is_present("slash-summary") {
+        env::set_var("SCOUTY_SLASH_SUMMARY", "true");
+    }
+
+    if let Some(hook_init_path) = matches.value_of("hook-init-path") {
+        env::set_var("SCOUTY_HOOK_INIT_PATH", hook_init_path);
+    }
+
+    if let Some(hook_new_session_path) = matches.value_of("hook-new-session-path") {
+        env::set_var("SCOUTY_HOOK_NEW_SESSION_PATH", hook_new_session_path);
+    }
+
+    if let Some(hook_new_era_path) = matches.value_of("hook-new-era-path") {
+        env::set_var("SCOUTY_HOOK_NEW_ERA_PATH", hook_new_era_path);
+    }
+
+    if let Some(hook_era_paid_path) = matches.value_of("hook-era-paid-path") {
+        env::set_var("SCOUTY_HOOK_ERA_PAID_PATH", hook_era_paid_path);
+    }
+
+    if let Some(hook_validator_starts_active_next_era_path) =
+        matches.value_of("hook-validator-starts-active-next-era-path")
+    {
+        env::set_var(
+            "SCOUTY_HOOK_VALIDATOR_STARTS_ACTIVE_NEXT_ERA_PATH",
+            hook_validator_starts_active_next_era_path,
+        );
+    }
+
+    if let Some(hook_validator_starts_inactive_next_era_path) =
+        matches.value_of("hook-validator-starts-inactive-next-era-path")
+    {
+        env::set_var(
+            "SCOUTY_HOOK_VALIDATOR_STARTS_INACTIVE_NEXT_ERA_PATH",
+            hook_validator_starts_inactive_next_era_path,
+        );
+    }
+
+    if let Some(hook_validator_slashed_path) =
+        matches.value_of("hook-validator-slashed-path")
+    {
+        env::set_var(
+            "SCOUTY_HOOK_VALIDATOR_SLASHED_PATH",
+            hook_validator_slashed_path,
+        );
+    }
+
+    if let Some(hook_validator_chilled_path) =
+        matches.value_of("hook-validator-chilled-path")
+    {
+        env::set_var(
+            "SCOUTY_HOOK_VALIDATOR_CHILLED_PATH",
+            hook_validator_chilled_path,
+        );
+    }
+
+    if let Some(hook_validator_offline_path) =
+        matches.value_of("hook-validator-offline-path")
+    {
+        env::set_var(
+            "SCOUTY_HOOK_VALIDATOR_OFFLINE_PATH",
+            hook_validator_offline_path,
+        );
+    }
+
+    if let Some(hook_validator_offline_risk_path) =
+        matches.value_of("hook-validator-offline-risk-path")
+    {
+        env::set_var(
+            "SCOUTY_HOOK_VALIDATOR_OFFLINE_RISK_PATH",
+            hook_validator_offline_risk_path,
+        );
+    }
+
+    if let Some(hook_session_keys_mismatch_path) =
+        matches.value_of("hook-session-keys-mismatch-path")
+    {
+        env::set_var(
+            "SCOUTY_HOOK_SESSION_KEYS_MISMATCH_PATH",
+            hook_session_keys_mismatch_path,
+        );
+    }
+
+    if let Some(hook_validator_prefs_changed_path) =
+        matches.value_of("hook-validator-prefs-changed-path")
+    {
+        env::set_var(
+            "SCOUTY_HOOK_VALIDATOR_PREFS_CHANGED_PATH",
+            hook_validator_prefs_changed_path,
+        );
     }
 
-    if matches.is_present("short") {
-        env::set_var("SCOUTY_IS_SHORT", "true");
+    if let Some(hook_dispute_path) = matches.value_of("hook-dispute-path") {
+        env::set_var("SCOUTY_HOOK_DISPUTE_PATH", hook_dispute_path);
     }
 
-    if let Some(hook_init_path) = matches.value_of("hook-init-path") {
-        env::set_var("SCOUTY_HOOK_INIT_PATH", hook_init_path);
+    if let Some(hook_referenda_submitted_path) =
+        matches.value_of("hook-referenda-submitted-path")
+    {
+        env::set_var(
+            "SCOUTY_HOOK_REFERENDA_SUBMITTED_PATH",
+            hook_referenda_submitted_path,
+        );
     }
 
-    if let Some(hook_new_session_path) = matches.value_of("hook-new-session-path") {
-        env::set_var("SCOUTY_HOOK_NEW_SESSION_PATH", hook_new_session_path);
+    if let Some(hook_node_unhealthy_path) = matches.value_of("hook-node-unhealthy-path") {
+        env::set_var("SCOUTY_HOOK_NODE_UNHEALTHY_PATH", hook_node_unhealthy_path);
     }
 
-    if let Some(hook_new_era_path) = matches.value_of("hook-new-era-path") {
-        env::set_var("SCOUTY_HOOK_NEW_ERA_PATH", hook_new_era_path);
+    if let Some(hook_finality_stalled_path) =
+        matches.value_of("hook-finality-stalled-path")
+    {
+        env::set_var(
+            "SCOUTY_HOOK_FINALITY_STALLED_PATH",
+            hook_finality_stalled_path,
+        );
     }
 
-    if let Some(hook_validator_starts_active_next_era_path) =
-        matches.value_of("hook-validator-starts-active-next-era-path")
+    if let Some(hook_runtime_upgrade_path) = matches.value_of("hook-runtime-upgrade-path")
     {
         env::set_var(
-            "SCOUTY_HOOK_VALIDATOR_STARTS_ACTIVE_NEXT_ERA_PATH",
-            hook_validator_starts_active_next_era_path,
+            "SCOUTY_HOOK_RUNTIME_UPGRADE_PATH",
+            hook_runtime_upgrade_path,
         );
     }
 
-    if let Some(hook_validator_starts_inactive_next_era_path) =
-        matches.value_of("hook-validator-starts-inactive-next-era-path")
+    if let Some(hook_commission_out_of_policy_path) =
+        matches.value_of("hook-commission-out-of-policy-path")
     {
         env::set_var(
-            "SCOUTY_HOOK_VALIDATOR_STARTS_INACTIVE_NEXT_ERA_PATH",
-            hook_validator_starts_inactive_next_era_path,
+            "SCOUTY_HOOK_COMMISSION_OUT_OF_POLICY_PATH",
+            hook_commission_out_of_policy_path,
         );
     }
 
-    if let Some(hook_validator_slashed_path) =
-        matches.value_of("hook-validator-slashed-path")
+    if let Some(hook_validator_disabled_path) =
+        matches.value_of("hook-validator-disabled-path")
     {
         env::set_var(
-            "SCOUTY_HOOK_VALIDATOR_SLASHED_PATH",
-            hook_validator_slashed_path,
+            "SCOUTY_HOOK_VALIDATOR_DISABLED_PATH",
+            hook_validator_disabled_path,
         );
     }
 
-    if let Some(hook_validator_chilled_path) =
-        matches.value_of("hook-validator-chilled-path")
+    if let Some(hook_payee_changed_path) = matches.value_of("hook-payee-changed-path") {
+        env::set_var("SCOUTY_HOOK_PAYEE_CHANGED_PATH", hook_payee_changed_path);
+    }
+
+    if let Some(hook_low_balance_path) = matches.value_of("hook-low-balance-path") {
+        env::set_var("SCOUTY_HOOK_LOW_BALANCE_PATH", hook_low_balance_path);
+    }
+
+    if let Some(hook_offence_reported_path) =
+        matches.value_of("hook-offence-reported-path")
     {
         env::set_var(
-            "SCOUTY_HOOK_VALIDATOR_CHILLED_PATH",
-            hook_validator_chilled_path,
+            "SCOUTY_HOOK_OFFENCE_REPORTED_PATH",
+            hook_offence_reported_path,
         );
     }
 
-    if let Some(hook_validator_offline_path) =
-        matches.value_of("hook-validator-offline-path")
+    if let Some(hook_catch_up_path) = matches.value_of("hook-catch-up-path") {
+        env::set_var("SCOUTY_HOOK_CATCH_UP_PATH", hook_catch_up_path);
+    }
+
+    if let Some(hook_weekly_report_path) = matches.value_of("hook-weekly-report-path") {
+        env::set_var("SCOUTY_HOOK_WEEKLY_REPORT_PATH", hook_weekly_report_path);
+    }
+
+    if let Some(hook_nomination_inactive_path) =
+        matches.value_of("hook-nomination-inactive-path")
     {
         env::set_var(
-            "SCOUTY_HOOK_VALIDATOR_OFFLINE_PATH",
-            hook_validator_offline_path,
+            "SCOUTY_HOOK_NOMINATION_INACTIVE_PATH",
+            hook_nomination_inactive_path,
         );
     }
 
-    if let Some(hook_referenda_submitted_path) =
-        matches.value_of("hook-referenda-submitted-path")
+    if let Some(hook_payout_submitted_path) =
+        matches.value_of("hook-payout-submitted-path")
     {
         env::set_var(
-            "SCOUTY_HOOK_REFERENDA_SUBMITTED_PATH",
-            hook_referenda_submitted_path,
+            "SCOUTY_HOOK_PAYOUT_SUBMITTED_PATH",
+            hook_payout_submitted_path,
         );
     }
 
@@ -472,12 +3099,28 @@ fn get_config() -> Config {
         env::set_var("SCOUTY_EXPOSE_ALL", "true");
     }
 
+    if matches.is_present("disable-node-syncing-wait") {
+        env::set_var("SCOUTY_NODE_SYNCING_WAIT_DISABLED", "true");
+    }
+
+    if matches.is_present("disable-session-keys-check") {
+        env::set_var("SCOUTY_SESSION_KEYS_CHECK_DISABLED", "true");
+    }
+
+    if matches.is_present("generic-runtime") {
+        env::set_var("SCOUTY_GENERIC_RUNTIME", "true");
+    }
+
     if matches.is_present("disable-matrix") {
         env::set_var("SCOUTY_MATRIX_DISABLED", "true");
     }
 
+    if matches.is_present("disable-matrix-bot-display-name") {
+        env::set_var("SCOUTY_MATRIX_BOT_DISPLAY_NAME_DISABLED", "true");
+    }
+
     if let Some(matrix_user) = matches.value_of("matrix-user") {
-        env::set_var("SCOUTY_MATRIX_ACCOUNT", matrix_user);
+        env::set_var("SCOUTY_MATRIX_USER", matrix_user);
     }
 
     if let Some(matrix_bot_user) = matches.value_of("matrix-bot-user") {
@@ -488,13 +3131,209 @@ fn get_config() -> Config {
         env::set_var("SCOUTY_MATRIX_BOT_PASSWORD", matrix_bot_password);
     }
 
+    if let Some(matrix_access_token) = matches.value_of("matrix-access-token") {
+        env::set_var("SCOUTY_MATRIX_ACCESS_TOKEN", matrix_access_token);
+    }
+
+    if let Some(matrix_session_path) = matches.value_of("matrix-session-path") {
+        env::set_var("SCOUTY_MATRIX_SESSION_PATH", matrix_session_path);
+    }
+
+    if let Some(matrix_room_info) = matches.value_of("matrix-room-info") {
+        env::set_var("SCOUTY_MATRIX_ROOM_INFO", matrix_room_info);
+    }
+
+    if let Some(matrix_room_critical) = matches.value_of("matrix-room-critical") {
+        env::set_var("SCOUTY_MATRIX_ROOM_CRITICAL", matrix_room_critical);
+    }
+
+    if let Some(matrix_rate_limit_retries) = matches.value_of("matrix-rate-limit-retries")
+    {
+        env::set_var(
+            "SCOUTY_MATRIX_RATE_LIMIT_RETRIES",
+            matrix_rate_limit_retries,
+        );
+    }
+
+    if let Some(matrix_retry_queue_size) = matches.value_of("matrix-retry-queue-size") {
+        env::set_var("SCOUTY_MATRIX_RETRY_QUEUE_SIZE", matrix_retry_queue_size);
+    }
+
+    if let Some(matrix_retry_max_age) = matches.value_of("matrix-retry-max-age") {
+        env::set_var("SCOUTY_MATRIX_RETRY_MAX_AGE", matrix_retry_max_age);
+    }
+
+    if let Some(templates_path) = matches.value_of("templates-path") {
+        env::set_var("SCOUTY_TEMPLATES_PATH", templates_path);
+    }
+
+    if let Some(block_explorer_url) = matches.value_of("block-explorer-url") {
+        env::set_var("SCOUTY_BLOCK_EXPLORER_URL", block_explorer_url);
+    }
+
+    if let Some(matrix_mention_users) = matches.value_of("matrix-mention-users") {
+        env::set_var("SCOUTY_MATRIX_MENTION_USERS", matrix_mention_users);
+    }
+
+    if let Some(matrix_msgtype) = matches.value_of("matrix-msgtype") {
+        env::set_var("SCOUTY_MATRIX_MSGTYPE", matrix_msgtype);
+    }
+
+    if let Some(matrix_msgtype_critical) = matches.value_of("matrix-msgtype-critical") {
+        env::set_var("SCOUTY_MATRIX_MSGTYPE_CRITICAL", matrix_msgtype_critical);
+    }
+
+    if let Some(webhook_url) = matches.value_of("webhook-url") {
+        env::set_var("SCOUTY_WEBHOOK_URL", webhook_url);
+    }
+
+    if let Some(webhook_token) = matches.value_of("webhook-token") {
+        env::set_var("SCOUTY_WEBHOOK_TOKEN", webhook_token);
+    }
+
+    if let Some(webhook_secret) = matches.value_of("webhook-secret") {
+        env::set_var("SCOUTY_WEBHOOK_SECRET", webhook_secret);
+    }
+
+    if let Some(telegram_bot_token) = matches.value_of("telegram-bot-token") {
+        env::set_var("SCOUTY_TELEGRAM_BOT_TOKEN", telegram_bot_token);
+    }
+
+    if let Some(telegram_chat_id) = matches.value_of("telegram-chat-id") {
+        env::set_var("SCOUTY_TELEGRAM_CHAT_ID", telegram_chat_id);
+    }
+
+    if let Some(discord_webhook_url) = matches.value_of("discord-webhook-url") {
+        env::set_var("SCOUTY_DISCORD_WEBHOOK_URL", discord_webhook_url);
+    }
+
+    if let Some(pagerduty_routing_key) = matches.value_of("pagerduty-routing-key") {
+        env::set_var("SCOUTY_PAGERDUTY_ROUTING_KEY", pagerduty_routing_key);
+    }
+
+    if let Some(pagerduty_min_severity) = matches.value_of("pagerduty-min-severity") {
+        env::set_var("SCOUTY_PAGERDUTY_MIN_SEVERITY", pagerduty_min_severity);
+    }
+
+    if let Some(smtp_host) = matches.value_of("smtp-host") {
+        env::set_var("SCOUTY_SMTP_HOST", smtp_host);
+    }
+
+    if let Some(smtp_port) = matches.value_of("smtp-port") {
+        env::set_var("SCOUTY_SMTP_PORT", smtp_port);
+    }
+
+    if let Some(smtp_tls) = matches.value_of("smtp-tls") {
+        env::set_var("SCOUTY_SMTP_TLS", smtp_tls);
+    }
+
+    if let Some(smtp_username) = matches.value_of("smtp-username") {
+        env::set_var("SCOUTY_SMTP_USERNAME", smtp_username);
+    }
+
+    if let Some(smtp_password) = matches.value_of("smtp-password") {
+        env::set_var("SCOUTY_SMTP_PASSWORD", smtp_password);
+    }
+
+    if let Some(smtp_from) = matches.value_of("smtp-from") {
+        env::set_var("SCOUTY_SMTP_FROM", smtp_from);
+    }
+
+    if let Some(smtp_to) = matches.value_of("smtp-to") {
+        env::set_var("SCOUTY_SMTP_TO", smtp_to);
+    }
+
+    if let Some(smtp_min_severity) = matches.value_of("smtp-min-severity") {
+        env::set_var("SCOUTY_SMTP_MIN_SEVERITY", smtp_min_severity);
+    }
+
+    if let Some(prometheus_port) = matches.value_of("prometheus-port") {
+        env::set_var("SCOUTY_PROMETHEUS_PORT", prometheus_port);
+    }
+
+    if let Some(healthcheck_ping_url) = matches.value_of("healthcheck-ping-url") {
+        env::set_var("SCOUTY_HEALTHCHECK_PING_URL", healthcheck_ping_url);
+    }
+
+    if matches.is_present("healthcheck-ping-fail") {
+        env::set_var("SCOUTY_HEALTHCHECK_PING_FAIL", "true");
+    }
+
+    if let Some(events_file_path) = matches.value_of("events-file-path") {
+        env::set_var("SCOUTY_EVENTS_FILE_PATH", events_file_path);
+    }
+
+    if let Some(events_file_max_size) = matches.value_of("events-file-max-size") {
+        env::set_var("SCOUTY_EVENTS_FILE_MAX_SIZE", events_file_max_size);
+    }
+
+    if matches.is_present("events-file-fsync-critical") {
+        env::set_var("SCOUTY_EVENTS_FILE_FSYNC_CRITICAL", "true");
+    }
+
+    if matches.is_present("matrix-batch-messages") {
+        env::set_var("SCOUTY_MATRIX_BATCH_MESSAGES", "true");
+    }
+
+    if let Some(report_mode) = matches.value_of("report-mode") {
+        env::set_var("SCOUTY_REPORT_MODE", report_mode);
+    }
+
+    if matches.is_present("notify-on-change-only") {
+        env::set_var("SCOUTY_NOTIFY_ON_CHANGE_ONLY", "true");
+    }
+
+    if matches.is_present("notify-era-heartbeat") {
+        env::set_var("SCOUTY_NOTIFY_ERA_HEARTBEAT", "true");
+    }
+
+    if matches.is_present("hooks-on-change-only") {
+        env::set_var("SCOUTY_HOOKS_ON_CHANGE_ONLY", "true");
+    }
+
+    if matches.is_present("enable-matrix-commands") {
+        env::set_var("SCOUTY_MATRIX_COMMANDS_ENABLED", "true");
+    }
+
+    if let Some(matrix_commands_users) = matches.value_of("matrix-commands-users") {
+        env::set_var("SCOUTY_MATRIX_COMMANDS_USERS", matrix_commands_users);
+    }
+
+    if matches.is_present("mute-hooks") {
+        env::set_var("SCOUTY_MUTE_HOOKS", "true");
+    }
+
+    if matches.is_present("mute-all") {
+        env::set_var("SCOUTY_MUTE_ALL", "true");
+    }
+
+    if let Some(quiet_hours) = matches.value_of("quiet-hours") {
+        env::set_var("SCOUTY_QUIET_HOURS", quiet_hours);
+    }
+
+    if let Some(quiet_hours_timezone) = matches.value_of("quiet-hours-timezone") {
+        env::set_var("SCOUTY_QUIET_HOURS_TIMEZONE", quiet_hours_timezone);
+    }
+
+    if let Some(weekly_report) = matches.value_of("weekly-report") {
+        env::set_var("SCOUTY_WEEKLY_REPORT", weekly_report);
+    }
+
+    if let Some(weekly_report_timezone) = matches.value_of("weekly-report-timezone") {
+        env::set_var("SCOUTY_WEEKLY_REPORT_TIMEZONE", weekly_report_timezone);
+    }
+
+    if matches.is_present("config-reload-announce") {
+        env::set_var("SCOUTY_CONFIG_RELOAD_ANNOUNCE", "true");
+    }
+
     if let Some(error_interval) = matches.value_of("error-interval") {
         env::set_var("SCOUTY_ERROR_INTERVAL", error_interval);
     }
 
-    match envy::prefixed("SCOUTY_").from_env::<Config>() {
-        Ok(config) => config,
-        Err(error) => panic!("Configuration error: {:#?}", error),
+    match Config::load() {
+        Ok(config) => LiveConfig::new(config),
+        Err(error) => panic!("{}", error),
     }
 }
 
@@ -504,13 +3343,493 @@ mod tests {
 
     #[test]
     fn it_gets_a_config() {
-        let config = get_config();
+        let config = get_config().current();
         assert_ne!(config.substrate_ws_url, "".to_string());
     }
 
+    #[test]
+    fn it_splits_urls() {
+        assert_eq!(
+            split_urls(" ws://127.0.0.1:9944,, wss://rpc.polkadot.io:443 "),
+            vec!["ws://127.0.0.1:9944", "wss://rpc.polkadot.io:443"]
+        );
+        assert!(split_urls("").is_empty());
+    }
+
+    #[test]
+    fn it_normalizes_the_stashes() {
+        let mut config = Config::clone(&get_config().current());
+        config.stashes = vec![
+            " 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string(),
+            "".to_string(),
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY ".to_string(),
+            "not_a_stash".to_string(),
+        ];
+        config.normalize_stashes();
+        assert_eq!(
+            config.stashes,
+            vec![
+                "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string(),
+                "not_a_stash".to_string()
+            ]
+        );
+        assert_eq!(config.invalid_stashes(), vec!["not_a_stash".to_string()]);
+    }
+
+    #[test]
+    fn it_dumps_the_config_with_the_secrets_redacted() {
+        let file = r#"
+            substrate_ws_url = "ws://127.0.0.1:9944"
+            stashes = ["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]
+            matrix_bot_password = "anotthateasypassword"
+            matrix_msgtype_critical = "notice"
+        "#;
+        let config = Config::load_from(Some(file), Vec::new()).unwrap();
+        let dump = config.dump();
+        assert!(dump.contains(&"matrix_bot_password=<redacted>".to_string()));
+        assert!(dump.contains(&"matrix_access_token=".to_string()));
+        assert!(dump.contains(&"substrate_ws_url=ws://127.0.0.1:9944".to_string()));
+        assert!(dump.contains(
+            &"stashes=5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string()
+        ));
+        assert!(dump.contains(&"error_interval=30".to_string()));
+        assert!(dump.contains(&"matrix_msgtype_critical=notice".to_string()));
+        assert!(!dump
+            .iter()
+            .any(|line| line.contains("anotthateasypassword")));
+        assert!(!dump.iter().any(|line| line.starts_with("values=")));
+        let mut sorted = dump.clone();
+        sorted.sort();
+        assert_eq!(dump, sorted);
+    }
+
+    #[test]
+    fn it_verifies_the_prefix_of_the_stashes() {
+        let mut config = Config::clone(&get_config().current());
+        // Alice with the generic, Polkadot and Kusama prefixes
+        config.stashes = vec![
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string(),
+            "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5".to_string(),
+            "HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F".to_string(),
+        ];
+        assert!(config.stash_prefix_mismatches("Westend", 42)[0]
+            .starts_with("stashes: 15oF4"));
+        assert_eq!(config.stash_prefix_mismatches("Westend", 42).len(), 2);
+        assert_eq!(config.stash_prefix_mismatches("Westend", 0).len(), 2);
+        assert!(
+            config.stash_prefix_mismatches("Westend", 2)[1].starts_with("stashes: 15oF4")
+        );
+    }
+
+    #[test]
+    fn it_loads_a_config_file_overridden_by_env_vars() {
+        let file = r#"
+            substrate_ws_url = "ws://127.0.0.1:9944"
+            stashes = ["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]
+            interval = 600
+            is_debug = true
+        "#;
+        let vars = vec![
+            ("SCOUTY_INTERVAL".to_string(), "300".to_string()),
+            ("HOME".to_string(), "/root".to_string()),
+        ];
+        let config = Config::load_from(Some(file), vars).unwrap();
+        assert_eq!(config.substrate_ws_url, "ws://127.0.0.1:9944");
+        assert_eq!(
+            config.stashes,
+            vec!["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string()]
+        );
+        assert_eq!(config.interval, 300);
+        assert!(config.is_debug);
+        assert_eq!(config.error_interval, default_error_interval());
+    }
+
+    #[test]
+    fn it_rejects_an_empty_list_of_substrate_ws_urls() {
+        let file = r#"
+            substrate_ws_url = " , "
+            stashes = ["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]
+        "#;
+        match Config::load_from(Some(file), Vec::new()) {
+            Err(ScoutyError::ConfigInvalid(errors)) => {
+                assert_eq!(
+                    errors,
+                    vec!["substrate_ws_url: no websocket endpoint defined".to_string()]
+                );
+            }
+            _ => panic!("config should be invalid"),
+        }
+    }
+
+    #[test]
+    fn it_reports_every_invalid_field_of_the_config() {
+        let file = r#"
+            interval = "often"
+            unknown_field = 1
+            matrix_msgtype = "emote"
+        "#;
+        match Config::load_from(Some(file), Vec::new()) {
+            Err(ScoutyError::ConfigInvalid(errors)) => {
+                assert!(errors.iter().any(|e| e.starts_with("unknown_field:")));
+                assert!(errors.iter().any(|e| e.starts_with("interval:")));
+                assert!(errors.iter().any(|e| e.starts_with("matrix_msgtype:")));
+                assert!(errors.contains(&"substrate_ws_url: missing field".to_string()));
+                assert!(errors.contains(&"stashes: missing field".to_string()));
+            }
+            _ => panic!("config should be invalid"),
+        }
+        let vars = vec![
+            (
+                "SCOUTY_SUBSTRATE_WS_URL".to_string(),
+                "ws://127.0.0.1:9944".to_string(),
+            ),
+            ("SCOUTY_STASHES".to_string(), "not_a_stash".to_string()),
+        ];
+        assert!(matches!(
+            Config::load_from(None, vars),
+            Err(ScoutyError::ConfigInvalid(errors)) if errors.len() == 1
+        ));
+    }
+
+    #[test]
+    fn it_reads_the_secrets_from_files() {
+        let path = env::temp_dir().join("scouty_matrix_bot_password");
+        fs::write(&path, "  secret_from_file\n").unwrap();
+        let vars = vec![
+            (
+                "SCOUTY_SUBSTRATE_WS_URL".to_string(),
+                "ws://127.0.0.1:9944".to_string(),
+            ),
+            (
+                "SCOUTY_STASHES".to_string(),
+                "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string(),
+            ),
+            (
+                "SCOUTY_MATRIX_BOT_PASSWORD".to_string(),
+                "secret_from_env".to_string(),
+            ),
+            (
+                "SCOUTY_MATRIX_BOT_PASSWORD_FILE".to_string(),
+                path.to_string_lossy().to_string(),
+            ),
+            (
+                "SCOUTY_SMTP_PASSWORD".to_string(),
+                "smtp_secret".to_string(),
+            ),
+        ];
+        let config = Config::load_from(None, vars.clone()).unwrap();
+        assert_eq!(config.matrix_bot_password, "secret_from_file");
+        assert_eq!(config.smtp_password, "smtp_secret");
+
+        let file = r#"
+            telegram_bot_token_file = "/scouty/_missing"
+        "#;
+        match Config::load_from(Some(file), vars) {
+            Err(ScoutyError::ConfigInvalid(errors)) => {
+                assert_eq!(errors.len(), 1);
+                assert!(errors[0].starts_with(
+                    "telegram_bot_token_file: /scouty/_missing could not be read"
+                ));
+            }
+            _ => panic!("config should be invalid"),
+        }
+    }
+
+    #[test]
+    fn it_validates_the_quiet_hours() {
+        let file = r#"
+            substrate_ws_url = "ws://127.0.0.1:9944"
+            stashes = ["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]
+            quiet_hours = "23:00-07:00"
+        "#;
+        let config = Config::load_from(Some(file), Vec::new()).unwrap();
+        assert_eq!(
+            config.quiet_hours().unwrap().unwrap().to_string(),
+            "23:00-07:00 UTC"
+        );
+        let vars = vec![(
+            "SCOUTY_QUIET_HOURS_TIMEZONE".to_string(),
+            "Mars/Olympus".to_string(),
+        )];
+        match Config::load_from(Some(file), vars) {
+            Err(ScoutyError::ConfigInvalid(errors)) => {
+                assert_eq!(
+                    errors,
+                    vec!["quiet_hours: Mars/Olympus is not a timezone (e.g. Europe/Lisbon)"]
+                );
+            }
+            _ => panic!("config should be invalid"),
+        }
+    }
+
+    #[test]
+    fn it_enables_the_payouts_per_chain() {
+        let file = r#"
+            substrate_ws_url = "ws://127.0.0.1:9944"
+            stashes = ["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]
+            payout_chains = ["polkadot"]
+            payout_seed_path = "/opt/scouty-cli/.payout_seed"
+        "#;
+        let config = Config::load_from(Some(file), Vec::new()).unwrap();
+        assert!(config.is_payout_enabled("Polkadot"));
+        assert!(!config.is_payout_enabled("Kusama"));
+        assert_eq!(config.payout_max_eras, default_payout_max_eras());
+
+        let file = r#"
+            substrate_ws_url = "ws://127.0.0.1:9944"
+            stashes = ["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]
+            payout_chains = ["polkadot"]
+        "#;
+        match Config::load_from(Some(file), Vec::new()) {
+            Err(ScoutyError::ConfigInvalid(errors)) => {
+                assert_eq!(errors, vec!["payout_seed_path: missing field"]);
+            }
+            _ => panic!("config should be invalid"),
+        }
+    }
+
+    #[test]
+    fn it_watches_the_balances_per_chain() {
+        let file = r#"
+            substrate_ws_url = "ws://127.0.0.1:9944"
+            stashes = ["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]
+            watch_balance_accounts = ["polkadot:5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY:10"]
+        "#;
+        let config = Config::load_from(Some(file), Vec::new()).unwrap();
+        assert_eq!(config.watched_accounts("Polkadot").len(), 1);
+        assert!(config.watched_accounts("Kusama").is_empty());
+
+        let file = r#"
+            substrate_ws_url = "ws://127.0.0.1:9944"
+            stashes = ["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]
+            watch_balance_accounts = ["polkadot:5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]
+        "#;
+        match Config::load_from(Some(file), Vec::new()) {
+            Err(ScoutyError::ConfigInvalid(errors)) => {
+                assert_eq!(
+                    errors,
+                    vec!["watch_balance_accounts: polkadot:5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY is not defined as chain:account:min_free_balance"]
+                );
+            }
+            _ => panic!("config should be invalid"),
+        }
+    }
+
+    #[test]
+    fn it_defines_the_endpoints_stashes_and_hooks_per_chain() {
+        let file = r#"
+            substrate_ws_url = "ws://127.0.0.1:9944"
+            stashes = ["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]
+        "#;
+        let config = Config::load_from(Some(file), Vec::new()).unwrap();
+        assert_eq!(
+            config.substrate_ws_urls_by_chain(),
+            vec![ChainEndpoints {
+                chain: None,
+                urls: vec!["ws://127.0.0.1:9944".to_string()],
+            }]
+        );
+        assert!(!config.is_multi_chain());
+
+        let file = r#"
+            substrate_ws_url = "ws://127.0.0.1:9944"
+            substrate_ws_urls_polkadot = "wss://rpc.polkadot.io, ws://127.0.0.1:9944"
+            substrate_ws_urls_kusama = "wss://kusama-rpc.polkadot.io"
+            stashes = ["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]
+            stashes_kusama = ["HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F"]
+            hooks_dir_kusama = "/opt/scouty-cli/hooks/kusama"
+        "#;
+        let config = Config::load_from(Some(file), Vec::new()).unwrap();
+        assert_eq!(
+            config.substrate_ws_urls_by_chain(),
+            vec![
+                ChainEndpoints {
+                    chain: Some("Polkadot".to_string()),
+                    urls: vec![
+                        "wss://rpc.polkadot.io".to_string(),
+                        "ws://127.0.0.1:9944".to_string()
+                    ],
+                },
+                ChainEndpoints {
+                    chain: Some("Kusama".to_string()),
+                    urls: vec!["wss://kusama-rpc.polkadot.io".to_string()],
+                }
+            ]
+        );
+        assert!(config.is_multi_chain());
+        assert_eq!(
+            config.chain_stashes("Polkadot"),
+            ["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]
+        );
+        assert_eq!(
+            config.validator_stashes("Kusama"),
+            vec!["HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F"]
+        );
+        assert_eq!(config.chain_hooks_dir("Polkadot"), None);
+        assert_eq!(
+            config.chain_hooks_dir("Kusama"),
+            Some("/opt/scouty-cli/hooks/kusama")
+        );
+
+        // Chain specific stashes are enough to monitor every chain
+        let file = r#"
+            substrate_ws_url = "ws://127.0.0.1:9944"
+            stashes_polkadot = ["not_a_stash"]
+        "#;
+        match Config::load_from(Some(file), Vec::new()) {
+            Err(ScoutyError::ConfigInvalid(errors)) => {
+                assert_eq!(
+                    errors,
+                    vec!["stashes: invalid SS58 format account not_a_stash".to_string()]
+                );
+            }
+            _ => panic!("config should be invalid"),
+        }
+    }
+
+    #[test]
+    fn it_monitors_the_stashes_by_account_type() {
+        let file = r#"
+            substrate_ws_url = "ws://127.0.0.1:9944"
+            stashes = ["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY", "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty"]
+            account_types = ["5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty:nominator"]
+        "#;
+        let config = Config::load_from(Some(file), Vec::new()).unwrap();
+        assert_eq!(
+            config.validator_stashes("Westend"),
+            vec!["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]
+        );
+        assert_eq!(
+            config.nominator_stashes("Westend"),
+            vec!["5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty"]
+        );
+
+        let file = r#"
+            substrate_ws_url = "ws://127.0.0.1:9944"
+            stashes = ["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]
+            account_types = ["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY:pool"]
+        "#;
+        match Config::load_from(Some(file), Vec::new()) {
+            Err(ScoutyError::ConfigInvalid(errors)) => {
+                assert_eq!(
+                    errors,
+                    vec!["account_types: pool is not an account type (validator or nominator)"]
+                );
+            }
+            _ => panic!("config should be invalid"),
+        }
+    }
+
+    #[test]
+    fn it_monitors_the_stashes_from_the_stashes_url() {
+        let vars = vec![
+            (
+                "SCOUTY_SUBSTRATE_WS_URL".to_string(),
+                "ws://127.0.0.1:9944".to_string(),
+            ),
+            (
+                "SCOUTY_STASHES_URL".to_string(),
+                "https://example.com/candidates".to_string(),
+            ),
+        ];
+        let mut config = Config::load_from(None, vars).unwrap();
+        assert!(config.stashes.is_empty());
+        assert_eq!(config.stashes_url_interval, 3600);
+        let file = r#"
+            substrate_ws_url = "ws://127.0.0.1:9944"
+            stashes_path = "/opt/scouty/stashes.txt"
+        "#;
+        assert!(Config::load_from(Some(file), Vec::new()).is_ok());
+
+        config.stashes =
+            vec!["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string()];
+        config.merge_stashes(&[
+            "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".to_string(),
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string(),
+        ]);
+        assert_eq!(
+            config.stashes,
+            vec![
+                "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string(),
+                "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_replaces_the_config_only_if_the_remote_stashes_changed() {
+        let vars = vec![
+            (
+                "SCOUTY_SUBSTRATE_WS_URL".to_string(),
+                "ws://127.0.0.1:9944".to_string(),
+            ),
+            (
+                "SCOUTY_STASHES".to_string(),
+                "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string(),
+            ),
+        ];
+        let live_config = LiveConfig::new(Config::load_from(None, vars).unwrap());
+        let stashes =
+            vec!["5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".to_string()];
+
+        let (before, after) =
+            live_config.set_remote_stashes("stashes_url", stashes.clone());
+        assert_eq!(
+            before,
+            vec!["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string()]
+        );
+        assert_eq!(
+            after,
+            vec![
+                "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string(),
+                "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".to_string(),
+            ]
+        );
+
+        let current = live_config.current();
+        let (before, after) = live_config.set_remote_stashes("stashes_url", stashes);
+        assert_eq!(before, after);
+        assert!(Arc::ptr_eq(&current, &live_config.current()));
+    }
+
+    #[test]
+    fn it_applies_only_the_reloadable_fields_that_changed() {
+        let running: BTreeMap<String, String> = vec![
+            ("stashes", "stash_1"),
+            ("substrate_ws_url", "ws://127.0.0.1:9944"),
+            ("hook_new_session_path", "/opt/hooks/_new_session.sh"),
+            ("interval", "600"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        let mut loaded = running.clone();
+        loaded.insert("stashes".to_string(), "stash_1,stash_2".to_string());
+        loaded.insert(
+            "substrate_ws_url".to_string(),
+            "wss://rpc.polkadot.io".to_string(),
+        );
+        loaded.remove("hook_new_session_path");
+
+        let (values, reload) = diff_values(&running, &loaded);
+        assert_eq!(
+            reload,
+            ConfigReload {
+                applied: vec!["stashes".to_string(), "hook_new_session_path".to_string()],
+                ignored: vec!["substrate_ws_url".to_string()],
+            }
+        );
+        assert_eq!(values["stashes"], "stash_1,stash_2");
+        assert_eq!(values["substrate_ws_url"], "ws://127.0.0.1:9944");
+        assert_eq!(values["interval"], "600");
+        assert!(!values.contains_key("hook_new_session_path"));
+
+        assert_eq!(diff_values(&running, &running).1, ConfigReload::default());
+    }
+
     #[test]
     fn it_gets_a_config_from_the_lazy_static() {
-        let config = &CONFIG;
+        let config = CONFIG.current();
         assert_ne!(config.substrate_ws_url, "".to_string());
     }
 }
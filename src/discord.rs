@@ -0,0 +1,234 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::config::CONFIG;
+use crate::errors::ScoutyError;
+use crate::matrix::Severity;
+use crate::notifier::{truncate, Notification, Notifier};
+use async_std::task;
+use futures::future::BoxFuture;
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use std::time;
+
+// Maximum number of characters of the description of the embeds sent, above which
+// discord rejects the message
+const DISCORD_MAX_DESCRIPTION_LENGTH: usize = 2000;
+
+// Maximum number of characters of the value of an embed field
+const DISCORD_MAX_FIELD_LENGTH: usize = 1024;
+
+// Number of times a message rate limited by discord is retried
+const DISCORD_RATE_LIMIT_RETRIES: u32 = 3;
+
+// Colors of the embeds by severity
+const DISCORD_COLOR_INFO: u32 = 0x3498db;
+const DISCORD_COLOR_CRITICAL: u32 = 0xe74c3c;
+
+#[derive(Debug, Serialize)]
+struct WebhookRequest {
+    embeds: Vec<Embed>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct Embed {
+    title: String,
+    description: String,
+    color: u32,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fields: Vec<EmbedField>,
+    timestamp: String,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct EmbedField {
+    name: String,
+    value: String,
+    inline: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct RateLimitResponse {
+    /// Seconds to wait before sending again
+    #[serde(default)]
+    retry_after: f64,
+}
+
+impl From<&Notification> for Embed {
+    fn from(notification: &Notification) -> Self {
+        let mut fields = Vec::new();
+        if !notification.stashes.is_empty() {
+            fields.push(EmbedField {
+                name: "Stash".to_string(),
+                value: truncate(
+                    &notification.stashes.join("\n"),
+                    DISCORD_MAX_FIELD_LENGTH,
+                ),
+                inline: false,
+            });
+        }
+        if let Some(era_index) = notification.era_index {
+            fields.push(EmbedField {
+                name: "Era".to_string(),
+                value: era_index.to_string(),
+                inline: true,
+            });
+        }
+        if let Some(session_index) = notification.session_index {
+            fields.push(EmbedField {
+                name: "Session".to_string(),
+                value: session_index.to_string(),
+                inline: true,
+            });
+        }
+        Embed {
+            title: format!("{} {}", notification.chain, notification.event),
+            description: truncate(&notification.message, DISCORD_MAX_DESCRIPTION_LENGTH),
+            color: match notification.severity {
+                Severity::Info => DISCORD_COLOR_INFO,
+                Severity::Critical => DISCORD_COLOR_CRITICAL,
+            },
+            fields,
+            timestamp: notification.timestamp.to_rfc3339(),
+        }
+    }
+}
+
+/// Posts every message as an embed to the discord webhook url defined
+#[derive(Debug, Clone)]
+pub struct Discord {
+    client: reqwest::Client,
+    webhook_url: String,
+}
+
+impl Discord {
+    pub fn new() -> Self {
+        Discord {
+            client: reqwest::Client::new(),
+            webhook_url: CONFIG.current().discord_webhook_url.clone(),
+        }
+    }
+
+    async fn post(&self, req: &WebhookRequest) -> Result<(), ScoutyError> {
+        let mut retries: u32 = 0;
+        loop {
+            let res = self
+                .client
+                .post(&self.webhook_url)
+                .timeout(time::Duration::from_secs(CONFIG.current().request_timeout))
+                .json(req)
+                .send()
+                .await
+                .map_err(|e| ScoutyError::DiscordError(e.without_url().to_string()))?;
+            debug!("response {:?}", res.status());
+            match res.status() {
+                status if status.is_success() => return Ok(()),
+                reqwest::StatusCode::TOO_MANY_REQUESTS => {
+                    let response =
+                        res.json::<RateLimitResponse>().await.map_err(|e| {
+                            ScoutyError::DiscordError(e.without_url().to_string())
+                        })?;
+                    if retries >= DISCORD_RATE_LIMIT_RETRIES {
+                        return Err(ScoutyError::DiscordError(format!(
+                            "rate limited -> gave up after {} retries",
+                            retries
+                        )));
+                    }
+                    retries += 1;
+                    let delay =
+                        time::Duration::from_secs_f64(response.retry_after.max(1.0));
+                    warn!(
+                        "Discord rate limited -> Wait {:.1} seconds and try again ({}/{})",
+                        delay.as_secs_f64(),
+                        retries,
+                        DISCORD_RATE_LIMIT_RETRIES
+                    );
+                    task::sleep(delay).await;
+                }
+                status => {
+                    return Err(ScoutyError::DiscordError(format!(
+                        "responded with status {}",
+                        status
+                    )))
+                }
+            }
+        }
+    }
+}
+
+impl Notifier for Discord {
+    fn name(&self) -> &str {
+        "Discord"
+    }
+
+    fn send<'a>(
+        &'a self,
+        notification: &'a Notification,
+    ) -> BoxFuture<'a, Result<(), ScoutyError>> {
+        Box::pin(async move {
+            let req = WebhookRequest {
+                embeds: vec![Embed::from(notification)],
+            };
+            self.post(&req).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_translates_the_notification_into_an_embed() {
+        let mut notification = Notification::new(
+            "slash",
+            "Polkadot",
+            Severity::Critical,
+            "Slash!",
+            "<b>Slash!</b>",
+        );
+        notification.stashes = vec!["5GrwvaEF".to_string()];
+        notification.era_index = Some(10);
+        let embed = Embed::from(&notification);
+        assert_eq!(embed.title, "Polkadot slash");
+        assert_eq!(embed.description, "Slash!");
+        assert_eq!(embed.color, DISCORD_COLOR_CRITICAL);
+        assert_eq!(
+            embed.fields,
+            vec![
+                EmbedField {
+                    name: "Stash".to_string(),
+                    value: "5GrwvaEF".to_string(),
+                    inline: false,
+                },
+                EmbedField {
+                    name: "Era".to_string(),
+                    value: "10".to_string(),
+                    inline: true,
+                },
+            ]
+        );
+        let message = Notification::new("message", "Kusama", Severity::Info, "Hi", "Hi");
+        let value = serde_json::to_value(Embed::from(&message)).unwrap();
+        assert_eq!(value["color"], DISCORD_COLOR_INFO);
+        assert!(value.get("fields").is_none());
+    }
+}
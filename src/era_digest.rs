@@ -0,0 +1,192 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::backing::BackingVotes;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt};
+use subxt::utils::AccountId32;
+
+/// How often the stashes are reported to the matrix rooms
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportMode {
+    /// A message at every new session
+    #[default]
+    Session,
+    /// A single digest at every new era, the critical alerts are still sent straight
+    /// away
+    Era,
+}
+
+impl fmt::Display for ReportMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Session => write!(f, "session"),
+            Self::Era => write!(f, "era"),
+        }
+    }
+}
+
+/// Summary of a stash over the sessions tracked in an era
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct EraDigest {
+    pub era_index: u32,
+    pub sessions: u32,
+    pub sessions_active: u32,
+    pub authored_blocks: u32,
+    /// Backing votes of the sessions as para validator, if any
+    pub backing_votes: Option<BackingVotes>,
+    /// Era points and rank by era points, only known once the era is over
+    pub points: u32,
+    pub rank: Option<u32>,
+    pub validator_count: u32,
+    /// Warnings raised during the era, each one only once
+    pub warnings: Vec<String>,
+}
+
+/// Sessions of the stashes predefined in the active era, summarized in a digest at
+/// every new era
+#[derive(Debug, Default)]
+pub struct EraDigestRecords {
+    era_index: u32,
+    digests: BTreeMap<AccountId32, EraDigest>,
+}
+
+impl EraDigestRecords {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Keep the blocks authored and the backing votes of the stash in the previous
+    /// session, which belongs to the era tracked so far, even on a new era
+    pub fn track_previous_session(
+        &mut self,
+        stash: &AccountId32,
+        authored_blocks: Option<u32>,
+        backing_votes: Option<BackingVotes>,
+    ) {
+        if let Some(digest) = self.digests.get_mut(stash) {
+            digest.authored_blocks += authored_blocks.unwrap_or_default();
+            if let Some(votes) = backing_votes {
+                let total = digest.backing_votes.get_or_insert_with(Default::default);
+                total.implicit += votes.implicit;
+                total.explicit += votes.explicit;
+                total.missed += votes.missed;
+            }
+        }
+    }
+
+    /// Keep the status and the warnings of the stash at a new session. The records of
+    /// a previous era that have not been taken are dropped.
+    pub fn track_session(
+        &mut self,
+        era_index: u32,
+        stash: &AccountId32,
+        is_active: bool,
+        warnings: Vec<String>,
+    ) {
+        if era_index != self.era_index {
+            self.era_index = era_index;
+            self.digests.clear();
+        }
+        let digest = self
+            .digests
+            .entry(stash.clone())
+            .or_insert_with(|| EraDigest {
+                era_index,
+                ..Default::default()
+            });
+        digest.sessions += 1;
+        if is_active {
+            digest.sessions_active += 1;
+        }
+        for warning in warnings {
+            if !digest.warnings.contains(&warning) {
+                digest.warnings.push(warning);
+            }
+        }
+    }
+
+    /// Returns the digests of the era tracked so far and clear the records
+    pub fn take(&mut self) -> BTreeMap<AccountId32, EraDigest> {
+        std::mem::take(&mut self.digests)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_summarizes_the_sessions_of_an_era() {
+        let stash = AccountId32::from([1; 32]);
+        let mut records = EraDigestRecords::new();
+
+        // Nothing is tracked before the first session
+        records.track_previous_session(&stash, Some(3), None);
+        records.track_session(100, &stash, true, vec![]);
+        records.track_previous_session(
+            &stash,
+            Some(4),
+            Some(BackingVotes {
+                implicit: 10,
+                explicit: 2,
+                missed: 1,
+            }),
+        );
+        records.track_session(100, &stash, false, vec!["low points".to_string()]);
+        records.track_previous_session(&stash, Some(0), None);
+        records.track_session(100, &stash, false, vec!["low points".to_string()]);
+
+        let digests = records.take();
+        assert_eq!(
+            digests.get(&stash),
+            Some(&EraDigest {
+                era_index: 100,
+                sessions: 3,
+                sessions_active: 1,
+                authored_blocks: 4,
+                backing_votes: Some(BackingVotes {
+                    implicit: 10,
+                    explicit: 2,
+                    missed: 1,
+                }),
+                warnings: vec!["low points".to_string()],
+                ..Default::default()
+            })
+        );
+        assert!(records.take().is_empty());
+    }
+
+    #[test]
+    fn it_drops_the_sessions_of_a_previous_era() {
+        let stash = AccountId32::from([1; 32]);
+        let mut records = EraDigestRecords::new();
+        records.track_session(100, &stash, true, vec![]);
+        records.track_session(101, &stash, true, vec![]);
+
+        let digests = records.take();
+        assert_eq!(
+            digests.get(&stash).map(|d| (d.era_index, d.sessions)),
+            Some((101, 1))
+        );
+    }
+}
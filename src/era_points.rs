@@ -0,0 +1,123 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::BTreeMap;
+use subxt::utils::AccountId32;
+
+/// Era points collected so far by a stash at a new session, compared with the era
+/// average per active validator and with the points of the previous session
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct EraPointsTrend {
+    pub points: u32,
+    pub era_avg: u32,
+    /// Points collected since the previous session, not known on the first session
+    /// tracked in the era
+    pub delta: Option<u32>,
+    /// Consecutive sessions with the points below the threshold of the era average
+    pub sessions_below: u32,
+}
+
+impl EraPointsTrend {
+    /// Returns true if the points have been below the threshold of the era average for
+    /// more than one consecutive session
+    pub fn is_below_average(&self) -> bool {
+        self.sessions_below > 1
+    }
+}
+
+/// Era points of the stashes predefined at every session of the active era, the
+/// records are reset on every new era
+#[derive(Debug, Default)]
+pub struct EraPointsRecords {
+    era_index: u32,
+    points: BTreeMap<AccountId32, u32>,
+    sessions_below: BTreeMap<AccountId32, u32>,
+}
+
+impl EraPointsRecords {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Keep the points of the stash at the new session and return the trend. The points
+    /// are below the average if lower than `threshold` (in percentage) of the era
+    /// average, a threshold of 0 never flags the points.
+    pub fn track(
+        &mut self,
+        era_index: u32,
+        stash: &AccountId32,
+        points: u32,
+        era_avg: u32,
+        threshold: u32,
+    ) -> EraPointsTrend {
+        if era_index != self.era_index {
+            self.era_index = era_index;
+            self.points.clear();
+            self.sessions_below.clear();
+        }
+        let delta = self
+            .points
+            .insert(stash.clone(), points)
+            .map(|previous| points.saturating_sub(previous));
+        let is_below = (points as u64) * 100 < (era_avg as u64) * (threshold as u64);
+        let sessions_below = self.sessions_below.entry(stash.clone()).or_default();
+        *sessions_below = if is_below { *sessions_below + 1 } else { 0 };
+        EraPointsTrend {
+            points,
+            era_avg,
+            delta,
+            sessions_below: *sessions_below,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_tracks_the_era_points_of_a_stash() {
+        let stash = AccountId32([1; 32]);
+        let mut records = EraPointsRecords::new();
+
+        let trend = records.track(10, &stash, 100, 400, 50);
+        assert_eq!(trend.delta, None);
+        assert_eq!(trend.sessions_below, 1);
+        assert!(!trend.is_below_average());
+
+        let trend = records.track(10, &stash, 300, 800, 50);
+        assert_eq!(trend.delta, Some(200));
+        assert!(trend.is_below_average());
+
+        let trend = records.track(10, &stash, 700, 1200, 50);
+        assert_eq!(trend.delta, Some(400));
+        assert_eq!(trend.sessions_below, 0);
+
+        // Records are reset on a new era
+        let trend = records.track(11, &stash, 20, 100, 50);
+        assert_eq!(trend.delta, None);
+        assert_eq!(trend.sessions_below, 1);
+
+        // Points are never flagged without a threshold
+        let trend = records.track(11, &stash, 0, 100, 0);
+        assert_eq!(trend.sessions_below, 0);
+    }
+}
@@ -19,8 +19,6 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use codec;
-use reqwest;
 use std::{str, string, string::String};
 use subxt::error::MetadataError;
 use thiserror::Error;
@@ -38,16 +36,60 @@ pub enum ScoutyError {
     FromUtf8Error(#[from] string::FromUtf8Error),
     #[error("Utf8 error: {0}")]
     Utf8Error(#[from] str::Utf8Error),
+    #[error("Serde error: {0}")]
+    SerdeError(#[from] serde_json::Error),
     #[error("Metadata error: {0}")]
     MetadataError(#[from] MetadataError),
     #[error("Matrix error: {0}")]
     MatrixError(String),
+    #[error("Webhook error: {0}")]
+    WebhookError(String),
+    #[error("Telegram error: {0}")]
+    TelegramError(String),
+    #[error("Discord error: {0}")]
+    DiscordError(String),
+    #[error("PagerDuty error: {0}")]
+    PagerDutyError(String),
+    #[error("Smtp error: {0}")]
+    SmtpError(String),
     #[error("Subscription finished")]
     SubscriptionFinished,
+    #[error("Runtime upgraded to {0}")]
+    RuntimeUpgraded(u32),
+    #[error("Unsupported chain: {0}")]
+    UnsupportedChain(String),
+    #[error(
+        "Incompatible runtime {0} -> scouty needs to be rebuilt with the latest metadata"
+    )]
+    IncompatibleRuntime(u32),
+    #[error("Hook script - {0} - killed on timeout")]
+    HookTimeout(String),
+    #[error("{0}")]
+    HookFailed(String, Option<i32>),
+    #[error("{0}")]
+    HookInvalid(String),
+    #[error("Hooks verification failed -> {}", .0.join(", "))]
+    HooksVerificationFailed(Vec<String>),
+    #[error("Configuration invalid -> {}", .0.join(", "))]
+    ConfigInvalid(Vec<String>),
+    #[error("Template invalid -> {0}")]
+    TemplateInvalid(String),
     #[error("Other error: {0}")]
     Other(String),
 }
 
+impl ScoutyError {
+    /// Returns true if the error was raised by the connection to the substrate node,
+    /// in which case the node client needs to be recreated
+    pub fn is_connection_error(&self) -> bool {
+        matches!(
+            self,
+            ScoutyError::SubxtError(subxt::Error::Rpc(_))
+                | ScoutyError::SubxtError(subxt::Error::Io(_))
+        )
+    }
+}
+
 /// Convert &str to ScoutyError
 impl From<&str> for ScoutyError {
     fn from(error: &str) -> Self {
@@ -62,6 +104,12 @@ pub enum MatrixError {
     ReqwestError(#[from] reqwest::Error),
     #[error("ParseError error: {0}")]
     ParseError(#[from] url::ParseError),
+    #[error("Matrix access token is invalid or expired -> {0}")]
+    InvalidAccessToken(String),
+    #[error("Matrix room {0} is end-to-end encrypted -> end-to-end encryption is not supported, use an unencrypted room")]
+    RoomEncrypted(String),
+    #[error("Matrix room {0} ({1}) join forbidden -> invite the bot user to the room")]
+    RoomJoinForbidden(String, String),
     #[error("{0}")]
     Other(String),
 }
@@ -69,7 +117,7 @@ pub enum MatrixError {
 /// Convert MatrixError to String
 impl From<MatrixError> for String {
     fn from(error: MatrixError) -> Self {
-        format!("{}", error).to_string()
+        format!("{}", error)
     }
 }
 
@@ -0,0 +1,182 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::config::CONFIG;
+use crate::errors::ScoutyError;
+use crate::matrix::Severity;
+use crate::notifier::{Notification, Notifier};
+use futures::future::BoxFuture;
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    sync::Mutex,
+};
+
+pub const EVENTS_FILE_VERSION: u32 = 1;
+
+lazy_static! {
+    /// Serialize the lines appended by the subscriptions of every chain
+    static ref EVENTS_FILE_LOCK: Mutex<()> = Mutex::new(());
+}
+
+/// JSON line appended to the events file for every event reported
+#[derive(Serialize, Debug)]
+struct EventLine<'a> {
+    version: u32,
+    event: &'a str,
+    chain: &'a str,
+    stashes: &'a [String],
+    session_index: Option<u32>,
+    era_index: Option<u32>,
+    severity: &'static str,
+    message: &'a str,
+    formatted_message: &'a str,
+    timestamp: String,
+    /// Unix timestamp in milliseconds
+    timestamp_ms: i64,
+}
+
+impl<'a> From<&'a Notification> for EventLine<'a> {
+    fn from(notification: &'a Notification) -> Self {
+        EventLine {
+            version: EVENTS_FILE_VERSION,
+            event: &notification.event,
+            chain: &notification.chain,
+            stashes: &notification.stashes,
+            session_index: notification.session_index,
+            era_index: notification.era_index,
+            severity: match notification.severity {
+                Severity::Info => "info",
+                Severity::Critical => "critical",
+            },
+            message: &notification.message,
+            formatted_message: &notification.formatted_message,
+            timestamp: notification.timestamp.to_rfc3339(),
+            timestamp_ms: notification.timestamp.timestamp_millis(),
+        }
+    }
+}
+
+/// Appends every event as a JSON line to the events file defined, so that another
+/// process can tail the file and forward the alerts
+#[derive(Debug, Clone)]
+pub struct EventsFile {
+    path: String,
+    max_size: u64,
+    fsync_critical: bool,
+}
+
+impl EventsFile {
+    pub fn new() -> Self {
+        let config = CONFIG.current();
+        EventsFile {
+            path: config.events_file_path.clone(),
+            max_size: config.events_file_max_size * 1024 * 1024,
+            fsync_critical: config.events_file_fsync_critical,
+        }
+    }
+
+    /// Append the line to the file, the file is rotated to `<path>.1` once it reaches
+    /// `max_size` bytes, set 0 to never rotate. Critical events are synced to disk
+    /// straight away if `fsync_critical` is set.
+    fn append(&self, notification: &Notification) -> Result<(), ScoutyError> {
+        let mut line = serde_json::to_vec(&EventLine::from(notification))?;
+        line.push(b'\n');
+        let _lock = EVENTS_FILE_LOCK.lock().unwrap();
+        if self.max_size > 0 {
+            if let Ok(metadata) = fs::metadata(&self.path) {
+                if metadata.len() >= self.max_size {
+                    fs::rename(&self.path, format!("{}.1", self.path))?;
+                }
+            }
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(&line)?;
+        if self.fsync_critical && notification.severity == Severity::Critical {
+            file.sync_all()?;
+        }
+        Ok(())
+    }
+}
+
+impl Notifier for EventsFile {
+    fn name(&self) -> &str {
+        "Events file"
+    }
+
+    fn send<'a>(
+        &'a self,
+        notification: &'a Notification,
+    ) -> BoxFuture<'a, Result<(), ScoutyError>> {
+        Box::pin(async move { self.append(notification) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_appends_and_rotates_the_events_file() {
+        let path = format!("/tmp/scouty_events_{}.jsonl", std::process::id());
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(format!("{}.1", path));
+        let events_file = EventsFile {
+            path: path.clone(),
+            max_size: 1,
+            fsync_critical: true,
+        };
+        let mut notification = Notification::new(
+            "slash",
+            "Polkadot",
+            Severity::Critical,
+            "Slash!",
+            "<b>Slash!</b>",
+        );
+        notification.stashes = vec!["5GrwvaEF".to_string()];
+        notification.era_index = Some(10);
+        events_file.append(&notification).unwrap();
+        let line: serde_json::Value =
+            serde_json::from_str(fs::read_to_string(&path).unwrap().trim()).unwrap();
+        assert_eq!(line["version"], EVENTS_FILE_VERSION);
+        assert_eq!(line["event"], "slash");
+        assert_eq!(line["stashes"][0], "5GrwvaEF");
+        assert_eq!(line["era_index"], 10);
+        assert_eq!(line["session_index"], serde_json::Value::Null);
+        assert_eq!(line["severity"], "critical");
+        assert_eq!(line["formatted_message"], "<b>Slash!</b>");
+        assert_eq!(
+            line["timestamp_ms"],
+            notification.timestamp.timestamp_millis()
+        );
+        // The file is rotated once it reaches the max size
+        events_file.append(&notification).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 1);
+        assert!(fs::metadata(format!("{}.1", path)).is_ok());
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(format!("{}.1", path));
+    }
+}
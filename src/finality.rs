@@ -0,0 +1,122 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use log::debug;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum FinalityStatus {
+    #[default]
+    Finalizing,
+    Stalled,
+}
+
+impl FinalityStatus {
+    pub fn is_stalled(&self) -> bool {
+        matches!(self, Self::Stalled)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct FinalityRecords {
+    max_lag: u32,
+    max_lag_duration: u64,
+    lagging_since: Option<u64>,
+    status: FinalityStatus,
+}
+
+impl FinalityRecords {
+    pub fn new(max_lag: u32, max_lag_duration: u64) -> Self {
+        Self {
+            max_lag,
+            max_lag_duration,
+            lagging_since: None,
+            status: FinalityStatus::Finalizing,
+        }
+    }
+
+    /// Track the latest best and finalized block numbers, `now` is defined in seconds.
+    /// Returns the new status only if it differs from the previous one.
+    pub fn update(
+        &mut self,
+        best_block_number: u32,
+        finalized_block_number: u32,
+        now: u64,
+    ) -> Option<FinalityStatus> {
+        let lag = best_block_number.saturating_sub(finalized_block_number);
+        if lag > self.max_lag {
+            if self.lagging_since.is_none() {
+                self.lagging_since = Some(now);
+            }
+        } else {
+            self.lagging_since = None;
+        }
+
+        let status = match self.lagging_since {
+            Some(since) if now - since >= self.max_lag_duration => {
+                FinalityStatus::Stalled
+            }
+            _ => FinalityStatus::Finalizing,
+        };
+        debug!("finality lag {} blocks status {:?}", lag, status);
+
+        // Only report state transitions
+        if status == self.status {
+            return None;
+        }
+        self.status = status;
+        Some(status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_reports_stalled_after_the_lag_duration() {
+        let mut records = FinalityRecords::new(10, 300);
+        assert_eq!(records.update(100, 98, 0), None);
+        assert_eq!(records.update(120, 98, 60), None);
+        assert_eq!(records.update(150, 98, 300), None);
+        assert_eq!(records.update(180, 98, 360), Some(FinalityStatus::Stalled));
+        assert_eq!(records.update(190, 98, 420), None);
+    }
+
+    #[test]
+    fn it_reports_recovery_when_lag_drops_below_threshold() {
+        let mut records = FinalityRecords::new(10, 0);
+        assert_eq!(records.update(120, 98, 0), Some(FinalityStatus::Stalled));
+        assert_eq!(
+            records.update(121, 115, 6),
+            Some(FinalityStatus::Finalizing)
+        );
+        assert_eq!(records.update(122, 118, 12), None);
+    }
+
+    #[test]
+    fn it_ignores_short_lags() {
+        let mut records = FinalityRecords::new(10, 300);
+        assert_eq!(records.update(120, 98, 0), None);
+        assert_eq!(records.update(121, 119, 60), None);
+        assert_eq!(records.update(140, 119, 120), None);
+        assert_eq!(records.update(141, 139, 400), None);
+    }
+}
@@ -0,0 +1,145 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use log::debug;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum HealthStatus {
+    #[default]
+    Healthy,
+    LowPeers(usize),
+    Syncing(u64),
+}
+
+impl HealthStatus {
+    pub fn is_healthy(&self) -> bool {
+        matches!(self, Self::Healthy)
+    }
+}
+
+impl std::fmt::Display for HealthStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Healthy => write!(f, "healthy"),
+            Self::LowPeers(peers) => write!(f, "low number of peers ({})", peers),
+            Self::Syncing(secs) => write!(f, "syncing for more than {} seconds", secs),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct HealthRecords {
+    min_peers: usize,
+    syncing_grace_period: u64,
+    syncing_since: Option<u64>,
+    status: HealthStatus,
+}
+
+impl HealthRecords {
+    pub fn new(min_peers: usize, syncing_grace_period: u64) -> Self {
+        Self {
+            min_peers,
+            syncing_grace_period,
+            syncing_since: None,
+            status: HealthStatus::Healthy,
+        }
+    }
+
+    /// Track the latest node health, `now` is defined in seconds.
+    /// Returns the new status only if it differs from the previous one.
+    pub fn update(
+        &mut self,
+        peers: usize,
+        is_syncing: bool,
+        should_have_peers: bool,
+        now: u64,
+    ) -> Option<HealthStatus> {
+        if is_syncing {
+            if self.syncing_since.is_none() {
+                self.syncing_since = Some(now);
+            }
+        } else {
+            self.syncing_since = None;
+        }
+
+        let status = match self.syncing_since {
+            Some(since) if now - since >= self.syncing_grace_period => {
+                HealthStatus::Syncing(now - since)
+            }
+            _ if should_have_peers && peers < self.min_peers => {
+                HealthStatus::LowPeers(peers)
+            }
+            _ => HealthStatus::Healthy,
+        };
+        debug!("node health status {:?}", status);
+
+        // Only report state transitions
+        if std::mem::discriminant(&status) == std::mem::discriminant(&self.status) {
+            self.status = status;
+            return None;
+        }
+        self.status = status;
+        Some(status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_reports_low_peers_only_once() {
+        let mut records = HealthRecords::new(3, 300);
+        assert_eq!(records.update(5, false, true, 0), None);
+        assert_eq!(
+            records.update(2, false, true, 60),
+            Some(HealthStatus::LowPeers(2))
+        );
+        assert_eq!(records.update(1, false, true, 120), None);
+        assert_eq!(
+            records.update(4, false, true, 180),
+            Some(HealthStatus::Healthy)
+        );
+    }
+
+    #[test]
+    fn it_waits_for_the_syncing_grace_period() {
+        let mut records = HealthRecords::new(1, 300);
+        assert_eq!(records.update(10, true, true, 0), None);
+        assert_eq!(records.update(10, true, true, 200), None);
+        assert_eq!(
+            records.update(10, true, true, 300),
+            Some(HealthStatus::Syncing(300))
+        );
+        assert_eq!(records.update(10, true, true, 360), None);
+        assert_eq!(
+            records.update(10, false, true, 420),
+            Some(HealthStatus::Healthy)
+        );
+    }
+
+    #[test]
+    fn it_ignores_peers_if_node_should_not_have_peers() {
+        let mut records = HealthRecords::new(1, 300);
+        assert_eq!(records.update(0, false, false, 0), None);
+        assert_eq!(records.update(0, false, false, 60), None);
+    }
+}
@@ -0,0 +1,85 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::config::CONFIG;
+use async_std::task;
+use log::{debug, warn};
+use std::time;
+
+/// Returns the url pinged when the subscription fails
+fn fail_url(url: &str) -> String {
+    format!("{}/fail", url.trim_end_matches('/'))
+}
+
+/// Ping the url in the background, so that an unreachable url never holds the
+/// processing of on-chain events
+fn ping(url: String) {
+    task::spawn(async move {
+        let client = reqwest::Client::new();
+        match client
+            .get(&url)
+            .timeout(time::Duration::from_secs(CONFIG.current().request_timeout))
+            .send()
+            .await
+        {
+            Ok(res) if res.status().is_success() => debug!("Healthcheck {} pinged", url),
+            Ok(res) => warn!("Healthcheck ping failed! {} -> {}", url, res.status()),
+            Err(e) => warn!("Healthcheck ping failed! {}", e),
+        }
+    });
+}
+
+/// Ping the healthcheck url every time a session is processed, as a dead man's
+/// switch for an external uptime service
+pub fn ping_success() {
+    if CONFIG.current().healthcheck_ping_url.is_empty() {
+        return;
+    }
+    ping(CONFIG.current().healthcheck_ping_url.clone());
+}
+
+/// Ping the fail variant of the healthcheck url when the subscription fails, if
+/// `healthcheck_ping_fail` is set
+pub fn ping_fail() {
+    if CONFIG.current().healthcheck_ping_url.is_empty()
+        || !CONFIG.current().healthcheck_ping_fail
+    {
+        return;
+    }
+    ping(fail_url(&CONFIG.current().healthcheck_ping_url));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_appends_fail_to_the_ping_url() {
+        assert_eq!(
+            fail_url("https://hc-ping.com/0a1b2c3d"),
+            "https://hc-ping.com/0a1b2c3d/fail"
+        );
+        assert_eq!(
+            fail_url("https://hc-ping.com/0a1b2c3d/"),
+            "https://hc-ping.com/0a1b2c3d/fail"
+        );
+    }
+}
@@ -19,24 +19,268 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+use crate::config::CONFIG;
 use crate::errors::ScoutyError;
-use log::{info, warn};
-use serde::Deserialize;
-use std::io::{BufRead, BufReader};
+use crate::metrics;
+use crate::report::Network;
+use crate::runtimes::support::SupportedRuntime;
+use async_std::{
+    channel::{self, Receiver, Sender},
+    task,
+};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::os::unix::{fs::PermissionsExt, process::CommandExt};
 use std::path::Path;
-use std::{process::Command, process::Stdio, result::Result};
+use std::{
+    collections::{BTreeMap, HashMap},
+    process::Command,
+    process::Stdio,
+    result::Result,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 
-pub const HOOK_INIT: &'static str = "Scouty initialized";
-pub const HOOK_NEW_SESSION: &'static str = "New session";
-pub const HOOK_NEW_ERA: &'static str = "New era";
-pub const HOOK_VALIDATOR_STARTS_ACTIVE_NEXT_ERA: &'static str =
+// Interval (in milliseconds) between checks of the hook process status
+const HOOK_WAIT_INTERVAL: u64 = 100;
+
+// Number of stderr lines included in the error of a failed hook
+const HOOK_STDERR_MAX_LINES: usize = 5;
+
+// Exit codes of the hook scripts:
+// 0 -> success, the matrix message of the event is sent as usual
+// 10 -> handled by the script, the matrix message of the event is suppressed
+// 75 -> failure that should not be retried
+// any other -> failure, retried if hook retries is set
+
+// Exit code used by hook scripts that handled the event themselves, e.g. by paging
+// the operator, so that the matrix message of the event is suppressed
+const HOOK_SUPPRESS_EXIT_CODE: i32 = 10;
+
+// Exit code used by hook scripts that should not be retried when they fail
+const HOOK_NO_RETRY_EXIT_CODE: i32 = 75;
+
+// Maximum number of hook output lines included in the matrix message
+const HOOK_OUTPUT_MAX_LINES: usize = 20;
+
+// Maximum number of characters of every hook output line included in the matrix message
+const HOOK_OUTPUT_MAX_CHARS: usize = 200;
+
+// Maximum number of characters of the stdout and stderr kept in the hook history log
+const HOOK_LOG_MAX_CHARS: usize = 1000;
+
+// Env vars with secrets that are never exported to the hook scripts
+const HOOK_ENV_SECRETS: [&str; 9] = [
+    "SCOUTY_MATRIX_BOT_PASSWORD",
+    "SCOUTY_MATRIX_ACCESS_TOKEN",
+    "SCOUTY_WEBHOOK_TOKEN",
+    "SCOUTY_WEBHOOK_SECRET",
+    "SCOUTY_TELEGRAM_BOT_TOKEN",
+    "SCOUTY_DISCORD_WEBHOOK_URL",
+    "SCOUTY_PAGERDUTY_ROUTING_KEY",
+    "SCOUTY_SMTP_PASSWORD",
+    "SCOUTY_HEALTHCHECK_PING_URL",
+];
+
+pub const HOOK_INIT: &str = "Scouty initialized";
+pub const HOOK_NEW_SESSION: &str = "New session";
+pub const HOOK_NEW_ERA: &str = "New era";
+pub const HOOK_ERA_PAID: &str = "Era paid";
+pub const HOOK_VALIDATOR_STARTS_ACTIVE_NEXT_ERA: &str =
     "Validator starts active next era";
-pub const HOOK_VALIDATOR_STARTS_INACTIVE_NEXT_ERA: &'static str =
+pub const HOOK_VALIDATOR_STARTS_INACTIVE_NEXT_ERA: &str =
     "Validator starts inactive next era";
-pub const HOOK_VALIDATOR_SLASHED: &'static str = "Validator has been slashed";
-pub const HOOK_VALIDATOR_CHILLED: &'static str = "Validator has been chilled";
-pub const HOOK_VALIDATOR_OFFLINE: &'static str = "Validator has been offline";
-pub const HOOK_REFERENDA_SUBMITTED: &'static str = "Referenda submitted";
+pub const HOOK_VALIDATOR_SLASHED: &str = "Validator has been slashed";
+pub const HOOK_VALIDATOR_CHILLED: &str = "Validator has been chilled";
+pub const HOOK_VALIDATOR_OFFLINE: &str = "Validator has been offline";
+pub const HOOK_VALIDATOR_OFFLINE_RISK: &str = "Validator at risk of being offline";
+pub const HOOK_SESSION_KEYS_MISMATCH: &str = "Session keys mismatch";
+pub const HOOK_VALIDATOR_PREFS_CHANGED: &str = "Validator preferences changed";
+pub const HOOK_DISPUTE: &str = "Dispute vote";
+pub const HOOK_REFERENDA_SUBMITTED: &str = "Referenda submitted";
+pub const HOOK_NODE_UNHEALTHY: &str = "Node unhealthy";
+pub const HOOK_FINALITY_STALLED: &str = "Finality stalled";
+pub const HOOK_RUNTIME_UPGRADE: &str = "Runtime upgrade";
+pub const HOOK_PAYOUT_SUBMITTED: &str = "Payout submitted";
+pub const HOOK_COMMISSION_OUT_OF_POLICY: &str = "Commission out of policy";
+pub const HOOK_VALIDATOR_DISABLED: &str = "Validator disabled";
+pub const HOOK_PAYEE_CHANGED: &str = "Payee changed";
+pub const HOOK_LOW_BALANCE: &str = "Low balance";
+pub const HOOK_OFFENCE_REPORTED: &str = "Offence reported";
+pub const HOOK_CATCH_UP: &str = "Catch up";
+pub const HOOK_WEEKLY_REPORT: &str = "Weekly report";
+pub const HOOK_NOMINATION_INACTIVE: &str = "Nomination inactive";
+
+/// Conventional script names of the hooks looked up in the hooks directory. A script
+/// matches with or without the leading '_' and the extension, e.g. _new_session.sh,
+/// new_session.sh or new_session
+pub const HOOK_SCRIPT_NAMES: [(&str, &str); 26] = [
+    (HOOK_INIT, "init"),
+    (HOOK_NEW_SESSION, "new_session"),
+    (HOOK_NEW_ERA, "new_era"),
+    (HOOK_ERA_PAID, "era_paid"),
+    (
+        HOOK_VALIDATOR_STARTS_ACTIVE_NEXT_ERA,
+        "validator_starts_active_next_era",
+    ),
+    (
+        HOOK_VALIDATOR_STARTS_INACTIVE_NEXT_ERA,
+        "validator_starts_inactive_next_era",
+    ),
+    (HOOK_VALIDATOR_SLASHED, "validator_slashed"),
+    (HOOK_VALIDATOR_CHILLED, "validator_chilled"),
+    (HOOK_VALIDATOR_OFFLINE, "validator_offline"),
+    (HOOK_VALIDATOR_OFFLINE_RISK, "validator_offline_risk"),
+    (HOOK_SESSION_KEYS_MISMATCH, "session_keys_mismatch"),
+    (HOOK_VALIDATOR_PREFS_CHANGED, "validator_prefs_changed"),
+    (HOOK_DISPUTE, "dispute"),
+    (HOOK_REFERENDA_SUBMITTED, "referenda_submitted"),
+    (HOOK_NODE_UNHEALTHY, "node_unhealthy"),
+    (HOOK_FINALITY_STALLED, "finality_stalled"),
+    (HOOK_RUNTIME_UPGRADE, "runtime_upgrade"),
+    (HOOK_PAYOUT_SUBMITTED, "payout_submitted"),
+    (HOOK_COMMISSION_OUT_OF_POLICY, "commission_out_of_policy"),
+    (HOOK_VALIDATOR_DISABLED, "validator_disabled"),
+    (HOOK_PAYEE_CHANGED, "payee_changed"),
+    (HOOK_LOW_BALANCE, "low_balance"),
+    (HOOK_OFFENCE_REPORTED, "offence_reported"),
+    (HOOK_CATCH_UP, "catch_up"),
+    (HOOK_WEEKLY_REPORT, "weekly_report"),
+    (HOOK_NOMINATION_INACTIVE, "nomination_inactive"),
+];
+
+/// Hooks of the events that are still run while alerts are muted, unless `mute_all`
+/// is set
+pub const HOOKS_MUTE_EXEMPT: [&str; 3] =
+    [HOOK_VALIDATOR_SLASHED, HOOK_OFFENCE_REPORTED, HOOK_DISPUTE];
+
+/// Placeholder replaced by the validator stash in the hook paths, so that every stash
+/// can run its own script, e.g. /opt/scouty-cli/hooks/{stash}/_new_era.sh
+pub const HOOK_STASH_PLACEHOLDER: &str = "{stash}";
+
+/// Version of the JSON payload written to the stdin of the hook scripts, it is
+/// increased on every breaking change of the payload fields
+pub const HOOK_PAYLOAD_VERSION: u32 = 1;
+
+/// JSON payload written to the stdin of the hook scripts, so that scripts can read
+/// the data by name instead of by the position of the arguments. Fields that are not
+/// available for a particular hook are omitted.
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct HookPayload {
+    pub version: u32,
+    pub hook: String,
+    pub chain: String,
+    pub timestamp: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identity: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queued_session_keys: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_active: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_queued: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub era_index: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_index: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eras_session_index: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_symbol: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_decimals: Option<u8>,
+    /// Hook specific fields (e.g. era points, slash amount or referendum index)
+    #[serde(flatten)]
+    pub details: BTreeMap<String, serde_json::Value>,
+}
+
+impl HookPayload {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the validator fields
+    pub fn validator(
+        mut self,
+        stash: String,
+        identity: String,
+        queued_session_keys: String,
+        is_active: bool,
+        is_queued: bool,
+    ) -> Self {
+        self.stash = Some(stash);
+        self.identity = Some(identity);
+        self.queued_session_keys = Some(queued_session_keys);
+        self.is_active = Some(is_active);
+        self.is_queued = Some(is_queued);
+        self
+    }
+
+    /// Set the era and session fields
+    pub fn session(
+        mut self,
+        era_index: u32,
+        session_index: u32,
+        eras_session_index: Option<u32>,
+    ) -> Self {
+        self.era_index = Some(era_index);
+        self.session_index = Some(session_index);
+        self.eras_session_index = eras_session_index;
+        self
+    }
+
+    /// Set the network fields
+    pub fn network(mut self, network: &Network) -> Self {
+        self.network_name = Some(network.name.to_string());
+        self.token_symbol = Some(network.token_symbol.to_string());
+        self.token_decimals = Some(network.token_decimals);
+        self
+    }
+
+    /// Set a hook specific field
+    pub fn with(mut self, key: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.details.insert(key.to_string(), value.into());
+        self
+    }
+
+    /// Environment variables exported to the hook scripts, only the fields
+    /// available for the hook are exported
+    pub fn env_vars(&self) -> Vec<(&'static str, String)> {
+        let mut vars = vec![
+            ("SCOUTY_CHAIN", self.chain.to_string()),
+            ("SCOUTY_HOOK_NAME", self.hook.to_string()),
+        ];
+        if let Some(stash) = &self.stash {
+            vars.push(("SCOUTY_STASH", stash.to_string()));
+        }
+        if let Some(identity) = &self.identity {
+            vars.push(("SCOUTY_IDENTITY", identity.to_string()));
+        }
+        if let Some(is_active) = self.is_active {
+            vars.push(("SCOUTY_IS_ACTIVE", is_active.to_string()));
+        }
+        if let Some(is_queued) = self.is_queued {
+            vars.push(("SCOUTY_IS_ACTIVE_NEXT_ERA", is_queued.to_string()));
+        }
+        if let Some(era_index) = self.era_index {
+            vars.push(("SCOUTY_ERA_INDEX", era_index.to_string()));
+        }
+        if let Some(session_index) = self.session_index {
+            vars.push(("SCOUTY_SESSION_INDEX", session_index.to_string()));
+        }
+        vars
+    }
+}
 
 #[derive(Debug, Deserialize, Default)]
 pub struct Hook {
@@ -48,62 +292,270 @@ pub struct Hook {
     pub filename_exists: bool,
     #[serde(default)]
     pub stdout: Vec<u8>,
+    /// The hook script exited with the suppress exit code (10)
+    #[serde(default)]
+    pub is_suppressed: bool,
+    /// Receives if the hook script queued in the background exited with the suppress
+    /// exit code, once the script finishes
+    #[serde(skip)]
+    pub suppression: Option<Receiver<bool>>,
 }
 
 impl Hook {
+    /// Run the hook script, the payload is written to its stdin and the main
+    /// fields are also exported as SCOUTY_* env vars
     pub fn try_run(
+        chain: SupportedRuntime,
         name: &str,
         filename: &str,
         args: Vec<String>,
+        payload: HookPayload,
+    ) -> Result<Hook, ScoutyError> {
+        let result = Self::try_run_with_retries(
+            chain,
+            name,
+            filename,
+            args,
+            payload,
+            CONFIG.current().hook_timeout,
+            CONFIG.current().hook_retries,
+            CONFIG.current().hook_retry_delay,
+        );
+        let status = match &result {
+            Ok(hook) if hook.is_suppressed => "suppressed",
+            Ok(_) => "success",
+            Err(ScoutyError::HookTimeout(_)) => "timeout",
+            Err(_) => "failed",
+        };
+        metrics::HOOK_EXECUTIONS.inc(&[("hook", name), ("status", status)]);
+        result
+    }
+
+    /// Run the hook script and retry it up to `retries` times, waiting `retry_delay`
+    /// seconds between attempts, if it exits with error. Hooks killed on timeout or
+    /// that exit with the no retry exit code (75) are not retried.
+    #[allow(clippy::too_many_arguments)]
+    fn try_run_with_retries(
+        chain: SupportedRuntime,
+        name: &str,
+        filename: &str,
+        args: Vec<String>,
+        payload: HookPayload,
+        timeout: u64,
+        retries: u32,
+        retry_delay: u64,
+    ) -> Result<Hook, ScoutyError> {
+        let mut attempt = 0;
+        loop {
+            match Self::try_run_with_timeout(
+                chain,
+                name,
+                filename,
+                args.clone(),
+                payload.clone(),
+                timeout,
+            ) {
+                Err(ScoutyError::HookFailed(e, exit_code))
+                    if attempt < retries
+                        && exit_code != Some(HOOK_NO_RETRY_EXIT_CODE) =>
+                {
+                    attempt += 1;
+                    warn!("{}", e);
+                    warn!(
+                        "Hook script - {} - retry {} of {} in {} seconds",
+                        name, attempt, retries, retry_delay
+                    );
+                    thread::sleep(Duration::from_secs(retry_delay));
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Run the hook script and kill it (and any process spawned by it) if it is
+    /// still running after `timeout` seconds, set 0 to wait forever
+    fn try_run_with_timeout(
+        chain: SupportedRuntime,
+        name: &str,
+        filename: &str,
+        args: Vec<String>,
+        payload: HookPayload,
+        timeout: u64,
     ) -> Result<Hook, ScoutyError> {
         if Path::new(filename).exists() {
-            info!("Run: {} {}", filename, args.join(" "));
+            let stash_desc = payload
+                .stash
+                .as_ref()
+                .map_or(String::new(), |stash| format!(" ({})", stash));
+            info!(
+                "Run: [{}] {}{} -> {} {}",
+                chain,
+                name,
+                stash_desc,
+                filename,
+                args.join(" ")
+            );
+
+            let payload = HookPayload {
+                version: HOOK_PAYLOAD_VERSION,
+                hook: name.to_string(),
+                chain: chain.to_string(),
+                timestamp: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs(),
+                ..payload
+            };
+            let timestamp = payload.timestamp;
+            let started_at = Instant::now();
 
-            let mut stdout_formatted: Vec<u8> = Vec::new();
+            // The hook inherits the environment of scouty, except for the secrets
+            let mut command = Command::new(filename);
+            command.args(&args).envs(payload.env_vars());
+            for key in HOOK_ENV_SECRETS {
+                command.env_remove(key);
+            }
+
+            let payload = serde_json::to_vec(&payload)?;
 
-            let mut child = Command::new(filename)
-                .args(args)
+            // The hook runs in its own process group, so that all the processes
+            // spawned by the script can be killed on timeout
+            let mut child = command
+                .process_group(0)
+                .stdin(Stdio::piped())
                 .stderr(Stdio::piped())
                 .stdout(Stdio::piped())
                 .spawn()?;
 
-            if let Some(child_stdout) = child.stdout.take() {
+            let (child_stdout, mut child_stderr) = match (
+                child.stdout.take(),
+                child.stderr.take(),
+            ) {
+                (Some(child_stdout), Some(child_stderr)) => (child_stdout, child_stderr),
+                _ => {
+                    warn!(
+                            "Hook script - {} - filename ({}) child stdout could not be captured",
+                            name, filename
+                        );
+                    return Err(ScoutyError::Other(format!(
+                            "Hook script - {} - filename ({}) child stdout could not be captured",
+                            name, filename
+                        )));
+                }
+            };
+
+            // Write and read the pipes on their own threads, so that the hook is never
+            // blocked on a full pipe while waiting for it to finish. Scripts are not
+            // required to read the payload, so write errors are ignored.
+            if let Some(mut child_stdin) = child.stdin.take() {
+                thread::spawn(move || {
+                    let _ = child_stdin.write_all(&payload);
+                });
+            }
+            let stdout_reader = thread::spawn(move || {
+                let mut stdout_formatted: Vec<u8> = Vec::new();
                 let reader = BufReader::new(child_stdout);
+                reader.lines().map_while(Result::ok).for_each(|line| {
+                    info!("$ {}", line);
+                    stdout_formatted.extend(format!("{}\n", line).as_bytes().to_vec());
+                });
+                stdout_formatted
+            });
+            let stderr_reader = thread::spawn(move || {
+                let mut stderr = Vec::new();
+                let _ = child_stderr.read_to_end(&mut stderr);
+                stderr
+            });
 
-                reader
-                    .lines()
-                    .filter_map(|line| line.ok())
-                    .for_each(|line| {
-                        info!("$ {}", line);
-                        stdout_formatted
-                            .extend(format!("{}\n", line).as_bytes().to_vec());
-                    });
-
-                let output = child.wait_with_output()?;
-
-                if output.status.success() {
-                    Ok(Hook {
-                        name: name.to_string(),
-                        filename: filename.to_string(),
-                        filename_exists: true,
-                        stdout: stdout_formatted,
-                    })
-                } else {
-                    let err = String::from_utf8(output.stderr)?;
-                    Err(ScoutyError::Other(format!(
-                        "Hook script - {} - filename ({}) executed with error: {:?}",
-                        name, filename, err
-                    )))
+            let deadline = Instant::now() + Duration::from_secs(timeout);
+            let status = loop {
+                if let Some(status) = child.try_wait()? {
+                    break status;
+                }
+                if timeout > 0 && Instant::now() >= deadline {
+                    // SAFETY: kill only signals the process group created for the hook
+                    unsafe {
+                        libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
+                    }
+                    child.wait()?;
+                    let stdout = stdout_reader.join().unwrap_or_default();
+                    warn!(
+                        "Hook script - {} - filename ({}) killed after {} seconds, stdout captured: {:?}",
+                        name,
+                        filename,
+                        timeout,
+                        String::from_utf8_lossy(&stdout)
+                    );
+                    HookLogEntry {
+                        timestamp,
+                        chain: chain.to_string(),
+                        hook: name,
+                        filename,
+                        args: &args,
+                        exit_code: None,
+                        is_timeout: true,
+                        duration_ms: started_at.elapsed().as_millis(),
+                        stdout: truncate(&String::from_utf8_lossy(&stdout)),
+                        stderr: String::new(),
+                    }
+                    .log();
+                    return Err(ScoutyError::HookTimeout(name.to_string()));
                 }
+                thread::sleep(Duration::from_millis(HOOK_WAIT_INTERVAL));
+            };
+
+            let stdout_formatted = stdout_reader.join().unwrap_or_default();
+            let stderr =
+                String::from_utf8_lossy(&stderr_reader.join().unwrap_or_default())
+                    .to_string();
+
+            HookLogEntry {
+                timestamp,
+                chain: chain.to_string(),
+                hook: name,
+                filename,
+                args: &args,
+                exit_code: status.code(),
+                is_timeout: false,
+                duration_ms: started_at.elapsed().as_millis(),
+                stdout: truncate(&String::from_utf8_lossy(&stdout_formatted)),
+                stderr: truncate(&stderr),
+            }
+            .log();
+
+            let is_suppressed = status.code() == Some(HOOK_SUPPRESS_EXIT_CODE);
+            if status.success() || is_suppressed {
+                // Scripts might write warnings to stderr and still succeed
+                stderr.lines().for_each(|line| debug!("$ {}", line));
+                if is_suppressed {
+                    info!(
+                        "Hook script - {} - filename ({}) handled the event -> matrix message suppressed",
+                        name, filename
+                    );
+                }
+                Ok(Hook {
+                    name: name.to_string(),
+                    filename: filename.to_string(),
+                    filename_exists: true,
+                    stdout: stdout_formatted,
+                    is_suppressed,
+                    suppression: None,
+                })
             } else {
-                warn!(
-                    "Hook script - {} - filename ({}) child stdout could not be captured",
-                    name, filename
-                );
-                Err(ScoutyError::Other(format!(
-                    "Hook script - {} - filename ({}) child stdout could not be captured",
-                    name, filename
-                )))
+                stderr.lines().for_each(|line| warn!("$ {}", line));
+                let exit_code = status
+                    .code()
+                    .map_or("none".to_string(), |code| code.to_string());
+                Err(ScoutyError::HookFailed(
+                    format!(
+                        "Hook script - {} - filename ({}) executed with error (exit code {}): {}",
+                        name,
+                        filename,
+                        exit_code,
+                        last_lines(&stderr, HOOK_STDERR_MAX_LINES)
+                    ),
+                    status.code(),
+                ))
             }
         } else {
             warn!(
@@ -115,10 +567,48 @@ impl Hook {
                 filename: filename.to_string(),
                 filename_exists: false,
                 stdout: vec![],
+                is_suppressed: false,
+                suppression: None,
             })
         }
     }
 
+    /// Resolve the hook path of the stash, if the stash script does not exist it falls
+    /// back to the global path, the same path without the stash directory
+    pub fn resolve_path(filename: &str, stash: &str) -> String {
+        if !filename.contains(HOOK_STASH_PLACEHOLDER) {
+            return filename.to_string();
+        }
+        let stash_filename = filename.replace(HOOK_STASH_PLACEHOLDER, stash);
+        if Path::new(&stash_filename).exists() {
+            return stash_filename;
+        }
+        filename.replace(&format!("/{}", HOOK_STASH_PLACEHOLDER), "")
+    }
+
+    /// Lines of the hook output to be included in the matrix message. Lines that start
+    /// by the special character '!' are always included, and if hook output to matrix
+    /// is set the remaining lines are also included HTML escaped
+    pub fn matrix_lines(&self) -> Vec<String> {
+        matrix_lines(&self.stdout, CONFIG.current().hook_output_to_matrix)
+    }
+
+    /// Verify that the hook script exists, is a file and is executable
+    pub fn verify(name: &str, filename: &str) -> Result<(), ScoutyError> {
+        let reason = match Path::new(filename).metadata() {
+            Err(_) => "not defined",
+            Ok(metadata) if !metadata.is_file() => "is not a file",
+            Ok(metadata) if metadata.permissions().mode() & 0o111 == 0 => {
+                "is not executable"
+            }
+            Ok(_) => return Ok(()),
+        };
+        Err(ScoutyError::HookInvalid(format!(
+            "Hook script - {} - filename ({}) {}",
+            name, filename, reason
+        )))
+    }
+
     pub fn exists(name: &str, filename: &str) -> bool {
         if !Path::new(filename).exists() {
             warn!(
@@ -127,6 +617,917 @@ impl Hook {
             );
             return false;
         }
-        return true;
+        true
+    }
+}
+
+fn matrix_lines(stdout: &[u8], all: bool) -> Vec<String> {
+    String::from_utf8_lossy(stdout)
+        .lines()
+        .filter_map(|line| match line.strip_prefix('!') {
+            Some(line) => Some(line.to_string()),
+            None if all && !line.trim().is_empty() => {
+                let line: String =
+                    line.trim().chars().take(HOOK_OUTPUT_MAX_CHARS).collect();
+                Some(escape_html(&line))
+            }
+            None => None,
+        })
+        .take(HOOK_OUTPUT_MAX_LINES)
+        .collect()
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+pub type HookResult = Result<Hook, ScoutyError>;
+
+/// Invocation of a hook, the instant it was queued at and the sender of the suppress
+/// decision of the script
+type HookJob = (String, Vec<String>, HookPayload, Instant, Sender<bool>);
+
+/// Queue of the invocations of a single hook and stash and the number of invocations
+/// pending
+type HookQueue = (Sender<HookJob>, Arc<AtomicUsize>);
+
+/// Permits shared by all the hooks, so that only a limited number of hook scripts
+/// run at the same time. A permit is taken from the channel before running a script
+/// and given back once it finishes.
+type HookPermits = (Sender<()>, Receiver<()>);
+
+/// Runs hook scripts in the background, so that slow scripts do not delay the
+/// processing of on-chain events. Invocations of the same hook and stash are queued
+/// and run one after the other, or skipped if `skip_overlap` is set, the results are
+/// sent to the receiver returned on creation.
+pub struct HookRunner {
+    chain: SupportedRuntime,
+    queues: Mutex<HashMap<String, HookQueue>>,
+    results: Sender<HookResult>,
+    permits: Option<HookPermits>,
+    skip_overlap: bool,
+}
+
+impl HookRunner {
+    /// Create the runner, set `max_concurrent` to 0 to run any number of hook
+    /// scripts at the same time
+    pub fn new(
+        chain: SupportedRuntime,
+        max_concurrent: usize,
+        skip_overlap: bool,
+    ) -> (Self, Receiver<HookResult>) {
+        let (results, receiver) = channel::unbounded();
+        let permits = if max_concurrent > 0 {
+            let (sender, receiver) = channel::bounded(max_concurrent);
+            for _ in 0..max_concurrent {
+                let _ = sender.try_send(());
+            }
+            Some((sender, receiver))
+        } else {
+            None
+        };
+        let runner = Self {
+            chain,
+            queues: Mutex::new(HashMap::new()),
+            results,
+            permits,
+            skip_overlap,
+        };
+        (runner, receiver)
+    }
+
+    /// Queue the hook script to run in the background. The hook returned only
+    /// describes the script, since the script output is not available yet.
+    pub fn spawn(
+        &self,
+        name: &str,
+        filename: &str,
+        args: Vec<String>,
+        payload: HookPayload,
+    ) -> Hook {
+        if !Hook::exists(name, filename) {
+            return Hook {
+                name: name.to_string(),
+                filename: filename.to_string(),
+                filename_exists: false,
+                stdout: vec![],
+                is_suppressed: false,
+                suppression: None,
+            };
+        }
+
+        // Validator hooks of different stashes are allowed to run at the same time
+        let key = payload
+            .stash
+            .as_ref()
+            .map_or(name.to_string(), |stash| format!("{} ({})", name, stash));
+
+        let mut queues = self.queues.lock().unwrap();
+        let (sender, pending) = queues
+            .entry(key.clone())
+            .or_insert_with(|| self.spawn_worker(name, &key));
+        if pending.load(Ordering::SeqCst) > 0 {
+            if self.skip_overlap {
+                warn!(
+                    "Hook script - {} - filename ({}) still running -> skipped",
+                    key, filename
+                );
+                return Hook {
+                    name: name.to_string(),
+                    filename: filename.to_string(),
+                    filename_exists: true,
+                    stdout: vec![],
+                    is_suppressed: false,
+                    suppression: None,
+                };
+            }
+            info!(
+                "Hook script - {} - filename ({}) still running -> queued",
+                key, filename
+            );
+        }
+        pending.fetch_add(1, Ordering::SeqCst);
+        let (decision, suppression) = channel::bounded(1);
+        if sender
+            .try_send((
+                filename.to_string(),
+                args,
+                payload,
+                Instant::now(),
+                decision,
+            ))
+            .is_err()
+        {
+            pending.fetch_sub(1, Ordering::SeqCst);
+            warn!(
+                "Hook script - {} - filename ({}) could not be queued",
+                key, filename
+            );
+        }
+
+        Hook {
+            name: name.to_string(),
+            filename: filename.to_string(),
+            filename_exists: true,
+            stdout: vec![],
+            is_suppressed: false,
+            suppression: Some(suppression),
+        }
+    }
+
+    /// Spawn the task that runs the queued invocations of the hook and stash, the
+    /// task finishes once the runner is dropped
+    fn spawn_worker(&self, name: &str, key: &str) -> HookQueue {
+        let (sender, receiver) = channel::unbounded::<HookJob>();
+        let pending = Arc::new(AtomicUsize::new(0));
+        let chain = self.chain;
+        let name = name.to_string();
+        let key = key.to_string();
+        let results = self.results.clone();
+        let permits = self.permits.clone();
+        let worker_pending = pending.clone();
+        task::spawn(async move {
+            while let Ok((filename, args, payload, queued_at, decision)) =
+                receiver.recv().await
+            {
+                // Wait for a permit if the number of hook scripts running is limited
+                if let Some((_, permits)) = &permits {
+                    let _ = permits.recv().await;
+                }
+                let waited = queued_at.elapsed();
+                let started_at = Instant::now();
+                let hook_name = name.clone();
+                let hook_filename = filename.clone();
+                let result = task::spawn_blocking(move || {
+                    Hook::try_run(chain, &hook_name, &hook_filename, args, payload)
+                })
+                .await;
+                if let Some((permits, _)) = &permits {
+                    let _ = permits.send(()).await;
+                }
+                info!(
+                    "Hook script - {} - filename ({}) waited {:.1}s and ran for {:.1}s",
+                    key,
+                    filename,
+                    waited.as_secs_f64(),
+                    started_at.elapsed().as_secs_f64()
+                );
+                worker_pending.fetch_sub(1, Ordering::SeqCst);
+                let _ =
+                    decision.try_send(matches!(&result, Ok(hook) if hook.is_suppressed));
+                // Results are dropped if no one is listening anymore
+                let _ = results.send(result).await;
+            }
+        });
+        (sender, pending)
+    }
+}
+
+/// Entry of the hook history log, one JSON line is appended per hook invocation
+#[derive(Debug, Serialize)]
+struct HookLogEntry<'a> {
+    timestamp: u64,
+    chain: String,
+    hook: &'a str,
+    filename: &'a str,
+    args: &'a [String],
+    exit_code: Option<i32>,
+    is_timeout: bool,
+    duration_ms: u128,
+    stdout: String,
+    stderr: String,
+}
+
+impl HookLogEntry<'_> {
+    /// Append the entry to the hook history log, if defined. Failing to write the log
+    /// never fails the hook, only a warning is logged.
+    fn log(&self) {
+        if CONFIG.current().hook_log_path.is_empty() {
+            return;
+        }
+        if let Err(e) = self.append_to(
+            &CONFIG.current().hook_log_path,
+            CONFIG.current().hook_log_max_size * 1024 * 1024,
+        ) {
+            warn!(
+                "Hook history log ({}) could not be written: {}",
+                CONFIG.current().hook_log_path,
+                e
+            );
+        }
+    }
+
+    /// Append the entry as a JSON line, the log is rotated to `<path>.1` once it
+    /// reaches `max_size` bytes, set 0 to never rotate
+    fn append_to(&self, path: &str, max_size: u64) -> Result<(), ScoutyError> {
+        if max_size > 0 {
+            if let Ok(metadata) = fs::metadata(path) {
+                if metadata.len() >= max_size {
+                    fs::rename(path, format!("{}.1", path))?;
+                }
+            }
+        }
+        let mut line = serde_json::to_vec(self)?;
+        line.push(b'\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?
+            .write_all(&line)?;
+        Ok(())
+    }
+}
+
+/// Hook scripts found in the hooks directory
+#[derive(Debug, Default)]
+pub struct HooksDir {
+    /// Hook name and path of the scripts matching a conventional script name
+    pub discovered: BTreeMap<&'static str, String>,
+    /// File names that don't match any conventional script name
+    pub unknown: Vec<String>,
+}
+
+impl HooksDir {
+    /// Enumerate the directory and map every file to a hook by its conventional name
+    pub fn discover(dir: &str) -> Result<Self, ScoutyError> {
+        let mut entries = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| !path.is_dir())
+            .collect::<Vec<_>>();
+        entries.sort();
+
+        let mut hooks_dir = HooksDir::default();
+        for path in entries {
+            let file_name = match path.file_name() {
+                Some(file_name) => file_name.to_string_lossy().to_string(),
+                None => continue,
+            };
+            // Hidden files (e.g. .gitkeep) are not hook scripts
+            if file_name.starts_with('.') {
+                continue;
+            }
+            let script_name = file_name.trim_start_matches('_');
+            let script_name = script_name
+                .split('.')
+                .next()
+                .unwrap_or(script_name)
+                .to_string();
+            match HOOK_SCRIPT_NAMES
+                .iter()
+                .find(|(_, name)| *name == script_name)
+            {
+                Some((hook, _)) if !hooks_dir.discovered.contains_key(hook) => {
+                    hooks_dir
+                        .discovered
+                        .insert(hook, path.to_string_lossy().to_string());
+                }
+                _ => hooks_dir.unknown.push(file_name),
+            }
+        }
+        Ok(hooks_dir)
+    }
+}
+
+/// Returns the hook of the identifier, either its conventional script name (e.g.
+/// validator_starts_active_next_era) or a unique suffix of it (e.g. active_next_era)
+pub fn hook_by_id(id: &str) -> Option<&'static str> {
+    let id = id.trim().trim_start_matches('_');
+    if let Some((hook, _)) = HOOK_SCRIPT_NAMES.iter().find(|(_, name)| *name == id) {
+        return Some(hook);
+    }
+    let suffix = format!("_{}", id);
+    let hooks: Vec<&'static str> = HOOK_SCRIPT_NAMES
+        .iter()
+        .filter(|(_, name)| name.ends_with(&suffix))
+        .map(|(hook, _)| *hook)
+        .collect();
+    match hooks.as_slice() {
+        [hook] => Some(*hook),
+        _ => None,
+    }
+}
+
+/// Synthetic event the hook scripts are tested with, so that scripts can be exercised
+/// without waiting for a real event
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntheticEvent {
+    pub chain: SupportedRuntime,
+    pub stash: String,
+    pub identity: String,
+    pub queued_session_keys: String,
+    pub is_active: bool,
+    pub is_queued: bool,
+    pub era_index: u32,
+    pub session_index: u32,
+    pub eras_session_index: u32,
+    pub block_number: u32,
+}
+
+impl Default for SyntheticEvent {
+    fn default() -> Self {
+        Self {
+            chain: SupportedRuntime::Polkadot,
+            stash: "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string(),
+            identity: "ALICE".to_string(),
+            queued_session_keys: "0x00".to_string(),
+            is_active: true,
+            is_queued: true,
+            era_index: 1000,
+            session_index: 6000,
+            eras_session_index: 6,
+            block_number: 20000000,
+        }
+    }
+}
+
+impl SyntheticEvent {
+    /// Returns the synthetic event with the fields overridden by the `key=value` pairs
+    /// defined, e.g. stash=5Grw.. or era=1234
+    pub fn with_overrides(overrides: &[String]) -> Result<Self, ScoutyError> {
+        let mut event = SyntheticEvent::default();
+        for pair in overrides.iter().filter(|pair| !pair.trim().is_empty()) {
+            let (key, value) = match pair.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => {
+                    return Err(ScoutyError::Other(format!(
+                        "Hook argument {} is not a key=value pair",
+                        pair
+                    )))
+                }
+            };
+            let invalid =
+                || ScoutyError::Other(format!("Hook argument {} is not valid", pair));
+            match key {
+                "chain" => {
+                    event.chain =
+                        SupportedRuntime::from_spec_name(&value.to_lowercase())
+                            .ok_or_else(invalid)?
+                }
+                "stash" => event.stash = value.to_string(),
+                "identity" => event.identity = value.to_string(),
+                "queued_session_keys" => event.queued_session_keys = value.to_string(),
+                "is_active" => event.is_active = value.parse().map_err(|_| invalid())?,
+                "is_queued" => event.is_queued = value.parse().map_err(|_| invalid())?,
+                "era" => event.era_index = value.parse().map_err(|_| invalid())?,
+                "session" => event.session_index = value.parse().map_err(|_| invalid())?,
+                "eras_session" => {
+                    event.eras_session_index = value.parse().map_err(|_| invalid())?
+                }
+                "block" => event.block_number = value.parse().map_err(|_| invalid())?,
+                _ => {
+                    return Err(ScoutyError::Other(format!(
+                        "Hook argument {} is not known, use one of chain, stash, identity, queued_session_keys, is_active, is_queued, era, session, eras_session or block",
+                        key
+                    )))
+                }
+            }
+        }
+        Ok(event)
+    }
+
+    /// Returns the positional arguments of the event, in the same order as the
+    /// arguments of the new session hook
+    pub fn args(&self) -> Vec<String> {
+        vec![
+            self.stash.to_string(),
+            self.identity.to_string(),
+            self.queued_session_keys.to_string(),
+            self.is_active.to_string(),
+            self.is_queued.to_string(),
+            self.era_index.to_string(),
+            self.session_index.to_string(),
+            self.eras_session_index.to_string(),
+            self.block_number.to_string(),
+        ]
+    }
+
+    pub fn payload(&self) -> HookPayload {
+        HookPayload::new()
+            .validator(
+                self.stash.to_string(),
+                self.identity.to_string(),
+                self.queued_session_keys.to_string(),
+                self.is_active,
+                self.is_queued,
+            )
+            .session(
+                self.era_index,
+                self.session_index,
+                Some(self.eras_session_index),
+            )
+            .with("block_number", self.block_number)
+    }
+}
+
+/// Returns the first characters of the text, up to the hook log limit
+fn truncate(text: &str) -> String {
+    text.chars().take(HOOK_LOG_MAX_CHARS).collect()
+}
+
+/// Returns the last `n` non empty lines of the text, joined by " | "
+fn last_lines(text: &str, n: usize) -> String {
+    let lines: Vec<&str> = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    lines[lines.len().saturating_sub(n)..].join(" | ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn create_script(filename: &str, body: &str) -> String {
+        let path = env::temp_dir().join(filename);
+        fs::write(&path, format!("#!/bin/bash\n{}\n", body)).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn it_runs_a_hook() {
+        let filename = create_script("scouty_hook_runs.sh", "echo \"! $1\"");
+        let hook = Hook::try_run_with_timeout(
+            SupportedRuntime::Kusama,
+            HOOK_NEW_SESSION,
+            &filename,
+            vec!["hello".to_string()],
+            HookPayload::new(),
+            10,
+        )
+        .unwrap();
+        assert!(hook.filename_exists);
+        assert_eq!(hook.stdout, b"! hello\n".to_vec());
+    }
+
+    #[test]
+    fn it_includes_stderr_and_exit_code_on_error() {
+        let filename = create_script(
+            "scouty_hook_fails.sh",
+            "echo first >&2\necho second >&2\nexit 3",
+        );
+        let result = Hook::try_run_with_timeout(
+            SupportedRuntime::Kusama,
+            HOOK_NEW_SESSION,
+            &filename,
+            vec![],
+            HookPayload::new(),
+            10,
+        );
+        match result {
+            Err(ScoutyError::HookFailed(e, exit_code)) => {
+                assert_eq!(exit_code, Some(3));
+                assert!(e.contains("exit code 3"));
+                assert!(e.ends_with("first | second"));
+            }
+            _ => panic!("hook should have failed"),
+        }
+    }
+
+    #[test]
+    fn it_retries_a_failed_hook() {
+        let counter = env::temp_dir().join("scouty_hook_retries.count");
+        let _ = fs::remove_file(&counter);
+        let filename = create_script(
+            "scouty_hook_retries.sh",
+            &format!(
+                "echo x >> {0}\n[ $(wc -l < {0}) -ge 3 ] && echo \"! done\" || exit 1",
+                counter.display()
+            ),
+        );
+        let hook = Hook::try_run_with_retries(
+            SupportedRuntime::Kusama,
+            HOOK_NEW_SESSION,
+            &filename,
+            vec![],
+            HookPayload::new(),
+            10,
+            2,
+            0,
+        )
+        .unwrap();
+        assert_eq!(hook.stdout, b"! done\n".to_vec());
+    }
+
+    #[test]
+    fn it_does_not_retry_the_no_retry_exit_code() {
+        let counter = env::temp_dir().join("scouty_hook_no_retry.count");
+        let _ = fs::remove_file(&counter);
+        let filename = create_script(
+            "scouty_hook_no_retry.sh",
+            &format!("echo x >> {}\nexit 75", counter.display()),
+        );
+        let result = Hook::try_run_with_retries(
+            SupportedRuntime::Kusama,
+            HOOK_NEW_SESSION,
+            &filename,
+            vec![],
+            HookPayload::new(),
+            10,
+            3,
+            0,
+        );
+        assert!(matches!(result, Err(ScoutyError::HookFailed(_, Some(75)))));
+        assert_eq!(fs::read_to_string(&counter).unwrap().lines().count(), 1);
+    }
+
+    #[test]
+    fn it_maps_the_exit_codes() {
+        let run = |filename: &str| {
+            Hook::try_run_with_timeout(
+                SupportedRuntime::Kusama,
+                HOOK_VALIDATOR_STARTS_INACTIVE_NEXT_ERA,
+                filename,
+                vec![],
+                HookPayload::new(),
+                10,
+            )
+        };
+        let hook = run(&create_script("scouty_hook_exit_0.sh", "exit 0")).unwrap();
+        assert!(!hook.is_suppressed);
+        let hook = run(&create_script(
+            "scouty_hook_exit_10.sh",
+            "echo \"! paged\"\nexit 10",
+        ))
+        .unwrap();
+        assert!(hook.is_suppressed);
+        assert_eq!(hook.stdout, b"! paged\n".to_vec());
+        let result = run(&create_script("scouty_hook_exit_11.sh", "exit 11"));
+        assert!(matches!(result, Err(ScoutyError::HookFailed(_, Some(11)))));
+    }
+
+    #[test]
+    fn it_appends_and_rotates_the_hook_log() {
+        let path = env::temp_dir().join("scouty_hooks.jsonl");
+        let path = path.to_string_lossy().to_string();
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(format!("{}.1", path));
+        let args = vec!["1234".to_string()];
+        let entry = HookLogEntry {
+            timestamp: 1700000000,
+            chain: "polkadot".to_string(),
+            hook: HOOK_NEW_ERA,
+            filename: "_new_era.sh",
+            args: &args,
+            exit_code: Some(0),
+            is_timeout: false,
+            duration_ms: 12,
+            stdout: truncate(&"a".repeat(2000)),
+            stderr: String::new(),
+        };
+        entry.append_to(&path, 0).unwrap();
+        entry.append_to(&path, 0).unwrap();
+        let log = fs::read_to_string(&path).unwrap();
+        assert_eq!(log.lines().count(), 2);
+        let line: serde_json::Value =
+            serde_json::from_str(log.lines().next().unwrap()).unwrap();
+        assert_eq!(line["hook"], HOOK_NEW_ERA);
+        assert_eq!(line["args"][0], "1234");
+        assert_eq!(line["stdout"].as_str().unwrap().len(), HOOK_LOG_MAX_CHARS);
+
+        entry.append_to(&path, 1).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 1);
+        let rotated = fs::read_to_string(format!("{}.1", path)).unwrap();
+        assert_eq!(rotated.lines().count(), 2);
+    }
+
+    #[test]
+    fn it_resolves_the_stash_path() {
+        let dir = env::temp_dir().join("scouty_hooks_stash");
+        fs::create_dir_all(dir.join("stash_a")).unwrap();
+        fs::write(dir.join("stash_a").join("_new_era.sh"), "").unwrap();
+        let filename = format!("{}/{{stash}}/_new_era.sh", dir.display());
+        assert_eq!(
+            Hook::resolve_path(&filename, "stash_a"),
+            format!("{}/stash_a/_new_era.sh", dir.display())
+        );
+        assert_eq!(
+            Hook::resolve_path(&filename, "stash_b"),
+            format!("{}/_new_era.sh", dir.display())
+        );
+        assert_eq!(
+            Hook::resolve_path("./_new_era.sh", "stash_a"),
+            "./_new_era.sh"
+        );
+    }
+
+    #[test]
+    fn it_keeps_the_last_lines() {
+        assert_eq!(last_lines("a\nb\n\nc\nd\n", 3), "b | c | d");
+        assert_eq!(last_lines("a\n", 3), "a");
+        assert_eq!(last_lines("", 3), "");
+    }
+
+    #[test]
+    fn it_queues_hooks_running_in_the_background() {
+        let filename = create_script("scouty_hook_queued.sh", "sleep 0.2\necho \"! $1\"");
+        let results = task::block_on(async {
+            let (runner, receiver) = HookRunner::new(SupportedRuntime::Kusama, 0, false);
+            let hook = runner.spawn(
+                HOOK_NEW_SESSION,
+                &filename,
+                vec!["1".to_string()],
+                HookPayload::new(),
+            );
+            assert!(hook.filename_exists && hook.stdout.is_empty());
+            runner.spawn(
+                HOOK_NEW_SESSION,
+                &filename,
+                vec!["2".to_string()],
+                HookPayload::new(),
+            );
+            let first = receiver.recv().await.unwrap().unwrap();
+            let second = receiver.recv().await.unwrap().unwrap();
+            (first.stdout, second.stdout)
+        });
+        assert_eq!(results, (b"! 1\n".to_vec(), b"! 2\n".to_vec()));
+    }
+
+    #[test]
+    fn it_decides_the_suppression_of_hooks_running_in_the_background() {
+        let suppressed =
+            create_script("scouty_hook_background_exit_10.sh", "sleep 0.2\nexit 10");
+        let failed = create_script("scouty_hook_background_exit_11.sh", "exit 11");
+        let notified = create_script("scouty_hook_background_exit_0.sh", "exit 0");
+        let results = task::block_on(async {
+            let (runner, _receiver) = HookRunner::new(SupportedRuntime::Kusama, 0, false);
+            let mut decisions = Vec::new();
+            for filename in [&suppressed, &failed, &notified] {
+                let hook =
+                    runner.spawn(HOOK_NEW_SESSION, filename, vec![], HookPayload::new());
+                assert!(!hook.is_suppressed);
+                let suppression = hook.suppression.unwrap();
+                decisions.push(suppression.recv().await.unwrap_or_default());
+            }
+            decisions
+        });
+        assert_eq!(results, vec![true, false, false]);
+    }
+
+    #[test]
+    fn it_limits_the_concurrent_hooks() {
+        let filename = create_script("scouty_hook_limited.sh", "sleep 0.3");
+        let started = Instant::now();
+        task::block_on(async {
+            let (runner, receiver) = HookRunner::new(SupportedRuntime::Kusama, 1, false);
+            for stash in ["stash_a", "stash_b"] {
+                runner.spawn(
+                    HOOK_NEW_SESSION,
+                    &filename,
+                    vec![],
+                    HookPayload::new().validator(
+                        stash.into(),
+                        "name".into(),
+                        "0x".into(),
+                        true,
+                        true,
+                    ),
+                );
+            }
+            receiver.recv().await.unwrap().unwrap();
+            receiver.recv().await.unwrap().unwrap();
+        });
+        assert!(started.elapsed() >= Duration::from_millis(600));
+    }
+
+    #[test]
+    fn it_skips_overlapping_hooks() {
+        let filename =
+            create_script("scouty_hook_overlap.sh", "sleep 0.2\necho \"! $1\"");
+        let results = task::block_on(async {
+            let (runner, receiver) = HookRunner::new(SupportedRuntime::Kusama, 0, true);
+            for arg in ["1", "2"] {
+                runner.spawn(
+                    HOOK_NEW_SESSION,
+                    &filename,
+                    vec![arg.to_string()],
+                    HookPayload::new(),
+                );
+            }
+            let first = receiver.recv().await.unwrap().unwrap();
+            drop(runner);
+            (first.stdout, receiver.recv().await.is_err())
+        });
+        assert_eq!(results, (b"! 1\n".to_vec(), true));
+    }
+
+    #[test]
+    fn it_writes_the_payload_to_stdin() {
+        let filename = create_script("scouty_hook_payload.sh", "echo \"! $(cat)\"");
+        let hook = Hook::try_run_with_timeout(
+            SupportedRuntime::Polkadot,
+            HOOK_NEW_SESSION,
+            &filename,
+            vec![],
+            HookPayload::new()
+                .session(10, 60, Some(6))
+                .with("block_number", 1000),
+            10,
+        )
+        .unwrap();
+        let stdout = String::from_utf8(hook.stdout).unwrap();
+        let payload: serde_json::Value =
+            serde_json::from_str(stdout.strip_prefix("! ").unwrap()).unwrap();
+        assert_eq!(payload["version"], HOOK_PAYLOAD_VERSION);
+        assert_eq!(payload["hook"], HOOK_NEW_SESSION);
+        assert_eq!(payload["chain"], "Polkadot");
+        assert_eq!(payload["era_index"], 10);
+        assert_eq!(payload["session_index"], 60);
+        assert_eq!(payload["block_number"], 1000);
+        assert!(payload.get("stash").is_none());
+    }
+
+    #[test]
+    fn it_exports_env_vars_without_secrets() {
+        env::set_var("SCOUTY_MATRIX_BOT_PASSWORD", "secret");
+        let filename = create_script(
+            "scouty_hook_env.sh",
+            "echo \"! $SCOUTY_HOOK_NAME $SCOUTY_STASH $SCOUTY_IS_ACTIVE_NEXT_ERA $SCOUTY_ERA_INDEX $SCOUTY_SESSION_INDEX ${SCOUTY_MATRIX_BOT_PASSWORD:-none}\"",
+        );
+        let hook = Hook::try_run_with_timeout(
+            SupportedRuntime::Kusama,
+            HOOK_NEW_ERA,
+            &filename,
+            vec![],
+            HookPayload::new()
+                .validator("stash".into(), "name".into(), "0x".into(), true, false)
+                .session(10, 60, None),
+            10,
+        )
+        .unwrap();
+        assert_eq!(hook.stdout, b"! New era stash false 10 60 none\n".to_vec());
+    }
+
+    #[test]
+    fn it_kills_a_hook_on_timeout() {
+        let filename = create_script("scouty_hook_hangs.sh", "echo started\nsleep 30");
+        let started = Instant::now();
+        let result = Hook::try_run_with_timeout(
+            SupportedRuntime::Kusama,
+            HOOK_NEW_SESSION,
+            &filename,
+            vec![],
+            HookPayload::new(),
+            1,
+        );
+        assert!(matches!(result, Err(ScoutyError::HookTimeout(_))));
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn it_filters_the_matrix_lines() {
+        let stdout = b"! <b>restarted</b>\nnode <healthy> in 12s\n  \n".to_vec();
+        assert_eq!(matrix_lines(&stdout, false), vec![" <b>restarted</b>"]);
+        assert_eq!(
+            matrix_lines(&stdout, true),
+            vec![" <b>restarted</b>", "node &lt;healthy&gt; in 12s"]
+        );
+        assert!(matrix_lines(b"\n \n", true).is_empty());
+
+        let stdout = format!("{}\n", "a".repeat(300)).repeat(30);
+        let lines = matrix_lines(stdout.as_bytes(), true);
+        assert_eq!(lines.len(), HOOK_OUTPUT_MAX_LINES);
+        assert_eq!(lines[0].len(), HOOK_OUTPUT_MAX_CHARS);
+    }
+
+    #[test]
+    fn it_verifies_a_hook() {
+        let filename = create_script("scouty_hook_verify.sh", "exit 0");
+        assert!(Hook::verify(HOOK_NEW_ERA, &filename).is_ok());
+
+        fs::set_permissions(&filename, fs::Permissions::from_mode(0o644)).unwrap();
+        let err = Hook::verify(HOOK_NEW_ERA, &filename).unwrap_err();
+        assert!(format!("{}", err).ends_with("is not executable"));
+
+        let dir = env::temp_dir().to_string_lossy().to_string();
+        let err = Hook::verify(HOOK_NEW_ERA, &dir).unwrap_err();
+        assert!(format!("{}", err).ends_with("is not a file"));
+
+        let err = Hook::verify(HOOK_NEW_ERA, "/scouty/_missing.sh").unwrap_err();
+        assert!(format!("{}", err).ends_with("not defined"));
+    }
+
+    #[test]
+    fn it_discovers_the_hooks_dir() {
+        let dir = env::temp_dir().join("scouty_hooks_dir");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for file_name in [
+            "_new_session.sh",
+            "new_era",
+            "validator_offline.sh",
+            "_validator_offline",
+            "_new_sesion.sh",
+            ".gitkeep",
+        ]
+        .iter()
+        {
+            fs::write(dir.join(file_name), "#!/bin/bash\n").unwrap();
+        }
+
+        let hooks_dir = HooksDir::discover(&dir.to_string_lossy()).unwrap();
+        assert_eq!(hooks_dir.discovered.len(), 3);
+        assert_eq!(
+            hooks_dir.discovered.get(HOOK_NEW_SESSION),
+            Some(&dir.join("_new_session.sh").to_string_lossy().to_string())
+        );
+        assert!(hooks_dir.discovered.contains_key(HOOK_NEW_ERA));
+        assert_eq!(
+            hooks_dir.discovered.get(HOOK_VALIDATOR_OFFLINE),
+            Some(&dir.join("_validator_offline").to_string_lossy().to_string())
+        );
+        assert_eq!(
+            hooks_dir.unknown,
+            vec![
+                "_new_sesion.sh".to_string(),
+                "validator_offline.sh".to_string()
+            ]
+        );
+        assert!(HooksDir::discover("/scouty/_missing").is_err());
+    }
+
+    #[test]
+    fn it_finds_the_hook_by_id() {
+        assert_eq!(
+            hook_by_id("active_next_era"),
+            Some(HOOK_VALIDATOR_STARTS_ACTIVE_NEXT_ERA)
+        );
+        assert_eq!(
+            hook_by_id("_validator_starts_inactive_next_era"),
+            Some(HOOK_VALIDATOR_STARTS_INACTIVE_NEXT_ERA)
+        );
+        assert_eq!(hook_by_id("new_session"), Some(HOOK_NEW_SESSION));
+        assert_eq!(hook_by_id("offline"), Some(HOOK_VALIDATOR_OFFLINE));
+        // Suffixes shared by more than one hook are ambiguous
+        assert_eq!(hook_by_id("next_era"), None);
+        assert_eq!(hook_by_id("unknown"), None);
+    }
+
+    #[test]
+    fn it_overrides_the_synthetic_event() {
+        let event = SyntheticEvent::with_overrides(&[
+            "stash=5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".to_string(),
+            "era=1234".to_string(),
+            "is_queued=false".to_string(),
+            "chain=Kusama".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(event.chain, SupportedRuntime::Kusama);
+        assert_eq!(event.era_index, 1234);
+        let args = event.args();
+        assert_eq!(args[0], "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty");
+        assert_eq!(args[4], "false");
+        assert_eq!(args[5], "1234");
+        assert_eq!(event.payload().era_index, Some(1234));
+
+        assert!(SyntheticEvent::with_overrides(&["era".to_string()]).is_err());
+        assert!(SyntheticEvent::with_overrides(&["era=x".to_string()]).is_err());
+        assert!(SyntheticEvent::with_overrides(&["points=1".to_string()]).is_err());
     }
 }
@@ -20,6 +20,11 @@
 // SOFTWARE.
 
 use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+use subxt::utils::AccountId32;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Identity {
@@ -51,3 +56,106 @@ impl std::fmt::Display for Identity {
         }
     }
 }
+
+/// Returns the first and last characters of the address, used in place of the
+/// identity of the accounts without an on-chain identity (e.g. F3opx…jRbN5)
+pub fn short_address(account: &AccountId32) -> String {
+    let s = account.to_string();
+    format!("{}…{}", &s[..5], &s[s.len() - 5..])
+}
+
+/// Returns the identity followed by the short address of the account (e.g.
+/// TURBOFLAKES.IO/MOMO (F3opx…jRbN5)), or only the short address if the account has
+/// no identity
+pub fn display_name(identity: Option<&str>, account: &AccountId32) -> String {
+    match identity {
+        Some(identity) => format!("{} ({})", identity, short_address(account)),
+        None => short_address(account),
+    }
+}
+
+/// Era in which the identities were resolved and the identities resolved, None if the
+/// account has no identity
+type CachedIdentities = (u32, BTreeMap<AccountId32, Option<Identity>>);
+
+/// Identities resolved in the active era, so that the identity of every stash is only
+/// queried once per era
+#[derive(Debug, Clone, Default)]
+pub struct IdentityCache(Arc<Mutex<CachedIdentities>>);
+
+impl IdentityCache {
+    /// Returns the identity resolved in the era, or None if not resolved yet. The
+    /// identities resolved in a previous era are discarded
+    pub fn get(&self, era_index: u32, account: &AccountId32) -> Option<Option<Identity>> {
+        let mut cache = self.0.lock().unwrap();
+        if cache.0 != era_index {
+            *cache = (era_index, BTreeMap::new());
+        }
+        cache.1.get(account).cloned()
+    }
+
+    pub fn insert(
+        &self,
+        era_index: u32,
+        account: AccountId32,
+        identity: Option<Identity>,
+    ) {
+        let mut cache = self.0.lock().unwrap();
+        if cache.0 != era_index {
+            *cache = (era_index, BTreeMap::new());
+        }
+        cache.1.insert(account, identity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+    #[test]
+    fn it_displays_the_identity_with_the_short_address() {
+        let alice = AccountId32::from_str(ALICE).unwrap();
+        assert_eq!(short_address(&alice), "5Grwv…KutQY");
+        let identity = Identity::with_name_and_sub("ALICE".to_string(), "1".to_string());
+        assert_eq!(
+            display_name(Some(&identity.to_string()), &alice),
+            "ALICE/1 (5Grwv…KutQY)"
+        );
+        assert_eq!(display_name(None, &alice), "5Grwv…KutQY");
+    }
+
+    #[test]
+    fn it_caches_the_identities_per_era() {
+        let alice = AccountId32::from_str(ALICE).unwrap();
+        let cache = IdentityCache::default();
+        assert!(cache.get(10, &alice).is_none());
+
+        cache.insert(
+            10,
+            alice.clone(),
+            Some(Identity::with_name("ALICE".to_string())),
+        );
+        assert_eq!(
+            cache
+                .get(10, &alice)
+                .flatten()
+                .map(|identity| identity.to_string()),
+            Some("ALICE".to_string())
+        );
+
+        // Accounts without identity are cached as well
+        let bob = AccountId32::from([2u8; 32]);
+        cache.insert(10, bob.clone(), None);
+        assert_eq!(
+            cache.get(10, &bob).map(|identity| identity.is_none()),
+            Some(true)
+        );
+
+        // Identities are resolved again in a new era
+        assert!(cache.get(11, &alice).is_none());
+        assert!(cache.get(10, &bob).is_none());
+    }
+}
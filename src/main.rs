@@ -19,25 +19,60 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+#![allow(clippy::result_large_err)]
+
 mod authority;
+mod backing;
+mod balances;
+mod backoff;
+mod catch_up;
+mod check;
+mod commands;
 mod config;
+mod discord;
+mod era_digest;
+mod era_points;
 mod errors;
+mod events_file;
+mod finality;
+mod health;
+mod healthcheck;
 mod hooks;
 mod identity;
 mod matrix;
+mod metrics;
+mod minimums;
+mod nomination;
+mod nominators;
+mod notifier;
+mod pagerduty;
 mod para;
+mod payouts;
+mod pools;
+mod quiet;
+mod reload;
 mod report;
 mod runtimes;
 mod scouty;
+mod shutdown;
+mod smtp;
+mod stashes;
+mod state;
 mod stats;
+mod status;
+mod telegram;
+mod templates;
+mod waiting;
+mod webhook;
+mod weekly;
 
 use crate::config::CONFIG;
 use crate::scouty::Scouty;
-use log::info;
-use std::env;
+use log::{error, info, warn};
+use std::{env, process};
 
 fn main() {
-    let config = CONFIG.clone();
+    let config = CONFIG.current();
     if config.is_debug {
         env::set_var("RUST_LOG", "scouty=debug,subxt=debug");
     } else {
@@ -46,11 +81,54 @@ fn main() {
     env_logger::try_init().unwrap_or_default();
 
     info!(
-        "{} v{} * {}",
+        "{} v{} ({}) * {}",
         env!("CARGO_PKG_NAME"),
         env!("CARGO_PKG_VERSION"),
+        env!("SCOUTY_GIT_COMMIT"),
         env!("CARGO_PKG_DESCRIPTION")
     );
 
+    if config.dry_run {
+        warn!(
+            "Dry run -> hook scripts will not run and matrix messages will not be sent"
+        );
+    }
+
+    // Bad templates are reported straight away instead of when a message is sent
+    if let Err(e) = templates::init() {
+        error!("{}", e);
+        process::exit(1);
+    }
+
+    // The configuration printed exits once the endpoints have been connected
+    if config.print_config {
+        Scouty::print_config();
+    }
+
+    // Hook tests exit once the hook script has run
+    if !config.test_hook.is_empty() {
+        Scouty::test_hook();
+    }
+
+    // Matrix tests exit once the test message has been sent to every room
+    if config.test_matrix {
+        Scouty::test_matrix();
+    }
+
+    // One-shot checks exit once the current session has been checked
+    if config.oneshot {
+        Scouty::check();
+    }
+
+    // The whole configuration is logged, so that it can be shared when reporting issues
+    for line in config.dump() {
+        info!("Configuration -> {}", line);
+    }
+
+    // Metrics are served on their own task, alongside the subscriptions
+    if config.prometheus_port > 0 {
+        metrics::spawn_server(config.prometheus_port);
+    }
+
     Scouty::subscribe();
 }
@@ -19,22 +19,98 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 #![allow(dead_code)]
+use crate::backoff::Backoff;
 use crate::config::CONFIG;
-use crate::errors::MatrixError;
+use crate::errors::{MatrixError, ScoutyError};
+use crate::metrics;
+use crate::notifier::{Notification, Notifier};
 use crate::runtimes::support::SupportedRuntime;
-use async_recursion::async_recursion;
+use async_std::{sync::Mutex as AsyncMutex, task};
 use base64::encode;
-use log::{debug, info, warn};
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use lazy_static::lazy_static;
+use log::{debug, error, info, warn};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, result::Result, thread, time};
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    os::unix::fs::OpenOptionsExt,
+    result::Result,
+    sync::{Arc, Mutex, Weak},
+    time,
+};
 use url::form_urlencoded::byte_serialize;
 
 const MATRIX_URL: &str = "https://matrix.org/_matrix/client/r0";
 
+// Delay (in milliseconds) before retrying a rate limited request, if the homeserver
+// does not specify it
+const MATRIX_RATE_LIMIT_DEFAULT_DELAY: u64 = 5000;
+
+// Messages are sent one at a time by every matrix client, since all of them share the
+// rate limit of the same bot user
+lazy_static! {
+    static ref MATRIX_SEND_LOCK: AsyncMutex<()> = AsyncMutex::new(());
+}
+
+// Serializes the read and write of the session file, which is shared by all the chains
+static MATRIX_SESSION_LOCK: Mutex<()> = Mutex::new(());
+
+// Interval (in seconds) between retries of the matrix messages queued, doubled on
+// every failed retry up to the max interval
+const MATRIX_RETRY_INITIAL_INTERVAL: u64 = 5;
+const MATRIX_RETRY_MAX_INTERVAL: u64 = 300;
+
 type AccessToken = String;
 type RoomID = String;
 type EventID = String;
 
+/// Severity of a message, used to route the message to the room configured for it
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Routine messages, e.g. session summaries
+    Info,
+    /// Messages that need attention, e.g. validator inactive next era, slashes or errors
+    Critical,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Info => write!(f, "Info"),
+            Self::Critical => write!(f, "Critical"),
+        }
+    }
+}
+
+/// Type of the matrix messages sent, bots conventionally send notices so that other
+/// bots and bridges ignore them
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MsgType {
+    #[default]
+    Text,
+    Notice,
+}
+
+impl MsgType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Text => "m.text",
+            Self::Notice => "m.notice",
+        }
+    }
+}
+
+impl std::fmt::Display for MsgType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 #[derive(Deserialize, Debug, Default)]
 struct Room {
     #[serde(default)]
@@ -63,18 +139,18 @@ fn define_private_room_alias_name(
 }
 
 impl Room {
-    fn new_private(chain: SupportedRuntime) -> Room {
-        let config = CONFIG.clone();
+    fn new_private(chain: &str) -> Room {
+        let config = CONFIG.current();
         let room_alias_name = define_private_room_alias_name(
             env!("CARGO_PKG_NAME"),
-            &chain.to_string(),
+            chain,
             &config.matrix_user,
             &config.matrix_bot_user,
         );
         let v: Vec<&str> = config.matrix_bot_user.split(":").collect();
         Room {
             room_alias_name: room_alias_name.to_string(),
-            room_alias: format!("#{}:{}", room_alias_name.to_string(), v.last().unwrap()),
+            room_alias: format!("#{}:{}", room_alias_name, v.last().unwrap()),
             ..Default::default()
         }
     }
@@ -85,6 +161,67 @@ struct LoginRequest {
     r#type: String,
     user: String,
     password: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    device_id: Option<String>,
+}
+
+/// Matrix session of the bot user kept between restarts, so that a new device is not
+/// created on every login
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct MatrixSession {
+    user_id: String,
+    access_token: AccessToken,
+    device_id: String,
+}
+
+impl MatrixSession {
+    fn load(path: &str) -> io::Result<Option<MatrixSession>> {
+        let _lock = MATRIX_SESSION_LOCK.lock().unwrap();
+        match fs::read_to_string(path) {
+            Ok(data) => Ok(Some(serde_json::from_str(&data)?)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Write a temporary file first, readable only by the owner since the session
+    /// holds the access token, so that the session file is never left half written
+    fn save(&self, path: &str) -> io::Result<()> {
+        let _lock = MATRIX_SESSION_LOCK.lock().unwrap();
+        let tmp_path = format!("{}.tmp", path);
+        let _ = fs::remove_file(&tmp_path);
+        OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(&tmp_path)?
+            .write_all(&serde_json::to_vec_pretty(self)?)?;
+        fs::rename(&tmp_path, path)
+    }
+}
+
+/// Returns the session of a previous login of the matrix bot user, if any
+fn load_session() -> Option<MatrixSession> {
+    let config = CONFIG.current();
+    if config.matrix_session_path.is_empty() {
+        return None;
+    }
+    match MatrixSession::load(&config.matrix_session_path) {
+        Ok(Some(session)) if session.user_id == config.matrix_bot_user => Some(session),
+        Ok(_) => None,
+        Err(e) => {
+            warn!(
+                "Matrix session file ({}) could not be read: {}",
+                config.matrix_session_path, e
+            );
+            None
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct WhoAmIResponse {
+    user_id: String,
 }
 
 #[derive(Deserialize, Debug)]
@@ -116,6 +253,74 @@ struct SendRoomMessageRequest {
     body: String,
     format: String,
     formatted_body: String,
+    #[serde(rename = "m.mentions", skip_serializing_if = "Option::is_none")]
+    mentions: Option<Mentions>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct Mentions {
+    user_ids: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct DisplayNameResponse {
+    #[serde(default)]
+    displayname: Option<String>,
+}
+
+/// Matrix user mentioned on critical messages
+#[derive(Debug, Clone, PartialEq)]
+struct Mention {
+    user_id: String,
+    display_name: String,
+}
+
+impl Mention {
+    /// Mention by the localpart of the user id, until the display name is known
+    fn new(user_id: &str) -> Self {
+        let display_name = user_id
+            .trim_start_matches('@')
+            .split(':')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        Mention {
+            user_id: user_id.to_string(),
+            display_name,
+        }
+    }
+}
+
+/// Returns true if the room is defined by its alias (e.g. #validators:matrix.org)
+/// instead of its room id (e.g. !abcdef:matrix.org)
+fn is_room_alias(room: &str) -> bool {
+    room.starts_with('#')
+}
+
+/// Prepend the mentions to the message, the display names to the plain body so that
+/// clients notify the users mentioned and the links to the formatted body
+fn with_mentions(
+    message: &str,
+    formatted_message: &str,
+    mentions: &[Mention],
+) -> (String, String) {
+    if mentions.is_empty() {
+        return (message.to_string(), formatted_message.to_string());
+    }
+    let names: Vec<&str> = mentions.iter().map(|m| m.display_name.as_str()).collect();
+    let links: Vec<String> = mentions
+        .iter()
+        .map(|m| {
+            format!(
+                "<a href=\"https://matrix.to/#/{}\">{}</a>",
+                m.user_id, m.display_name
+            )
+        })
+        .collect();
+    (
+        format!("{}: {}", names.join(", "), message),
+        format!("{}: {}", links.join(", "), formatted_message),
+    )
 }
 
 #[derive(Deserialize, Debug)]
@@ -123,6 +328,80 @@ struct SendRoomMessageResponse {
     event_id: EventID,
 }
 
+#[derive(Deserialize, Debug)]
+struct SyncResponse {
+    next_batch: String,
+    #[serde(default)]
+    rooms: SyncRooms,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct SyncRooms {
+    #[serde(default)]
+    join: BTreeMap<RoomID, SyncJoinedRoom>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct SyncJoinedRoom {
+    #[serde(default)]
+    timeline: SyncTimeline,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct SyncTimeline {
+    #[serde(default)]
+    events: Vec<SyncRoomEvent>,
+}
+
+#[derive(Deserialize, Debug)]
+struct SyncRoomEvent {
+    #[serde(rename = "type")]
+    event_type: String,
+    sender: String,
+    #[serde(default)]
+    content: SyncMessageContent,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct SyncMessageContent {
+    #[serde(default)]
+    msgtype: String,
+    #[serde(default)]
+    body: String,
+}
+
+impl SyncResponse {
+    /// Returns the text messages received in the rooms defined, in order
+    fn room_messages(self, room_ids: &[&str]) -> Vec<RoomMessage> {
+        let mut messages = Vec::new();
+        for (room_id, room) in self.rooms.join {
+            if !room_ids.contains(&room_id.as_str()) {
+                continue;
+            }
+            for event in room.timeline.events {
+                if event.event_type == "m.room.message"
+                    && event.content.msgtype == "m.text"
+                {
+                    messages.push(RoomMessage {
+                        room_id: room_id.clone(),
+                        sender: event.sender,
+                        body: event.content.body,
+                    });
+                }
+            }
+        }
+        messages
+    }
+}
+
+/// Text message received in one of the rooms the messages are sent to
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoomMessage {
+    pub room_id: RoomID,
+    pub sender: String,
+    pub body: String,
+}
+
 #[derive(Deserialize, Debug)]
 struct JoinedRoomsResponse {
     joined_rooms: Vec<String>,
@@ -132,15 +411,31 @@ struct JoinedRoomsResponse {
 struct ErrorResponse {
     errcode: String,
     error: String,
+    #[serde(default)]
+    retry_after_ms: Option<u64>,
+}
+
+impl ErrorResponse {
+    /// Returns how long to wait before retrying a rate limited request
+    fn retry_after(&self) -> time::Duration {
+        time::Duration::from_millis(
+            self.retry_after_ms
+                .unwrap_or(MATRIX_RATE_LIMIT_DEFAULT_DELAY),
+        )
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct Matrix {
     pub client: reqwest::Client,
     access_token: Option<String>,
-    chain: SupportedRuntime,
+    chain: String,
     private_room_id: String,
     public_room_id: String,
+    /// Rooms joined by severity, messages without a room are sent to the private room
+    rooms: BTreeMap<Severity, RoomID>,
+    /// Users mentioned on critical messages
+    mentions: Vec<Mention>,
     disabled: bool,
 }
 
@@ -149,9 +444,11 @@ impl Default for Matrix {
         Matrix {
             client: reqwest::Client::new(),
             access_token: None,
-            chain: SupportedRuntime::Westend,
+            chain: SupportedRuntime::Westend.to_string(),
             private_room_id: String::from(""),
             public_room_id: String::from(""),
+            rooms: BTreeMap::new(),
+            mentions: Vec::new(),
             disabled: false,
         }
     }
@@ -159,19 +456,25 @@ impl Default for Matrix {
 
 impl Matrix {
     pub fn new() -> Matrix {
-        let config = CONFIG.clone();
+        let config = CONFIG.current();
         Matrix {
-            disabled: config.matrix_disabled,
+            // Nothing is posted to matrix in dry run mode
+            disabled: config.matrix_disabled || config.dry_run,
             ..Default::default()
         }
     }
 
-    pub async fn login(&mut self) -> Result<(), MatrixError> {
+    pub fn is_authenticated(&self) -> bool {
+        self.access_token.is_some()
+    }
+
+    /// Login with the password, reusing the device of a previous login if defined
+    pub async fn login(&mut self, device_id: Option<String>) -> Result<(), MatrixError> {
         if self.disabled {
             return Ok(());
         }
-        let config = CONFIG.clone();
-        if let None = config.matrix_bot_user.find(":") {
+        let config = CONFIG.current();
+        if !config.matrix_bot_user.contains(':') {
             return Err(MatrixError::Other(format!("matrix bot user '{}' does specifed the matrix server e.g. '@your-own-scouty-bot-account:matrix.org'", config.matrix_bot_user)));
         }
         let client = self.client.clone();
@@ -179,6 +482,7 @@ impl Matrix {
             r#type: "m.login.password".to_string(),
             user: config.matrix_bot_user.to_string(),
             password: config.matrix_bot_password.to_string(),
+            device_id,
         };
 
         let res = client
@@ -191,13 +495,77 @@ impl Matrix {
         match res.status() {
             reqwest::StatusCode::OK => {
                 let response = res.json::<LoginResponse>().await?;
-                self.access_token = Some(response.access_token);
                 info!(
                     "The 'Scouty Bot' user {} has been authenticated at {}",
                     response.user_id, response.home_server
                 );
+                // Keep the session, so that it is reused on the next restart
+                if !config.matrix_session_path.is_empty() {
+                    let session = MatrixSession {
+                        user_id: response.user_id,
+                        access_token: response.access_token.to_string(),
+                        device_id: response.device_id,
+                    };
+                    if let Err(e) = session.save(&config.matrix_session_path) {
+                        warn!(
+                            "Matrix session file ({}) could not be written: {}",
+                            config.matrix_session_path, e
+                        );
+                    }
+                }
+                self.access_token = Some(response.access_token);
+                Ok(())
+            }
+            _ => {
+                let response = res.json::<ErrorResponse>().await?;
+                Err(MatrixError::Other(response.error))
+            }
+        }
+    }
+
+    /// Use the access token instead of the password login, the token is verified to
+    /// belong to the matrix bot user
+    pub async fn login_with_access_token(
+        &mut self,
+        access_token: &str,
+    ) -> Result<(), MatrixError> {
+        if self.disabled {
+            return Ok(());
+        }
+        let config = CONFIG.current();
+        let client = self.client.clone();
+        let res = client
+            .get(format!(
+                "{}/account/whoami?access_token={}",
+                MATRIX_URL, access_token
+            ))
+            .send()
+            .await?;
+
+        debug!("response {:?}", res);
+        match res.status() {
+            reqwest::StatusCode::OK => {
+                let response = res.json::<WhoAmIResponse>().await?;
+                if response.user_id != config.matrix_bot_user {
+                    return Err(MatrixError::InvalidAccessToken(format!(
+                        "token belongs to {} instead of the matrix bot user {}",
+                        response.user_id, config.matrix_bot_user
+                    )));
+                }
+                self.access_token = Some(access_token.to_string());
+                info!(
+                    "The 'Scouty Bot' user {} has been authenticated with an access token",
+                    response.user_id
+                );
                 Ok(())
             }
+            reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => {
+                let response = res.json::<ErrorResponse>().await?;
+                Err(MatrixError::InvalidAccessToken(format!(
+                    "{} ({})",
+                    response.error, response.errcode
+                )))
+            }
             _ => {
                 let response = res.json::<ErrorResponse>().await?;
                 Err(MatrixError::Other(response.error))
@@ -237,26 +605,93 @@ impl Matrix {
     }
 
     // Login user, get or create private room
-    pub async fn authenticate(
-        &mut self,
-        chain: SupportedRuntime,
-    ) -> Result<(), MatrixError> {
+    pub async fn authenticate(&mut self, chain: &str) -> Result<(), MatrixError> {
         if self.disabled {
+            debug!("Matrix disabled -> authentication skipped");
             return Ok(());
         }
-        let config = CONFIG.clone();
+        let config = CONFIG.current();
         // Set chain
-        self.chain = chain;
-        // Login
-        self.login().await?;
+        self.chain = chain.to_string();
+        // Login, with the access token if defined so that no new device is created on
+        // every restart, otherwise with the session of a previous login or the password
+        if !config.matrix_access_token.is_empty() {
+            self.login_with_access_token(&config.matrix_access_token)
+                .await?;
+        } else {
+            // The device of a session rejected is reused by the new login
+            let device_id = match load_session() {
+                Some(session) => {
+                    match self.login_with_access_token(&session.access_token).await {
+                        Ok(()) => None,
+                        Err(MatrixError::InvalidAccessToken(e)) => {
+                            warn!("Matrix session rejected -> login again: {}", e);
+                            Some(session.device_id)
+                        }
+                        Err(e) => return Err(e),
+                    }
+                }
+                None => None,
+            };
+            if !self.is_authenticated() {
+                self.login(device_id).await?;
+            }
+        }
         // Get or create user private room
         if let Some(private_room) = self.get_or_create_private_room().await? {
+            // Messages sent in plain text to an encrypted room are not shown by clients
+            if self.is_room_encrypted(&private_room.room_id).await? {
+                return Err(MatrixError::RoomEncrypted(private_room.room_alias));
+            }
             self.private_room_id = private_room.room_id;
             info!(
                 "Messages will be sent to room {} (Private)",
                 private_room.room_alias
             );
         }
+        // Join the rooms the messages are routed to by severity
+        for (severity, room) in [
+            (Severity::Info, &config.matrix_room_info),
+            (Severity::Critical, &config.matrix_room_critical),
+        ] {
+            if room.is_empty() {
+                continue;
+            }
+            match self.resolve_and_join_room(room.trim()).await {
+                Ok(room_id) => {
+                    info!("{} messages will be sent to room {}", severity, room);
+                    self.rooms.insert(severity, room_id);
+                }
+                // End-to-end encrypted rooms are refused, messages would not be shown
+                Err(e @ MatrixError::RoomEncrypted(_)) => return Err(e),
+                Err(e @ MatrixError::RoomJoinForbidden(..)) => error!(
+                    "{} -> {} messages will be sent to the private room",
+                    e, severity
+                ),
+                Err(e) => warn!(
+                    "Matrix room {} not joined -> {} messages will be sent to the private room: {}",
+                    room, severity, e
+                ),
+            }
+        }
+        // Resolve the display names of the users mentioned on critical messages
+        for user_id in config.matrix_mention_users.iter() {
+            let user_id = user_id.trim();
+            if user_id.is_empty() {
+                continue;
+            }
+            let mut mention = Mention::new(user_id);
+            match self.get_display_name(user_id).await {
+                Ok(Some(display_name)) => mention.display_name = display_name,
+                Ok(None) => {}
+                Err(e) => warn!(
+                    "Matrix display name of {} not found -> mentioned as {}: {}",
+                    user_id, mention.display_name, e
+                ),
+            }
+            info!("{} will be mentioned on critical messages", user_id);
+            self.mentions.push(mention);
+        }
         // Change Scouty Bot display name
         if !config.matrix_bot_display_name_disabled {
             self.change_bot_display_name().await?;
@@ -267,7 +702,7 @@ impl Matrix {
     async fn change_bot_display_name(&self) -> Result<(), MatrixError> {
         match &self.access_token {
             Some(access_token) => {
-                let config = CONFIG.clone();
+                let config = CONFIG.current();
                 let client = self.client.clone();
                 let v: Vec<&str> = config.matrix_user.split(":").collect();
                 let username = v.first().unwrap();
@@ -301,6 +736,131 @@ impl Matrix {
         }
     }
 
+    async fn get_display_name(
+        &self,
+        user_id: &str,
+    ) -> Result<Option<String>, MatrixError> {
+        let client = self.client.clone();
+        let user_id_encoded: String = byte_serialize(user_id.as_bytes()).collect();
+        let res = client
+            .get(format!(
+                "{}/profile/{}/displayname",
+                MATRIX_URL, user_id_encoded
+            ))
+            .send()
+            .await?;
+
+        debug!("response {:?}", res);
+        match res.status() {
+            reqwest::StatusCode::OK => {
+                let response = res.json::<DisplayNameResponse>().await?;
+                Ok(response.displayname.filter(|name| !name.is_empty()))
+            }
+            _ => {
+                let response = res.json::<ErrorResponse>().await?;
+                Err(MatrixError::Other(response.error))
+            }
+        }
+    }
+
+    /// Join the room by its id or alias, rooms already joined are joined again
+    /// without any change
+    /// Resolve the room alias if needed and join the room, accepting the pending invite
+    /// if any, so that messages can be sent to the room
+    async fn resolve_and_join_room(&self, room: &str) -> Result<RoomID, MatrixError> {
+        let room_id = if is_room_alias(room) {
+            match self.get_room_id_by_room_alias(room).await? {
+                Some(room_id) => {
+                    info!("Matrix room alias {} resolved to {}", room, room_id);
+                    room_id
+                }
+                None => {
+                    return Err(MatrixError::Other(format!(
+                        "room alias {} not found",
+                        room
+                    )))
+                }
+            }
+        } else {
+            room.to_string()
+        };
+        // Join with the room defined, so that the homeserver of the alias is reached
+        // for rooms not known to the homeserver of the bot yet
+        let room_id = self.join_room(room, &room_id).await?;
+        // Messages sent in plain text to an encrypted room are not shown by clients
+        if self.is_room_encrypted(&room_id).await? {
+            return Err(MatrixError::RoomEncrypted(room.to_string()));
+        }
+        Ok(room_id)
+    }
+
+    async fn join_room(&self, room: &str, room_id: &str) -> Result<RoomID, MatrixError> {
+        match &self.access_token {
+            Some(access_token) => {
+                let client = self.client.clone();
+                let room_encoded: String = byte_serialize(room.as_bytes()).collect();
+                let res = client
+                    .post(format!(
+                        "{}/join/{}?access_token={}",
+                        MATRIX_URL, room_encoded, access_token
+                    ))
+                    .json(&HashMap::<String, String>::new())
+                    .send()
+                    .await?;
+
+                debug!("response {:?}", res);
+                match res.status() {
+                    reqwest::StatusCode::OK => {
+                        let r = res.json::<Room>().await?;
+                        debug!("{} * Matrix room joined", room);
+                        Ok(r.room_id)
+                    }
+                    reqwest::StatusCode::FORBIDDEN => {
+                        Err(MatrixError::RoomJoinForbidden(
+                            room.to_string(),
+                            room_id.to_string(),
+                        ))
+                    }
+                    _ => {
+                        let response = res.json::<ErrorResponse>().await?;
+                        Err(MatrixError::Other(response.error))
+                    }
+                }
+            }
+            None => Err(MatrixError::Other("access_token not defined".to_string())),
+        }
+    }
+
+    /// Returns true if the room has end-to-end encryption enabled
+    async fn is_room_encrypted(&self, room_id: &str) -> Result<bool, MatrixError> {
+        match &self.access_token {
+            Some(access_token) => {
+                let client = self.client.clone();
+                let room_id_encoded: String =
+                    byte_serialize(room_id.as_bytes()).collect();
+                let res = client
+                    .get(format!(
+                        "{}/rooms/{}/state/m.room.encryption?access_token={}",
+                        MATRIX_URL, room_id_encoded, access_token
+                    ))
+                    .send()
+                    .await?;
+
+                debug!("response {:?}", res);
+                match res.status() {
+                    reqwest::StatusCode::OK => Ok(true),
+                    // Rooms without the encryption state event are not encrypted
+                    reqwest::StatusCode::NOT_FOUND => Ok(false),
+                    _ => {
+                        let response = res.json::<ErrorResponse>().await?;
+                        Err(MatrixError::Other(response.error))
+                    }
+                }
+            }
+            None => Err(MatrixError::Other("access_token not defined".to_string())),
+        }
+    }
+
     async fn get_room_id_by_room_alias(
         &self,
         room_alias: &str,
@@ -332,15 +892,15 @@ impl Matrix {
     async fn create_private_room(&self) -> Result<Option<Room>, MatrixError> {
         match &self.access_token {
             Some(access_token) => {
-                let config = CONFIG.clone();
+                let config = CONFIG.current();
                 let client = self.client.clone();
-                let room: Room = Room::new_private(self.chain);
+                let room: Room = Room::new_private(&self.chain);
                 let req = CreateRoomRequest {
                     name: format!("{} Scouty Bot (Private)", self.chain),
                     room_alias_name: room.room_alias_name.to_string(),
                     topic: "Scouty Bot <> Leading nodes every session".to_string(),
                     preset: "trusted_private_chat".to_string(),
-                    invite: vec![config.matrix_user],
+                    invite: vec![config.matrix_user.clone()],
                     is_direct: true,
                 };
                 let res = client
@@ -374,7 +934,7 @@ impl Matrix {
     async fn get_or_create_private_room(&self) -> Result<Option<Room>, MatrixError> {
         match &self.access_token {
             Some(_) => {
-                let mut room: Room = Room::new_private(self.chain);
+                let mut room: Room = Room::new_private(&self.chain);
                 match self.get_room_id_by_room_alias(&room.room_alias).await? {
                     Some(room_id) => {
                         room.room_id = room_id;
@@ -387,76 +947,268 @@ impl Matrix {
         }
     }
 
+    /// Returns the private room and the rooms joined by severity, without duplicates
+    fn room_ids(&self) -> Vec<&str> {
+        let mut room_ids: Vec<&str> = vec![&self.private_room_id];
+        for room_id in self.rooms.values() {
+            if !room_ids.contains(&room_id.as_str()) {
+                room_ids.push(room_id);
+            }
+        }
+        room_ids.retain(|room_id| !room_id.is_empty());
+        room_ids
+    }
+
+    /// Wait up to `timeout` milliseconds for new events since the `since` token, and
+    /// return the next token with the text messages received in the rooms the
+    /// messages are sent to. Without a `since` token only the next token is returned,
+    /// so that messages sent before are not replied to.
+    pub async fn sync(
+        &self,
+        since: Option<&str>,
+        timeout: u64,
+    ) -> Result<(String, Vec<RoomMessage>), MatrixError> {
+        match &self.access_token {
+            Some(access_token) => {
+                let client = self.client.clone();
+                let mut url = format!(
+                    "{}/sync?timeout={}&access_token={}",
+                    MATRIX_URL,
+                    if since.is_some() { timeout } else { 0 },
+                    access_token
+                );
+                if let Some(since) = since {
+                    let since_encoded: String =
+                        byte_serialize(since.as_bytes()).collect();
+                    url.push_str(&format!("&since={}", since_encoded));
+                }
+                let res = client.get(url).send().await?;
+
+                debug!("response {:?}", res);
+                match res.status() {
+                    reqwest::StatusCode::OK => {
+                        let response = res.json::<SyncResponse>().await?;
+                        let next_batch = response.next_batch.clone();
+                        if since.is_none() {
+                            return Ok((next_batch, Vec::new()));
+                        }
+                        Ok((next_batch, response.room_messages(&self.room_ids())))
+                    }
+                    _ => {
+                        let response = res.json::<ErrorResponse>().await?;
+                        Err(MatrixError::Other(response.error))
+                    }
+                }
+            }
+            None => Err(MatrixError::Other("access_token not defined".to_string())),
+        }
+    }
+
+    /// Send the test message to the private room and to every room configured by
+    /// severity, and return the event id or the error of each room
+    pub async fn send_test_messages(
+        &self,
+        message: &str,
+        formatted_message: &str,
+    ) -> Result<Vec<RoomTest>, MatrixError> {
+        if self.disabled {
+            return Err(MatrixError::Other(
+                "matrix is disabled or dry run is set".to_string(),
+            ));
+        }
+        let config = CONFIG.current();
+        let mut targets: Vec<(String, Option<RoomID>)> =
+            vec![("Private".to_string(), Some(self.private_room_id.clone()))];
+        for (severity, room) in [
+            (Severity::Info, &config.matrix_room_info),
+            (Severity::Critical, &config.matrix_room_critical),
+        ] {
+            if !room.is_empty() {
+                targets.push((
+                    format!("{} ({})", severity, room.trim()),
+                    self.rooms.get(&severity).cloned(),
+                ));
+            }
+        }
+
+        let mut tests = Vec::new();
+        let mut room_ids: Vec<RoomID> = Vec::new();
+        for (target, room_id) in targets {
+            let result = match room_id {
+                // Rooms shared by more than one severity are only tested once
+                Some(room_id) if room_ids.contains(&room_id) => continue,
+                Some(room_id) => {
+                    room_ids.push(room_id.clone());
+                    match self
+                        .dispatch_message(
+                            &room_id,
+                            message,
+                            formatted_message,
+                            config.matrix_msgtype,
+                            None,
+                        )
+                        .await
+                    {
+                        Ok(Some(event_id)) => Ok(event_id),
+                        Ok(None) => Err(MatrixError::Other(
+                            "message forbidden by the homeserver".to_string(),
+                        )),
+                        Err(e) => Err(e),
+                    }
+                }
+                None => Err(MatrixError::Other("room not joined".to_string())),
+            };
+            tests.push(RoomTest { target, result });
+        }
+        Ok(tests)
+    }
+
+    /// Send the message to the room defined, e.g. as the reply to a command
+    pub async fn send_message_to_room(
+        &self,
+        room_id: &str,
+        message: &str,
+        formatted_message: &str,
+    ) -> Result<(), MatrixError> {
+        if self.disabled {
+            return Ok(());
+        }
+        self.dispatch_message(
+            room_id,
+            message,
+            formatted_message,
+            CONFIG.current().matrix_msgtype,
+            None,
+        )
+        .await?;
+
+        Ok(())
+    }
+
     pub async fn send_message(
         &self,
         message: &str,
         formatted_message: &str,
+        severity: Severity,
+    ) -> Result<(), MatrixError> {
+        self.send_message_as(
+            message,
+            formatted_message,
+            severity,
+            CONFIG.current().matrix_msgtype_of(severity),
+        )
+        .await
+    }
+
+    /// Send the message with the message type defined, overriding the one configured
+    /// for the severity
+    pub async fn send_message_as(
+        &self,
+        message: &str,
+        formatted_message: &str,
+        severity: Severity,
+        msgtype: MsgType,
     ) -> Result<(), MatrixError> {
         if self.disabled {
             return Ok(());
         }
-        // Send message to private room (private assigned to the matrix_username in config)
-        self.dispatch_message(&self.private_room_id, &message, &formatted_message)
+        // Send message to the room of the severity, or to the private room (private
+        // assigned to the matrix_username in config) if no room is configured for it
+        let room_id = self.rooms.get(&severity).unwrap_or(&self.private_room_id);
+        // Only critical messages mention the users defined
+        if severity == Severity::Critical && !self.mentions.is_empty() {
+            let (message, formatted_message) =
+                with_mentions(message, formatted_message, &self.mentions);
+            let mentions = Mentions {
+                user_ids: self.mentions.iter().map(|m| m.user_id.clone()).collect(),
+            };
+            self.dispatch_message(
+                room_id,
+                &message,
+                &formatted_message,
+                msgtype,
+                Some(mentions),
+            )
             .await?;
+        } else {
+            self.dispatch_message(room_id, message, formatted_message, msgtype, None)
+                .await?;
+        }
 
         Ok(())
     }
 
-    #[async_recursion]
     async fn dispatch_message(
         &self,
         room_id: &str,
         message: &str,
         formatted_message: &str,
+        msgtype: MsgType,
+        mentions: Option<Mentions>,
     ) -> Result<Option<EventID>, MatrixError> {
         if self.disabled {
             return Ok(None);
         }
         match &self.access_token {
             Some(access_token) => {
+                // Serialize the messages sent, so that concurrent callers don't exceed
+                // the rate limit of the homeserver at the same time
+                let _send_lock = MATRIX_SEND_LOCK.lock().await;
                 let client = self.client.clone();
                 let req = SendRoomMessageRequest {
-                    msgtype: "m.text".to_string(),
+                    msgtype: msgtype.to_string(),
                     body: message.to_string(),
                     format: "org.matrix.custom.html".to_string(),
                     formatted_body: formatted_message.to_string(),
+                    mentions,
                 };
 
-                let res = client
-                    .post(format!(
-                        "{}/rooms/{}/send/m.room.message?access_token={}",
-                        MATRIX_URL, room_id, access_token
-                    ))
-                    .json(&req)
-                    .send()
-                    .await?;
+                let mut retries: u32 = 0;
+                loop {
+                    let res = client
+                        .post(format!(
+                            "{}/rooms/{}/send/m.room.message?access_token={}",
+                            MATRIX_URL, room_id, access_token
+                        ))
+                        .json(&req)
+                        .send()
+                        .await?;
 
-                debug!("response {:?} {:?}", res, res.status());
-                match res.status() {
-                    reqwest::StatusCode::OK => {
-                        let response = res.json::<SendRoomMessageResponse>().await?;
-                        debug!("{:?} * Matrix messsage dispatched", response);
-                        Ok(Some(response.event_id))
-                    }
-                    reqwest::StatusCode::TOO_MANY_REQUESTS => {
-                        let response = res.json::<ErrorResponse>().await?;
-                        warn!(
-                            "Matrix {} -> Wait 5 seconds and try again",
-                            response.error
-                        );
-                        thread::sleep(time::Duration::from_secs(5));
-                        return self
-                            .dispatch_message(room_id, message, formatted_message)
-                            .await;
-                    }
-                    reqwest::StatusCode::FORBIDDEN => {
-                        let response = res.json::<ErrorResponse>().await?;
-                        warn!("Matrix message not sent: {:?}", response.error);
-                        Ok(None)
-                    }
-                    _ => {
-                        let response = res.json::<ErrorResponse>().await?;
-                        Err(MatrixError::Other(response.error))
+                    debug!("response {:?} {:?}", res, res.status());
+                    match res.status() {
+                        reqwest::StatusCode::OK => {
+                            let response = res.json::<SendRoomMessageResponse>().await?;
+                            debug!("{:?} * Matrix messsage dispatched", response);
+                            return Ok(Some(response.event_id));
+                        }
+                        reqwest::StatusCode::TOO_MANY_REQUESTS => {
+                            let response = res.json::<ErrorResponse>().await?;
+                            if retries >= CONFIG.current().matrix_rate_limit_retries {
+                                return Err(MatrixError::Other(format!(
+                                    "{} -> gave up after {} retries",
+                                    response.error, retries
+                                )));
+                            }
+                            retries += 1;
+                            let delay = response.retry_after();
+                            warn!(
+                                "Matrix {} -> Wait {:.1} seconds and try again ({}/{})",
+                                response.error,
+                                delay.as_secs_f64(),
+                                retries,
+                                CONFIG.current().matrix_rate_limit_retries
+                            );
+                            task::sleep(delay).await;
+                        }
+                        reqwest::StatusCode::FORBIDDEN => {
+                            let response = res.json::<ErrorResponse>().await?;
+                            warn!("Matrix message not sent: {:?}", response.error);
+                            return Ok(None);
+                        }
+                        _ => {
+                            let response = res.json::<ErrorResponse>().await?;
+                            return Err(MatrixError::Other(response.error));
+                        }
                     }
                 }
             }
@@ -464,3 +1216,487 @@ impl Matrix {
         }
     }
 }
+
+/// Result of the test message sent to a room, the event id if delivered
+#[derive(Debug)]
+pub struct RoomTest {
+    pub target: String,
+    pub result: Result<String, MatrixError>,
+}
+
+/// Returns the plain and formatted test message, labelled with the host and the time
+/// it was sent from
+pub fn test_message(host: &str, time: DateTime<Utc>) -> (String, String) {
+    let time = time.format("%Y-%m-%d %H:%M:%S UTC");
+    (
+        format!(
+            "[TEST] {} connectivity test from host {} at {}",
+            env!("CARGO_PKG_NAME"),
+            host,
+            time
+        ),
+        format!(
+            "<br/>🧪 <b>[TEST]</b> <code>{}</code> connectivity test from host <code>{}</code> at {}<br/><br/>",
+            env!("CARGO_PKG_NAME"),
+            host,
+            time
+        ),
+    )
+}
+
+/// Returns the hostname of the machine, used to label the test message
+pub fn hostname() -> String {
+    fs::read_to_string("/etc/hostname")
+        .map(|hostname| hostname.trim().to_string())
+        .ok()
+        .filter(|hostname| !hostname.is_empty())
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Matrix message that failed to be delivered, retained to be retried later
+#[derive(Debug, Clone, PartialEq)]
+struct QueuedMessage {
+    message: String,
+    formatted_message: String,
+    severity: Severity,
+    timestamp: DateTime<Utc>,
+}
+
+impl QueuedMessage {
+    /// Returns true if the message has been queued for longer than `max_age` seconds
+    fn is_stale(&self, now: DateTime<Utc>, max_age: u64) -> bool {
+        now.signed_duration_since(self.timestamp).num_seconds() > max_age as i64
+    }
+
+    /// Returns the message prefixed with its original timestamp, so that the room
+    /// history is not misleading when it is delivered late
+    fn late_message(&self) -> (String, String) {
+        let timestamp = self.timestamp.format("%Y-%m-%d %H:%M:%S UTC");
+        (
+            format!("[{}] {}", timestamp, self.message),
+            format!("<i>[{}]</i> {}", timestamp, self.formatted_message),
+        )
+    }
+}
+
+type QueuedMessages = Arc<Mutex<VecDeque<QueuedMessage>>>;
+
+/// In-memory queue of the matrix messages that failed to be delivered. Messages are
+/// retried in order in the background until the homeserver is reachable again.
+#[derive(Debug, Clone)]
+pub struct MessageQueue {
+    matrix: Matrix,
+    messages: QueuedMessages,
+    max_size: usize,
+}
+
+impl MessageQueue {
+    /// Start retrying the messages queued with the matrix client, messages are not
+    /// retained if `max_size` is zero
+    pub fn new(matrix: Matrix, max_size: usize, max_age: u64) -> Self {
+        let queue = MessageQueue {
+            matrix: matrix.clone(),
+            messages: Arc::new(Mutex::new(VecDeque::new())),
+            max_size,
+        };
+        if queue.is_enabled() {
+            task::spawn(retry_messages(
+                matrix,
+                Arc::downgrade(&queue.messages),
+                max_age,
+            ));
+        }
+        queue
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.max_size > 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.lock().unwrap().is_empty()
+    }
+
+    /// Retain the message to be retried, the oldest message is dropped if the queue
+    /// is full
+    pub fn push(&self, message: &str, formatted_message: &str, severity: Severity) {
+        let mut messages = self.messages.lock().unwrap();
+        while messages.len() >= self.max_size {
+            if let Some(dropped) = messages.pop_front() {
+                warn!(
+                    "Matrix retry queue full -> message queued at {} dropped: {}",
+                    dropped.timestamp, dropped.message
+                );
+                count_message("dropped");
+            }
+        }
+        messages.push_back(QueuedMessage {
+            message: message.to_string(),
+            formatted_message: formatted_message.to_string(),
+            severity,
+            timestamp: Utc::now(),
+        });
+        info!(
+            "Matrix message queued to be retried ({} queued)",
+            messages.len()
+        );
+    }
+}
+
+impl Notifier for Matrix {
+    fn name(&self) -> &str {
+        "Matrix"
+    }
+
+    fn send<'a>(
+        &'a self,
+        notification: &'a Notification,
+    ) -> BoxFuture<'a, Result<(), ScoutyError>> {
+        let message = notification.message.as_str();
+        let formatted_message = notification.formatted_message.as_str();
+        let severity = notification.severity;
+        Box::pin(async move {
+            Ok(self
+                .send_message(message, formatted_message, severity)
+                .await?)
+        })
+    }
+}
+
+/// Matrix messages that fail to be delivered are queued to be retried, if the queue
+/// is enabled
+impl Notifier for MessageQueue {
+    fn name(&self) -> &str {
+        "Matrix"
+    }
+
+    fn send<'a>(
+        &'a self,
+        notification: &'a Notification,
+    ) -> BoxFuture<'a, Result<(), ScoutyError>> {
+        let message = notification.message.as_str();
+        let formatted_message = notification.formatted_message.as_str();
+        let severity = notification.severity;
+        Box::pin(async move {
+            // Keep the messages in order while older messages are still to be retried
+            if self.is_enabled() && !self.is_empty() {
+                self.push(message, formatted_message, severity);
+                count_message("queued");
+                return Ok(());
+            }
+            match self
+                .matrix
+                .send_message(message, formatted_message, severity)
+                .await
+            {
+                Ok(()) => {
+                    if self.matrix.is_authenticated() {
+                        count_message("sent");
+                    }
+                    Ok(())
+                }
+                Err(e) if self.is_enabled() => {
+                    warn!("Matrix message not sent! {}", e);
+                    self.push(message, formatted_message, severity);
+                    count_message("queued");
+                    Ok(())
+                }
+                Err(e) => {
+                    count_message("failed");
+                    Err(e.into())
+                }
+            }
+        })
+    }
+}
+
+/// Count the matrix message by its status in the metrics exported
+fn count_message(status: &str) {
+    metrics::MATRIX_MESSAGES.inc(&[("status", status)]);
+}
+
+/// Drop the messages queued for longer than `max_age` seconds and return the next
+/// message to be retried
+fn next_message(
+    messages: &mut VecDeque<QueuedMessage>,
+    now: DateTime<Utc>,
+    max_age: u64,
+) -> Option<QueuedMessage> {
+    while let Some(queued) = messages.front() {
+        if !queued.is_stale(now, max_age) {
+            return Some(queued.clone());
+        }
+        warn!(
+            "Matrix message queued at {} is stale -> dropped: {}",
+            queued.timestamp, queued.message
+        );
+        count_message("dropped");
+        messages.pop_front();
+    }
+    None
+}
+
+/// Flush the messages queued in order, with backoff while the homeserver is
+/// unreachable. The task stops once the queue is dropped.
+async fn retry_messages(
+    matrix: Matrix,
+    messages: Weak<Mutex<VecDeque<QueuedMessage>>>,
+    max_age: u64,
+) {
+    let mut backoff =
+        Backoff::new(MATRIX_RETRY_INITIAL_INTERVAL, MATRIX_RETRY_MAX_INTERVAL);
+    let mut delay = time::Duration::from_secs(MATRIX_RETRY_INITIAL_INTERVAL);
+    loop {
+        task::sleep(delay).await;
+        let messages = match messages.upgrade() {
+            Some(messages) => messages,
+            None => return,
+        };
+        let next = next_message(&mut messages.lock().unwrap(), Utc::now(), max_age);
+        let queued = match next {
+            Some(queued) => queued,
+            None => {
+                backoff.reset();
+                delay = time::Duration::from_secs(MATRIX_RETRY_INITIAL_INTERVAL);
+                continue;
+            }
+        };
+        let (message, formatted_message) = queued.late_message();
+        match matrix
+            .send_message(&message, &formatted_message, queued.severity)
+            .await
+        {
+            Ok(()) => {
+                let mut messages = messages.lock().unwrap();
+                // The message might have been dropped meanwhile if the queue got full
+                if messages.front() == Some(&queued) {
+                    messages.pop_front();
+                }
+                info!("Matrix message queued at {} delivered", queued.timestamp);
+                count_message("retried");
+                // Flush the remaining messages straight away
+                backoff.reset();
+                delay = time::Duration::from_secs(0);
+            }
+            Err(e) => {
+                delay = backoff.next_delay();
+                warn!(
+                    "Matrix message retry failed! {} -> next retry in {:.1}s",
+                    e,
+                    delay.as_secs_f64()
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn it_labels_the_test_message() {
+        let time = DateTime::parse_from_rfc3339("2024-03-01T12:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let (message, formatted_message) = test_message("validator-01", time);
+        assert_eq!(
+            message,
+            "[TEST] scouty connectivity test from host validator-01 at 2024-03-01 12:30:00 UTC"
+        );
+        assert!(formatted_message.contains("<code>validator-01</code>"));
+    }
+
+    fn queued_message(message: &str, timestamp: DateTime<Utc>) -> QueuedMessage {
+        QueuedMessage {
+            message: message.to_string(),
+            formatted_message: format!("<b>{}</b>", message),
+            severity: Severity::Info,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn it_saves_the_session_for_the_owner_only() {
+        let path = std::env::temp_dir().join("scouty_matrix_session.json");
+        let path = path.to_string_lossy().to_string();
+        let _ = fs::remove_file(&path);
+        assert_eq!(MatrixSession::load(&path).unwrap(), None);
+
+        let session = MatrixSession {
+            user_id: "@scouty-bot:matrix.org".to_string(),
+            access_token: "syt_token".to_string(),
+            device_id: "ABCDEFGHIJ".to_string(),
+        };
+        session.save(&path).unwrap();
+        session.save(&path).unwrap();
+        assert_eq!(MatrixSession::load(&path).unwrap(), Some(session));
+
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn it_extracts_the_text_messages_of_the_rooms_defined() {
+        let response: SyncResponse = serde_json::from_str(
+            r#"{
+                "next_batch": "s72595_4483_1934",
+                "rooms": {
+                    "join": {
+                        "!private:matrix.org": {
+                            "timeline": {
+                                "events": [
+                                    {
+                                        "type": "m.room.message",
+                                        "sender": "@alice:matrix.org",
+                                        "content": { "msgtype": "m.text", "body": "!status" }
+                                    },
+                                    {
+                                        "type": "m.room.member",
+                                        "sender": "@bob:matrix.org",
+                                        "content": { "membership": "join" }
+                                    }
+                                ]
+                            }
+                        },
+                        "!other:matrix.org": {
+                            "timeline": {
+                                "events": [
+                                    {
+                                        "type": "m.room.message",
+                                        "sender": "@alice:matrix.org",
+                                        "content": { "msgtype": "m.text", "body": "!era" }
+                                    }
+                                ]
+                            }
+                        }
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            response.room_messages(&["!private:matrix.org"]),
+            vec![RoomMessage {
+                room_id: "!private:matrix.org".to_string(),
+                sender: "@alice:matrix.org".to_string(),
+                body: "!status".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn it_mentions_the_users_by_display_name() {
+        let mut ops = Mention::new("@ops:example.org");
+        assert_eq!(ops.display_name, "ops");
+        ops.display_name = "Ops Team".to_string();
+        let mentions = vec![ops, Mention::new("@alice:matrix.org")];
+        let (message, formatted_message) =
+            with_mentions("Slash!", "<b>Slash!</b>", &mentions);
+        assert_eq!(message, "Ops Team, alice: Slash!");
+        assert_eq!(
+            formatted_message,
+            "<a href=\"https://matrix.to/#/@ops:example.org\">Ops Team</a>, <a href=\"https://matrix.to/#/@alice:matrix.org\">alice</a>: <b>Slash!</b>"
+        );
+        assert_eq!(
+            with_mentions("Session", "Session", &[]),
+            ("Session".to_string(), "Session".to_string())
+        );
+    }
+
+    #[test]
+    fn it_distinguishes_room_aliases_from_room_ids() {
+        assert!(is_room_alias("#validators:example.org"));
+        assert!(!is_room_alias("!abcdef:example.org"));
+        assert_eq!(
+            MatrixError::RoomJoinForbidden(
+                "#validators:example.org".to_string(),
+                "!abcdef:example.org".to_string()
+            )
+            .to_string(),
+            "Matrix room #validators:example.org (!abcdef:example.org) join forbidden -> invite the bot user to the room"
+        );
+    }
+
+    #[test]
+    fn it_serializes_the_msgtype_of_the_messages() {
+        let msgtype: MsgType = serde_json::from_str(r#""notice""#).unwrap();
+        assert_eq!(msgtype, MsgType::Notice);
+        assert_eq!(MsgType::default(), MsgType::Text);
+        assert!(serde_json::from_str::<MsgType>(r#""emote""#).is_err());
+        let req = SendRoomMessageRequest {
+            msgtype: msgtype.to_string(),
+            body: "Session".to_string(),
+            format: "org.matrix.custom.html".to_string(),
+            formatted_body: "Session".to_string(),
+            mentions: None,
+        };
+        let value = serde_json::to_value(&req).unwrap();
+        assert_eq!(value["msgtype"], "m.notice");
+    }
+
+    #[test]
+    fn it_parses_the_retry_after_of_rate_limited_requests() {
+        let response: ErrorResponse = serde_json::from_str(
+            r#"{"errcode":"M_LIMIT_EXCEEDED","error":"Too Many Requests","retry_after_ms":2500}"#,
+        )
+        .unwrap();
+        assert_eq!(response.retry_after(), time::Duration::from_millis(2500));
+
+        let response: ErrorResponse = serde_json::from_str(
+            r#"{"errcode":"M_LIMIT_EXCEEDED","error":"Too Many Requests"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            response.retry_after(),
+            time::Duration::from_millis(MATRIX_RATE_LIMIT_DEFAULT_DELAY)
+        );
+    }
+
+    #[test]
+    fn it_drops_the_stale_messages() {
+        let now = Utc::now();
+        let mut messages = VecDeque::from(vec![
+            queued_message("stale", now - Duration::seconds(120)),
+            queued_message("recent", now - Duration::seconds(30)),
+        ]);
+        let queued = next_message(&mut messages, now, 60).unwrap();
+        assert_eq!(queued.message, "recent");
+        assert_eq!(messages.len(), 1);
+        assert!(next_message(&mut messages, now + Duration::seconds(60), 60).is_none());
+        assert!(messages.is_empty());
+    }
+
+    #[test]
+    fn it_prefixes_late_messages_with_the_timestamp() {
+        let timestamp = DateTime::parse_from_rfc3339("2024-02-19T10:20:30Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let (message, formatted_message) =
+            queued_message("New era", timestamp).late_message();
+        assert_eq!(message, "[2024-02-19 10:20:30 UTC] New era");
+        assert_eq!(
+            formatted_message,
+            "<i>[2024-02-19 10:20:30 UTC]</i> <b>New era</b>"
+        );
+    }
+
+    #[test]
+    fn it_drops_the_oldest_message_when_full() {
+        let queue = MessageQueue {
+            matrix: Matrix::default(),
+            messages: Arc::new(Mutex::new(VecDeque::new())),
+            max_size: 2,
+        };
+        for message in ["first", "second", "third"].iter() {
+            queue.push(message, message, Severity::Info);
+        }
+        let messages = queue.messages.lock().unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].message, "second");
+        assert_eq!(messages[1].message, "third");
+    }
+}
@@ -0,0 +1,333 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::status::{ChainStatus, SharedStatus, StashStatus};
+use async_std::{
+    io::{self, ReadExt, WriteExt},
+    net::{TcpListener, TcpStream},
+    task,
+};
+use futures::StreamExt;
+use lazy_static::lazy_static;
+use log::{debug, error, info, warn};
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{Mutex, RwLock},
+    time,
+};
+
+// Time (in seconds) a scrape has to send its request before the connection is closed
+const METRICS_REQUEST_TIMEOUT: u64 = 10;
+
+// Maximum number of bytes read from the request of a scrape
+const METRICS_REQUEST_MAX_SIZE: usize = 4096;
+
+type Labels = Vec<(&'static str, String)>;
+
+/// Counter of a metric by its labels
+#[derive(Debug, Default)]
+pub struct Counter(Mutex<BTreeMap<Labels, u64>>);
+
+impl Counter {
+    pub fn inc(&self, labels: &[(&'static str, &str)]) {
+        let labels: Labels = labels.iter().map(|(k, v)| (*k, v.to_string())).collect();
+        *self.0.lock().unwrap().entry(labels).or_default() += 1;
+    }
+
+    fn samples(&self) -> Vec<(Labels, u64)> {
+        self.0
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(labels, value)| (labels.clone(), *value))
+            .collect()
+    }
+}
+
+lazy_static! {
+    /// Status of every chain monitored, read on every scrape
+    static ref CHAINS: RwLock<BTreeMap<String, SharedStatus>> = RwLock::new(BTreeMap::new());
+    pub static ref HOOK_EXECUTIONS: Counter = Counter::default();
+    pub static ref MATRIX_MESSAGES: Counter = Counter::default();
+    pub static ref RPC_RECONNECTS: Counter = Counter::default();
+}
+
+/// Register the status of the chain, so that its metrics are exported on every scrape
+pub fn register_chain(chain: &str, status: &SharedStatus) {
+    CHAINS
+        .write()
+        .unwrap()
+        .insert(chain.to_string(), status.clone());
+}
+
+/// Escape the label value as defined by the prometheus text format
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn write_metric(
+    out: &mut String,
+    name: &str,
+    kind: &str,
+    help: &str,
+    samples: &[(Labels, u64)],
+) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+    for (labels, value) in samples.iter() {
+        if labels.is_empty() {
+            let _ = writeln!(out, "{} {}", name, value);
+            continue;
+        }
+        let labels: Vec<String> = labels
+            .iter()
+            .map(|(k, v)| format!("{}=\"{}\"", k, escape_label_value(v)))
+            .collect();
+        let _ = writeln!(out, "{}{{{}}} {}", name, labels.join(","), value);
+    }
+}
+
+/// Returns the samples of every stash of every chain
+fn stash_samples(
+    chains: &BTreeMap<String, ChainStatus>,
+    value: fn(&StashStatus) -> u64,
+) -> Vec<(Labels, u64)> {
+    chains
+        .iter()
+        .flat_map(|(chain, status)| {
+            status.stashes.iter().map(move |(stash, s)| {
+                (
+                    vec![("chain", chain.to_string()), ("stash", stash.to_string())],
+                    value(s),
+                )
+            })
+        })
+        .collect()
+}
+
+/// Returns the samples of every chain with a value defined
+fn chain_samples(
+    chains: &BTreeMap<String, ChainStatus>,
+    value: fn(&ChainStatus) -> Option<u32>,
+) -> Vec<(Labels, u64)> {
+    chains
+        .iter()
+        .filter_map(|(chain, status)| {
+            value(status).map(|v| (vec![("chain", chain.to_string())], v as u64))
+        })
+        .collect()
+}
+
+/// Render the metrics in the prometheus text format
+pub fn render() -> String {
+    let chains: BTreeMap<String, ChainStatus> = CHAINS
+        .read()
+        .unwrap()
+        .iter()
+        .map(|(chain, status)| (chain.to_string(), status.get()))
+        .collect();
+    let mut out = String::new();
+    write_metric(
+        &mut out,
+        "scouty_current_session_index",
+        "gauge",
+        "Last session processed",
+        &chain_samples(&chains, |s| s.session_index),
+    );
+    write_metric(
+        &mut out,
+        "scouty_current_era_index",
+        "gauge",
+        "Active era of the last session processed",
+        &chain_samples(&chains, |s| s.era_index),
+    );
+    write_metric(
+        &mut out,
+        "scouty_active",
+        "gauge",
+        "Whether the stash is active in the current era (1) or not (0)",
+        &stash_samples(&chains, |s| s.is_active as u64),
+    );
+    write_metric(
+        &mut out,
+        "scouty_active_next_era",
+        "gauge",
+        "Whether the stash is active next era (1) or not (0)",
+        &stash_samples(&chains, |s| s.is_queued as u64),
+    );
+    write_metric(
+        &mut out,
+        "scouty_era_points",
+        "gauge",
+        "Points collected so far by the stash in the active era",
+        &stash_samples(&chains, |s| s.era_points as u64),
+    );
+    write_metric(
+        &mut out,
+        "scouty_hook_executions_total",
+        "counter",
+        "Hook scripts run by hook and status",
+        &HOOK_EXECUTIONS.samples(),
+    );
+    write_metric(
+        &mut out,
+        "scouty_matrix_messages_total",
+        "counter",
+        "Matrix messages by status",
+        &MATRIX_MESSAGES.samples(),
+    );
+    write_metric(
+        &mut out,
+        "scouty_rpc_reconnects_total",
+        "counter",
+        "Reconnections to the substrate node after the connection was lost",
+        &RPC_RECONNECTS.samples(),
+    );
+    out
+}
+
+/// Returns the http response to the request, only the metrics path is served
+fn response(request: &str) -> String {
+    let mut parts = request
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .split_whitespace();
+    let (status, content_type, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => (
+            "200 OK",
+            "text/plain; version=0.0.4; charset=utf-8",
+            render(),
+        ),
+        (Some("GET"), Some(_)) => {
+            ("404 Not Found", "text/plain", "Not Found\n".to_string())
+        }
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain",
+            "Method Not Allowed\n".to_string(),
+        ),
+    };
+    format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )
+}
+
+async fn handle_connection(mut stream: TcpStream) -> io::Result<()> {
+    let mut buffer = vec![0_u8; METRICS_REQUEST_MAX_SIZE];
+    let size = io::timeout(
+        time::Duration::from_secs(METRICS_REQUEST_TIMEOUT),
+        stream.read(&mut buffer),
+    )
+    .await?;
+    let request = String::from_utf8_lossy(&buffer[..size]);
+    stream.write_all(response(&request).as_bytes()).await?;
+    stream.flush().await
+}
+
+/// Serve the metrics on its own task, so that a scrape never blocks the processing
+/// of on-chain events
+pub fn spawn_server(port: u16) {
+    task::spawn(async move {
+        let listener = match TcpListener::bind(("0.0.0.0", port)).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                error!("Prometheus metrics not served on port {}: {}", port, e);
+                return;
+            }
+        };
+        info!("Prometheus metrics served on port {} at /metrics", port);
+        let mut incoming = listener.incoming();
+        while let Some(stream) = incoming.next().await {
+            match stream {
+                Ok(stream) => {
+                    task::spawn(async move {
+                        if let Err(e) = handle_connection(stream).await {
+                            debug!("Prometheus scrape failed: {}", e);
+                        }
+                    });
+                }
+                Err(e) => warn!("Prometheus connection failed: {}", e),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_renders_the_metrics_of_the_chains_registered() {
+        let status = SharedStatus::default();
+        status.update(|s| {
+            s.session_index = Some(8125);
+            s.era_index = Some(1354);
+            s.stashes.insert(
+                "5GrwvaEF".to_string(),
+                StashStatus {
+                    name: "Alice".to_string(),
+                    is_active: true,
+                    is_queued: false,
+                    era_points: 1200,
+                },
+            );
+        });
+        register_chain("Metrics\"Test", &status);
+        HOOK_EXECUTIONS.inc(&[("hook", "Metrics test"), ("status", "success")]);
+        HOOK_EXECUTIONS.inc(&[("hook", "Metrics test"), ("status", "success")]);
+        let metrics = render();
+        assert!(metrics
+            .contains("scouty_current_session_index{chain=\"Metrics\\\"Test\"} 8125\n"));
+        assert!(metrics
+            .contains("scouty_current_era_index{chain=\"Metrics\\\"Test\"} 1354\n"));
+        assert!(metrics.contains(
+            "scouty_active_next_era{chain=\"Metrics\\\"Test\",stash=\"5GrwvaEF\"} 0\n"
+        ));
+        assert!(metrics.contains(
+            "scouty_era_points{chain=\"Metrics\\\"Test\",stash=\"5GrwvaEF\"} 1200\n"
+        ));
+        assert!(metrics.contains("# TYPE scouty_hook_executions_total counter\n"));
+        assert!(metrics.contains(
+            "scouty_hook_executions_total{hook=\"Metrics test\",status=\"success\"} 2\n"
+        ));
+    }
+
+    #[test]
+    fn it_only_serves_the_metrics_path() {
+        assert!(response("GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(
+            response("GET / HTTP/1.1\r\n\r\n").starts_with("HTTP/1.1 404 Not Found\r\n")
+        );
+        assert!(response("POST /metrics HTTP/1.1\r\n\r\n")
+            .starts_with("HTTP/1.1 405 Method Not Allowed\r\n"));
+        assert!(response("").starts_with("HTTP/1.1 405"));
+    }
+}
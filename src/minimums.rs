@@ -0,0 +1,152 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::report::Network;
+use std::sync::{Arc, Mutex};
+
+/// Staking minimums of an era. Nominators bonded below the minimum active stake are
+/// not elected and silently stop earning rewards.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct StakingMinimums {
+    pub era_index: u32,
+    /// Lowest stake of the nominators elected in the era
+    pub minimum_active_stake: u128,
+    pub min_nominator_bond: u128,
+    /// Minimum bond of a validator, None if the chain does not define one
+    pub min_validator_bond: Option<u128>,
+}
+
+impl StakingMinimums {
+    /// Returns true if the bonded amount is below the minimum active stake, the minimum
+    /// active stake is not defined until the first election
+    pub fn is_below_minimum_active_stake(&self, bonded: u128) -> bool {
+        self.minimum_active_stake > 0 && bonded < self.minimum_active_stake
+    }
+
+    /// Returns the minimum active stake and the minimum nominator bond
+    pub fn nominator_summary(&self, network: &Network) -> String {
+        format!(
+            "Minimum active stake {} -> min nominator bond {}",
+            network.format_amount(self.minimum_active_stake),
+            network.format_amount(self.min_nominator_bond)
+        )
+    }
+
+    /// Returns the nominator summary followed by the minimum validator bond, if the
+    /// chain defines one
+    pub fn summary(&self, network: &Network) -> String {
+        match self.min_validator_bond {
+            Some(bond) => format!(
+                "{} -> min validator bond {}",
+                self.nominator_summary(network),
+                network.format_amount(bond)
+            ),
+            None => self.nominator_summary(network),
+        }
+    }
+}
+
+/// Staking minimums read in the active era, so that they are only queried once per era
+#[derive(Debug, Clone, Default)]
+pub struct MinimumsCache(Arc<Mutex<Option<StakingMinimums>>>);
+
+impl MinimumsCache {
+    /// Returns the minimums read in the era, or None if read in a previous era
+    pub fn get(&self, era_index: u32) -> Option<StakingMinimums> {
+        let cache = self.0.lock().unwrap();
+        cache.filter(|minimums| minimums.era_index == era_index)
+    }
+
+    pub fn insert(&self, minimums: StakingMinimums) {
+        *self.0.lock().unwrap() = Some(minimums);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn network() -> Network {
+        Network {
+            name: "polkadot".to_string(),
+            token_symbol: "DOT".to_string(),
+            token_decimals: 10,
+        }
+    }
+
+    #[test]
+    fn it_verifies_the_minimum_active_stake() {
+        let minimums = StakingMinimums {
+            era_index: 1200,
+            minimum_active_stake: 2_500_000_000_000,
+            min_nominator_bond: 2_500_000_000_000,
+            min_validator_bond: None,
+        };
+        assert!(minimums.is_below_minimum_active_stake(1_000_000_000_000));
+        assert!(!minimums.is_below_minimum_active_stake(2_500_000_000_000));
+
+        let minimums = StakingMinimums {
+            minimum_active_stake: 0,
+            ..minimums
+        };
+        assert!(!minimums.is_below_minimum_active_stake(1_000_000_000_000));
+    }
+
+    #[test]
+    fn it_summarizes_the_minimums() {
+        let minimums = StakingMinimums {
+            era_index: 1200,
+            minimum_active_stake: 2_500_000_000_000,
+            min_nominator_bond: 2_500_000_000_000,
+            min_validator_bond: None,
+        };
+        assert_eq!(
+            minimums.summary(&network()),
+            "Minimum active stake 250.00 DOT -> min nominator bond 250.00 DOT"
+        );
+
+        let minimums = StakingMinimums {
+            min_validator_bond: Some(10_000_000_000_000),
+            ..minimums
+        };
+        assert_eq!(
+            minimums.summary(&network()),
+            "Minimum active stake 250.00 DOT -> min nominator bond 250.00 DOT -> min validator bond 1.0k DOT"
+        );
+    }
+
+    #[test]
+    fn it_caches_the_minimums_per_era() {
+        let cache = MinimumsCache::default();
+        assert!(cache.get(1200).is_none());
+
+        let minimums = StakingMinimums {
+            era_index: 1200,
+            minimum_active_stake: 2_500_000_000_000,
+            ..Default::default()
+        };
+        cache.insert(minimums);
+        assert_eq!(cache.get(1200), Some(minimums));
+
+        // Minimums are read again in a new era
+        assert!(cache.get(1201).is_none());
+    }
+}
@@ -0,0 +1,374 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::minimums::StakingMinimums;
+use crate::report::Network;
+use std::{fmt, str::FromStr};
+use subxt::utils::AccountId32;
+
+/// Role of an account monitored, defined as `account:type` (e.g. 15abc..:nominator).
+/// Accounts without a type defined are monitored as validator stashes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AccountType {
+    #[default]
+    Validator,
+    /// The nominations of the account are verified at every new era, no validator hook
+    /// is run for the account
+    Nominator,
+}
+
+impl AccountType {
+    /// Parse the account type defined for an account, the type is matched case
+    /// insensitive
+    pub fn parse(value: &str) -> Result<(AccountId32, Self), String> {
+        let (account, account_type) = value
+            .split_once(':')
+            .ok_or_else(|| format!("{} is not defined as account:type", value))?;
+        let account = AccountId32::from_str(account.trim()).map_err(|_| {
+            format!("{} is not a valid SS58 format account", account.trim())
+        })?;
+        let account_type = match account_type.trim().to_lowercase().as_str() {
+            "validator" => Self::Validator,
+            "nominator" => Self::Nominator,
+            other => {
+                return Err(format!(
+                    "{} is not an account type (validator or nominator)",
+                    other
+                ))
+            }
+        };
+        Ok((account, account_type))
+    }
+}
+
+impl fmt::Display for AccountType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Validator => write!(f, "validator"),
+            Self::Nominator => write!(f, "nominator"),
+        }
+    }
+}
+
+/// Validator nominated by a nominator account, as seen in the active era
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct NominationTarget {
+    pub name: String,
+    pub is_active: bool,
+    /// Stake of the nominator in the exposure of the validator, if applied
+    pub exposed_stake: Option<u128>,
+    /// The nominators of the validator do not fit in a single exposure page
+    pub is_oversubscribed: bool,
+    /// Commission of the active era and of the previous era (in parts per billion)
+    pub commission: u32,
+    pub previous_commission: Option<u32>,
+    /// The validator has been slashed in the previous era
+    pub is_slashed: bool,
+}
+
+impl NominationTarget {
+    pub fn is_commission_raised(&self) -> bool {
+        self.previous_commission
+            .is_some_and(|previous| self.commission > previous)
+    }
+
+    /// Returns the warnings of the validator nominated
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.is_slashed {
+            warnings.push(format!("{} slashed in the previous era", self.name));
+        }
+        if !self.is_active {
+            warnings.push(format!("{} inactive", self.name));
+        }
+        if self.is_oversubscribed {
+            warnings.push(format!("{} oversubscribed", self.name));
+        }
+        if let (true, Some(previous)) =
+            (self.is_commission_raised(), self.previous_commission)
+        {
+            warnings.push(format!(
+                "{} raised commission {} -> {}",
+                self.name,
+                format_commission(previous),
+                format_commission(self.commission)
+            ));
+        }
+        warnings
+    }
+}
+
+/// Nominations of a nominator account in the active era
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Nomination {
+    pub name: String,
+    pub era_index: u32,
+    /// Active bonded stake of the nominator
+    pub bonded: u128,
+    pub targets: Vec<NominationTarget>,
+    /// Staking minimums of the era, if available
+    pub minimums: Option<StakingMinimums>,
+}
+
+impl Nomination {
+    /// Returns true if the stake of the nominator is in the exposure of at least one of
+    /// the validators nominated
+    pub fn is_stake_applied(&self) -> bool {
+        self.targets.iter().any(|t| t.exposed_stake.is_some())
+    }
+
+    /// Returns the stake of the nominator applied in the exposures of the validators
+    pub fn applied_stake(&self) -> u128 {
+        self.targets.iter().filter_map(|t| t.exposed_stake).sum()
+    }
+
+    /// Returns the validators nominated that are not active
+    pub fn inactive_targets(&self) -> Vec<String> {
+        self.targets
+            .iter()
+            .filter(|t| !t.is_active)
+            .map(|t| t.name.to_string())
+            .collect()
+    }
+
+    /// Returns true if the bonded stake is below the minimum active stake of the era
+    pub fn is_below_minimum_active_stake(&self) -> bool {
+        self.minimums
+            .is_some_and(|minimums| minimums.is_below_minimum_active_stake(self.bonded))
+    }
+
+    /// Returns the warnings of the nominations, the bonded stake below the minimum
+    /// active stake and the stake not applied first
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.is_below_minimum_active_stake() {
+            warnings.push("bonded stake below the minimum active stake".to_string());
+        }
+        if self.targets.is_empty() {
+            warnings.push("no validators nominated".to_string());
+        } else if !self.is_stake_applied() {
+            warnings.push(format!("stake not applied in era {}", self.era_index));
+        }
+        warnings.extend(self.targets.iter().flat_map(|t| t.warnings()));
+        warnings
+    }
+
+    /// Returns true if the stake is not applied, if the bonded stake is below the
+    /// minimum active stake or if one of the validators nominated has been slashed
+    pub fn is_critical(&self) -> bool {
+        !self.is_stake_applied()
+            || self.is_below_minimum_active_stake()
+            || self.targets.iter().any(|t| t.is_slashed)
+    }
+
+    /// Returns the summary of the era of the nominations
+    pub fn summary(&self, network: &Network) -> String {
+        format!(
+            "{} -> era {} nominating {} validator(s), {} of {} bonded applied",
+            self.name,
+            self.era_index,
+            self.targets.len(),
+            network.format_amount(self.applied_stake()),
+            network.format_amount(self.bonded)
+        )
+    }
+
+    /// Returns the validators nominated with the stake of the nominator applied
+    pub fn backing(&self, network: &Network) -> Vec<String> {
+        self.targets
+            .iter()
+            .filter_map(|t| {
+                t.exposed_stake
+                    .map(|stake| format!("{} ({})", t.name, network.format_amount(stake)))
+            })
+            .collect()
+    }
+
+    /// Returns the plain and formatted message with the summary of the era and the
+    /// warnings of the nominations, the lines given (e.g. the output of the hooks) are
+    /// shown before the warnings
+    pub fn message(&self, network: &Network, lines: &[String]) -> (String, String) {
+        let summary = self.summary(network);
+        let backing = self.backing(network);
+        let mut message = summary.to_string();
+        let mut formatted_message = format!("<br/>🗳️ {}<br/>", summary);
+        if !backing.is_empty() {
+            message.push_str(&format!("\nbacking: {}", backing.join(", ")));
+            formatted_message
+                .push_str(&format!("‣ Backing: {}<br/>", backing.join(", ")));
+        }
+        if let Some(minimums) = &self.minimums {
+            let minimums = minimums.nominator_summary(network);
+            message.push_str(&format!("\n{}", minimums));
+            formatted_message.push_str(&format!("‣ 📏 {}<br/>", minimums));
+        }
+        for line in lines.iter() {
+            message.push_str(&format!("\n{}", line));
+            formatted_message.push_str(&format!("‣ {}<br/>", line));
+        }
+        for warning in self.warnings() {
+            message.push_str(&format!("\nwarning: {}", warning));
+            formatted_message.push_str(&format!("‣ ⚠️ {}<br/>", warning));
+        }
+        formatted_message.push_str("<br/>");
+        (message, formatted_message)
+    }
+}
+
+/// Returns the commission as a percentage (e.g. 5.00%)
+pub fn format_commission(commission: u32) -> String {
+    format!("{:.2}%", commission as f64 / 10_000_000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+    fn network() -> Network {
+        Network {
+            name: "polkadot".to_string(),
+            token_symbol: "DOT".to_string(),
+            token_decimals: 10,
+        }
+    }
+
+    fn target(name: &str, exposed_stake: Option<u128>) -> NominationTarget {
+        NominationTarget {
+            name: name.to_string(),
+            is_active: true,
+            exposed_stake,
+            commission: 50_000_000,
+            previous_commission: Some(50_000_000),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn it_parses_the_account_type() {
+        let (account, account_type) =
+            AccountType::parse(&format!("{}:Nominator", ALICE)).unwrap();
+        assert_eq!(account, AccountId32::from_str(ALICE).unwrap());
+        assert_eq!(account_type, AccountType::Nominator);
+        assert!(AccountType::parse(ALICE).is_err());
+        assert!(AccountType::parse("alice:nominator").is_err());
+        assert!(AccountType::parse(&format!("{}:collator", ALICE)).is_err());
+    }
+
+    #[test]
+    fn it_warns_on_the_stake_not_applied() {
+        let nomination = Nomination {
+            name: "Nominator".to_string(),
+            era_index: 1200,
+            bonded: 1_000_000_000_000,
+            targets: vec![target("A", None), target("B", None)],
+            ..Default::default()
+        };
+        assert!(!nomination.is_stake_applied());
+        assert!(nomination.is_critical());
+        assert_eq!(nomination.warnings(), vec!["stake not applied in era 1200"]);
+
+        let nomination = Nomination {
+            targets: vec![],
+            ..nomination
+        };
+        assert_eq!(nomination.warnings(), vec!["no validators nominated"]);
+    }
+
+    #[test]
+    fn it_warns_on_the_validators_nominated() {
+        let mut inactive = target("A", None);
+        inactive.is_active = false;
+        let mut raised = target("B", Some(1_000_000_000_000));
+        raised.commission = 100_000_000;
+        raised.is_oversubscribed = true;
+        let mut slashed = target("C", None);
+        slashed.is_slashed = true;
+        let nomination = Nomination {
+            name: "Nominator".to_string(),
+            era_index: 1200,
+            bonded: 1_000_000_000_000,
+            targets: vec![inactive, raised, slashed],
+            ..Default::default()
+        };
+        assert!(nomination.is_stake_applied());
+        assert!(nomination.is_critical());
+        assert_eq!(nomination.applied_stake(), 1_000_000_000_000);
+        assert_eq!(nomination.inactive_targets(), vec!["A"]);
+        assert_eq!(
+            nomination.warnings(),
+            vec![
+                "A inactive",
+                "B oversubscribed",
+                "B raised commission 5.00% -> 10.00%",
+                "C slashed in the previous era"
+            ]
+        );
+    }
+
+    #[test]
+    fn it_summarizes_the_nominations() {
+        let nomination = Nomination {
+            name: "Nominator".to_string(),
+            era_index: 1200,
+            bonded: 1_000_000_000_000,
+            targets: vec![target("A", Some(1_000_000_000_000)), target("B", None)],
+            ..Default::default()
+        };
+        let (message, _) = nomination.message(&network(), &[]);
+        assert_eq!(
+            message,
+            "Nominator -> era 1200 nominating 2 validator(s), 100.00 DOT of 100.00 DOT bonded applied\nbacking: A (100.00 DOT)"
+        );
+    }
+
+    #[test]
+    fn it_warns_on_the_bonded_stake_below_the_minimum_active_stake() {
+        let mut nomination = Nomination {
+            name: "Nominator".to_string(),
+            era_index: 1200,
+            bonded: 1_000_000_000_000,
+            targets: vec![target("A", Some(1_000_000_000_000))],
+            minimums: Some(StakingMinimums {
+                era_index: 1200,
+                minimum_active_stake: 2_500_000_000_000,
+                min_nominator_bond: 2_500_000_000_000,
+                min_validator_bond: None,
+            }),
+        };
+        assert!(nomination.is_below_minimum_active_stake());
+        assert!(nomination.is_critical());
+        assert_eq!(
+            nomination.warnings(),
+            vec!["bonded stake below the minimum active stake"]
+        );
+        let (message, _) = nomination.message(&network(), &[]);
+        assert!(message.contains(
+            "\nMinimum active stake 250.00 DOT -> min nominator bond 250.00 DOT"
+        ));
+
+        nomination.bonded = 2_500_000_000_000;
+        assert!(!nomination.is_below_minimum_active_stake());
+        assert!(!nomination.is_critical());
+        assert!(nomination.warnings().is_empty());
+    }
+}
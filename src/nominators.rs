@@ -0,0 +1,196 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::BTreeMap;
+use subxt::utils::AccountId32;
+
+/// Stake backing a stash in an era, with the stake of every nominator of all the
+/// exposure pages
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Backing {
+    pub total: u128,
+    pub nominators: BTreeMap<AccountId32, u128>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Nominator {
+    pub stash: AccountId32,
+    /// Identity of the nominator, defined once the changes are reported
+    pub name: String,
+    pub value: u128,
+}
+
+/// Meaningful changes of the nominators backing a stash between consecutive eras
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct NominatorChanges {
+    pub previous_total: u128,
+    pub total: u128,
+    /// Nominators above the amount threshold that left, with the stake of the
+    /// previous era
+    pub left: Vec<Nominator>,
+    /// Nominators above the amount threshold that arrived
+    pub arrived: Vec<Nominator>,
+    /// True if the total backing dropped by more than the percentage threshold
+    pub is_backing_drop: bool,
+}
+
+impl NominatorChanges {
+    /// Compare the backing of consecutive eras. Only nominators with a stake of at
+    /// least `amount_threshold` (in plancks) and a drop of the total backing above
+    /// `drop_threshold` (in percentage) are meaningful, a threshold of 0 disables
+    /// each of them.
+    pub fn between(
+        previous: &Backing,
+        current: &Backing,
+        amount_threshold: u128,
+        drop_threshold: u32,
+    ) -> Self {
+        let above = |from: &Backing, to: &Backing| -> Vec<Nominator> {
+            if amount_threshold == 0 {
+                return Vec::new();
+            }
+            from.nominators
+                .iter()
+                .filter(|(stash, value)| {
+                    **value >= amount_threshold && !to.nominators.contains_key(*stash)
+                })
+                .map(|(stash, value)| Nominator {
+                    stash: stash.clone(),
+                    name: "".to_string(),
+                    value: *value,
+                })
+                .collect()
+        };
+        let mut changes = NominatorChanges {
+            previous_total: previous.total,
+            total: current.total,
+            left: above(previous, current),
+            arrived: above(current, previous),
+            is_backing_drop: false,
+        };
+        changes.is_backing_drop =
+            drop_threshold > 0 && changes.drop_percentage() > drop_threshold;
+        changes
+    }
+
+    /// Returns the percentage by which the total backing dropped, 0 if it did not drop
+    pub fn drop_percentage(&self) -> u32 {
+        if self.previous_total == 0 || self.total >= self.previous_total {
+            return 0;
+        }
+        ((self.previous_total - self.total) * 100 / self.previous_total) as u32
+    }
+
+    /// Returns true if a large nominator left or the total backing dropped
+    pub fn is_critical(&self) -> bool {
+        !self.left.is_empty() || self.is_backing_drop
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.is_critical() && self.arrived.is_empty()
+    }
+}
+
+/// Backing of the stashes predefined in the last era seen, kept to be compared with
+/// the backing of the next era
+#[derive(Debug, Default)]
+pub struct NominatorRecords {
+    backings: BTreeMap<AccountId32, (u32, Backing)>,
+}
+
+impl NominatorRecords {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Keep the backing of the stash in the era and return the backing kept for the
+    /// previous era, if any
+    pub fn insert(
+        &mut self,
+        era_index: u32,
+        stash: &AccountId32,
+        backing: Backing,
+    ) -> Option<Backing> {
+        match self.backings.insert(stash.clone(), (era_index, backing)) {
+            Some((previous_era_index, previous))
+                if previous_era_index + 1 == era_index =>
+            {
+                Some(previous)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backing(nominators: &[(u8, u128)]) -> Backing {
+        let nominators: BTreeMap<AccountId32, u128> = nominators
+            .iter()
+            .map(|(id, value)| (AccountId32([*id; 32]), *value))
+            .collect();
+        Backing {
+            total: nominators.values().sum(),
+            nominators,
+        }
+    }
+
+    #[test]
+    fn it_finds_the_meaningful_nominator_changes() {
+        let previous = backing(&[(1, 1000), (2, 50), (3, 500)]);
+        let current = backing(&[(2, 50), (3, 500), (4, 200), (5, 10)]);
+
+        let changes = NominatorChanges::between(&previous, &current, 100, 40);
+        assert_eq!(changes.left.len(), 1);
+        assert_eq!(changes.left[0].stash, AccountId32([1; 32]));
+        assert_eq!(changes.left[0].value, 1000);
+        assert_eq!(changes.arrived.len(), 1);
+        assert_eq!(changes.arrived[0].stash, AccountId32([4; 32]));
+        assert_eq!(changes.drop_percentage(), 50);
+        assert!(changes.is_backing_drop);
+        assert!(changes.is_critical());
+
+        // Thresholds of 0 are disabled
+        let changes = NominatorChanges::between(&previous, &current, 0, 0);
+        assert!(changes.is_empty());
+
+        // Increase of the total backing is never a drop
+        let changes = NominatorChanges::between(&current, &previous, 2000, 1);
+        assert_eq!(changes.drop_percentage(), 0);
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn it_keeps_the_backing_of_the_previous_era() {
+        let stash = AccountId32([1; 32]);
+        let mut records = NominatorRecords::new();
+
+        assert_eq!(records.insert(10, &stash, backing(&[(2, 10)])), None);
+        assert_eq!(
+            records.insert(11, &stash, backing(&[(3, 10)])),
+            Some(backing(&[(2, 10)]))
+        );
+        // Backing of eras not consecutive is not returned
+        assert_eq!(records.insert(13, &stash, backing(&[(4, 10)])), None);
+    }
+}
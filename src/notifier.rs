@@ -0,0 +1,204 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::errors::ScoutyError;
+use crate::matrix::Severity;
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use log::warn;
+
+// Events of the messages that are not reports, e.g. hook scripts output or errors
+pub const EVENT_MESSAGE: &str = "message";
+pub const EVENT_ERROR: &str = "error";
+
+/// Message delivered to the notifiers, with the context of the event reported
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    /// Event reported, e.g. new_session or slash
+    pub event: String,
+    pub chain: String,
+    pub stashes: Vec<String>,
+    pub session_index: Option<u32>,
+    pub era_index: Option<u32>,
+    pub severity: Severity,
+    pub message: String,
+    pub formatted_message: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Notification {
+    pub fn new(
+        event: &str,
+        chain: &str,
+        severity: Severity,
+        message: &str,
+        formatted_message: &str,
+    ) -> Self {
+        Self {
+            event: event.to_string(),
+            chain: chain.to_string(),
+            stashes: Vec::new(),
+            session_index: None,
+            era_index: None,
+            severity,
+            message: message.to_string(),
+            formatted_message: formatted_message.to_string(),
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+/// Returns the text truncated to the maximum number of characters, with an ellipsis
+/// if truncated
+pub fn truncate(text: &str, max_length: usize) -> String {
+    if text.chars().count() <= max_length {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max_length - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// Channel the messages are delivered to, e.g. a matrix room
+pub trait Notifier: Send + Sync {
+    /// Name of the notifier, used to log its errors
+    fn name(&self) -> &str;
+
+    /// Send the message to the channel for the severity of the notification
+    fn send<'a>(
+        &'a self,
+        notification: &'a Notification,
+    ) -> BoxFuture<'a, Result<(), ScoutyError>>;
+}
+
+/// Send the message to every notifier, a notifier that fails does not prevent the
+/// delivery to the others. Returns the first error if every notifier failed.
+pub async fn notify_all(
+    notifiers: &[Box<dyn Notifier>],
+    notification: &Notification,
+) -> Result<(), ScoutyError> {
+    let mut first_error: Option<ScoutyError> = None;
+    let mut delivered = false;
+    for notifier in notifiers.iter() {
+        match notifier.send(notification).await {
+            Ok(()) => delivered = true,
+            Err(e) => {
+                warn!("{} message not sent! {}", notifier.name(), e);
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    match first_error {
+        Some(e) if !delivered => Err(e),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_std::task;
+    use std::sync::{Arc, Mutex};
+
+    struct TestNotifier {
+        name: String,
+        fails: bool,
+        sent: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Notifier for TestNotifier {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn send<'a>(
+            &'a self,
+            notification: &'a Notification,
+        ) -> BoxFuture<'a, Result<(), ScoutyError>> {
+            Box::pin(async move {
+                if self.fails {
+                    return Err(ScoutyError::Other(format!("{} unreachable", self.name)));
+                }
+                self.sent.lock().unwrap().push(notification.message.clone());
+                Ok(())
+            })
+        }
+    }
+
+    fn notifier(
+        name: &str,
+        fails: bool,
+        sent: &Arc<Mutex<Vec<String>>>,
+    ) -> Box<dyn Notifier> {
+        Box::new(TestNotifier {
+            name: name.to_string(),
+            fails,
+            sent: sent.clone(),
+        })
+    }
+
+    #[test]
+    fn it_truncates_long_texts() {
+        assert_eq!(truncate("Session", 10), "Session");
+        assert_eq!(truncate("New session", 5), "New …");
+        assert_eq!(truncate(&"🚨".repeat(3000), 2000).chars().count(), 2000);
+    }
+
+    #[test]
+    fn it_delivers_to_the_other_notifiers_if_one_fails() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let notifiers = vec![
+            notifier("first", true, &sent),
+            notifier("second", false, &sent),
+        ];
+        let notification = Notification::new(
+            EVENT_MESSAGE,
+            "Polkadot",
+            Severity::Info,
+            "Session",
+            "Session",
+        );
+        assert!(task::block_on(notify_all(&notifiers, &notification)).is_ok());
+        assert_eq!(*sent.lock().unwrap(), vec!["Session".to_string()]);
+    }
+
+    #[test]
+    fn it_fails_if_every_notifier_fails() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let notifiers = vec![
+            notifier("first", true, &sent),
+            notifier("second", true, &sent),
+        ];
+        let notification = Notification::new(
+            EVENT_ERROR,
+            "Polkadot",
+            Severity::Critical,
+            "Slash",
+            "Slash",
+        );
+        let result = task::block_on(notify_all(&notifiers, &notification));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Other error: first unreachable"
+        );
+        assert!(task::block_on(notify_all(&[], &notification)).is_ok());
+    }
+}
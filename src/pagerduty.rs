@@ -0,0 +1,252 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::config::CONFIG;
+use crate::errors::ScoutyError;
+use crate::matrix::Severity;
+use crate::notifier::{truncate, Notification, Notifier};
+use crate::templates::{TEMPLATE_ACTIVE_NEXT_ERA, TEMPLATE_INACTIVE_NEXT_ERA};
+use futures::future::BoxFuture;
+use log::debug;
+use serde::Serialize;
+use std::time;
+
+const PAGERDUTY_EVENTS_URL: &str = "https://events.pagerduty.com/v2/enqueue";
+
+// Maximum number of characters of the summary of an event
+const PAGERDUTY_MAX_SUMMARY_LENGTH: usize = 1024;
+
+#[derive(Debug, Serialize, PartialEq)]
+struct Event {
+    routing_key: String,
+    event_action: &'static str,
+    dedup_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    payload: Option<EventPayload>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct EventPayload {
+    summary: String,
+    source: String,
+    severity: &'static str,
+    timestamp: String,
+    custom_details: CustomDetails,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct CustomDetails {
+    event: String,
+    stash: Option<String>,
+    session_index: Option<u32>,
+    era_index: Option<u32>,
+}
+
+/// Returns the key that deduplicates the incidents of the same stash and event,
+/// so that repeated sessions do not open a new incident
+fn dedup_key(chain: &str, stash: Option<&str>, event: &str) -> String {
+    match stash {
+        Some(stash) => format!("scouty-{}-{}-{}", chain.to_lowercase(), stash, event),
+        None => format!("scouty-{}-{}", chain.to_lowercase(), event),
+    }
+}
+
+/// Returns the stashes of the notification, or none if the event is not about a stash
+fn stashes(notification: &Notification) -> Vec<Option<&str>> {
+    if notification.stashes.is_empty() {
+        return vec![None];
+    }
+    notification
+        .stashes
+        .iter()
+        .map(|s| Some(s.as_str()))
+        .collect()
+}
+
+/// Returns the events of the notification, a trigger event for every stash if the
+/// notification is at or above the minimum severity, or a resolve event of the
+/// inactive next era incident of every stash that becomes active next era again
+fn events(
+    routing_key: &str,
+    min_severity: Severity,
+    notification: &Notification,
+) -> Vec<Event> {
+    if notification.event == TEMPLATE_ACTIVE_NEXT_ERA {
+        return stashes(notification)
+            .into_iter()
+            .map(|stash| Event {
+                routing_key: routing_key.to_string(),
+                event_action: "resolve",
+                dedup_key: dedup_key(
+                    &notification.chain,
+                    stash,
+                    TEMPLATE_INACTIVE_NEXT_ERA,
+                ),
+                payload: None,
+            })
+            .collect();
+    }
+    if notification.severity < min_severity {
+        return Vec::new();
+    }
+    stashes(notification)
+        .into_iter()
+        .map(|stash| Event {
+            routing_key: routing_key.to_string(),
+            event_action: "trigger",
+            dedup_key: dedup_key(&notification.chain, stash, &notification.event),
+            payload: Some(EventPayload {
+                summary: truncate(&notification.message, PAGERDUTY_MAX_SUMMARY_LENGTH),
+                source: notification.chain.clone(),
+                severity: match notification.severity {
+                    Severity::Info => "info",
+                    Severity::Critical => "critical",
+                },
+                timestamp: notification.timestamp.to_rfc3339(),
+                custom_details: CustomDetails {
+                    event: notification.event.clone(),
+                    stash: stash.map(|s| s.to_string()),
+                    session_index: notification.session_index,
+                    era_index: notification.era_index,
+                },
+            }),
+        })
+        .collect()
+}
+
+/// Pages with the PagerDuty Events API v2 for the events at or above the minimum
+/// severity, other events are ignored
+#[derive(Debug, Clone)]
+pub struct PagerDuty {
+    client: reqwest::Client,
+    routing_key: String,
+    min_severity: Severity,
+}
+
+impl PagerDuty {
+    pub fn new() -> Self {
+        let config = CONFIG.current();
+        PagerDuty {
+            client: reqwest::Client::new(),
+            routing_key: config.pagerduty_routing_key.clone(),
+            min_severity: config.pagerduty_min_severity,
+        }
+    }
+
+    async fn enqueue(&self, event: &Event) -> Result<(), ScoutyError> {
+        let res = self
+            .client
+            .post(PAGERDUTY_EVENTS_URL)
+            .timeout(time::Duration::from_secs(CONFIG.current().request_timeout))
+            .json(event)
+            .send()
+            .await
+            .map_err(|e| ScoutyError::PagerDutyError(e.to_string()))?;
+        debug!("response {:?}", res);
+        if !res.status().is_success() {
+            return Err(ScoutyError::PagerDutyError(format!(
+                "{} event {} responded with status {}",
+                event.event_action,
+                event.dedup_key,
+                res.status()
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Notifier for PagerDuty {
+    fn name(&self) -> &str {
+        "PagerDuty"
+    }
+
+    fn send<'a>(
+        &'a self,
+        notification: &'a Notification,
+    ) -> BoxFuture<'a, Result<(), ScoutyError>> {
+        Box::pin(async move {
+            for event in events(&self.routing_key, self.min_severity, notification) {
+                self.enqueue(&event).await?;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification(event: &str, severity: Severity, stashes: &[&str]) -> Notification {
+        let mut notification =
+            Notification::new(event, "Polkadot", severity, "Alert", "Alert");
+        notification.stashes = stashes.iter().map(|s| s.to_string()).collect();
+        notification
+    }
+
+    #[test]
+    fn it_triggers_an_event_per_stash_at_or_above_the_min_severity() {
+        let inactive = notification(
+            TEMPLATE_INACTIVE_NEXT_ERA,
+            Severity::Critical,
+            &["5G", "5H"],
+        );
+        let triggered = events("key", Severity::Critical, &inactive);
+        assert_eq!(triggered.len(), 2);
+        assert_eq!(triggered[0].event_action, "trigger");
+        assert_eq!(
+            triggered[0].dedup_key,
+            "scouty-polkadot-5G-inactive_next_era"
+        );
+        assert_eq!(
+            triggered[1].dedup_key,
+            "scouty-polkadot-5H-inactive_next_era"
+        );
+        let payload = triggered[0].payload.as_ref().unwrap();
+        assert_eq!(payload.severity, "critical");
+        assert_eq!(payload.custom_details.stash, Some("5G".to_string()));
+
+        let session = notification("new_session", Severity::Info, &["5G"]);
+        assert!(events("key", Severity::Critical, &session).is_empty());
+        assert_eq!(events("key", Severity::Info, &session).len(), 1);
+
+        let error = notification("error", Severity::Critical, &[]);
+        assert_eq!(
+            events("key", Severity::Critical, &error)[0].dedup_key,
+            "scouty-polkadot-error"
+        );
+    }
+
+    #[test]
+    fn it_resolves_the_inactive_next_era_incident_once_active_again() {
+        let active = notification(TEMPLATE_ACTIVE_NEXT_ERA, Severity::Info, &["5G"]);
+        let resolved = events("key", Severity::Critical, &active);
+        assert_eq!(
+            resolved,
+            vec![Event {
+                routing_key: "key".to_string(),
+                event_action: "resolve",
+                dedup_key: "scouty-polkadot-5G-inactive_next_era".to_string(),
+                payload: None,
+            }]
+        );
+    }
+}
@@ -47,14 +47,15 @@ impl ParaRecords {
 
     pub fn reset_config_stashes(
         &mut self,
+        chain: &str,
         active_validators: Vec<AccountId32>,
     ) -> Result<(), ScoutyError> {
-        let config = CONFIG.clone();
+        let config = CONFIG.current();
 
         let mut config_stashes: Vec<(AccountId32, u32)> = vec![];
 
         // Find stash indices
-        for stash_str in config.stashes.iter() {
+        for stash_str in config.validator_stashes(chain).iter() {
             let stash = AccountId32::from_str(stash_str).map_err(|e| {
                 ScoutyError::Other(format!(
                     "Invalid SS58 format account: {:?} error: {e:?}",
@@ -113,6 +114,6 @@ impl ParaRecords {
                 None => 0,
             };
         }
-        return total;
+        total
     }
 }
@@ -0,0 +1,168 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::errors::ScoutyError;
+use log::warn;
+use std::{fs, ops::Range, os::unix::fs::PermissionsExt};
+use subxt::{
+    ext::sp_core::{sr25519, Pair},
+    tx::PairSigner,
+    PolkadotConfig,
+};
+
+pub type PayoutSigner = PairSigner<PolkadotConfig, sr25519::Pair>;
+
+/// Era with exposure pages of a stash not claimed yet
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct UnclaimedEra {
+    pub era_index: u32,
+    pub pages: u32,
+    /// Estimated reward (in plancks) of the era, including nominators share
+    pub estimated_reward: u128,
+}
+
+/// Loads the signer of the payouts from the secret seed (or secret uri) in the file.
+/// The seed is never logged nor included in the errors returned.
+pub fn load_signer(path: &str) -> Result<PayoutSigner, ScoutyError> {
+    let seed = fs::read_to_string(path).map_err(|e| {
+        ScoutyError::Other(format!("Payout seed file {} not readable: {}", path, e))
+    })?;
+    if let Ok(metadata) = fs::metadata(path) {
+        if metadata.permissions().mode() & 0o077 != 0 {
+            warn!(
+                "Payout seed file {} is accessible by other users, restrict it with chmod 600",
+                path
+            );
+        }
+    }
+    let pair = sr25519::Pair::from_string(seed.trim(), None).map_err(|_| {
+        ScoutyError::Other(format!(
+            "Payout seed file {} does not define a valid secret seed",
+            path
+        ))
+    })?;
+    Ok(PairSigner::new(pair))
+}
+
+/// Returns the last `depth` eras before the active era for which the rewards can still
+/// be claimed, limited by the history depth of the chain
+pub fn claimable_eras(
+    active_era_index: u32,
+    depth: u32,
+    history_depth: u32,
+) -> Range<u32> {
+    active_era_index.saturating_sub(depth.min(history_depth))..active_era_index
+}
+
+/// Returns the oldest eras with pages not claimed yet, up to `max_eras`, so that the
+/// eras closer to expire are paid out first
+pub fn select_eras(mut eras: Vec<UnclaimedEra>, max_eras: u32) -> Vec<UnclaimedEra> {
+    eras.retain(|era| era.pages > 0);
+    eras.sort_by_key(|era| era.era_index);
+    eras.truncate(max_eras as usize);
+    eras
+}
+
+/// Returns the number of eras elapsed since the oldest era with pages not claimed yet
+pub fn oldest_unclaimed_age(active_era_index: u32, eras: &[UnclaimedEra]) -> Option<u32> {
+    eras.iter()
+        .filter(|era| era.pages > 0)
+        .map(|era| active_era_index.saturating_sub(era.era_index))
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_returns_the_claimable_eras() {
+        assert_eq!(claimable_eras(100, 8, 84), 92..100);
+        assert_eq!(claimable_eras(100, 90, 84), 16..100);
+        assert_eq!(claimable_eras(3, 8, 84), 0..3);
+        assert!(claimable_eras(100, 0, 84).is_empty());
+    }
+
+    #[test]
+    fn it_selects_the_oldest_unclaimed_eras() {
+        let eras = vec![
+            UnclaimedEra {
+                era_index: 12,
+                pages: 1,
+                ..Default::default()
+            },
+            UnclaimedEra {
+                era_index: 10,
+                pages: 2,
+                ..Default::default()
+            },
+            UnclaimedEra {
+                era_index: 11,
+                pages: 0,
+                ..Default::default()
+            },
+            UnclaimedEra {
+                era_index: 13,
+                pages: 1,
+                ..Default::default()
+            },
+        ];
+        let selected = select_eras(eras, 2);
+        assert_eq!(
+            selected
+                .iter()
+                .map(|era| era.era_index)
+                .collect::<Vec<u32>>(),
+            vec![10, 12]
+        );
+        assert_eq!(selected[0].pages, 2);
+    }
+
+    #[test]
+    fn it_returns_the_age_of_the_oldest_unclaimed_era() {
+        let eras = vec![
+            UnclaimedEra {
+                era_index: 30,
+                pages: 0,
+                ..Default::default()
+            },
+            UnclaimedEra {
+                era_index: 40,
+                pages: 1,
+                ..Default::default()
+            },
+        ];
+        assert_eq!(oldest_unclaimed_age(100, &eras), Some(60));
+        assert_eq!(oldest_unclaimed_age(100, &eras[..1]), None);
+    }
+
+    #[test]
+    fn it_never_exposes_the_seed_of_an_invalid_signer() {
+        let path = std::env::temp_dir().join("scouty_payout_seed_invalid");
+        fs::write(&path, "not a secret seed").unwrap();
+        let path = path.to_str().unwrap();
+        match load_signer(path) {
+            Err(e) => assert!(!e.to_string().contains("not a secret seed")),
+            Ok(_) => panic!("signer should be invalid"),
+        }
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,297 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::nomination::{format_commission, Nomination};
+use crate::report::Network;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use subxt::utils::AccountId32;
+
+/// Account type of the bonded account of a pool, as derived by the nomination pools
+/// pallet
+pub const POOL_BONDED_ACCOUNT: u8 = 0;
+
+pub const POOL_STATE_OPEN: &str = "open";
+pub const POOL_STATE_BLOCKED: &str = "blocked";
+pub const POOL_STATE_DESTROYING: &str = "destroying";
+/// The pool does not exist anymore
+pub const POOL_STATE_DESTROYED: &str = "destroyed";
+
+/// Nomination pool monitored, defined as `pool_id` or as `pool_id:member` to also
+/// report the pending rewards of a member account (e.g. 12:15abc..)
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitoredPool {
+    pub pool_id: u32,
+    pub member: Option<AccountId32>,
+}
+
+impl MonitoredPool {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let (pool_id, member) = match value.split_once(':') {
+            Some((pool_id, member)) => (pool_id.trim(), Some(member.trim())),
+            None => (value.trim(), None),
+        };
+        let pool_id = pool_id
+            .parse::<u32>()
+            .map_err(|_| format!("{} is not a pool id", pool_id))?;
+        let member =
+            match member {
+                Some(member) => Some(AccountId32::from_str(member).map_err(|_| {
+                    format!("{} is not a valid SS58 format account", member)
+                })?),
+                None => None,
+            };
+        Ok(Self { pool_id, member })
+    }
+}
+
+/// Returns the account of the pool derived from the pallet id, the same way the
+/// nomination pools pallet does (i.e. `modl`, the pallet id, the account type and the
+/// pool id, zero padded)
+pub fn pool_account(pallet_id: [u8; 8], account_type: u8, pool_id: u32) -> AccountId32 {
+    let mut account = [0u8; 32];
+    account[..4].copy_from_slice(b"modl");
+    account[4..12].copy_from_slice(&pallet_id);
+    account[12] = account_type;
+    account[13..17].copy_from_slice(&pool_id.to_le_bytes());
+    AccountId32(account)
+}
+
+/// State of a pool compared between eras, to alert on its transitions
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct PoolSnapshot {
+    /// open, blocked, destroying or destroyed
+    pub state: String,
+    /// Current commission of the pool (in parts per billion), if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commission: Option<u32>,
+}
+
+/// Nomination pool as seen in the active era
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Pool {
+    pub pool_id: u32,
+    /// Name of the pool, from its metadata
+    pub name: String,
+    pub member_count: u32,
+    pub snapshot: PoolSnapshot,
+    /// Snapshot of the last era verified, if any
+    pub previous: Option<PoolSnapshot>,
+    /// Nominations of the bonded account of the pool, unless the pool is destroyed
+    pub nomination: Option<Nomination>,
+    /// Member account defined and its pending rewards
+    pub member: Option<(String, u128)>,
+}
+
+impl Pool {
+    pub fn is_destroyed(&self) -> bool {
+        self.snapshot.state == POOL_STATE_DESTROYED
+    }
+
+    /// Returns the alerts of the pool transitions since the last era verified, a pool
+    /// verified for the first time is only alerted if it is not open
+    pub fn alerts(&self) -> Vec<String> {
+        let mut alerts = Vec::new();
+        let is_state_changed = self
+            .previous
+            .as_ref()
+            .is_none_or(|previous| previous.state != self.snapshot.state);
+        if is_state_changed && self.snapshot.state != POOL_STATE_OPEN {
+            alerts.push(format!("pool {}", self.snapshot.state));
+        }
+        if let Some(previous) = &self.previous {
+            if previous.commission != self.snapshot.commission && !self.is_destroyed() {
+                alerts.push(format!(
+                    "commission changed {} -> {}",
+                    previous
+                        .commission
+                        .map_or("none".to_string(), format_commission),
+                    self.snapshot
+                        .commission
+                        .map_or("none".to_string(), format_commission)
+                ));
+            }
+        }
+        alerts
+    }
+
+    /// Returns true if the pool has alerts or if the stake of the pool is not applied
+    pub fn is_critical(&self) -> bool {
+        !self.alerts().is_empty()
+            || self
+                .nomination
+                .as_ref()
+                .is_some_and(|nomination| nomination.is_critical())
+    }
+
+    /// Returns the plain and formatted message with the summary of the pool in the era
+    pub fn message(&self, network: &Network) -> (String, String) {
+        let name = if self.name.is_empty() {
+            format!("Pool #{}", self.pool_id)
+        } else {
+            format!("Pool #{} {}", self.pool_id, self.name)
+        };
+        let summary = if self.is_destroyed() {
+            format!("{} -> {}", name, self.snapshot.state)
+        } else {
+            format!(
+                "{} -> {} with {} member(s), commission {}",
+                name,
+                self.snapshot.state,
+                self.member_count,
+                self.snapshot
+                    .commission
+                    .map_or("none".to_string(), format_commission)
+            )
+        };
+        let mut lines = Vec::new();
+        let mut warnings = self.alerts();
+        if let Some(nomination) = &self.nomination {
+            lines.push(nomination.summary(network));
+            let backing = nomination.backing(network);
+            if !backing.is_empty() {
+                lines.push(format!("backing: {}", backing.join(", ")));
+            }
+            warnings.extend(nomination.warnings());
+        }
+        if let Some((member, pending_rewards)) = &self.member {
+            lines.push(format!(
+                "pending rewards of {}: {}",
+                member,
+                network.format_tokens(*pending_rewards)
+            ));
+        }
+        let mut message = summary.to_string();
+        let mut formatted_message = format!("<br/>🏊 {}<br/>", summary);
+        for line in lines.iter() {
+            message.push_str(&format!("\n{}", line));
+            formatted_message.push_str(&format!("‣ {}<br/>", line));
+        }
+        for warning in warnings.iter() {
+            message.push_str(&format!("\nwarning: {}", warning));
+            formatted_message.push_str(&format!("‣ ⚠️ {}<br/>", warning));
+        }
+        formatted_message.push_str("<br/>");
+        (message, formatted_message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nomination::NominationTarget;
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+    fn snapshot(state: &str, commission: Option<u32>) -> PoolSnapshot {
+        PoolSnapshot {
+            state: state.to_string(),
+            commission,
+        }
+    }
+
+    fn pool(previous: Option<PoolSnapshot>, snapshot: PoolSnapshot) -> Pool {
+        Pool {
+            pool_id: 12,
+            name: "Pool".to_string(),
+            member_count: 25,
+            snapshot,
+            previous,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn it_parses_the_pools_monitored() {
+        let pool = MonitoredPool::parse("12").unwrap();
+        assert_eq!((pool.pool_id, pool.member), (12, None));
+        let pool = MonitoredPool::parse(&format!("12:{}", ALICE)).unwrap();
+        assert_eq!(pool.member, Some(AccountId32::from_str(ALICE).unwrap()));
+        assert!(MonitoredPool::parse("pool").is_err());
+        assert!(MonitoredPool::parse("12:alice").is_err());
+    }
+
+    #[test]
+    fn it_derives_the_pool_accounts() {
+        let AccountId32(bonded) = pool_account(*b"py/nopls", POOL_BONDED_ACCOUNT, 12);
+        assert_eq!(&bonded[..12], b"modlpy/nopls");
+        assert_eq!(&bonded[12..17], &[0, 12, 0, 0, 0]);
+        assert!(bonded[17..].iter().all(|b| *b == 0));
+        assert_ne!(
+            pool_account(*b"py/nopls", POOL_BONDED_ACCOUNT, 13),
+            AccountId32(bonded)
+        );
+    }
+
+    #[test]
+    fn it_alerts_on_the_pool_transitions() {
+        let open = snapshot(POOL_STATE_OPEN, Some(50_000_000));
+        assert!(pool(None, open.clone()).alerts().is_empty());
+        assert!(pool(Some(open.clone()), open.clone()).alerts().is_empty());
+        assert_eq!(
+            pool(None, snapshot(POOL_STATE_BLOCKED, Some(50_000_000))).alerts(),
+            vec!["pool blocked"]
+        );
+        assert_eq!(
+            pool(
+                Some(open.clone()),
+                snapshot(POOL_STATE_BLOCKED, Some(100_000_000))
+            )
+            .alerts(),
+            vec!["pool blocked", "commission changed 5.00% -> 10.00%"]
+        );
+        let destroyed = snapshot(POOL_STATE_DESTROYED, None);
+        assert_eq!(
+            pool(Some(open), destroyed.clone()).alerts(),
+            vec!["pool destroyed"]
+        );
+        assert!(pool(Some(destroyed.clone()), destroyed).alerts().is_empty());
+    }
+
+    #[test]
+    fn it_summarizes_the_pool() {
+        let network = Network {
+            name: "polkadot".to_string(),
+            token_symbol: "DOT".to_string(),
+            token_decimals: 10,
+        };
+        let mut pool = pool(None, snapshot(POOL_STATE_OPEN, Some(50_000_000)));
+        pool.nomination = Some(Nomination {
+            name: "13UVJ…S9uj".to_string(),
+            era_index: 1200,
+            bonded: 1_000_000_000_000,
+            targets: vec![NominationTarget {
+                name: "A".to_string(),
+                is_active: true,
+                exposed_stake: Some(1_000_000_000_000),
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+        pool.member = Some(("Member".to_string(), 12_345_000_000));
+        assert!(!pool.is_critical());
+        let (message, _) = pool.message(&network);
+        assert_eq!(
+            message,
+            "Pool #12 Pool -> open with 25 member(s), commission 5.00%\n13UVJ…S9uj -> era 1200 nominating 1 validator(s), 100.00 DOT of 100.00 DOT bonded applied\nbacking: A (100.00 DOT)\npending rewards of Member: 1.2345 DOT"
+        );
+    }
+}
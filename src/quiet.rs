@@ -0,0 +1,187 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use chrono::{DateTime, NaiveTime, Utc};
+use chrono_tz::Tz;
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+/// Daily period, in the local time of the timezone defined, during which
+/// informational messages are not sent but summarized in a digest
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuietHours {
+    start: NaiveTime,
+    end: NaiveTime,
+    timezone: Tz,
+}
+
+impl QuietHours {
+    /// Parse the quiet hours range (e.g. 23:00-07:00) in the timezone defined (e.g.
+    /// Europe/Lisbon), ranges crossing midnight end on the next day
+    pub fn parse(range: &str, timezone: &str) -> Result<Self, String> {
+        let (start, end) = range
+            .split_once('-')
+            .ok_or_else(|| format!("{} is not a range (e.g. 23:00-07:00)", range))?;
+        let parse_time = |time: &str| {
+            NaiveTime::parse_from_str(time.trim(), "%H:%M")
+                .map_err(|_| format!("{} is not a time (e.g. 23:00)", time.trim()))
+        };
+        let (start, end) = (parse_time(start)?, parse_time(end)?);
+        if start == end {
+            return Err(format!("{} starts and ends at the same time", range));
+        }
+        let timezone = timezone.trim().parse::<Tz>().map_err(|_| {
+            format!("{} is not a timezone (e.g. Europe/Lisbon)", timezone)
+        })?;
+        Ok(Self {
+            start,
+            end,
+            timezone,
+        })
+    }
+
+    /// Returns true if the time is within the quiet hours. The time is converted to
+    /// the local time of the timezone, so that quiet hours follow the DST transitions
+    /// as a wall clock does.
+    pub fn contains(&self, now: DateTime<Utc>) -> bool {
+        let time = now.with_timezone(&self.timezone).time();
+        if self.start < self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+impl std::fmt::Display for QuietHours {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}-{} {}",
+            self.start.format("%H:%M"),
+            self.end.format("%H:%M"),
+            self.timezone
+        )
+    }
+}
+
+/// Number of informational messages suppressed during quiet hours by event
+#[derive(Debug, Clone, Default)]
+pub struct QuietDigest(Arc<Mutex<BTreeMap<String, u32>>>);
+
+impl QuietDigest {
+    pub fn add(&self, event: &str) {
+        *self.0.lock().unwrap().entry(event.to_string()).or_default() += 1;
+    }
+
+    /// Returns the messages suppressed so far and start over the digest
+    pub fn take(&self) -> BTreeMap<String, u32> {
+        std::mem::take(&mut self.0.lock().unwrap())
+    }
+
+    /// Returns the plain and formatted message summarizing the messages suppressed,
+    /// instead of replaying every message
+    pub fn message(
+        quiet_hours: &QuietHours,
+        suppressed: &BTreeMap<String, u32>,
+    ) -> (String, String) {
+        let total: u32 = suppressed.values().sum();
+        let events = suppressed
+            .iter()
+            .map(|(event, count)| format!("{} {}", count, event.replace('_', " ")))
+            .collect::<Vec<String>>()
+            .join(", ");
+        (
+            format!(
+                "Quiet hours ({}) ended -> {} informational message(s) suppressed: {}",
+                quiet_hours, total, events
+            ),
+            format!(
+                "<br/>🌅 Quiet hours (<code>{}</code>) ended -> {} informational message(s) suppressed: {}<br/><br/>",
+                quiet_hours, total, events
+            ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(time)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn it_parses_the_quiet_hours() {
+        let quiet_hours = QuietHours::parse("23:00-07:00", "Europe/Lisbon").unwrap();
+        assert_eq!(quiet_hours.to_string(), "23:00-07:00 Europe/Lisbon");
+        assert!(QuietHours::parse("23:00", "UTC").is_err());
+        assert!(QuietHours::parse("23:00-7h", "UTC").is_err());
+        assert!(QuietHours::parse("07:00-07:00", "UTC").is_err());
+        assert!(QuietHours::parse("23:00-07:00", "Europe/Nowhere").is_err());
+    }
+
+    #[test]
+    fn it_handles_ranges_crossing_midnight() {
+        let quiet_hours = QuietHours::parse("23:00-07:00", "UTC").unwrap();
+        assert!(quiet_hours.contains(utc("2024-01-10T23:00:00Z")));
+        assert!(quiet_hours.contains(utc("2024-01-11T03:00:00Z")));
+        assert!(!quiet_hours.contains(utc("2024-01-11T07:00:00Z")));
+        assert!(!quiet_hours.contains(utc("2024-01-11T12:00:00Z")));
+
+        let quiet_hours = QuietHours::parse("01:00-05:00", "UTC").unwrap();
+        assert!(quiet_hours.contains(utc("2024-01-11T01:30:00Z")));
+        assert!(!quiet_hours.contains(utc("2024-01-11T23:30:00Z")));
+    }
+
+    #[test]
+    fn it_follows_the_dst_transitions() {
+        let quiet_hours = QuietHours::parse("23:00-07:00", "Europe/Lisbon").unwrap();
+        // Winter time is UTC+0 and summer time is UTC+1
+        assert!(quiet_hours.contains(utc("2024-01-11T06:30:00Z")));
+        assert!(!quiet_hours.contains(utc("2024-07-11T06:30:00Z")));
+        assert!(quiet_hours.contains(utc("2024-07-11T22:30:00Z")));
+        // Night of the spring forward transition, 01:00 UTC becomes 02:00 local time
+        assert!(quiet_hours.contains(utc("2024-03-31T05:30:00Z")));
+        assert!(!quiet_hours.contains(utc("2024-03-31T06:00:00Z")));
+    }
+
+    #[test]
+    fn it_summarizes_the_messages_suppressed() {
+        let quiet_hours = QuietHours::parse("23:00-07:00", "UTC").unwrap();
+        let digest = QuietDigest::default();
+        digest.add("new_session");
+        digest.add("new_session");
+        digest.add("era_paid");
+        let suppressed = digest.take();
+        assert!(digest.take().is_empty());
+        let (message, _) = QuietDigest::message(&quiet_hours, &suppressed);
+        assert_eq!(
+            message,
+            "Quiet hours (23:00-07:00 UTC) ended -> 3 informational message(s) suppressed: 1 era paid, 2 new session"
+        );
+    }
+}
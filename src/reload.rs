@@ -0,0 +1,146 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::config::{ConfigReload, CONFIG};
+use async_signal::{Signal, Signals};
+use async_std::task;
+use futures::StreamExt;
+use lazy_static::lazy_static;
+use log::{error, info, warn};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Mutex,
+};
+
+static GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+lazy_static! {
+    /// Message with the result of the last configuration reload
+    static ref LAST_MESSAGE: Mutex<(String, String)> = Mutex::new(Default::default());
+}
+
+/// Returns the number of configuration reloads since startup
+pub fn generation() -> usize {
+    GENERATION.load(Ordering::SeqCst)
+}
+
+/// Returns the plain and formatted message of the last configuration reload
+pub fn last_message() -> (String, String) {
+    LAST_MESSAGE.lock().unwrap().clone()
+}
+
+/// Returns the plain and formatted message of the result of a configuration reload
+fn message(result: &Result<ConfigReload, String>) -> (String, String) {
+    match result {
+        Ok(reload) if reload.applied.is_empty() && reload.ignored.is_empty() => (
+            "Configuration reloaded -> no changes".to_string(),
+            "<br/>🔄 Configuration reloaded -> no changes<br/><br/>".to_string(),
+        ),
+        Ok(reload) => {
+            let mut message = "Configuration reloaded".to_string();
+            let mut formatted_message = "<br/>🔄 Configuration reloaded".to_string();
+            if !reload.applied.is_empty() {
+                message.push_str(&format!(" -> applied: {}", reload.applied.join(", ")));
+                formatted_message.push_str(&format!(
+                    " -> applied: <code>{}</code>",
+                    reload.applied.join(", ")
+                ));
+            }
+            if !reload.ignored.is_empty() {
+                message.push_str(&format!(
+                    " -> restart needed: {}",
+                    reload.ignored.join(", ")
+                ));
+                formatted_message.push_str(&format!(
+                    " -> restart needed: <code>{}</code>",
+                    reload.ignored.join(", ")
+                ));
+            }
+            formatted_message.push_str("<br/><br/>");
+            (message, formatted_message)
+        }
+        Err(e) => (
+            format!("Configuration not reloaded -> {}", e),
+            format!("<br/>⚠️ Configuration not reloaded -> {}<br/><br/>", e),
+        ),
+    }
+}
+
+/// Reload the configuration on every SIGHUP, the fields that can not be applied live
+/// are logged as needing a restart
+pub fn listen() {
+    let mut signals = match Signals::new([Signal::Hup]) {
+        Ok(signals) => signals,
+        Err(e) => {
+            error!("Signal handler could not be registered: {}", e);
+            return;
+        }
+    };
+    task::spawn(async move {
+        while let Some(signal) = signals.next().await {
+            info!("{:?} received -> reloading configuration..", signal);
+            let result = CONFIG.reload().map_err(|e| e.to_string());
+            match &result {
+                Ok(reload) => {
+                    if !reload.applied.is_empty() {
+                        info!("Configuration applied: {}", reload.applied.join(", "));
+                    }
+                    if !reload.ignored.is_empty() {
+                        warn!(
+                            "Configuration ignored: {} -> restart needed",
+                            reload.ignored.join(", ")
+                        );
+                    }
+                }
+                Err(e) => error!("Configuration not reloaded! {}", e),
+            }
+            *LAST_MESSAGE.lock().unwrap() = message(&result);
+            GENERATION.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_builds_the_message_of_a_reload() {
+        let reload = ConfigReload {
+            applied: vec!["stashes".to_string(), "mute_all".to_string()],
+            ignored: vec!["substrate_ws_url".to_string()],
+        };
+        let (plain, formatted) = message(&Ok(reload));
+        assert_eq!(
+            plain,
+            "Configuration reloaded -> applied: stashes, mute_all -> restart needed: substrate_ws_url"
+        );
+        assert!(formatted.contains("<code>stashes, mute_all</code>"));
+        assert_eq!(
+            message(&Ok(ConfigReload::default())).0,
+            "Configuration reloaded -> no changes"
+        );
+        assert_eq!(
+            message(&Err("interval: invalid".to_string())).0,
+            "Configuration not reloaded -> interval: invalid"
+        );
+    }
+}
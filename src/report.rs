@@ -18,9 +18,27 @@
 // LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
+use crate::authority::AuthoredBlocks;
+use crate::backing::{ParaAssignment, SessionBacking};
+use crate::catch_up::{CatchUp, MissedEra};
 use crate::config::CONFIG;
+use crate::era_digest::EraDigest;
+use crate::era_points::EraPointsTrend;
 use crate::errors::ScoutyError;
-use crate::hooks::Hook;
+use crate::finality::FinalityStatus;
+use crate::health::HealthStatus;
+use crate::hooks::{
+    Hook, HOOK_VALIDATOR_STARTS_ACTIVE_NEXT_ERA, HOOK_VALIDATOR_STARTS_INACTIVE_NEXT_ERA,
+};
+use crate::identity;
+use crate::matrix::Severity;
+use crate::minimums::StakingMinimums;
+use crate::nominators::{Nominator, NominatorChanges};
+use crate::notifier::{Notification, EVENT_MESSAGE};
+use crate::payouts::UnclaimedEra;
+use crate::templates::{self, Template, Variables};
+use crate::waiting::WaitingPosition;
+use async_std::channel::Receiver;
 use log::info;
 use serde::Deserialize;
 use std::{convert::TryInto, result::Result};
@@ -29,10 +47,11 @@ use subxt::{backend::legacy::LegacyRpcMethods, utils::AccountId32, PolkadotConfi
 #[derive(Debug, Default)]
 pub struct Init {
     pub block_number: u32,
+    #[allow(dead_code)]
     pub now: u64,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct Network {
     pub name: String,
     pub token_symbol: String,
@@ -69,22 +88,207 @@ impl Network {
             token_decimals,
         })
     }
+
+    /// Returns the amount in tokens, abbreviated in thousands or millions, with the
+    /// token symbol of the network (e.g. 12.3k DOT)
+    pub fn format_amount(&self, value: u128) -> String {
+        let amount = value as f64 / 10f64.powi(self.token_decimals.into());
+        if amount >= 1_000_000.0 {
+            format!("{:.1}M {}", amount / 1_000_000.0, self.token_symbol)
+        } else if amount >= 1_000.0 {
+            format!("{:.1}k {}", amount / 1_000.0, self.token_symbol)
+        } else {
+            format!("{:.2} {}", amount, self.token_symbol)
+        }
+    }
+
+    /// Returns the amount in tokens with four decimals and the token symbol of the
+    /// network (e.g. 12.3456 DOT)
+    pub fn format_tokens(&self, value: u128) -> String {
+        format!(
+            "{:.4} {}",
+            value as f64 / 10f64.powi(self.token_decimals.into()),
+            self.token_symbol
+        )
+    }
+
+    /// Returns the amount in tokens converted to plancks
+    pub fn to_plancks(&self, amount: f64) -> u128 {
+        (amount * 10f64.powi(self.token_decimals.into())) as u128
+    }
+
+    /// Returns the difference in tokens with its sign (e.g. -1.2k DOT)
+    pub fn format_delta(&self, delta: i128) -> String {
+        let sign = if delta < 0 { "-" } else { "+" };
+        format!("{}{}", sign, self.format_amount(delta.unsigned_abs()))
+    }
+}
+
+/// Stake backing a validator in an era, the nominators of every exposure page included
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Stake {
+    pub own: u128,
+    pub total: u128,
+    pub nominator_count: u32,
+}
+
+/// Stake backing a validator at the start of an era, compared with the previous era
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct StakeChange {
+    pub current: Stake,
+    /// Not defined if the validator was not active in the previous era
+    pub previous: Option<Stake>,
+    /// Lowest total stake backing a validator elected in the era, only defined if the
+    /// stake margin is verified
+    pub min_elected_stake: Option<u128>,
+}
+
+impl StakeChange {
+    pub fn own_delta(&self) -> Option<i128> {
+        self.previous
+            .map(|previous| self.current.own as i128 - previous.own as i128)
+    }
+
+    pub fn total_delta(&self) -> Option<i128> {
+        self.previous
+            .map(|previous| self.current.total as i128 - previous.total as i128)
+    }
+
+    pub fn nominator_count_delta(&self) -> Option<i64> {
+        self.previous.map(|previous| {
+            self.current.nominator_count as i64 - previous.nominator_count as i64
+        })
+    }
+
+    /// Returns how far above the minimum elected stake the total backing is, in
+    /// percentage of the minimum elected stake
+    pub fn margin_percentage(&self) -> Option<f64> {
+        match self.min_elected_stake {
+            Some(min) if min > 0 => {
+                Some((self.current.total as f64 - min as f64) * 100.0 / min as f64)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns true if the margin above the minimum elected stake is below `threshold`
+    /// (in percentage), a threshold of 0 never flags the margin
+    pub fn is_margin_below(&self, threshold: u32) -> bool {
+        threshold > 0
+            && self
+                .margin_percentage()
+                .is_some_and(|margin| margin < threshold as f64)
+    }
 }
 
 #[derive(Debug)]
 pub struct Points {
     pub validator: u32,
     pub era_avg: f64,
+    #[allow(dead_code)]
     pub ci99_9_interval: (f64, f64),
+    #[allow(dead_code)]
     pub outlier_limits: (f64, f64),
 }
 
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Deserialize, Default, Clone)]
 pub struct Session {
     pub active_era_index: u32,
     pub current_session_index: u32,
     pub eras_session_index: u32,
     pub queued_session_keys_changed: bool,
+    /// First session index of the active era
+    #[serde(default)]
+    pub era_start_session_index: u32,
+    /// Start of the active era (unix timestamp in seconds)
+    #[serde(default)]
+    pub era_start: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct Reward {
+    pub points: u32,
+    /// Estimated reward (in plancks) from the era points, including nominators share
+    pub amount_value: u128,
+    /// Reward (in plancks) earned by the stash itself, the commission plus the share
+    /// of its own stake
+    pub stash_amount_value: u128,
+    /// Rank by era points within the validators that earned points in the era
+    pub rank: Option<u32>,
+    pub is_claimed: bool,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Prefs {
+    /// Commission in parts per billion
+    pub commission: u32,
+    pub blocked: bool,
+}
+
+/// Range of the commission allowed by the policy, in parts per billion
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct CommissionPolicy {
+    pub min: u32,
+    pub max: u32,
+}
+
+impl CommissionPolicy {
+    pub fn contains(&self, commission: u32) -> bool {
+        commission >= self.min && commission <= self.max
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct PrefsChange {
+    pub previous: Prefs,
+    pub current: Prefs,
+}
+
+/// Reward destination of a stash, with the account the rewards are paid to if it is
+/// not the stash or the controller
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Payee {
+    pub destination: String,
+    pub account: Option<AccountId32>,
+}
+
+/// Reward destinations of a stash before and after a change, described with the
+/// accounts and their identities
+#[derive(Debug, Default, Clone)]
+pub struct PayeeChange {
+    pub previous: String,
+    pub current: String,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Payout {
+    pub era_index: u32,
+    /// Hash of the finalized block that included the payout
+    pub block_hash: String,
+    pub fee: u128,
+    /// Sum of the rewards paid out to the stash and its nominators
+    pub amount: u128,
+    /// Reason of the payout failure, if any
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct DisputeVote {
+    pub candidate_hash: String,
+    /// Session index of the candidate disputed
+    pub session_index: u32,
+    pub is_valid: bool,
+}
+
+impl DisputeVote {
+    /// Returns the direction of the vote, for or against the candidate
+    pub fn direction(&self) -> &str {
+        if self.is_valid {
+            "for"
+        } else {
+            "against"
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -93,6 +297,8 @@ pub struct Validator {
     #[serde(default)]
     pub name: String,
     #[serde(default)]
+    pub has_identity: bool,
+    #[serde(default)]
     pub is_active: bool,
     #[serde(default)]
     pub is_queued: bool,
@@ -105,20 +311,179 @@ pub struct Validator {
     #[serde(default)]
     pub is_offline: bool,
     #[serde(default)]
+    pub is_offender: bool,
+    #[serde(default)]
+    pub is_disabled: bool,
+    #[serde(default)]
+    pub is_session_keys_mismatch: bool,
+    /// Points collected so far in the active era
+    #[serde(skip)]
+    pub era_points: u32,
+    /// Points collected so far compared with the era average and the previous session,
+    /// only defined for the active stashes at a new session
+    #[serde(skip)]
+    pub era_points_trend: Option<EraPointsTrend>,
+    /// Blocks authored by the stash in the previous session, if it was an authority
+    #[serde(skip)]
+    pub authored_blocks: Option<AuthoredBlocks>,
+    /// Para validator group of the stash in the current session
+    #[serde(skip)]
+    pub para_assignment: Option<ParaAssignment>,
+    /// Backing votes of the stash in the previous session, if it was a para validator
+    #[serde(skip)]
+    pub backing: Option<SessionBacking>,
+    /// Reason inferred for the stash not being active in the next era
+    #[serde(skip)]
+    pub inactive_next_era_reason: String,
+    /// Stake backing the stash at the start of a new era
+    #[serde(skip)]
+    pub stake_change: Option<StakeChange>,
+    /// Position in the validators waiting to be elected, at the start of a new era
+    #[serde(skip)]
+    pub waiting_position: Option<WaitingPosition>,
+    /// Nominators that left or arrived since the previous era
+    #[serde(skip)]
+    pub nominator_changes: Option<NominatorChanges>,
+    /// Commission of the stash (in parts per billion) and the range allowed by the
+    /// policy, once the commission falls outside or back within the range
+    #[serde(skip)]
+    pub commission_policy: Option<(u32, CommissionPolicy)>,
+    /// Payouts submitted for the unclaimed eras
+    #[serde(skip)]
+    pub payouts: Vec<Payout>,
+    /// Eras not claimed yet, if the oldest is older than the threshold
+    #[serde(skip)]
+    pub unclaimed_eras: Vec<UnclaimedEra>,
+    #[serde(skip)]
+    pub reward: Reward,
+    #[serde(skip)]
+    pub prefs_change: PrefsChange,
+    /// Reward destination before and after a change
+    #[serde(skip)]
+    pub payee_change: Option<PayeeChange>,
+    #[serde(skip)]
+    pub dispute_votes: Vec<DisputeVote>,
+    /// Summary of the eras missed while scouty was down
+    #[serde(skip)]
+    pub missed_eras: Vec<MissedEra>,
+    /// Summary of the sessions of the previous era, at a new era
+    #[serde(skip)]
+    pub era_digest: Option<EraDigest>,
+    #[serde(default)]
     pub hooks: Vec<Hook>,
 }
 
 impl Validator {
+    /// Returns the identity followed by the short address of the stash (e.g.
+    /// TURBOFLAKES.IO/MOMO (F3opx…jRbN5)), or only the short address without identity
+    pub fn display_name(&self) -> String {
+        let identity = Some(self.name.as_str()).filter(|_| self.has_identity);
+        identity::display_name(identity, &self.stash)
+    }
+
+    /// Returns true if the session of the stash is worth a warning, i.e. era points
+    /// below average, no blocks authored, stake margin or missed votes beyond the
+    /// thresholds
+    pub fn has_session_warnings(&self) -> bool {
+        !self.session_warnings().is_empty()
+    }
+
+    /// Returns the warnings of the session of the stash
+    pub fn session_warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self
+            .era_points_trend
+            .as_ref()
+            .is_some_and(|trend| trend.is_below_average())
+        {
+            warnings.push("Era points below the era average".to_string());
+        }
+        if let Some(blocks) = self.authored_blocks.as_ref().filter(|b| b.count == 0) {
+            warnings.push(format!(
+                "No blocks authored in session {}",
+                blocks.session_index
+            ));
+        }
+        if self.stake_change.as_ref().is_some_and(|change| {
+            change.is_margin_below(CONFIG.current().stake_margin_threshold)
+        }) {
+            warnings.push(format!(
+                "Total backing less than {}% above the minimum elected stake",
+                CONFIG.current().stake_margin_threshold
+            ));
+        }
+        if let Some(backing) = self
+            .backing
+            .as_ref()
+            .filter(|b| b.votes.is_above(CONFIG.current().missed_votes_threshold))
+        {
+            warnings.push(format!(
+                "Backing votes missed above {}% in session {}",
+                CONFIG.current().missed_votes_threshold,
+                backing.session_index
+            ));
+        }
+        warnings
+    }
+
+    /// Returns the variables available in the validator templates, the output lines
+    /// of the hooks are joined by the separator
+    fn template_variables(&self, separator: &str, network: &Network) -> Variables {
+        let hook_output: Vec<String> = self
+            .hooks
+            .iter()
+            .flat_map(|hook| hook.matrix_lines())
+            .collect();
+        let mut variables = Variables::new();
+        variables.insert("stash", self.stash.to_string());
+        variables.insert("identity", self.name.to_string());
+        variables.insert("display_name", self.display_name());
+        variables.insert(
+            "explorer_url",
+            explorer_url(network, "validator", &self.stash.to_string()),
+        );
+        variables.insert("is_active", self.is_active.to_string());
+        variables.insert("is_queued", self.is_queued.to_string());
+        variables.insert(
+            "inactive_next_era_reason",
+            self.inactive_next_era_reason.to_string(),
+        );
+        variables.insert("hook_output", hook_output.join(separator));
+        variables
+    }
+
     pub fn new(stash: AccountId32) -> Self {
         Self {
             stash,
             name: "".to_string(),
+            has_identity: false,
             is_active: false,
             is_queued: false,
             queued_session_keys: Vec::new(),
             is_slashed: false,
             is_chilled: false,
             is_offline: false,
+            is_offender: false,
+            is_disabled: false,
+            is_session_keys_mismatch: false,
+            era_points: 0,
+            era_points_trend: None,
+            authored_blocks: None,
+            para_assignment: None,
+            backing: None,
+            inactive_next_era_reason: "".to_string(),
+            stake_change: None,
+            waiting_position: None,
+            nominator_changes: None,
+            commission_policy: None,
+            payouts: Vec::new(),
+            unclaimed_eras: Vec::new(),
+            reward: Reward::default(),
+            prefs_change: PrefsChange::default(),
+            payee_change: None,
+            dispute_votes: Vec::new(),
+            missed_eras: Vec::new(),
+            era_digest: None,
             hooks: Vec::new(),
         }
     }
@@ -138,27 +503,117 @@ pub struct Referendum {
 
 #[derive(Debug, Deserialize, Default)]
 pub struct Slash {
+    #[allow(dead_code)]
     pub who: Option<AccountId32>,
     #[serde(default)]
     pub amount_value: u128,
     #[serde(default)]
+    pub era_index: u32,
+    /// Number of slashed validators other than the stashes predefined
+    #[serde(default)]
+    pub others_slashed: u32,
+    /// Kind of the offences reported (e.g. im-online:offlin)
+    #[serde(default)]
+    pub offences: Vec<String>,
+    #[serde(default)]
+    pub hook: Hook,
+}
+
+#[derive(Debug, Default)]
+pub struct EraPayout {
+    pub era_index: u32,
+    pub validator_payout: u128,
+    pub total_points: u32,
+    /// Number of validators that earned points in the era
+    pub validator_count: u32,
+}
+
+#[derive(Debug, Default)]
+pub struct Chill {
+    /// Reason of the chill, inferred from the event that triggered it
+    pub reason: String,
+}
+
+#[derive(Debug, Default)]
+pub struct OfflineRisk {
+    /// Percentage of the session elapsed when the risk was detected
+    pub session_progress: u32,
+}
+
+#[derive(Debug, Default)]
+pub struct Health {
+    pub url: String,
+    pub peers: usize,
+    pub is_syncing: bool,
+    pub status: HealthStatus,
+    pub hook: Hook,
+}
+
+#[derive(Debug, Default)]
+pub struct Finality {
+    pub best_block_number: u32,
+    pub best_block_hash: String,
+    pub finalized_block_number: u32,
+    pub finalized_block_hash: String,
+    pub status: FinalityStatus,
+    pub hook: Hook,
+}
+
+#[derive(Debug, Default)]
+pub struct RuntimeUpgrade {
+    pub previous_spec_version: u32,
+    pub spec_version: u32,
+    /// Block number at which the runtime upgrade was observed
+    pub block_number: u32,
     pub hook: Hook,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// Free balance of an account watched, compared with the minimum free balance defined
+#[derive(Debug, Default)]
+pub struct WatchedBalance {
+    pub account: String,
+    pub identity: Option<String>,
+    pub free: u128,
+    pub min_free_balance: u128,
+    pub hooks: Vec<Hook>,
+}
+
+impl WatchedBalance {
+    pub fn is_low(&self) -> bool {
+        self.free < self.min_free_balance
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum Section {
     Init,
+    #[default]
     Session,
+    SessionBatch,
+    EraPaid,
     Slash,
+    SlashSummary,
+    Offence,
     Chill,
+    #[allow(dead_code)]
     Offline,
+    OfflineRisk,
+    SessionKeys,
+    ValidatorPrefs,
+    CommissionPolicy,
+    ValidatorDisabled,
+    PayeeChanged,
+    LowBalance,
+    CatchUp,
+    EraDigest,
+    Nominators,
+    Payout,
+    UnclaimedEras,
+    Dispute,
     Referenda,
-}
-
-impl Default for Section {
-    fn default() -> Self {
-        Section::Session
-    }
+    Health,
+    Finality,
+    RuntimeUpgrade,
 }
 
 #[derive(Default)]
@@ -169,23 +624,359 @@ pub struct RawData {
     pub session: Session,
     pub referendum: Referendum,
     pub slash: Slash,
+    pub chill: Chill,
+    pub era_paid: EraPayout,
+    pub offline_risk: OfflineRisk,
+    pub health: Health,
+    pub finality: Finality,
+    pub runtime_upgrade: RuntimeUpgrade,
+    pub balances: Vec<WatchedBalance>,
+    pub catch_up: CatchUp,
+    /// Staking minimums, only at a new era
+    pub minimums: Option<StakingMinimums>,
     pub section: Section,
 }
 
+impl RawData {
+    /// Returns true if every hook script run for the event exited with the suppress
+    /// exit code or is still running in the background, hooks not defined are ignored
+    pub fn is_suppressed(&self) -> bool {
+        let hooks = self.hooks();
+        !hooks.is_empty()
+            && hooks
+                .iter()
+                .all(|hook| hook.is_suppressed || hook.suppression.is_some())
+    }
+
+    /// Returns the suppress decisions of the hook scripts still running in the
+    /// background
+    fn suppressions(&self) -> Vec<Receiver<bool>> {
+        self.hooks()
+            .iter()
+            .filter_map(|hook| hook.suppression.clone())
+            .collect()
+    }
+
+    /// Returns the hook scripts defined that were run for the event
+    fn hooks(&self) -> Vec<&Hook> {
+        self.validators
+            .iter()
+            .flat_map(|v| v.hooks.iter())
+            .chain(
+                self.balances
+                    .iter()
+                    .flat_map(|balance| balance.hooks.iter()),
+            )
+            .chain(vec![
+                &self.referendum.hook,
+                &self.slash.hook,
+                &self.health.hook,
+                &self.finality.hook,
+                &self.runtime_upgrade.hook,
+            ])
+            .filter(|hook| hook.filename_exists)
+            .collect()
+    }
+
+    /// Returns true if the event is still notified while alerts are muted, unless
+    /// `mute_all` is set
+    pub fn is_mute_exempt(&self) -> bool {
+        matches!(
+            self.section,
+            Section::Slash | Section::Offence | Section::Dispute
+        )
+    }
+
+    /// Returns true if the hook has been run for any of the validators
+    fn has_hook(&self, name: &str) -> bool {
+        self.validators
+            .iter()
+            .any(|v| v.hooks.iter().any(|hook| hook.name == name))
+    }
+
+    /// Returns true if the era points of any stash have been below the threshold of the
+    /// era average for more than one consecutive session
+    fn has_era_points_below_average(&self) -> bool {
+        self.validators.iter().any(|v| {
+            v.era_points_trend
+                .as_ref()
+                .is_some_and(|trend| trend.is_below_average())
+        })
+    }
+
+    /// Returns true if the total backing of any stash is close to the minimum elected
+    /// stake, below the margin threshold
+    fn has_stake_margin_below_threshold(&self) -> bool {
+        self.validators.iter().any(|v| {
+            v.stake_change.as_ref().is_some_and(|change| {
+                change.is_margin_below(CONFIG.current().stake_margin_threshold)
+            })
+        })
+    }
+
+    /// Returns true if any stash did not author a single block during the previous
+    /// session while being an authority
+    fn has_no_authored_blocks(&self) -> bool {
+        self.validators.iter().any(|v| {
+            v.authored_blocks
+                .as_ref()
+                .is_some_and(|blocks| blocks.count == 0)
+        })
+    }
+
+    /// Returns true if any stash missed more backing votes than the threshold in the
+    /// previous session
+    fn has_missed_votes_above_threshold(&self) -> bool {
+        self.validators.iter().any(|v| {
+            v.backing.as_ref().is_some_and(|backing| {
+                backing
+                    .votes
+                    .is_above(CONFIG.current().missed_votes_threshold)
+            })
+        })
+    }
+
+    /// Returns true if a large nominator left any of the stashes or the total backing
+    /// dropped above the threshold
+    fn has_nominator_losses(&self) -> bool {
+        self.validators.iter().any(|v| {
+            v.nominator_changes
+                .as_ref()
+                .is_some_and(|changes| changes.is_critical())
+        })
+    }
+
+    /// Returns true if any of the payouts submitted failed
+    fn has_payout_errors(&self) -> bool {
+        self.validators
+            .iter()
+            .any(|v| v.payouts.iter().any(|payout| payout.error.is_some()))
+    }
+
+    /// Returns the name of the template of the event
+    pub fn template_key(&self) -> &'static str {
+        match self.section {
+            Section::Init => templates::TEMPLATE_INIT,
+            Section::Session | Section::SessionBatch => {
+                if self.has_hook(HOOK_VALIDATOR_STARTS_INACTIVE_NEXT_ERA) {
+                    templates::TEMPLATE_INACTIVE_NEXT_ERA
+                } else if self.has_hook(HOOK_VALIDATOR_STARTS_ACTIVE_NEXT_ERA) {
+                    templates::TEMPLATE_ACTIVE_NEXT_ERA
+                } else {
+                    templates::TEMPLATE_NEW_SESSION
+                }
+            }
+            Section::EraPaid => templates::TEMPLATE_ERA_PAID,
+            Section::Slash => templates::TEMPLATE_SLASH,
+            Section::SlashSummary => templates::TEMPLATE_SLASH_SUMMARY,
+            Section::Offence => templates::TEMPLATE_OFFENCE,
+            Section::Chill => templates::TEMPLATE_CHILL,
+            Section::Offline => templates::TEMPLATE_OFFLINE,
+            Section::OfflineRisk => templates::TEMPLATE_OFFLINE_RISK,
+            Section::SessionKeys => templates::TEMPLATE_SESSION_KEYS,
+            Section::ValidatorPrefs => templates::TEMPLATE_VALIDATOR_PREFS,
+            Section::CommissionPolicy => templates::TEMPLATE_COMMISSION_POLICY,
+            Section::ValidatorDisabled => templates::TEMPLATE_VALIDATOR_DISABLED,
+            Section::PayeeChanged => templates::TEMPLATE_PAYEE_CHANGED,
+            Section::LowBalance => templates::TEMPLATE_LOW_BALANCE,
+            Section::CatchUp => templates::TEMPLATE_CATCH_UP,
+            Section::EraDigest => templates::TEMPLATE_ERA_DIGEST,
+            Section::Nominators => templates::TEMPLATE_NOMINATORS,
+            Section::Payout => templates::TEMPLATE_PAYOUT,
+            Section::UnclaimedEras => templates::TEMPLATE_UNCLAIMED_ERAS,
+            Section::Dispute => templates::TEMPLATE_DISPUTE,
+            Section::Referenda => templates::TEMPLATE_REFERENDA,
+            Section::Health => templates::TEMPLATE_NODE_HEALTH,
+            Section::Finality => templates::TEMPLATE_FINALITY,
+            Section::RuntimeUpgrade => templates::TEMPLATE_RUNTIME_UPGRADE,
+        }
+    }
+
+    /// Render the message and formatted message of the template, variants that are not
+    /// defined are returned as None
+    fn render_template(&self, template: &Template) -> (Option<String>, Option<String>) {
+        let era_index = match self.section {
+            Section::EraPaid => self.era_paid.era_index,
+            _ => self.session.active_era_index,
+        };
+        let render = |text: &Option<String>,
+                      validator: &Option<String>,
+                      separator: &str| {
+            text.as_ref().map(|text| {
+                let validators: Vec<String> = self
+                    .validators
+                    .iter()
+                    .map(|v| match validator {
+                        Some(validator) => templates::render(
+                            validator,
+                            &v.template_variables(separator, &self.network),
+                        ),
+                        None => v.name.to_string(),
+                    })
+                    .collect();
+                let mut variables = Variables::new();
+                variables.insert("version", env!("CARGO_PKG_VERSION").to_string());
+                variables.insert("chain", self.network.name.to_string());
+                variables.insert("era", era_index.to_string());
+                variables
+                    .insert("session", self.session.current_session_index.to_string());
+                variables.insert("validators", validators.join(separator));
+                templates::render(text, &variables)
+            })
+        };
+        (
+            render(&template.message, &template.validator, "\n"),
+            render(
+                &template.formatted_message,
+                &template.formatted_validator,
+                "<br/>",
+            ),
+        )
+    }
+
+    /// Returns the severity of the event, sessions are only critical if a validator
+    /// is not going to be active in the next era
+    pub fn severity(&self) -> Severity {
+        match self.section {
+            Section::Slash
+            | Section::Offence
+            | Section::Chill
+            | Section::Offline
+            | Section::OfflineRisk
+            | Section::SessionKeys
+            | Section::CommissionPolicy
+            | Section::PayeeChanged
+            | Section::Dispute
+            | Section::UnclaimedEras
+            | Section::Health
+            | Section::Finality => Severity::Critical,
+            Section::Session
+                if self.has_hook(HOOK_VALIDATOR_STARTS_INACTIVE_NEXT_ERA) =>
+            {
+                Severity::Critical
+            }
+            Section::Session | Section::SessionBatch
+                if self.has_era_points_below_average() =>
+            {
+                Severity::Critical
+            }
+            Section::Session | Section::SessionBatch if self.has_no_authored_blocks() => {
+                Severity::Critical
+            }
+            Section::Session | Section::SessionBatch
+                if self.has_stake_margin_below_threshold() =>
+            {
+                Severity::Critical
+            }
+            Section::Session | Section::SessionBatch
+                if self.has_missed_votes_above_threshold() =>
+            {
+                Severity::Critical
+            }
+            Section::ValidatorDisabled
+                if self.validators.iter().any(|v| v.is_disabled) =>
+            {
+                Severity::Critical
+            }
+            Section::LowBalance if self.balances.iter().any(|b| b.is_low()) => {
+                Severity::Critical
+            }
+            Section::CatchUp
+                if self
+                    .validators
+                    .iter()
+                    .any(|v| v.missed_eras.iter().any(|era| era.slashed.is_some())) =>
+            {
+                Severity::Critical
+            }
+            Section::EraDigest
+                if self.validators.iter().any(|v| {
+                    v.era_digest
+                        .as_ref()
+                        .is_some_and(|digest| !digest.warnings.is_empty())
+                }) =>
+            {
+                Severity::Critical
+            }
+            Section::Nominators if self.has_nominator_losses() => Severity::Critical,
+            Section::Payout if self.has_payout_errors() => Severity::Critical,
+            _ => Severity::Info,
+        }
+    }
+}
+
 type Body = Vec<String>;
 
 pub struct Report {
     body: Body,
     is_short: bool,
+    is_suppressed: bool,
+    /// Suppress decisions of the hook scripts still running in the background
+    suppressions: Vec<Receiver<bool>>,
+    is_mute_exempt: bool,
+    severity: Severity,
+    /// Messages rendered from the templates, in place of the body
+    templated_message: Option<String>,
+    templated_formatted_message: Option<String>,
+    /// Context of the event reported, delivered to the notifiers
+    event: String,
+    stashes: Vec<String>,
+    session_index: Option<u32>,
+    era_index: Option<u32>,
 }
 
 impl Report {
     pub fn new() -> Report {
-        let config = CONFIG.clone();
+        let config = CONFIG.current();
         Report {
             body: Vec::new(),
             is_short: config.is_short,
+            is_suppressed: false,
+            suppressions: Vec::new(),
+            is_mute_exempt: false,
+            severity: Severity::Info,
+            templated_message: None,
+            templated_formatted_message: None,
+            event: EVENT_MESSAGE.to_string(),
+            stashes: Vec::new(),
+            session_index: None,
+            era_index: None,
+        }
+    }
+
+    /// Returns true if the hook scripts handled the event themselves, in which case
+    /// the matrix message is not sent. The hook scripts still running in the
+    /// background are awaited to finish, hook scripts that fail are never suppressed.
+    pub async fn is_suppressed(&self) -> bool {
+        if !self.is_suppressed {
+            return false;
+        }
+        for suppression in self.suppressions.iter() {
+            if !suppression.recv().await.unwrap_or_default() {
+                return false;
+            }
         }
+        true
+    }
+
+    /// Returns true if the message is still sent while alerts are muted
+    pub fn is_mute_exempt(&self) -> bool {
+        self.is_mute_exempt
+    }
+
+    /// Returns the notification of the report, with the context of the event reported
+    pub fn notification(&self, chain: &str) -> Notification {
+        let mut notification = Notification::new(
+            &self.event,
+            chain,
+            self.severity,
+            &self.message(),
+            &self.formatted_message(),
+        );
+        notification.stashes = self.stashes.clone();
+        notification.session_index = self.session_index;
+        notification.era_index = self.era_index;
+        notification
     }
 
     pub fn add_raw_text(&mut self, t: String) {
@@ -202,11 +993,17 @@ impl Report {
     }
 
     pub fn message(&self) -> String {
-        self.body.join("\n")
+        match &self.templated_message {
+            Some(message) => message.to_string(),
+            None => self.body.join("\n"),
+        }
     }
 
     pub fn formatted_message(&self) -> String {
-        self.body.join("<br/>")
+        match &self.templated_formatted_message {
+            Some(formatted_message) => formatted_message.to_string(),
+            None => self.body.join("<br/>"),
+        }
     }
 
     pub fn log(&self) {
@@ -222,6 +1019,28 @@ impl From<RawData> for Report {
     /// Converts a Scouty `RawData` into a [`Report`].
     fn from(data: RawData) -> Report {
         let mut report = Report::new();
+        report.is_suppressed = data.is_suppressed();
+        if report.is_suppressed {
+            report.suppressions = data.suppressions();
+        }
+        report.is_mute_exempt = data.is_mute_exempt();
+        // Messages defined by the templates take precedence over the built-in ones
+        if let Some(template) = templates::templates().get(data.template_key()) {
+            let (message, formatted_message) = data.render_template(template);
+            report.templated_message = message;
+            report.templated_formatted_message = formatted_message;
+        }
+        report.severity = data.severity();
+        report.event = data.template_key().to_string();
+        report.stashes = data
+            .validators
+            .iter()
+            .map(|v| v.stash.to_string())
+            .collect();
+        if data.session.current_session_index > 0 {
+            report.session_index = Some(data.session.current_session_index);
+            report.era_index = Some(data.session.active_era_index);
+        }
 
         // Scouty package
         report.add_raw_text(format!(
@@ -235,10 +1054,30 @@ impl From<RawData> for Report {
         match data.section {
             Section::Init => section_init(&mut report, data),
             Section::Session => section_session(&mut report, data),
+            Section::SessionBatch => section_session_batch(&mut report, data),
+            Section::EraPaid => section_era_paid(&mut report, data),
             Section::Referenda => section_referenda(&mut report, data),
             Section::Slash => section_slash(&mut report, data),
+            Section::SlashSummary => section_slash_summary(&mut report, data),
+            Section::Offence => section_offence(&mut report, data),
             Section::Chill => section_chill(&mut report, data),
             Section::Offline => section_offline(&mut report, data),
+            Section::OfflineRisk => section_offline_risk(&mut report, data),
+            Section::SessionKeys => section_session_keys(&mut report, data),
+            Section::ValidatorPrefs => section_validator_prefs(&mut report, data),
+            Section::CommissionPolicy => section_commission_policy(&mut report, data),
+            Section::ValidatorDisabled => section_validator_disabled(&mut report, data),
+            Section::PayeeChanged => section_payee_changed(&mut report, data),
+            Section::LowBalance => section_low_balance(&mut report, data),
+            Section::CatchUp => section_catch_up(&mut report, data),
+            Section::EraDigest => section_era_digest(&mut report, data),
+            Section::Nominators => section_nominators(&mut report, data),
+            Section::Payout => section_payout(&mut report, data),
+            Section::UnclaimedEras => section_unclaimed_eras(&mut report, data),
+            Section::Dispute => section_dispute(&mut report, data),
+            Section::Health => section_health(&mut report, data),
+            Section::Finality => section_finality(&mut report, data),
+            Section::RuntimeUpgrade => section_runtime_upgrade(&mut report, data),
         };
 
         // --- Specific report section here [END] ---|
@@ -262,12 +1101,29 @@ fn sub_section_validators(report: &mut Report, data: RawData) -> &Report {
 
         let is_active_desc = if validator.is_active { "🟢" } else { "🔴" };
         report.add_raw_text(format!(
-            "{} <b><a href=\"https://{}.subscan.io/validator/{}\">{}</a></b>",
+            "{} <b>{}</b>",
             is_active_desc,
-            data.network.name.to_lowercase(),
-            validator.stash,
-            validator.name,
+            validator_link(&data.network, &validator),
         ));
+        if let Some(trend) = &validator.era_points_trend {
+            sub_section_era_points(report, trend);
+        }
+        if let Some(blocks) = &validator.authored_blocks {
+            sub_section_authored_blocks(report, blocks);
+        }
+        sub_section_backing(report, &validator.para_assignment, &validator.backing);
+        if let Some(change) = &validator.stake_change {
+            sub_section_stake(report, change, &data.network);
+        }
+        if let Some(position) = &validator.waiting_position {
+            sub_section_waiting_position(report, position, &data.network);
+        }
+        if !validator.inactive_next_era_reason.is_empty() {
+            report.add_raw_text(format!(
+                "💤 Inactive next era: {}",
+                validator.inactive_next_era_reason
+            ));
+        }
         for (i, hook) in validator.hooks.into_iter().enumerate() {
             if i != 0 {
                 report.add_break();
@@ -275,16 +1131,171 @@ fn sub_section_validators(report: &mut Report, data: RawData) -> &Report {
             let exists_desc = if !hook.filename_exists { "❌" } else { "" };
             report.add_text(format!("🪝 <code>{}</code> {}", hook.filename, exists_desc));
 
-            let raw_output = String::from_utf8(hook.stdout).unwrap();
-            // filter lines that start by special character '!'
-            for line in raw_output.lines().filter(|line| line.starts_with("!")) {
-                report.add_raw_text(format!("‣ {}", line.strip_prefix("!").unwrap()));
+            for line in hook.matrix_lines() {
+                report.add_raw_text(format!("‣ {}", line));
             }
         }
     }
     report
 }
 
+fn sub_section_era_points(report: &mut Report, trend: &EraPointsTrend) {
+    let delta_desc = match trend.delta {
+        Some(delta) => format!(" (+{} since the previous session)", delta),
+        None => "".to_string(),
+    };
+    report.add_text(format!(
+        "🎲 Era points so far: <b>{}</b>{} -> era average {}",
+        trend.points, delta_desc, trend.era_avg
+    ));
+    if trend.is_below_average() {
+        report.add_raw_text(format!(
+            "⚠️ Era points below {}% of the era average for {} consecutive sessions",
+            CONFIG.current().era_points_threshold,
+            trend.sessions_below
+        ));
+    }
+}
+
+fn sub_section_authored_blocks(report: &mut Report, blocks: &AuthoredBlocks) {
+    report.add_text(format!(
+        "📦 Authored blocks in session {}: <b>{}</b> -> session average {:.1}",
+        blocks.session_index, blocks.count, blocks.session_avg
+    ));
+    if blocks.count == 0 {
+        report.add_raw_text(
+            "⚠️ No blocks authored during the whole session while active -> verify the babe keys"
+                .to_string(),
+        );
+    }
+}
+
+fn sub_section_backing(
+    report: &mut Report,
+    assignment: &Option<ParaAssignment>,
+    backing: &Option<SessionBacking>,
+) {
+    if let Some(assignment) = assignment {
+        report.add_text(format!(
+            "🏭 Para validator in group <b>#{}</b> this session",
+            assignment.group_index
+        ));
+    }
+    if let Some(backing) = backing {
+        let ratio_desc = match backing.votes.missed_ratio() {
+            Some(ratio) => format!(" -> MVR {:.2}", ratio),
+            None => "".to_string(),
+        };
+        report.add_text(format!(
+            "🗳️ Backing votes in session {} (group #{}): <b>{}</b> -> {} implicit, {} explicit, {} missed{}",
+            backing.session_index,
+            backing.assignment.group_index,
+            backing.votes.total(),
+            backing.votes.implicit,
+            backing.votes.explicit,
+            backing.votes.missed,
+            ratio_desc
+        ));
+        if backing
+            .votes
+            .is_above(CONFIG.current().missed_votes_threshold)
+        {
+            report.add_raw_text(format!(
+                "⚠️ Backing votes missed above {}% in the previous session",
+                CONFIG.current().missed_votes_threshold
+            ));
+        }
+    }
+}
+
+fn sub_section_stake(report: &mut Report, change: &StakeChange, network: &Network) {
+    let delta_desc = |delta: Option<String>| match delta {
+        Some(delta) => format!(" ({})", delta),
+        None => "".to_string(),
+    };
+    report.add_text(format!(
+        "💰 Own stake <b>{}</b>{} -> total backing <b>{}</b>{} -> {} nominators{}",
+        network.format_amount(change.current.own),
+        delta_desc(change.own_delta().map(|delta| network.format_delta(delta))),
+        network.format_amount(change.current.total),
+        delta_desc(
+            change
+                .total_delta()
+                .map(|delta| network.format_delta(delta))
+        ),
+        change.current.nominator_count,
+        delta_desc(
+            change
+                .nominator_count_delta()
+                .map(|delta| format!("{:+}", delta))
+        ),
+    ));
+    if let (Some(min), Some(margin)) =
+        (change.min_elected_stake, change.margin_percentage())
+    {
+        report.add_raw_text(format!(
+            "📐 Minimum elected stake {} -> margin {} ({:.2}%)",
+            network.format_amount(min),
+            network.format_delta(change.current.total as i128 - min as i128),
+            margin
+        ));
+        if change.is_margin_below(CONFIG.current().stake_margin_threshold) {
+            report.add_raw_text(format!(
+                "⚠️ Total backing less than {}% above the minimum elected stake",
+                CONFIG.current().stake_margin_threshold
+            ));
+        }
+    }
+}
+
+fn sub_section_waiting_position(
+    report: &mut Report,
+    position: &WaitingPosition,
+    network: &Network,
+) {
+    report.add_text(format!(
+        "⏳ Waiting position <b>#{}</b> of {} -> approval stake <b>{}</b> -> minimum elected stake {}",
+        position.position,
+        position.waiting_count,
+        network.format_amount(position.approval_stake),
+        network.format_amount(position.min_elected_stake),
+    ));
+    let delta_desc = match position.gap_delta() {
+        Some(delta) if delta < 0 => {
+            format!(
+                " (shrank by {} since the previous era)",
+                network.format_amount(delta.unsigned_abs())
+            )
+        }
+        Some(delta) if delta > 0 => {
+            format!(
+                " (grew by {} since the previous era)",
+                network.format_amount(delta.unsigned_abs())
+            )
+        }
+        Some(_) => " (same as the previous era)".to_string(),
+        None => "".to_string(),
+    };
+    if position.gap() > 0 {
+        report.add_raw_text(format!(
+            "📏 Gap to the minimum elected stake: {}{}",
+            network.format_amount(position.gap()),
+            delta_desc
+        ));
+        if let Some(eras) = position.estimated_eras() {
+            report.add_raw_text(format!(
+                "🗓️ About {} eras until activation at the pace of the previous era",
+                eras
+            ));
+        }
+    } else {
+        report.add_raw_text(format!(
+            "📏 Approval stake above the minimum elected stake{}",
+            delta_desc
+        ));
+    }
+}
+
 fn section_init(report: &mut Report, data: RawData) -> &Report {
     report.add_break();
     report.add_raw_text(format!(
@@ -298,7 +1309,7 @@ fn section_init(report: &mut Report, data: RawData) -> &Report {
     sub_section_validators(report, data)
 }
 
-fn section_session(report: &mut Report, data: RawData) -> &Report {
+fn sub_section_session(report: &mut Report, data: &RawData) {
     // Network info
     report.add_break();
     report.add_raw_text(format!(
@@ -310,25 +1321,84 @@ fn section_session(report: &mut Report, data: RawData) -> &Report {
         data.session.active_era_index
     ));
 
+    sub_section_minimums(report, data);
+}
+
+fn sub_section_minimums(report: &mut Report, data: &RawData) {
+    if let Some(minimums) = &data.minimums {
+        report.add_raw_text(format!("📏 {}", minimums.summary(&data.network)));
+    }
+}
+
+fn section_session(report: &mut Report, data: RawData) -> &Report {
+    sub_section_session(report, &data);
+
     sub_section_validators(report, data)
 }
 
-fn section_referenda(report: &mut Report, data: RawData) -> &Report {
-    // Network info
-    report.add_break();
-    report.add_raw_text(format!(
-        "⛓️ <b>{}</b> -> 🗳️ Referendum {} ({}) has been submitted.",
-        data.network.name, data.referendum.index, data.referendum.track,
-    ));
+fn section_session_batch(report: &mut Report, data: RawData) -> &Report {
+    sub_section_session(report, &data);
 
+    // Validators info, one row per stash
     report.add_break();
-    report.add_raw_text(format!(
-        "Vote here -> <a href=\"https://polkadot.js.org/apps/?rpc=wss%3A%2F%2Frpc.ibp.network%2F{}#/referenda\">Polkadot.js</a>",
-        data.network.name.to_lowercase()
-    ));
-    report.add_raw_text(format!(
-        "Or here -> <a href=\"https://{}.polkassembly.io/referenda/{}\">Polkassembly</a>",
-        data.network.name.to_lowercase(),
+    let mut table = String::from(
+        "<table><tr><th>Stash</th><th>Active</th><th>Active next era</th><th>Era points</th><th>Queued keys</th></tr>",
+    );
+    for validator in data.validators.iter() {
+        let queued_keys_desc = if !validator.is_queued {
+            "-"
+        } else if validator.is_session_keys_mismatch {
+            "⚠️ not owned by the node"
+        } else {
+            "✅"
+        };
+        let era_points_desc = match &validator.era_points_trend {
+            Some(trend) if trend.is_below_average() => format!(
+                "⚠️ {} (era average {})",
+                validator.era_points, trend.era_avg
+            ),
+            _ => validator.era_points.to_string(),
+        };
+        table.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            validator_link(&data.network, validator),
+            if validator.is_active { "🟢" } else { "🔴" },
+            if validator.is_queued { "🟢" } else { "🔴" },
+            era_points_desc,
+            queued_keys_desc,
+        ));
+    }
+    table.push_str("</table>");
+    report.add_raw_text(table);
+
+    // Hooks output
+    for validator in data.validators {
+        for hook in validator.hooks {
+            for line in hook.matrix_lines() {
+                report.add_raw_text(format!("‣ {} -> {}", validator.name, line));
+            }
+        }
+    }
+
+    report
+}
+
+fn section_referenda(report: &mut Report, data: RawData) -> &Report {
+    // Network info
+    report.add_break();
+    report.add_raw_text(format!(
+        "⛓️ <b>{}</b> -> 🗳️ Referendum {} ({}) has been submitted.",
+        data.network.name, data.referendum.index, data.referendum.track,
+    ));
+
+    report.add_break();
+    report.add_raw_text(format!(
+        "Vote here -> <a href=\"https://polkadot.js.org/apps/?rpc=wss%3A%2F%2Frpc.ibp.network%2F{}#/referenda\">Polkadot.js</a>",
+        data.network.name.to_lowercase()
+    ));
+    report.add_raw_text(format!(
+        "Or here -> <a href=\"https://{}.polkassembly.io/referenda/{}\">Polkassembly</a>",
+        data.network.name.to_lowercase(),
         data.referendum.index
     ));
     report.add_raw_text(format!(
@@ -349,10 +1419,71 @@ fn section_referenda(report: &mut Report, data: RawData) -> &Report {
         data.referendum.hook.filename, exists_desc
     ));
 
-    let raw_output = String::from_utf8(data.referendum.hook.stdout).unwrap();
-    // filter lines that start by special character '!'
-    for line in raw_output.lines().filter(|line| line.starts_with("!")) {
-        report.add_raw_text(format!("‣ {}", line.strip_prefix("!").unwrap()));
+    for line in data.referendum.hook.matrix_lines() {
+        report.add_raw_text(format!("‣ {}", line));
+    }
+
+    report
+}
+
+fn section_era_paid(report: &mut Report, data: RawData) -> &Report {
+    let network = data.network.clone();
+    let to_tokens = |value: u128| network.format_tokens(value);
+
+    // Network info
+    report.add_break();
+    report.add_raw_text(format!(
+        "⛓️ <b>{}</b> -> 💰 Era {} paid -> 💸 <b>{}</b>",
+        data.network.name,
+        data.era_paid.era_index,
+        to_tokens(data.era_paid.validator_payout),
+    ));
+
+    // Validators info
+    for validator in data.validators {
+        report.add_break();
+
+        let is_active_desc = if validator.is_active { "🟢" } else { "🔴" };
+        report.add_raw_text(format!(
+            "{} <b>{}</b>",
+            is_active_desc,
+            validator_link(&data.network, &validator),
+        ));
+
+        let is_claimed_desc = if validator.reward.is_claimed {
+            "✅ claimed"
+        } else {
+            "⏳ not claimed yet"
+        };
+        report.add_raw_text(format!(
+            "🎲 Points {} / {} -> estimated reward 💸 <b>{}</b> {}",
+            validator.reward.points,
+            data.era_paid.total_points,
+            to_tokens(validator.reward.amount_value),
+            is_claimed_desc,
+        ));
+        if validator.reward.amount_value > 0 {
+            report.add_raw_text(format!(
+                "🏦 Stash reward (commission + own stake) <b>{}</b> ({} plancks)",
+                to_tokens(validator.reward.stash_amount_value),
+                validator.reward.stash_amount_value,
+            ));
+        }
+        if let Some(rank) = validator.reward.rank {
+            report.add_raw_text(format!(
+                "🏆 Rank {} / {} by era points",
+                rank, data.era_paid.validator_count,
+            ));
+        }
+
+        for hook in validator.hooks {
+            let exists_desc = if !hook.filename_exists { "❌" } else { "" };
+            report.add_text(format!("🪝 <code>{}</code> {}", hook.filename, exists_desc));
+
+            for line in hook.matrix_lines() {
+                report.add_raw_text(format!("‣ {}", line));
+            }
+        }
     }
 
     report
@@ -362,7 +1493,7 @@ fn section_slash(report: &mut Report, data: RawData) -> &Report {
     // Network info
     report.add_break();
     report.add_raw_text(format!(
-        "⛓️ <b>{}</b> -> <a href=\"https://polkadot.js.org/apps/?rpc=wss%3A%2F%2F{}.api.onfinality.io%2Fpublic-ws#/staking/slashes\">🏴‍☠️ Slash occurred!</a>",
+        "🚨 <b>{}</b> -> <a href=\"https://polkadot.js.org/apps/?rpc=wss%3A%2F%2F{}.api.onfinality.io%2Fpublic-ws#/staking/slashes\">🏴‍☠️ Slash occurred!</a> 🚨",
         data.network.name,
         data.network.name.to_lowercase(),
     ));
@@ -380,16 +1511,14 @@ fn section_slash(report: &mut Report, data: RawData) -> &Report {
 
             let is_active_desc = if validator.is_active { "🟢" } else { "🔴" };
             report.add_raw_text(format!(
-                "{} <b><a href=\"https://{}.subscan.io/validator/{}\">{}</a></b>",
+                "{} <b>{}</b>",
                 is_active_desc,
-                data.network.name.to_lowercase(),
-                validator.stash,
-                validator.name,
+                validator_link(&data.network, &validator),
             ));
 
             report.add_raw_text(format!(
-                "🤬 Slashed amount -> 💸 <b>{}</b>",
-                slashed_amount,
+                "🤬 Slashed amount -> 💸 <b>{}</b> in era {}",
+                slashed_amount, data.slash.era_index,
             ));
         }
     }
@@ -406,10 +1535,78 @@ fn section_slash(report: &mut Report, data: RawData) -> &Report {
         data.slash.hook.filename, exists_desc
     ));
 
-    let raw_output = String::from_utf8(data.slash.hook.stdout).unwrap();
-    // filter lines that start by special character '!'
-    for line in raw_output.lines().filter(|line| line.starts_with("!")) {
-        report.add_raw_text(format!("‣ {}", line.strip_prefix("!").unwrap()));
+    for line in data.slash.hook.matrix_lines() {
+        report.add_raw_text(format!("‣ {}", line));
+    }
+
+    report
+}
+
+fn section_slash_summary(report: &mut Report, data: RawData) -> &Report {
+    // Network info
+    report.add_break();
+    report.add_raw_text(format!(
+        "⛓️ <b>{}</b> -> <a href=\"https://polkadot.js.org/apps/?rpc=wss%3A%2F%2F{}.api.onfinality.io%2Fpublic-ws#/staking/slashes\">🏴‍☠️ Slashes summary</a>",
+        data.network.name,
+        data.network.name.to_lowercase(),
+    ));
+
+    if data.slash.others_slashed > 0 {
+        report.add_raw_text(format!(
+            "🤬 Validators slashed in era {} -> <b>{}</b>",
+            data.slash.era_index, data.slash.others_slashed,
+        ));
+    }
+
+    if !data.slash.offences.is_empty() {
+        report.add_raw_text(format!(
+            "⚖️ Offences reported -> <b>{}</b> <code>{}</code>",
+            data.slash.offences.len(),
+            data.slash.offences.join(", "),
+        ));
+    }
+
+    report
+}
+
+fn section_offence(report: &mut Report, data: RawData) -> &Report {
+    // Network info
+    report.add_break();
+    report.add_raw_text(format!(
+        "🚨 <b>{}</b> -> ⚖️ Offence reported -> <code>{}</code> 🚨",
+        data.network.name,
+        data.slash.offences.join(", "),
+    ));
+
+    // Validators info
+    for validator in data.validators {
+        if validator.is_offender {
+            report.add_break();
+
+            let is_active_desc = if validator.is_active { "🟢" } else { "🔴" };
+            report.add_raw_text(format!(
+                "{} <b>{}</b>",
+                is_active_desc,
+                validator_link(&data.network, &validator),
+            ));
+
+            report.add_raw_text(format!(
+                "👆 Has been reported in era {} session {} -> a slash might follow 🏴‍☠️",
+                data.slash.era_index, data.session.current_session_index
+            ));
+
+            for hook in validator.hooks {
+                let exists_desc = if !hook.filename_exists { "❌" } else { "" };
+                report.add_text(format!(
+                    "🪝 <code>{}</code> {}",
+                    hook.filename, exists_desc
+                ));
+
+                for line in hook.matrix_lines() {
+                    report.add_raw_text(format!("‣ {}", line));
+                }
+            }
+        }
     }
 
     report
@@ -419,8 +1616,10 @@ fn section_chill(report: &mut Report, data: RawData) -> &Report {
     // Network info
     report.add_break();
     report.add_raw_text(format!(
-        "⛓️ <b>{}</b> -> 🧊 Chill detected.",
-        data.network.name
+        "🚨 <b>{}</b> -> 🧊 Chill detected in era {} session {} 🚨",
+        data.network.name,
+        data.session.active_era_index,
+        data.session.current_session_index,
     ));
 
     // Validators info
@@ -430,14 +1629,13 @@ fn section_chill(report: &mut Report, data: RawData) -> &Report {
 
             let is_active_desc = if validator.is_active { "🟢" } else { "🔴" };
             report.add_raw_text(format!(
-                "{} <b><a href=\"https://{}.subscan.io/validator/{}\">{}</a></b>",
+                "{} <b>{}</b>",
                 is_active_desc,
-                data.network.name.to_lowercase(),
-                validator.stash,
-                validator.name,
+                validator_link(&data.network, &validator),
             ));
 
-            report.add_raw_text(format!("👆 Has been chilled -> 🥶"));
+            report.add_raw_text("👆 Has been chilled -> 🥶".to_string());
+            report.add_raw_text(format!("🤔 Reason -> {}", data.chill.reason));
 
             for hook in validator.hooks {
                 let exists_desc = if !hook.filename_exists { "❌" } else { "" };
@@ -446,10 +1644,8 @@ fn section_chill(report: &mut Report, data: RawData) -> &Report {
                     hook.filename, exists_desc
                 ));
 
-                let raw_output = String::from_utf8(hook.stdout).unwrap();
-                // filter lines that start by special character '!'
-                for line in raw_output.lines().filter(|line| line.starts_with("!")) {
-                    report.add_raw_text(format!("‣ {}", line.strip_prefix("!").unwrap()));
+                for line in hook.matrix_lines() {
+                    report.add_raw_text(format!("‣ {}", line));
                 }
             }
         }
@@ -473,14 +1669,12 @@ fn section_offline(report: &mut Report, data: RawData) -> &Report {
 
             let is_active_desc = if validator.is_active { "🟢" } else { "🔴" };
             report.add_raw_text(format!(
-                "{} <b><a href=\"https://{}.subscan.io/validator/{}\">{}</a></b>",
+                "{} <b>{}</b>",
                 is_active_desc,
-                data.network.name.to_lowercase(),
-                validator.stash,
-                validator.name,
+                validator_link(&data.network, &validator),
             ));
 
-            report.add_raw_text(format!("👆 Has been seen offline -> ⛑️"));
+            report.add_raw_text("👆 Has been seen offline -> ⛑️".to_string());
 
             for hook in validator.hooks {
                 let exists_desc = if !hook.filename_exists { "❌" } else { "" };
@@ -489,10 +1683,8 @@ fn section_offline(report: &mut Report, data: RawData) -> &Report {
                     hook.filename, exists_desc
                 ));
 
-                let raw_output = String::from_utf8(hook.stdout).unwrap();
-                // filter lines that start by special character '!'
-                for line in raw_output.lines().filter(|line| line.starts_with("!")) {
-                    report.add_raw_text(format!("‣ {}", line.strip_prefix("!").unwrap()));
+                for line in hook.matrix_lines() {
+                    report.add_raw_text(format!("‣ {}", line));
                 }
             }
         }
@@ -501,6 +1693,813 @@ fn section_offline(report: &mut Report, data: RawData) -> &Report {
     report
 }
 
+fn section_session_keys(report: &mut Report, data: RawData) -> &Report {
+    // Network info
+    report.add_break();
+    report.add_raw_text(format!(
+        "⛓️ <b>{}</b> -> 🔑 Next session keys verified in session {}",
+        data.network.name, data.session.current_session_index,
+    ));
+
+    // Validators info
+    for validator in data.validators {
+        report.add_break();
+
+        let is_active_desc = if validator.is_active { "🟢" } else { "🔴" };
+        report.add_raw_text(format!(
+            "{} <b>{}</b>",
+            is_active_desc,
+            validator_link(&data.network, &validator),
+        ));
+
+        if validator.is_session_keys_mismatch {
+            report.add_raw_text(
+                "🚨 Node does NOT own the next session keys set on-chain -> 🔑❌ 🚨"
+                    .to_string(),
+            );
+        } else {
+            report.add_raw_text(
+                "👆 Node owns the next session keys again -> 🔑✅".to_string(),
+            );
+        }
+
+        for hook in validator.hooks {
+            let exists_desc = if !hook.filename_exists { "❌" } else { "" };
+            report.add_text(format!("🪝 <code>{}</code> {}", hook.filename, exists_desc));
+
+            for line in hook.matrix_lines() {
+                report.add_raw_text(format!("‣ {}", line));
+            }
+        }
+    }
+
+    report
+}
+
+fn section_validator_prefs(report: &mut Report, data: RawData) -> &Report {
+    // Network info
+    report.add_break();
+    report.add_raw_text(format!(
+        "⛓️ <b>{}</b> -> ⚙️ Validator preferences changed in session {}",
+        data.network.name, data.session.current_session_index,
+    ));
+
+    // Validators info
+    for validator in data.validators {
+        report.add_break();
+
+        let is_active_desc = if validator.is_active { "🟢" } else { "🔴" };
+        report.add_raw_text(format!(
+            "{} <b>{}</b>",
+            is_active_desc,
+            validator_link(&data.network, &validator),
+        ));
+
+        let change = validator.prefs_change;
+        if change.previous.commission != change.current.commission {
+            report.add_raw_text(format!(
+                "💸 Commission {}% -> <b>{}%</b>",
+                change.previous.commission as f64 / 10_000_000.0,
+                change.current.commission as f64 / 10_000_000.0
+            ));
+        }
+        if change.previous.blocked != change.current.blocked {
+            report.add_raw_text(format!(
+                "⛔ Blocked {} -> <b>{}</b>",
+                change.previous.blocked, change.current.blocked
+            ));
+        }
+
+        for hook in validator.hooks {
+            let exists_desc = if !hook.filename_exists { "❌" } else { "" };
+            report.add_text(format!("🪝 <code>{}</code> {}", hook.filename, exists_desc));
+
+            for line in hook.matrix_lines() {
+                report.add_raw_text(format!("‣ {}", line));
+            }
+        }
+    }
+
+    report
+}
+
+fn section_payee_changed(report: &mut Report, data: RawData) -> &Report {
+    // Network info
+    report.add_break();
+    report.add_raw_text(format!(
+        "⛓️ <b>{}</b> -> 🚨 Reward destination changed in session {}",
+        data.network.name, data.session.current_session_index,
+    ));
+
+    // Validators info
+    for validator in data.validators {
+        report.add_break();
+
+        let is_active_desc = if validator.is_active { "🟢" } else { "🔴" };
+        report.add_raw_text(format!(
+            "{} <b>{}</b>",
+            is_active_desc,
+            validator_link(&data.network, &validator),
+        ));
+
+        if let Some(change) = validator.payee_change {
+            report.add_raw_text(format!(
+                "💳 Reward destination {} -> <b>{}</b>",
+                change.previous, change.current
+            ));
+            report.add_raw_text(
+                "👆 Verify the change was intended, otherwise the stash might be compromised ⚠️"
+                    .to_string(),
+            );
+        }
+
+        for hook in validator.hooks {
+            let exists_desc = if !hook.filename_exists { "❌" } else { "" };
+            report.add_text(format!("🪝 <code>{}</code> {}", hook.filename, exists_desc));
+
+            for line in hook.matrix_lines() {
+                report.add_raw_text(format!("‣ {}", line));
+            }
+        }
+    }
+
+    report
+}
+
+fn section_commission_policy(report: &mut Report, data: RawData) -> &Report {
+    // Network info
+    report.add_break();
+    report.add_raw_text(format!(
+        "⛓️ <b>{}</b> -> 💸 Commission policy verified in session {}",
+        data.network.name, data.session.current_session_index,
+    ));
+
+    // Validators info
+    for validator in data.validators {
+        report.add_break();
+
+        let is_active_desc = if validator.is_active { "🟢" } else { "🔴" };
+        report.add_raw_text(format!(
+            "{} <b>{}</b>",
+            is_active_desc,
+            validator_link(&data.network, &validator),
+        ));
+
+        if let Some((commission, policy)) = validator.commission_policy {
+            let to_percent = |perbill: u32| perbill as f64 / 10_000_000.0;
+            if policy.contains(commission) {
+                report.add_raw_text(format!(
+                    "👆 Commission <b>{}%</b> back within the policy {}% - {}% -> ✅",
+                    to_percent(commission),
+                    to_percent(policy.min),
+                    to_percent(policy.max)
+                ));
+            } else {
+                report.add_raw_text(format!(
+                    "🚨 Commission <b>{}%</b> outside the policy {}% - {}% -> ❌ 🚨",
+                    to_percent(commission),
+                    to_percent(policy.min),
+                    to_percent(policy.max)
+                ));
+            }
+        }
+
+        for hook in validator.hooks {
+            let exists_desc = if !hook.filename_exists { "❌" } else { "" };
+            report.add_text(format!("🪝 <code>{}</code> {}", hook.filename, exists_desc));
+
+            for line in hook.matrix_lines() {
+                report.add_raw_text(format!("‣ {}", line));
+            }
+        }
+    }
+
+    report
+}
+
+fn section_validator_disabled(report: &mut Report, data: RawData) -> &Report {
+    // Network info
+    report.add_break();
+    report.add_raw_text(format!(
+        "⛓️ <b>{}</b> -> 🚫 Disabled validators verified in session {}",
+        data.network.name, data.session.current_session_index,
+    ));
+
+    // Validators info
+    for validator in data.validators {
+        report.add_break();
+
+        let is_active_desc = if validator.is_active { "🟢" } else { "🔴" };
+        report.add_raw_text(format!(
+            "{} <b>{}</b>",
+            is_active_desc,
+            validator_link(&data.network, &validator),
+        ));
+
+        if validator.is_disabled {
+            report.add_raw_text(
+                "🚨 Validator disabled on-chain for the remainder of the session -> no rewards 🚨"
+                    .to_string(),
+            );
+        } else {
+            report.add_raw_text(
+                "👆 Validator no longer disabled in the new session -> ✅".to_string(),
+            );
+        }
+
+        for hook in validator.hooks {
+            let exists_desc = if !hook.filename_exists { "❌" } else { "" };
+            report.add_text(format!("🪝 <code>{}</code> {}", hook.filename, exists_desc));
+
+            for line in hook.matrix_lines() {
+                report.add_raw_text(format!("‣ {}", line));
+            }
+        }
+    }
+
+    report
+}
+
+fn section_nominators(report: &mut Report, data: RawData) -> &Report {
+    // Network info
+    report.add_break();
+    report.add_raw_text(format!(
+        "⛓️ <b>{}</b> -> 🫂 Nominators changed in era {}",
+        data.network.name, data.session.active_era_index,
+    ));
+
+    // Validators info
+    for validator in data.validators {
+        report.add_break();
+
+        let is_active_desc = if validator.is_active { "🟢" } else { "🔴" };
+        report.add_raw_text(format!(
+            "{} <b>{}</b>",
+            is_active_desc,
+            validator_link(&data.network, &validator),
+        ));
+
+        let changes = match validator.nominator_changes {
+            Some(changes) => changes,
+            None => continue,
+        };
+        let drop_desc = if changes.is_backing_drop {
+            format!(" ⚠️ -{}%", changes.drop_percentage())
+        } else {
+            "".to_string()
+        };
+        report.add_raw_text(format!(
+            "💰 Total backing {} -> <b>{}</b>{}",
+            data.network.format_amount(changes.previous_total),
+            data.network.format_amount(changes.total),
+            drop_desc,
+        ));
+        for nominator in &changes.left {
+            sub_section_nominator(report, "👋 Left", nominator, &data.network);
+        }
+        for nominator in &changes.arrived {
+            sub_section_nominator(report, "🆕 Arrived", nominator, &data.network);
+        }
+    }
+
+    report
+}
+
+fn sub_section_nominator(
+    report: &mut Report,
+    desc: &str,
+    nominator: &Nominator,
+    network: &Network,
+) {
+    report.add_raw_text(format!(
+        "{} <a href=\"{}\">{}</a> with <b>{}</b>",
+        desc,
+        explorer_url(network, "account", &nominator.stash.to_string()),
+        nominator.name,
+        network.format_amount(nominator.value),
+    ));
+}
+
+fn section_payout(report: &mut Report, data: RawData) -> &Report {
+    // Network info
+    report.add_break();
+    report.add_raw_text(format!(
+        "⛓️ <b>{}</b> -> 💸 Payouts of the unclaimed eras submitted",
+        data.network.name,
+    ));
+
+    // Validators info
+    for validator in data.validators {
+        report.add_break();
+
+        let is_active_desc = if validator.is_active { "🟢" } else { "🔴" };
+        report.add_raw_text(format!(
+            "{} <b>{}</b>",
+            is_active_desc,
+            validator_link(&data.network, &validator),
+        ));
+
+        for payout in &validator.payouts {
+            match &payout.error {
+                Some(error) => report.add_raw_text(format!(
+                    "❌ Era {} payout failed -> <code>{}</code>",
+                    payout.era_index, error
+                )),
+                None => report.add_raw_text(format!(
+                    "✅ Era {} paid out <b>{}</b> (fee {}) in block <a href=\"https://{}.subscan.io/block/{}\">{}</a>",
+                    payout.era_index,
+                    data.network.format_amount(payout.amount),
+                    data.network.format_amount(payout.fee),
+                    data.network.name.to_lowercase(),
+                    payout.block_hash,
+                    payout.block_hash,
+                )),
+            }
+        }
+
+        for hook in validator.hooks {
+            let exists_desc = if !hook.filename_exists { "❌" } else { "" };
+            report.add_text(format!("🪝 <code>{}</code> {}", hook.filename, exists_desc));
+
+            for line in hook.matrix_lines() {
+                report.add_raw_text(format!("‣ {}", line));
+            }
+        }
+    }
+
+    report
+}
+
+fn section_unclaimed_eras(report: &mut Report, data: RawData) -> &Report {
+    // Network info
+    report.add_break();
+    report.add_raw_text(format!(
+        "⛓️ <b>{}</b> -> ⏳ Rewards not claimed for more than {} eras",
+        data.network.name,
+        CONFIG.current().unclaimed_eras_threshold,
+    ));
+
+    // Validators info
+    for validator in data.validators {
+        report.add_break();
+
+        let is_active_desc = if validator.is_active { "🟢" } else { "🔴" };
+        report.add_raw_text(format!(
+            "{} <b>{}</b>",
+            is_active_desc,
+            validator_link(&data.network, &validator),
+        ));
+
+        let eras: Vec<String> = validator
+            .unclaimed_eras
+            .iter()
+            .map(|era| era.era_index.to_string())
+            .collect();
+        let at_risk: u128 = validator
+            .unclaimed_eras
+            .iter()
+            .map(|era| era.estimated_reward)
+            .sum();
+        report.add_raw_text(format!(
+            "⏳ {} unclaimed eras -> <code>{}</code>",
+            eras.len(),
+            eras.join(", ")
+        ));
+        report.add_raw_text(format!(
+            "💰 Estimated rewards at risk <b>{}</b>",
+            data.network.format_amount(at_risk)
+        ));
+    }
+
+    report
+}
+
+fn section_dispute(report: &mut Report, data: RawData) -> &Report {
+    // Network info
+    report.add_break();
+    report.add_raw_text(format!(
+        "⛓️ <b>{}</b> -> ⚖️ Dispute votes included in session {}",
+        data.network.name, data.session.current_session_index,
+    ));
+
+    // Validators info
+    for validator in data.validators {
+        report.add_break();
+
+        let is_active_desc = if validator.is_active { "🟢" } else { "🔴" };
+        report.add_raw_text(format!(
+            "{} <b>{}</b>",
+            is_active_desc,
+            validator_link(&data.network, &validator),
+        ));
+
+        for vote in validator.dispute_votes.iter() {
+            let is_against_desc = if !vote.is_valid { "⚠️" } else { "" };
+            report.add_raw_text(format!(
+                "⚖️ Voted <b>{}</b> candidate <code>{}</code> of session {} {}",
+                vote.direction(),
+                vote.candidate_hash,
+                vote.session_index,
+                is_against_desc
+            ));
+        }
+
+        for hook in validator.hooks {
+            let exists_desc = if !hook.filename_exists { "❌" } else { "" };
+            report.add_text(format!("🪝 <code>{}</code> {}", hook.filename, exists_desc));
+
+            for line in hook.matrix_lines() {
+                report.add_raw_text(format!("‣ {}", line));
+            }
+        }
+    }
+
+    report
+}
+
+fn section_offline_risk(report: &mut Report, data: RawData) -> &Report {
+    // Network info
+    report.add_break();
+    report.add_raw_text(format!(
+        "⚠️ <b>{}</b> -> 💔 Offline risk detected at {}% of session {} ⚠️",
+        data.network.name,
+        data.offline_risk.session_progress,
+        data.session.current_session_index,
+    ));
+
+    // Validators info
+    for validator in data.validators {
+        if validator.is_offline {
+            report.add_break();
+
+            let is_active_desc = if validator.is_active { "🟢" } else { "🔴" };
+            report.add_raw_text(format!(
+                "{} <b>{}</b>",
+                is_active_desc,
+                validator_link(&data.network, &validator),
+            ));
+
+            report.add_raw_text(
+                "👆 Has not sent a heartbeat nor authored a block -> ⛑️".to_string(),
+            );
+
+            for hook in validator.hooks {
+                let exists_desc = if !hook.filename_exists { "❌" } else { "" };
+                report.add_text(format!(
+                    "🪝 <code>{}</code> {}",
+                    hook.filename, exists_desc
+                ));
+
+                for line in hook.matrix_lines() {
+                    report.add_raw_text(format!("‣ {}", line));
+                }
+            }
+        }
+    }
+
+    report
+}
+
+fn section_health(report: &mut Report, data: RawData) -> &Report {
+    // Network info
+    report.add_break();
+    if data.health.status.is_healthy() {
+        report.add_raw_text(format!(
+            "⛓️ <b>{}</b> -> 💚 Node is healthy again.",
+            data.network.name
+        ));
+    } else {
+        report.add_raw_text(format!(
+            "⛓️ <b>{}</b> -> 🩺 Node is unhealthy: {}.",
+            data.network.name, data.health.status
+        ));
+    }
+
+    report.add_break();
+    report.add_raw_text(format!(
+        "🔌 <code>{}</code> -> peers: {}, syncing: {}",
+        data.health.url, data.health.peers, data.health.is_syncing
+    ));
+
+    // Hook
+    if !data.health.status.is_healthy() {
+        report.add_break();
+        let exists_desc = if !data.health.hook.filename_exists {
+            "❌"
+        } else {
+            ""
+        };
+        report.add_text(format!(
+            "🪝 <code>{}</code> {}",
+            data.health.hook.filename, exists_desc
+        ));
+
+        for line in data.health.hook.matrix_lines() {
+            report.add_raw_text(format!("‣ {}", line));
+        }
+    }
+
+    report
+}
+
+fn section_finality(report: &mut Report, data: RawData) -> &Report {
+    // Network info
+    report.add_break();
+    if data.finality.status.is_stalled() {
+        report.add_raw_text(format!(
+            "⛓️ <b>{}</b> -> 🧊 Finality is stalled, {} blocks behind.",
+            data.network.name,
+            data.finality
+                .best_block_number
+                .saturating_sub(data.finality.finalized_block_number)
+        ));
+    } else {
+        report.add_raw_text(format!(
+            "⛓️ <b>{}</b> -> ✅ Finality has recovered.",
+            data.network.name
+        ));
+    }
+
+    report.add_break();
+    report.add_raw_text(format!(
+        "📦 Best block #{} <code>{}</code>",
+        data.finality.best_block_number, data.finality.best_block_hash
+    ));
+    report.add_raw_text(format!(
+        "🔒 Finalized block #{} <code>{}</code>",
+        data.finality.finalized_block_number, data.finality.finalized_block_hash
+    ));
+
+    // Hook
+    if data.finality.status.is_stalled() {
+        report.add_break();
+        let exists_desc = if !data.finality.hook.filename_exists {
+            "❌"
+        } else {
+            ""
+        };
+        report.add_text(format!(
+            "🪝 <code>{}</code> {}",
+            data.finality.hook.filename, exists_desc
+        ));
+
+        for line in data.finality.hook.matrix_lines() {
+            report.add_raw_text(format!("‣ {}", line));
+        }
+    }
+
+    report
+}
+
+fn section_low_balance(report: &mut Report, data: RawData) -> &Report {
+    // Network info
+    report.add_break();
+    report.add_raw_text(format!(
+        "⛓️ <b>{}</b> -> 💰 Free balance verified in session {}",
+        data.network.name, data.session.current_session_index,
+    ));
+
+    // Accounts info
+    for balance in data.balances {
+        report.add_break();
+
+        let is_low_desc = if balance.is_low() { "🔴" } else { "🟢" };
+        report.add_raw_text(format!(
+            "{} <b><a href=\"{}\">{}</a></b>",
+            is_low_desc,
+            explorer_url(&data.network, "account", &balance.account),
+            balance.identity.as_ref().unwrap_or(&balance.account),
+        ));
+
+        if balance.is_low() {
+            report.add_raw_text(format!(
+                "💸 Free balance <b>{}</b> is below the minimum of {} ⚠️",
+                data.network.format_amount(balance.free),
+                data.network.format_amount(balance.min_free_balance),
+            ));
+            report.add_raw_text(
+                "👆 Top up the account, otherwise the extrinsics signed by it might fail"
+                    .to_string(),
+            );
+        } else {
+            report.add_raw_text(format!(
+                "💰 Free balance <b>{}</b> is back above the minimum of {} ✅",
+                data.network.format_amount(balance.free),
+                data.network.format_amount(balance.min_free_balance),
+            ));
+        }
+
+        for hook in balance.hooks {
+            let exists_desc = if !hook.filename_exists { "❌" } else { "" };
+            report.add_text(format!("🪝 <code>{}</code> {}", hook.filename, exists_desc));
+
+            for line in hook.matrix_lines() {
+                report.add_raw_text(format!("‣ {}", line));
+            }
+        }
+    }
+
+    report
+}
+
+fn section_catch_up(report: &mut Report, data: RawData) -> &Report {
+    // Network info
+    report.add_break();
+    report.add_raw_text(format!(
+        "⛓️ <b>{}</b> -> 💤 While scouty was away -> eras {} to {}",
+        data.network.name,
+        data.catch_up.pruned.start,
+        data.catch_up.eras.end.saturating_sub(1),
+    ));
+    if !data.catch_up.pruned.is_empty() {
+        report.add_raw_text(format!(
+            "❓ Eras {} to {} are beyond the history kept on-chain and are not summarized",
+            data.catch_up.pruned.start,
+            data.catch_up.pruned.end - 1,
+        ));
+    }
+
+    // Validators info, only the notable eras are listed
+    for validator in data.validators {
+        report.add_break();
+
+        let active_eras = validator.missed_eras.iter().filter(|era| era.is_active);
+        let is_active_desc = if validator.is_active { "🟢" } else { "🔴" };
+        report.add_raw_text(format!(
+            "{} <b>{}</b>",
+            is_active_desc,
+            validator_link(&data.network, &validator),
+        ));
+        report.add_raw_text(format!(
+            "📅 Active in {} of {} eras -> {} points -> 💸 ~{}",
+            active_eras.clone().count(),
+            validator.missed_eras.len(),
+            active_eras.clone().map(|era| era.points).sum::<u32>(),
+            data.network
+                .format_amount(active_eras.map(|era| era.reward).sum::<u128>()),
+        ));
+
+        for era in validator
+            .missed_eras
+            .iter()
+            .filter(|era| era.is_notable(CONFIG.current().era_points_threshold))
+        {
+            if !era.is_available {
+                report.add_raw_text(format!(
+                    "‣ Era {} -> ❓ era points not available anymore",
+                    era.era_index
+                ));
+                continue;
+            }
+            if let Some(slashed) = era.slashed {
+                report.add_raw_text(format!(
+                    "‣ Era {} -> 🩸 slashed <b>{}</b> ⚠️",
+                    era.era_index,
+                    data.network.format_amount(slashed),
+                ));
+            }
+            if era.is_below_average(CONFIG.current().era_points_threshold) {
+                report.add_raw_text(format!(
+                    "‣ Era {} -> 📉 {} points below the era average of {}{}",
+                    era.era_index,
+                    era.points,
+                    era.era_avg,
+                    era.rank
+                        .map_or("".to_string(), |rank| format!(" (rank #{})", rank)),
+                ));
+            }
+        }
+
+        for hook in validator.hooks {
+            let exists_desc = if !hook.filename_exists { "❌" } else { "" };
+            report.add_text(format!("🪝 <code>{}</code> {}", hook.filename, exists_desc));
+
+            for line in hook.matrix_lines() {
+                report.add_raw_text(format!("‣ {}", line));
+            }
+        }
+    }
+
+    report
+}
+
+fn section_era_digest(report: &mut Report, data: RawData) -> &Report {
+    let era_index = data
+        .validators
+        .iter()
+        .find_map(|v| v.era_digest.as_ref().map(|digest| digest.era_index))
+        .unwrap_or_default();
+
+    // Network info
+    report.add_break();
+    report.add_raw_text(format!(
+        "⛓️ <b>{}</b> -> 🗓️ Era <b>{}</b> digest",
+        data.network.name, era_index,
+    ));
+    sub_section_minimums(report, &data);
+
+    // Validators info
+    for validator in data.validators {
+        let digest = match &validator.era_digest {
+            Some(digest) => digest,
+            None => continue,
+        };
+        report.add_break();
+
+        let is_active_desc = if validator.is_active { "🟢" } else { "🔴" };
+        report.add_raw_text(format!(
+            "{} <b>{}</b>",
+            is_active_desc,
+            validator_link(&data.network, &validator),
+        ));
+        report.add_raw_text(format!(
+            "📅 Active in {} of {} sessions tracked",
+            digest.sessions_active, digest.sessions
+        ));
+        if digest.sessions_active > 0 {
+            let rank_desc = digest.rank.map_or("".to_string(), |rank| {
+                format!(" -> rank #{} of {}", rank, digest.validator_count)
+            });
+            report.add_raw_text(format!(
+                "🎲 Era points <b>{}</b>{}",
+                digest.points, rank_desc
+            ));
+            report.add_raw_text(format!("📦 {} blocks authored", digest.authored_blocks));
+        }
+        if let Some(votes) = &digest.backing_votes {
+            let missed_ratio_desc = votes
+                .missed_ratio()
+                .map_or("".to_string(), |ratio| format!(" ({:.2}%)", ratio * 100.0));
+            report.add_raw_text(format!(
+                "🗳️ Backing votes -> {} implicit, {} explicit, {} missed{}",
+                votes.implicit, votes.explicit, votes.missed, missed_ratio_desc
+            ));
+        }
+        if let Some(change) = &validator.stake_change {
+            sub_section_stake(report, change, &data.network);
+        }
+        for warning in digest.warnings.iter() {
+            report.add_raw_text(format!("⚠️ {}", warning));
+        }
+    }
+
+    report
+}
+
+fn section_runtime_upgrade(report: &mut Report, data: RawData) -> &Report {
+    // Network info
+    report.add_break();
+    report.add_raw_text(format!(
+        "⛓️ <b>{}</b> -> ⚙️ Runtime upgraded from <b>{}</b> to <b>{}</b> at block #{}",
+        data.network.name,
+        data.runtime_upgrade.previous_spec_version,
+        data.runtime_upgrade.spec_version,
+        data.runtime_upgrade.block_number
+    ));
+
+    // Hook
+    report.add_break();
+    let exists_desc = if !data.runtime_upgrade.hook.filename_exists {
+        "❌"
+    } else {
+        ""
+    };
+    report.add_text(format!(
+        "🪝 <code>{}</code> {}",
+        data.runtime_upgrade.hook.filename, exists_desc
+    ));
+
+    for line in data.runtime_upgrade.hook.matrix_lines() {
+        report.add_raw_text(format!("‣ {}", line));
+    }
+
+    report
+}
+
+/// Returns the url of the address in the block explorer defined, with `{chain}` and
+/// `{address}` replaced, by default the subscan page of the address
+fn explorer_url(network: &Network, path: &str, address: &str) -> String {
+    let chain = network.name.to_lowercase();
+    if CONFIG.current().block_explorer_url.is_empty() {
+        return format!("https://{}.subscan.io/{}/{}", chain, path, address);
+    }
+    CONFIG
+        .current()
+        .block_explorer_url
+        .replace("{chain}", &chain)
+        .replace("{address}", address)
+}
+
+/// Returns the link of the stash in the block explorer, labelled with the identity
+/// followed by the short address of the stash
+fn validator_link(network: &Network, validator: &Validator) -> String {
+    format!(
+        "<a href=\"{}\">{}</a>",
+        explorer_url(network, "validator", &validator.stash.to_string()),
+        validator.display_name()
+    )
+}
+
 fn session_flag(index: u32) -> String {
     match index {
         1 => "🎬".to_string(),
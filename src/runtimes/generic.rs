@@ -0,0 +1,485 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// Generic runtime for substrate-based chains that are not natively supported but use
+// the standard session and staking pallets. Storage items and events are looked up by
+// name at runtime from the chain metadata, instead of the statically generated modules.
+
+use crate::config::CONFIG;
+use crate::errors::ScoutyError;
+use crate::healthcheck;
+use crate::hooks::{
+    HookPayload, HOOK_NEW_SESSION, HOOK_VALIDATOR_STARTS_ACTIVE_NEXT_ERA,
+    HOOK_VALIDATOR_STARTS_INACTIVE_NEXT_ERA,
+};
+use crate::report::{Network, RawData, Report, Section, Session, Validator, Validators};
+use crate::scouty::Scouty;
+use log::{debug, info, warn};
+use std::{result::Result, str::FromStr};
+use subxt::{
+    dynamic::{self, At, Value},
+    storage::DynamicAddress,
+    utils::AccountId32,
+};
+
+/// Storage items queried by the generic runtime, as (pallet, entry) names
+const STORAGE_ENTRIES: [(&str, &str); 6] = [
+    ("Session", "CurrentIndex"),
+    ("Session", "Validators"),
+    ("Session", "QueuedChanged"),
+    ("Staking", "ActiveEra"),
+    ("Staking", "ErasStartSessionIndex"),
+    ("Staking", "ErasStakers"),
+];
+
+/// Verify that the storage items queried by the generic runtime are available in the
+/// metadata of the connected chain
+pub fn validate_codegen(scouty: &Scouty) -> Result<(), ScoutyError> {
+    let metadata = scouty.client().metadata();
+    for (pallet_name, entry_name) in STORAGE_ENTRIES {
+        if metadata
+            .pallet_by_name(pallet_name)
+            .and_then(|pallet| pallet.storage())
+            .and_then(|storage| storage.entry_by_name(entry_name))
+            .is_none()
+        {
+            return Err(ScoutyError::Other(format!(
+                "Storage {}.{} not available in the chain metadata",
+                pallet_name, entry_name
+            )));
+        }
+    }
+    if metadata
+        .pallet_by_name("Staking")
+        .and_then(|pallet| pallet.constant_by_name("SessionsPerEra"))
+        .is_none()
+    {
+        return Err("Constant Staking.SessionsPerEra not available".into());
+    }
+    Ok(())
+}
+
+pub async fn init_and_subscribe_on_chain_events(
+    scouty: &Scouty,
+) -> Result<(), ScoutyError> {
+    let api = scouty.client().clone();
+
+    // Stop straight away if the chain does not provide the pallets required
+    validate_codegen(&scouty)?;
+
+    info!("Subscribe on-chain finalized blocks (generic runtime)");
+    let mut blocks_sub = api.blocks().subscribe_finalized().await?;
+    while let Some(block) = blocks_sub.next().await {
+        let block = block?;
+        let block_number = block.header().number;
+        let events = block.events().await?;
+        scouty
+            .status()
+            .update(|status| status.block_number = block_number);
+
+        // Event --> session::NewSession
+        for event in events.iter() {
+            let event = event?;
+            if event.pallet_name() == "Session" && event.variant_name() == "NewSession" {
+                try_run_session_hooks(&scouty, block_number).await?;
+
+                // Let the uptime service know that the session has been processed
+                healthcheck::ping_success();
+                break;
+            }
+        }
+    }
+    // If subscription has closed for some reason await and subscribe again
+    Err(ScoutyError::SubscriptionFinished)
+}
+
+async fn try_run_session_hooks(
+    scouty: &Scouty,
+    block_number: u32,
+) -> Result<(), ScoutyError> {
+    let config = CONFIG.current();
+
+    // Collect session data
+    let session = collect_session_data(&scouty).await?;
+    let sessions_per_era = get_sessions_per_era(&scouty)?;
+
+    let network = Network::load(scouty.rpc()).await?;
+    debug!("network {:?}", network);
+
+    // Collect validators info based on config stashes
+    let mut validators =
+        collect_validators_data(&scouty, session.active_era_index).await?;
+
+    // Update the chain status shared with the matrix bot commands, era points are not
+    // available in the generic runtime
+    scouty.status().update(|status| {
+        status.session_index = Some(session.current_session_index);
+        status.era_index = Some(session.active_era_index);
+        status.eras_session_index = session.eras_session_index;
+        status.sessions_per_era = sessions_per_era;
+        for v in validators.iter() {
+            let stash = status
+                .stashes
+                .entry(scouty.to_ss58(v.stash.clone()))
+                .or_default();
+            stash.name = v.name.to_string();
+            stash.is_active = v.is_active;
+            stash.is_queued = v.is_queued;
+        }
+    });
+
+    // Try to run hooks for each stash
+    for v in validators.iter_mut() {
+        // Try HOOK_NEW_SESSION
+        // NOTE: Positional arguments are kept in the same order as the native runtimes,
+        // data not available in the generic runtime is set to "-"
+        let mut args = vec![
+            scouty.to_ss58(v.stash.clone()),
+            v.name.to_string(),
+            "-".to_string(),
+            v.is_active.to_string(),
+            v.is_queued.to_string(),
+            session.active_era_index.to_string(),
+            session.current_session_index.to_string(),
+            session.eras_session_index.to_string(),
+            block_number.to_string(),
+        ];
+
+        let mut payload = HookPayload::new()
+            .validator(
+                scouty.to_ss58(v.stash.clone()),
+                v.name.to_string(),
+                "-".to_string(),
+                v.is_active,
+                v.is_queued,
+            )
+            .session(
+                session.active_era_index,
+                session.current_session_index,
+                Some(session.eras_session_index),
+            )
+            .with("block_number", block_number);
+
+        if config.expose_network || config.expose_all {
+            args.push(network.name.to_string());
+            args.push(network.token_symbol.to_string());
+            args.push(network.token_decimals.to_string());
+            payload = payload.network(&network);
+        } else {
+            args.push("-".to_string());
+            args.push("-".to_string());
+            args.push("-".to_string());
+        }
+
+        // Nominators, authored blocks, all nominators and para validator details
+        args.extend(vec!["-".to_string(); 11]);
+
+        // Try run hook
+        let hook = scouty
+            .try_run_hook(
+                HOOK_NEW_SESSION,
+                &config.hook_new_session_path,
+                args,
+                payload,
+            )
+            .await;
+        v.hooks.push(hook);
+
+        if is_last_session_of_era(session.eras_session_index, sessions_per_era)
+            && session.queued_session_keys_changed
+        {
+            let next_era_index = session.active_era_index + 1;
+            let next_session_index = session.current_session_index + 1;
+            let mut args = vec![
+                scouty.to_ss58(v.stash.clone()),
+                v.name.to_string(),
+                "-".to_string(),
+                format!("{}", next_era_index),
+                format!("{}", next_session_index),
+            ];
+
+            let mut payload = HookPayload::new()
+                .validator(
+                    scouty.to_ss58(v.stash.clone()),
+                    v.name.to_string(),
+                    "-".to_string(),
+                    v.is_active,
+                    v.is_queued,
+                )
+                .session(next_era_index, next_session_index, None);
+
+            if config.expose_network || config.expose_all {
+                args.push(network.name.to_string());
+                args.push(network.token_symbol.to_string());
+                args.push(network.token_decimals.to_string());
+                payload = payload.network(&network);
+            } else {
+                args.push("-".to_string());
+                args.push("-".to_string());
+                args.push("-".to_string());
+            }
+
+            // Try HOOK_VALIDATOR_STARTS_ACTIVE_NEXT_ERA
+            if !v.is_active && v.is_queued {
+                let hook = scouty
+                    .try_run_hook(
+                        HOOK_VALIDATOR_STARTS_ACTIVE_NEXT_ERA,
+                        &config.hook_validator_starts_active_next_era_path,
+                        args.clone(),
+                        payload.clone(),
+                    )
+                    .await;
+                v.hooks.push(hook);
+            }
+
+            // Try HOOK_VALIDATOR_STARTS_INACTIVE_NEXT_ERA
+            if v.is_active && !v.is_queued {
+                let hook = scouty
+                    .try_run_hook(
+                        HOOK_VALIDATOR_STARTS_INACTIVE_NEXT_ERA,
+                        &config.hook_validator_starts_inactive_next_era_path,
+                        args.clone(),
+                        payload.clone(),
+                    )
+                    .await;
+                v.hooks.push(hook);
+            }
+        }
+    }
+
+    // Prepare notification report
+    debug!("validators {:?}", validators);
+
+    let data = RawData {
+        network,
+        session,
+        validators,
+        section: Section::Session,
+        ..Default::default()
+    };
+
+    let report = Report::from(data);
+    scouty.send_report(&report).await?;
+
+    Ok(())
+}
+
+/// Run the checks of a new session once for the current session, as if the session had
+/// just started, so that the stashes can be checked without subscribing on-chain events
+pub async fn run_session_checks_once(scouty: &Scouty) -> Result<(), ScoutyError> {
+    validate_codegen(&scouty)?;
+    let block_number = scouty.client().blocks().at_latest().await?.number();
+    try_run_session_hooks(&scouty, block_number).await
+}
+
+async fn collect_session_data(scouty: &Scouty) -> Result<Session, ScoutyError> {
+    // Get Era index
+    let active_era_index = match fetch_value(
+        &scouty,
+        dynamic::storage("Staking", "ActiveEra", Vec::<Value>::new()),
+    )
+    .await?
+    .as_ref()
+    .and_then(|info| info.at("index"))
+    .and_then(|index| index.as_u128())
+    {
+        Some(index) => index as u32,
+        None => return Err("Current era index not defined".into()),
+    };
+
+    // Get current session
+    let current_session_index = fetch_u32(
+        &scouty,
+        dynamic::storage("Session", "CurrentIndex", Vec::<Value>::new()),
+    )
+    .await?
+    .unwrap_or_default();
+
+    // Get start session index
+    let start_session_index = fetch_u32(
+        &scouty,
+        dynamic::storage(
+            "Staking",
+            "ErasStartSessionIndex",
+            vec![Value::u128(active_era_index as u128)],
+        ),
+    )
+    .await?
+    .unwrap_or(current_session_index);
+
+    // Get session keys queued status
+    let queued_session_keys_changed = fetch_value(
+        &scouty,
+        dynamic::storage("Session", "QueuedChanged", Vec::<Value>::new()),
+    )
+    .await?
+    .and_then(|value| value.as_bool())
+    .unwrap_or_default();
+
+    let session = Session {
+        active_era_index,
+        current_session_index,
+        eras_session_index: 1 + current_session_index - start_session_index,
+        queued_session_keys_changed,
+        era_start_session_index: start_session_index,
+        ..Default::default()
+    };
+    debug!("session {:?}", session);
+
+    Ok(session)
+}
+
+async fn collect_validators_data(
+    scouty: &Scouty,
+    active_era_index: u32,
+) -> Result<Validators, ScoutyError> {
+    let api = scouty.client().clone();
+    let config = CONFIG.current();
+
+    // Verify session active validators
+    let active_validators: Vec<AccountId32> = match api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&dynamic::storage(
+            "Session",
+            "Validators",
+            Vec::<Value>::new(),
+        ))
+        .await?
+    {
+        Some(thunk) => thunk.as_type().map_err(subxt::Error::from)?,
+        None => Vec::new(),
+    };
+
+    let mut validators: Validators = Vec::new();
+    for stash_str in config
+        .validator_stashes(&scouty.runtime().to_string())
+        .iter()
+    {
+        let stash = AccountId32::from_str(stash_str).map_err(|e| {
+            ScoutyError::Other(format!(
+                "Invalid SS58 format account: {:?} error: {e:?}",
+                stash_str
+            ))
+        })?;
+        let mut v = Validator::new(stash.clone());
+
+        // Check if validator is in active set
+        v.is_active = active_validators.contains(&v.stash);
+
+        // Check if validator has been elected for the next era. Note that the
+        // exposure of the next era is only available on the last session of an era
+        let exposure = match fetch_value(
+            &scouty,
+            dynamic::storage(
+                "Staking",
+                "ErasStakers",
+                vec![
+                    Value::u128((active_era_index + 1) as u128),
+                    Value::from_bytes(&stash),
+                ],
+            ),
+        )
+        .await
+        {
+            Ok(exposure) => exposure,
+            Err(e) => {
+                // A failure querying one stash must not abort the processing of the
+                // others, the stash is skipped until the next session
+                warn!(
+                    "Exposure of {} not available -> skipped! {}",
+                    scouty.to_ss58(stash.clone()),
+                    e
+                );
+                continue;
+            }
+        };
+        v.is_queued = exposure
+            .as_ref()
+            .and_then(|exposure| exposure.at("total"))
+            .and_then(|total| total.as_u128())
+            .is_some_and(|total| total > 0);
+
+        validators.push(v);
+    }
+
+    debug!("validators {:?}", validators);
+    Ok(validators)
+}
+
+fn get_sessions_per_era(scouty: &Scouty) -> Result<u32, ScoutyError> {
+    let sessions_per_era = scouty
+        .client()
+        .constants()
+        .at(&dynamic::constant("Staking", "SessionsPerEra"))?
+        .to_value()?;
+    match sessions_per_era.as_u128() {
+        Some(sessions_per_era) => Ok(sessions_per_era as u32),
+        None => Err("Constant Staking.SessionsPerEra not defined".into()),
+    }
+}
+
+async fn fetch_value(
+    scouty: &Scouty,
+    address: DynamicAddress<Value>,
+) -> Result<Option<Value<u32>>, ScoutyError> {
+    match scouty
+        .client()
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&address)
+        .await?
+    {
+        Some(thunk) => Ok(Some(thunk.to_value()?)),
+        None => Ok(None),
+    }
+}
+
+async fn fetch_u32(
+    scouty: &Scouty,
+    address: DynamicAddress<Value>,
+) -> Result<Option<u32>, ScoutyError> {
+    Ok(fetch_value(scouty, address)
+        .await?
+        .and_then(|value| value.as_u128())
+        .map(|value| value as u32))
+}
+
+/// Returns true if the session is the last one of the era, in which case the
+/// validators elected for the next era are already known
+fn is_last_session_of_era(eras_session_index: u32, sessions_per_era: u32) -> bool {
+    sessions_per_era > 0 && eras_session_index == sessions_per_era
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_detects_the_last_session_of_an_era() {
+        assert!(is_last_session_of_era(6, 6));
+        assert!(is_last_session_of_era(1, 1));
+        assert!(!is_last_session_of_era(5, 6));
+        assert!(!is_last_session_of_era(0, 0));
+    }
+}
@@ -0,0 +1,6027 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+// Shared implementation for all the relay chain runtimes. This file is included by
+// each runtime module right after the `node_runtime` module has been generated
+// from the runtime metadata, e.g.
+//
+// #[subxt::subxt(runtime_metadata_path = "metadata/kusama_metadata.scale")]
+// mod node_runtime {}
+//
+// include!("relay_chain.rs");
+use crate::authority::{AuthorityIndex, AuthorityRecords};
+use crate::backing::{BackingRecords, ParaAssignment, SessionBacking};
+use crate::catch_up::{CatchUp, MissedEra};
+use crate::config::CONFIG;
+use crate::era_digest::{EraDigestRecords, ReportMode};
+use crate::era_points::EraPointsRecords;
+use crate::errors::ScoutyError;
+use crate::healthcheck;
+use crate::hooks::{
+    Hook, HookPayload, HOOK_CATCH_UP, HOOK_COMMISSION_OUT_OF_POLICY, HOOK_DISPUTE,
+    HOOK_ERA_PAID, HOOK_INIT, HOOK_LOW_BALANCE, HOOK_NEW_ERA, HOOK_NEW_SESSION,
+    HOOK_NOMINATION_INACTIVE, HOOK_OFFENCE_REPORTED, HOOK_PAYEE_CHANGED,
+    HOOK_PAYOUT_SUBMITTED, HOOK_REFERENDA_SUBMITTED, HOOK_SESSION_KEYS_MISMATCH,
+    HOOK_VALIDATOR_CHILLED, HOOK_VALIDATOR_DISABLED, HOOK_VALIDATOR_OFFLINE_RISK,
+    HOOK_VALIDATOR_PREFS_CHANGED, HOOK_VALIDATOR_SLASHED,
+    HOOK_VALIDATOR_STARTS_ACTIVE_NEXT_ERA, HOOK_VALIDATOR_STARTS_INACTIVE_NEXT_ERA,
+};
+use crate::identity::{self, Identity};
+use crate::matrix::Severity;
+use crate::minimums::StakingMinimums;
+use crate::nomination::{Nomination, NominationTarget};
+use crate::nominators::{Backing, NominatorChanges, NominatorRecords};
+use crate::para::ParaRecords;
+use crate::payouts::{self, PayoutSigner, UnclaimedEra};
+use crate::pools::{
+    pool_account, Pool, PoolSnapshot, POOL_BONDED_ACCOUNT, POOL_STATE_BLOCKED,
+    POOL_STATE_DESTROYED, POOL_STATE_DESTROYING, POOL_STATE_OPEN,
+};
+use crate::report::{
+    Chill, CommissionPolicy, DisputeVote, EraPayout, Init, Network, OfflineRisk, Payee,
+    PayeeChange, Payout, Points, Prefs, PrefsChange, RawData, Referendum, Report,
+    Section, Session, Slash, Stake, StakeChange, Validator, Validators, WatchedBalance,
+};
+use crate::scouty::{get_account_id_from_storage_key, Scouty};
+use crate::state::{self, ChainState, StashState};
+use crate::stats;
+use crate::waiting::{self, WaitingPosition, WaitingRecords};
+use async_recursion::async_recursion;
+use codec::{Decode, Encode};
+use log::{debug, info, warn};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    convert::TryInto,
+    ops::Range,
+    result::Result,
+    str::FromStr,
+};
+use subxt::{
+    blocks::Block,
+    config::substrate::{Digest, DigestItem},
+    dynamic::{self, Value},
+    events::{Events, Phase},
+    ext::sp_core::{hexdisplay::HexDisplay, H256},
+    utils::{
+        bits::{DecodedBits, Lsb0},
+        AccountId32,
+    },
+    OnlineClient, PolkadotConfig,
+};
+
+use node_runtime::{
+    // im_online::events::SomeOffline,
+    offences::events::Offence,
+    para_inclusion::events::CandidateBacked,
+    para_inherent::calls::types::Enter,
+    paras_disputes::events::{DisputeConcluded, DisputeInitiated},
+    referenda::events::Submitted,
+    runtime_types::{
+        bounded_collections::bounded_vec::BoundedVec,
+        pallet_identity::types::Data,
+        polkadot_primitives::v6::{
+            DisputeStatement, ValidatorIndex, ValidityAttestation,
+        },
+        sp_consensus_babe::digests::PreDigest,
+    },
+    session::events::NewSession,
+    staking::events::Chilled,
+    staking::events::EraPaid,
+    staking::events::Rewarded,
+    staking::events::Slashed,
+    transaction_payment::events::TransactionFeePaid,
+};
+
+const ERAS_PER_DAY: u32 = 4;
+
+/// Maximum time (in seconds) waited for a payout submitted to be finalized
+const PAYOUT_FINALIZATION_TIMEOUT: u64 = 300;
+
+/// Verify that the storage items queried by 'scouty' are still compatible with the
+/// metadata in use by the client (e.g. after a runtime upgrade)
+pub fn validate_codegen(scouty: &Scouty) -> Result<(), ScoutyError> {
+    let storage = scouty.client().storage();
+    storage.validate(&node_runtime::storage().system().number())?;
+    storage.validate(&node_runtime::storage().timestamp().now())?;
+    storage.validate(&node_runtime::storage().session().current_index())?;
+    storage.validate(&node_runtime::storage().session().validators())?;
+    storage.validate(&node_runtime::storage().session().queued_changed())?;
+    storage.validate(&node_runtime::storage().session().queued_keys())?;
+    storage.validate(&node_runtime::storage().session().next_keys_iter())?;
+    storage.validate(&node_runtime::storage().staking().active_era())?;
+    storage.validate(&node_runtime::storage().staking().bonded_iter())?;
+    storage.validate(&node_runtime::storage().staking().ledger_iter())?;
+    storage.validate(&node_runtime::storage().staking().nominators_iter())?;
+    storage.validate(&node_runtime::storage().staking().validators_iter())?;
+    storage.validate(&node_runtime::storage().staking().eras_reward_points_iter())?;
+    storage.validate(
+        &node_runtime::storage()
+            .staking()
+            .validator_slash_in_era_iter(),
+    )?;
+    storage.validate(
+        &node_runtime::storage()
+            .staking()
+            .eras_validator_reward_iter(),
+    )?;
+    storage.validate(&node_runtime::storage().staking().eras_stakers_paged_iter())?;
+    storage.validate(
+        &node_runtime::storage()
+            .staking()
+            .eras_stakers_overview_iter(),
+    )?;
+    storage.validate(
+        &node_runtime::storage()
+            .staking()
+            .eras_start_session_index_iter(),
+    )?;
+    if is_identity_available(scouty) {
+        storage.validate(&node_runtime::storage().identity().identity_of_iter())?;
+        storage.validate(&node_runtime::storage().identity().super_of_iter())?;
+    }
+    storage.validate(&node_runtime::storage().staking().claimed_rewards_iter())?;
+    storage.validate(&node_runtime::storage().offences().reports_iter())?;
+    storage.validate(&node_runtime::storage().babe().genesis_slot())?;
+    storage.validate(&node_runtime::storage().babe().current_slot())?;
+    storage.validate(&node_runtime::storage().babe().epoch_index())?;
+    storage.validate(
+        &node_runtime::storage()
+            .offences()
+            .concurrent_reports_index_iter(),
+    )?;
+    storage.validate(
+        &node_runtime::storage()
+            .paras_shared()
+            .active_validator_indices(),
+    )?;
+    if scouty
+        .client()
+        .metadata()
+        .pallet_by_name("ParasDisputes")
+        .is_some()
+    {
+        storage.validate(
+            &node_runtime::storage()
+                .para_session_info()
+                .account_keys_iter(),
+        )?;
+        storage.validate(&node_runtime::storage().para_session_info().sessions_iter())?;
+    }
+    Ok(())
+}
+
+pub async fn init_and_subscribe_on_chain_events(
+    scouty: &Scouty,
+) -> Result<(), ScoutyError> {
+    let api = scouty.client().clone();
+
+    // Initialize authority records
+    let mut authority_records = AuthorityRecords::new();
+    init_authority_records(&scouty, &mut authority_records).await?;
+
+    // Initialize para records
+    let mut para_records = ParaRecords::new();
+    init_para_records(&scouty, &mut para_records).await?;
+
+    // Era points of the stashes are tracked from the next session
+    let mut era_points_records = EraPointsRecords::new();
+
+    // Stashes chilled in the active era, to explain why a stash is inactive next era
+    let mut chilled_stashes: BTreeSet<AccountId32> = BTreeSet::new();
+
+    // Backing of the stashes in the last era, to be compared with the next era
+    let mut nominator_records = NominatorRecords::new();
+
+    // Gap to the minimum elected stake of the waiting stashes in the last era
+    let mut waiting_records = WaitingRecords::new();
+
+    // Backing votes of the para validator stashes are tracked from the next session
+    let mut backing_records = BackingRecords::new();
+
+    // Sessions of the stashes summarized at every new era, if reported per era
+    let mut era_digest_records = EraDigestRecords::new();
+
+    // Initialize the stashes predefined that are currently intending to validate
+    let mut validator_intentions = get_validator_intentions(&scouty).await?;
+
+    // im-online has been removed from some chains, in which case the offline risk
+    // check is skipped
+    let is_im_online_available = api.metadata().pallet_by_name("ImOnline").is_some();
+    if !is_im_online_available {
+        debug!("ImOnline pallet not available -> skip offline risk check");
+    }
+    let mut offline_risk_session: Option<u32> = None;
+
+    // The disputes pallet is not available in every chain (e.g. older Westend runtimes),
+    // in which case disputes are skipped
+    let is_disputes_available = api.metadata().pallet_by_name("ParasDisputes").is_some();
+    if !is_disputes_available {
+        debug!("ParasDisputes pallet not available -> skip disputes");
+    }
+    let mut active_disputes: BTreeSet<H256> = BTreeSet::new();
+
+    // Backing votes are only tracked in the chains with the para inclusion pallet
+    let is_backing_available = is_backing_available(&scouty);
+    if !is_backing_available {
+        debug!("ParaInclusion pallet not available -> skip backing votes");
+    }
+
+    // Verify straight away that the node owns the next session keys
+    let mut session_keys_mismatches: BTreeMap<AccountId32, bool> = BTreeMap::new();
+    try_run_session_keys_mismatch_hook(&scouty, &mut session_keys_mismatches).await?;
+
+    // The first validator preferences observed are the baseline, no alert is sent
+    let mut validator_prefs: BTreeMap<AccountId32, Prefs> = BTreeMap::new();
+    try_run_validator_prefs_changed_hook(&scouty, &mut validator_prefs).await?;
+
+    // Verify straight away that the commission is within the policy
+    let mut commission_out_of_policy: BTreeMap<AccountId32, bool> = BTreeMap::new();
+    try_run_commission_out_of_policy_hook(
+        &scouty,
+        &validator_prefs,
+        &mut commission_out_of_policy,
+    )
+    .await?;
+
+    // The first reward destinations observed are the baseline, no alert is sent
+    let mut payees: BTreeMap<AccountId32, Payee> = BTreeMap::new();
+    try_run_payee_changed_hook(&scouty, &mut payees).await?;
+
+    // Verify straight away that the stashes are not disabled in the current session
+    let mut disabled_validators: BTreeMap<AccountId32, bool> = BTreeMap::new();
+    try_run_validator_disabled_hook(&scouty, &mut disabled_validators).await?;
+
+    // Verify straight away that the free balance of the accounts watched is above the
+    // minimum defined
+    let mut low_balances: BTreeMap<AccountId32, bool> = BTreeMap::new();
+    try_run_low_balance_hook(&scouty, &mut low_balances).await?;
+
+    // Sessions and eras already processed before a restart are not notified again
+    let chain_state = state::get(&scouty.runtime().to_string());
+    info!(
+        "Last session processed: {:?}, last era paid processed: {:?}",
+        chain_state.session_index, chain_state.era_paid_index
+    );
+
+    // Summarize the eras missed while scouty was down
+    try_run_catch_up(&scouty, &chain_state).await?;
+
+    // Start by calling init hook
+    try_init_hook(&scouty, &authority_records, &para_records).await?;
+    //
+    info!("Subscribe on-chain finalized blocks");
+    let mut blocks_sub = api.blocks().subscribe_finalized().await?;
+    while let Some(block) = blocks_sub.next().await {
+        let block = block?;
+        let block_number = block.header().number;
+        let events = block.events().await?;
+        let block_hash = events.block_hash();
+
+        if let Some(authority_index) =
+            get_authority_index(&scouty, Some(block_hash)).await?
+        {
+            // Event --> session::NewSession
+            let event = events.find_first::<NewSession>()?;
+            try_run_session_hooks(
+                &scouty,
+                event,
+                &mut authority_records,
+                block_number,
+                authority_index,
+                &mut para_records,
+                &mut era_points_records,
+                &mut chilled_stashes,
+                &mut nominator_records,
+                &mut waiting_records,
+                &mut backing_records,
+                &mut era_digest_records,
+                &session_keys_mismatches,
+            )
+            .await?;
+
+            // Event --> staking::EraPaid
+            let event = events.find_first::<EraPaid>()?;
+            let is_era_paid = event.is_some();
+            try_run_era_paid_hook(&scouty, event).await?;
+
+            // Submit the payouts of the unclaimed eras once the era has been paid,
+            // failures are reported without stopping the subscription
+            if is_era_paid {
+                if let Err(e) = try_run_payouts(&scouty).await {
+                    if e.is_connection_error() {
+                        return Err(e);
+                    }
+                    warn!("Payouts not submitted: {}", e);
+                }
+                try_run_unclaimed_eras_check(&scouty).await?;
+            }
+
+            // Events --> staking::Slashed and offences::Offence
+            let slashes = events.find::<Slashed>().collect::<Result<Vec<_>, _>>()?;
+            let offences = events.find::<Offence>().collect::<Result<Vec<_>, _>>()?;
+            let has_offences = !offences.is_empty();
+            try_run_staking_slashed_hooks(&scouty, slashes, offences).await?;
+
+            // Validators might be disabled for the remainder of the session after an
+            // offence, and are enabled again on a new session
+            if has_offences || events.has::<NewSession>()? {
+                try_run_validator_disabled_hook(&scouty, &mut disabled_validators)
+                    .await?;
+            }
+
+            // Event --> staking::Chilled
+            for event in events.iter() {
+                let event = event?;
+                if let Some(chilled) = event.as_event::<Chilled>()? {
+                    if is_stash_predefined(&chilled.stash) {
+                        let reason = get_chill_reason(&block, event.phase()).await?;
+                        validator_intentions.retain(|stash| stash != &chilled.stash);
+                        chilled_stashes.insert(chilled.stash.clone());
+                        try_run_staking_chilled_hook(&scouty, chilled.stash, reason)
+                            .await?;
+                    }
+                }
+            }
+
+            // Events --> parasDisputes::DisputeInitiated and DisputeConcluded
+            if is_disputes_available {
+                for event in events.find::<DisputeInitiated>() {
+                    active_disputes.insert(event?.0 .0);
+                }
+                // Votes are only looked up in the blocks while disputes are ongoing
+                if !active_disputes.is_empty() {
+                    try_run_dispute_hooks(&scouty, &block).await?;
+                }
+                for event in events.find::<DisputeConcluded>() {
+                    active_disputes.remove(&event?.0 .0);
+                }
+            }
+
+            // Events --> paraInclusion::CandidateBacked
+            if is_backing_available && !backing_records.is_empty() {
+                track_backed_candidates(&block, &events, &mut backing_records).await?;
+            }
+
+            // Validator intentions removed without a chilled event are only
+            // verified at every new session
+            if events.has::<NewSession>()? {
+                let intentions = get_validator_intentions(&scouty).await?;
+                for stash in validator_intentions
+                    .iter()
+                    .filter(|stash| !intentions.contains(stash))
+                {
+                    let reason = "Validator intention removed since the previous session";
+                    try_run_staking_chilled_hook(&scouty, stash.clone(), reason.into())
+                        .await?;
+                }
+                validator_intentions = intentions;
+
+                try_run_session_keys_mismatch_hook(&scouty, &mut session_keys_mismatches)
+                    .await?;
+
+                try_run_validator_prefs_changed_hook(&scouty, &mut validator_prefs)
+                    .await?;
+
+                try_run_commission_out_of_policy_hook(
+                    &scouty,
+                    &validator_prefs,
+                    &mut commission_out_of_policy,
+                )
+                .await?;
+
+                try_run_payee_changed_hook(&scouty, &mut payees).await?;
+
+                try_run_low_balance_hook(&scouty, &mut low_balances).await?;
+            }
+
+            // Verify heartbeats and authored blocks once, late in every session
+            if is_im_online_available {
+                try_run_offline_risk_hook(&scouty, &mut offline_risk_session).await?;
+            }
+
+            // // Event --> im_online::SomeOffline
+            // let event = events.find_first::<SomeOffline>()?;
+            // try_run_im_online_some_offline_hook(&scouty, event).await?;
+
+            // Event --> referenda::Submitted
+            let event = events.find_first::<Submitted>()?;
+            try_run_referenda_submitted_hook(&scouty, event).await?;
+
+            // Track authority record
+            authority_records.insert_record(
+                &scouty.runtime().to_string(),
+                block_number,
+                Some(authority_index),
+            )?;
+        }
+
+        // Keep the era points up to date for the matrix bot commands and the metrics
+        if CONFIG.current().matrix_commands_enabled
+            || CONFIG.current().prometheus_port > 0
+        {
+            update_status_era_points(&scouty).await?;
+        }
+        scouty
+            .status()
+            .update(|status| status.block_number = block_number);
+    }
+    // If subscription has closed for some reason await and subscribe again
+    Err(ScoutyError::SubscriptionFinished)
+}
+
+async fn try_init_hook(
+    scouty: &Scouty,
+    authority_records: &AuthorityRecords,
+    para_records: &ParaRecords,
+) -> Result<(), ScoutyError> {
+    let api = scouty.client().clone();
+    let config = CONFIG.current();
+
+    // Get the current block number being processed
+    let block_number_addr = node_runtime::storage().system().number();
+    let block_number = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&block_number_addr)
+        .await?
+        .unwrap();
+
+    // timestamp of current block
+    let now_addr = node_runtime::storage().timestamp().now();
+    let now = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&now_addr)
+        .await?
+        .unwrap();
+
+    let init = Init { block_number, now };
+
+    // Collect session data
+    let current_session_index_addr = node_runtime::storage().session().current_index();
+    let current_session_index = match api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&current_session_index_addr)
+        .await?
+    {
+        Some(index) => index,
+        None => return Err("Current session index not defined".into()),
+    };
+
+    let session = collect_session_data(&scouty, current_session_index).await?;
+
+    let network = Network::load(scouty.rpc()).await?;
+    debug!("network {:?}", network);
+
+    // Sync all nominators
+    let all_nominators_map = if config.expose_all_nominators || config.expose_all {
+        get_nominators(&scouty).await?
+    } else {
+        BTreeMap::new()
+    };
+
+    // Fetch era reward points from previous era
+    let era_reward_points_addr = node_runtime::storage()
+        .staking()
+        .eras_reward_points(&(session.active_era_index - 1));
+    let era_reward_points = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&era_reward_points_addr)
+        .await?
+        .unwrap();
+
+    // Collect previusly era reward
+    let era_reward_addr = node_runtime::storage()
+        .staking()
+        .eras_validator_reward(&(session.active_era_index - 1));
+    let era_reward = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&era_reward_addr)
+        .await?
+        .unwrap();
+
+    // Collect session active validators
+    let validators_addr = node_runtime::storage().session().validators();
+    let active_validators = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&validators_addr)
+        .await?
+        .unwrap();
+
+    // Collect validators info based on config stashes
+    let mut validators = collect_validators_data(&scouty).await?;
+    update_status(&scouty, &session, &validators)?;
+
+    // Try to run hooks for each stash
+    for v in validators.iter_mut() {
+        // Try HOOK_INIT
+        let mut args = vec![
+            scouty.to_ss58(v.stash.clone()),
+            v.name.to_string(),
+            format!("0x{:?}", HexDisplay::from(&v.queued_session_keys)),
+            v.is_active.to_string(),
+            v.is_queued.to_string(),
+            session.active_era_index.to_string(),
+            session.current_session_index.to_string(),
+            session.eras_session_index.to_string(),
+            block_number.to_string(),
+        ];
+
+        info!("__ {:?}", args);
+
+        let mut payload = HookPayload::new()
+            .validator(
+                scouty.to_ss58(v.stash.clone()),
+                v.name.to_string(),
+                format!("0x{:?}", HexDisplay::from(&v.queued_session_keys)),
+                v.is_active,
+                v.is_queued,
+            )
+            .session(
+                session.active_era_index,
+                session.current_session_index,
+                Some(session.eras_session_index),
+            )
+            .with("block_number", block_number);
+
+        if config.expose_network || config.expose_all {
+            args.push(network.name.to_string());
+            args.push(network.token_symbol.to_string());
+            args.push(network.token_decimals.to_string());
+            payload = payload.network(&network);
+        } else {
+            args.push("-".to_string());
+            args.push("-".to_string());
+            args.push("-".to_string());
+        }
+
+        if v.is_active && (config.expose_nominators || config.expose_all) {
+            // get active nominators info
+            let (
+                total_active_stake,
+                own_stake,
+                active_nominators,
+                active_nominators_stake,
+            ) = get_active_nominators(&scouty, session.active_era_index, &v.stash)
+                .await?;
+
+            info!(
+                "__ {:?} {:?} {:?} {:?}",
+                total_active_stake, own_stake, active_nominators, active_nominators_stake
+            );
+            // calculate APR
+            let apr = calculate_projected_apr(
+                &scouty,
+                &v.stash,
+                network.token_decimals,
+                total_active_stake,
+                era_reward,
+                active_validators.len().try_into().unwrap(),
+            )
+            .await?;
+            //
+            args.push(format!("{:.2}", apr * 100.0));
+            args.push(total_active_stake.to_string());
+            args.push(own_stake.to_string());
+            payload = payload
+                .with("projected_apr", format!("{:.2}", apr * 100.0))
+                .with("total_stake", total_active_stake.to_string())
+                .with("own_stake", own_stake.to_string());
+            args.push(active_nominators.join(",").to_string());
+            args.push(
+                active_nominators_stake
+                    .iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<String>>()
+                    .join(","),
+            );
+        } else {
+            args.push("-".to_string());
+            args.push("-".to_string());
+            args.push("-".to_string());
+            args.push("-".to_string());
+            args.push("-".to_string());
+        }
+
+        if v.is_active && (config.expose_authored_blocks || config.expose_all) {
+            let current_session_total = authority_records.current_session_total(&v.stash);
+            args.push(current_session_total.to_string());
+            args.push("-".to_string());
+        } else {
+            args.push("-".to_string());
+            args.push("-".to_string());
+        }
+
+        if config.expose_all_nominators || config.expose_all {
+            if let Some(all_nominators) = all_nominators_map.get(&v.stash.to_string()) {
+                let all_nominators_stashes = all_nominators
+                    .iter()
+                    .map(|(x, _, _)| x.to_string())
+                    .collect::<Vec<String>>()
+                    .join(",");
+
+                let total_nominators_stake: u128 =
+                    all_nominators.iter().map(|(_, x, _)| x).sum();
+
+                let raw_nominees_stake: u128 =
+                    all_nominators.iter().map(|(_, x, y)| x / *y as u128).sum();
+                args.push(all_nominators_stashes.to_string());
+                args.push(format!("{},{}", total_nominators_stake, raw_nominees_stake));
+            } else {
+                args.push("-".to_string());
+                args.push("-".to_string());
+            }
+        } else {
+            args.push("-".to_string());
+            args.push("-".to_string());
+        }
+
+        if v.is_active && (config.expose_para_validator || config.expose_all) {
+            let is_para_validator = para_records.is_para_validator(&v.stash);
+            args.push(is_para_validator.to_string());
+            args.push("-".to_string());
+        } else {
+            args.push("-".to_string());
+            args.push("-".to_string());
+        }
+
+        if v.is_active && (config.expose_era_points || config.expose_all) {
+            let points =
+                get_validator_points_info(&v.stash, era_reward_points.clone()).await?;
+            args.push(points.validator.to_string());
+            args.push(points.era_avg.to_string());
+            payload = payload
+                .with("era_points", points.validator)
+                .with("era_points_avg", points.era_avg);
+        } else {
+            args.push("-".to_string());
+            args.push("-".to_string());
+        }
+
+        // Try run hook
+        let hook = scouty
+            .try_run_hook(HOOK_INIT, &config.hook_init_path, args, payload)
+            .await;
+        v.hooks.push(hook);
+    }
+
+    // Prepare notification report
+    debug!("validators {:?}", validators);
+
+    let data = RawData {
+        init,
+        network,
+        session,
+        validators,
+        section: Section::Init,
+        ..Default::default()
+    };
+
+    let report = Report::from(data);
+    scouty.send_report(&report).await?;
+
+    Ok(())
+}
+
+async fn try_run_staking_chilled_hook(
+    scouty: &Scouty,
+    stash: AccountId32,
+    reason: String,
+) -> Result<(), ScoutyError> {
+    let config = CONFIG.current();
+
+    // Collect validators info based on config stashes
+    let mut validators = collect_validators_data(&scouty).await?;
+
+    let session = collect_session_data(&scouty, 0).await?;
+
+    let network = Network::load(scouty.rpc()).await?;
+    debug!("network {:?}", network);
+
+    // Try to run hooks for each stash
+    for v in validators.iter_mut() {
+        // Identify if the stash has been chilled
+        if stash == v.stash {
+            v.is_chilled = true;
+
+            // Try HOOK_VALIDATOR_CHILLED
+            let mut args = vec![
+                scouty.to_ss58(v.stash.clone()),
+                v.name.to_string(),
+                format!("0x{:?}", HexDisplay::from(&v.queued_session_keys)),
+                v.is_active.to_string(),
+                v.is_queued.to_string(),
+                session.active_era_index.to_string(),
+                session.current_session_index.to_string(),
+                reason.to_string(),
+            ];
+
+            let mut payload = HookPayload::new()
+                .validator(
+                    scouty.to_ss58(v.stash.clone()),
+                    v.name.to_string(),
+                    format!("0x{:?}", HexDisplay::from(&v.queued_session_keys)),
+                    v.is_active,
+                    v.is_queued,
+                )
+                .session(
+                    session.active_era_index,
+                    session.current_session_index,
+                    Some(session.eras_session_index),
+                )
+                .with("reason", reason.to_string());
+
+            if config.expose_network || config.expose_all {
+                args.push(network.name.to_string());
+                args.push(network.token_symbol.to_string());
+                args.push(network.token_decimals.to_string());
+                payload = payload.network(&network);
+            } else {
+                args.push("-".to_string());
+                args.push("-".to_string());
+                args.push("-".to_string());
+            }
+
+            // Try run hook
+            let hook = scouty
+                .try_run_hook(
+                    HOOK_VALIDATOR_CHILLED,
+                    &config.hook_validator_chilled_path,
+                    args.clone(),
+                    payload.clone(),
+                )
+                .await;
+            v.hooks.push(hook);
+            break;
+        }
+    }
+
+    debug!("validators {:?}", validators);
+
+    // NOTE: Only send chilled message if the chilled account is
+    // one of the stashes defined in config
+    if validators.iter().any(|v| v.is_chilled) {
+        // Prepare notification report
+        let data = RawData {
+            network,
+            validators,
+            session,
+            chill: Chill { reason },
+            section: Section::Chill,
+            ..Default::default()
+        };
+
+        let report = Report::from(data);
+        scouty.send_report(&report).await?;
+    }
+
+    Ok(())
+}
+
+/// Returns the stashes predefined that are currently intending to validate
+async fn get_validator_intentions(
+    scouty: &Scouty,
+) -> Result<Vec<AccountId32>, ScoutyError> {
+    let api = scouty.client().clone();
+    let config = CONFIG.current();
+
+    let mut intentions: Vec<AccountId32> = Vec::new();
+    for stash_str in config
+        .validator_stashes(&scouty.runtime().to_string())
+        .iter()
+    {
+        let stash = AccountId32::from_str(stash_str).map_err(|e| {
+            ScoutyError::Other(format!(
+                "Invalid SS58 format account: {:?} error: {e:?}",
+                stash_str
+            ))
+        })?;
+        let validator_addr = node_runtime::storage().staking().validators(&stash);
+        // A stash that could not be queried is kept as an intention, so that a
+        // failure querying one stash does not report it as chilled
+        match api
+            .storage()
+            .at_latest()
+            .await?
+            .fetch(&validator_addr)
+            .await
+        {
+            Ok(Some(_)) => intentions.push(stash),
+            Ok(None) => {}
+            Err(e) => {
+                warn!(
+                    "Validator intention of {} not available! {}",
+                    scouty.to_ss58(stash.clone()),
+                    e
+                );
+                intentions.push(stash);
+            }
+        }
+    }
+    Ok(intentions)
+}
+
+/// Context gathered when a stash active now is not going to be active in the next era
+#[derive(Debug, Default)]
+struct InactiveNextEra {
+    is_chilled: bool,
+    has_intention: bool,
+    has_next_keys: bool,
+    backing_stake: Option<u128>,
+    min_elected_stake: Option<u128>,
+}
+
+impl InactiveNextEra {
+    /// Returns the reason inferred from the context, the first cause found is reported
+    fn reason(&self, network: &Network) -> String {
+        if self.is_chilled {
+            return "validator chilled this era".to_string();
+        }
+        if !self.has_intention {
+            return "validator intention not set (staking.Validators)".to_string();
+        }
+        if !self.has_next_keys {
+            return "session keys not set (session.NextKeys)".to_string();
+        }
+        match (self.backing_stake, self.min_elected_stake) {
+            (Some(backing), Some(min_elected)) if backing < min_elected => format!(
+                "backing stake {} below minimum elected {}",
+                network.format_amount(backing),
+                network.format_amount(min_elected)
+            ),
+            (Some(backing), Some(min_elected)) => format!(
+                "not elected with backing stake {} above minimum elected {}",
+                network.format_amount(backing),
+                network.format_amount(min_elected)
+            ),
+            _ => "not elected for the next era".to_string(),
+        }
+    }
+}
+
+/// Returns the reason inferred for a stash active now not being active in the next
+/// era, from its validator intention, its next session keys and its backing stake
+/// compared with the minimum stake elected for the next era
+async fn get_inactive_next_era_reason(
+    scouty: &Scouty,
+    stash: &AccountId32,
+    active_era_index: u32,
+    is_chilled: bool,
+    network: &Network,
+) -> Result<String, ScoutyError> {
+    let api = scouty.client().clone();
+    let storage = api.storage().at_latest().await?;
+
+    let validator_addr = node_runtime::storage().staking().validators(stash);
+    let next_keys_addr = node_runtime::storage().session().next_keys(stash);
+    let eras_stakers_overview_addr = node_runtime::storage()
+        .staking()
+        .eras_stakers_overview(&active_era_index, stash);
+    let mut context = InactiveNextEra {
+        is_chilled,
+        has_intention: storage.fetch(&validator_addr).await?.is_some(),
+        has_next_keys: storage.fetch(&next_keys_addr).await?.is_some(),
+        backing_stake: storage
+            .fetch(&eras_stakers_overview_addr)
+            .await?
+            .map(|exposure| exposure.total),
+        ..Default::default()
+    };
+
+    // The exposures of the next era are only available once it has been planned
+    let current_era_addr = node_runtime::storage().staking().current_era();
+    if let Some(next_era_index) = storage.fetch(&current_era_addr).await? {
+        if next_era_index > active_era_index {
+            let eras_stakers_overview_addr = node_runtime::storage()
+                .staking()
+                .eras_stakers_overview_iter1(&next_era_index);
+            let mut iter = storage.iter(eras_stakers_overview_addr).await?;
+            while let Some(Ok((_, exposure))) = iter.next().await {
+                context.min_elected_stake = Some(
+                    context
+                        .min_elected_stake
+                        .map_or(exposure.total, |min| min.min(exposure.total)),
+                );
+            }
+        }
+    }
+
+    Ok(context.reason(network))
+}
+
+/// Infer the reason of a chill from the extrinsic that triggered the event, chills that
+/// are not triggered by an extrinsic come from the runtime itself (e.g. governance)
+async fn get_chill_reason(
+    block: &Block<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+    phase: Phase,
+) -> Result<String, ScoutyError> {
+    if let Phase::ApplyExtrinsic(index) = phase {
+        let extrinsics = block.extrinsics().await?;
+        for extrinsic in extrinsics.iter() {
+            let extrinsic = extrinsic?;
+            if extrinsic.index() == index {
+                return Ok(parse_chill_reason(
+                    extrinsic.pallet_name()?,
+                    extrinsic.variant_name()?,
+                ));
+            }
+        }
+    }
+    Ok("Chilled by the runtime (e.g. governance or scheduled call)".to_string())
+}
+
+async fn try_run_session_keys_mismatch_hook(
+    scouty: &Scouty,
+    session_keys_mismatches: &mut BTreeMap<AccountId32, bool>,
+) -> Result<(), ScoutyError> {
+    let api = scouty.client().clone();
+    let config = CONFIG.current();
+
+    if config.session_keys_check_disabled {
+        return Ok(());
+    }
+
+    // Collect validators info based on config stashes
+    let mut validators = collect_validators_data(&scouty).await?;
+
+    let session = collect_session_data(&scouty, 0).await?;
+
+    let network = Network::load(scouty.rpc()).await?;
+    debug!("network {:?}", network);
+
+    let mut transitions: Vec<AccountId32> = Vec::new();
+    for v in validators.iter_mut() {
+        let next_keys_addr = node_runtime::storage().session().next_keys(&v.stash);
+        let next_keys = match api
+            .storage()
+            .at_latest()
+            .await?
+            .fetch(&next_keys_addr)
+            .await?
+        {
+            Some(keys) => keys.encode(),
+            // Stashes without session keys set are not verified
+            None => continue,
+        };
+
+        let has_session_keys = match scouty
+            .rpc()
+            .author_has_session_keys(next_keys.clone())
+            .await
+        {
+            Ok(has_session_keys) => has_session_keys,
+            Err(e) => {
+                warn!(
+                    "Session keys could not be verified (use --disable-session-keys-check if 'scouty' is not connected to the validator node): {}",
+                    e
+                );
+                return Ok(());
+            }
+        };
+        v.is_session_keys_mismatch = !has_session_keys;
+
+        let previous = session_keys_mismatches.insert(v.stash.clone(), !has_session_keys);
+        if !is_session_keys_transition(previous, !has_session_keys) {
+            continue;
+        }
+        transitions.push(v.stash.clone());
+
+        // Only run the hook when the node stops owning the keys
+        if has_session_keys {
+            continue;
+        }
+
+        // Try HOOK_SESSION_KEYS_MISMATCH
+        let mut args = vec![
+            scouty.to_ss58(v.stash.clone()),
+            v.name.to_string(),
+            format!("0x{:?}", HexDisplay::from(&next_keys)),
+            v.is_active.to_string(),
+            v.is_queued.to_string(),
+            session.active_era_index.to_string(),
+            session.current_session_index.to_string(),
+        ];
+
+        let mut payload = HookPayload::new()
+            .validator(
+                scouty.to_ss58(v.stash.clone()),
+                v.name.to_string(),
+                format!("0x{:?}", HexDisplay::from(&v.queued_session_keys)),
+                v.is_active,
+                v.is_queued,
+            )
+            .session(
+                session.active_era_index,
+                session.current_session_index,
+                Some(session.eras_session_index),
+            )
+            .with(
+                "next_session_keys",
+                format!("0x{:?}", HexDisplay::from(&next_keys)),
+            );
+
+        if config.expose_network || config.expose_all {
+            args.push(network.name.to_string());
+            args.push(network.token_symbol.to_string());
+            args.push(network.token_decimals.to_string());
+            payload = payload.network(&network);
+        } else {
+            args.push("-".to_string());
+            args.push("-".to_string());
+            args.push("-".to_string());
+        }
+
+        // Try run hook
+        let hook = scouty
+            .try_run_hook(
+                HOOK_SESSION_KEYS_MISMATCH,
+                &config.hook_session_keys_mismatch_path,
+                args.clone(),
+                payload.clone(),
+            )
+            .await;
+        v.hooks.push(hook);
+    }
+
+    debug!("validators {:?}", validators);
+
+    // NOTE: Only send a message for the stashes that changed state
+    validators.retain(|v| transitions.contains(&v.stash));
+    if !validators.is_empty() {
+        // Prepare notification report
+        let data = RawData {
+            network,
+            validators,
+            session,
+            section: Section::SessionKeys,
+            ..Default::default()
+        };
+
+        let report = Report::from(data);
+        scouty.send_report(&report).await?;
+    }
+
+    Ok(())
+}
+
+async fn try_run_validator_prefs_changed_hook(
+    scouty: &Scouty,
+    validator_prefs: &mut BTreeMap<AccountId32, Prefs>,
+) -> Result<(), ScoutyError> {
+    let api = scouty.client().clone();
+    let config = CONFIG.current();
+
+    // Collect validators info based on config stashes
+    let mut validators = collect_validators_data(&scouty).await?;
+
+    let session = collect_session_data(&scouty, 0).await?;
+
+    let network = Network::load(scouty.rpc()).await?;
+    debug!("network {:?}", network);
+
+    let mut changes: Vec<AccountId32> = Vec::new();
+    for v in validators.iter_mut() {
+        let validator_prefs_addr = node_runtime::storage().staking().validators(&v.stash);
+        let prefs = match api
+            .storage()
+            .at_latest()
+            .await?
+            .fetch(&validator_prefs_addr)
+            .await?
+        {
+            Some(prefs) => {
+                let node_runtime::runtime_types::sp_arithmetic::per_things::Perbill(
+                    commission,
+                ) = prefs.commission;
+                Prefs {
+                    commission,
+                    blocked: prefs.blocked,
+                }
+            }
+            // Stashes not intending to validate are verified by the chilled hook
+            None => continue,
+        };
+
+        let previous = match validator_prefs.insert(v.stash.clone(), prefs.clone()) {
+            Some(previous) if previous != prefs => previous,
+            _ => continue,
+        };
+        changes.push(v.stash.clone());
+
+        // Try HOOK_VALIDATOR_PREFS_CHANGED
+        let mut args = vec![
+            scouty.to_ss58(v.stash.clone()),
+            v.name.to_string(),
+            session.active_era_index.to_string(),
+            session.current_session_index.to_string(),
+            previous.commission.to_string(),
+            prefs.commission.to_string(),
+            previous.blocked.to_string(),
+            prefs.blocked.to_string(),
+        ];
+
+        let mut payload = HookPayload::new()
+            .validator(
+                scouty.to_ss58(v.stash.clone()),
+                v.name.to_string(),
+                format!("0x{:?}", HexDisplay::from(&v.queued_session_keys)),
+                v.is_active,
+                v.is_queued,
+            )
+            .session(
+                session.active_era_index,
+                session.current_session_index,
+                Some(session.eras_session_index),
+            )
+            .with("previous_commission", previous.commission)
+            .with("commission", prefs.commission)
+            .with("previous_blocked", previous.blocked)
+            .with("blocked", prefs.blocked);
+
+        if config.expose_network || config.expose_all {
+            args.push(network.name.to_string());
+            args.push(network.token_symbol.to_string());
+            args.push(network.token_decimals.to_string());
+            payload = payload.network(&network);
+        } else {
+            args.push("-".to_string());
+            args.push("-".to_string());
+            args.push("-".to_string());
+        }
+
+        // Try run hook
+        let hook = scouty
+            .try_run_hook(
+                HOOK_VALIDATOR_PREFS_CHANGED,
+                &config.hook_validator_prefs_changed_path,
+                args.clone(),
+                payload.clone(),
+            )
+            .await;
+        v.hooks.push(hook);
+        v.prefs_change = PrefsChange {
+            previous,
+            current: prefs,
+        };
+    }
+
+    debug!("validators {:?}", validators);
+
+    // NOTE: Only send a message for the stashes that changed preferences
+    validators.retain(|v| changes.contains(&v.stash));
+    if !validators.is_empty() {
+        // Prepare notification report
+        let data = RawData {
+            network,
+            validators,
+            session,
+            section: Section::ValidatorPrefs,
+            ..Default::default()
+        };
+
+        let report = Report::from(data);
+        scouty.send_report(&report).await?;
+    }
+
+    Ok(())
+}
+
+/// Verify the reward destination of the stashes predefined, the hook is only run and a
+/// message is only sent when the destination changed since the previous verification
+async fn try_run_payee_changed_hook(
+    scouty: &Scouty,
+    payees: &mut BTreeMap<AccountId32, Payee>,
+) -> Result<(), ScoutyError> {
+    let api = scouty.client().clone();
+    let config = CONFIG.current();
+
+    // Collect validators info based on config stashes
+    let mut validators = collect_validators_data(&scouty).await?;
+
+    let session = collect_session_data(&scouty, 0).await?;
+
+    let network = Network::load(scouty.rpc()).await?;
+    debug!("network {:?}", network);
+
+    let mut changes: Vec<AccountId32> = Vec::new();
+    for v in validators.iter_mut() {
+        let payee_addr = node_runtime::storage().staking().payee(&v.stash);
+        let payee = match api.storage().at_latest().await?.fetch(&payee_addr).await? {
+            Some(destination) => to_payee(destination),
+            // Stashes not bonded do not have a reward destination
+            None => continue,
+        };
+
+        let previous = match payees.insert(v.stash.clone(), payee.clone()) {
+            Some(previous) if previous != payee => previous,
+            _ => continue,
+        };
+        changes.push(v.stash.clone());
+
+        let previous_identity = get_payee_identity(&scouty, &previous).await?;
+        let identity = get_payee_identity(&scouty, &payee).await?;
+        let to_account = |payee: &Payee| {
+            payee
+                .account
+                .as_ref()
+                .map_or("-".to_string(), |account| scouty.to_ss58(account.clone()))
+        };
+
+        // Try HOOK_PAYEE_CHANGED
+        let mut args = vec![
+            scouty.to_ss58(v.stash.clone()),
+            v.name.to_string(),
+            session.active_era_index.to_string(),
+            session.current_session_index.to_string(),
+            previous.destination.to_string(),
+            to_account(&previous),
+            payee.destination.to_string(),
+            to_account(&payee),
+            identity.clone().unwrap_or("-".to_string()),
+        ];
+
+        let mut payload = HookPayload::new()
+            .validator(
+                scouty.to_ss58(v.stash.clone()),
+                v.name.to_string(),
+                format!("0x{:?}", HexDisplay::from(&v.queued_session_keys)),
+                v.is_active,
+                v.is_queued,
+            )
+            .session(
+                session.active_era_index,
+                session.current_session_index,
+                Some(session.eras_session_index),
+            )
+            .with("previous_payee", previous.destination.to_string())
+            .with("payee", payee.destination.to_string());
+        if let Some(account) = &previous.account {
+            payload =
+                payload.with("previous_payee_account", scouty.to_ss58(account.clone()));
+        }
+        if let Some(account) = &payee.account {
+            payload = payload.with("payee_account", scouty.to_ss58(account.clone()));
+        }
+
+        if config.expose_network || config.expose_all {
+            args.push(network.name.to_string());
+            args.push(network.token_symbol.to_string());
+            args.push(network.token_decimals.to_string());
+            payload = payload.network(&network);
+        } else {
+            args.push("-".to_string());
+            args.push("-".to_string());
+            args.push("-".to_string());
+        }
+
+        // Try run hook
+        let hook = scouty
+            .try_run_hook(
+                HOOK_PAYEE_CHANGED,
+                &config.hook_payee_changed_path,
+                args.clone(),
+                payload.clone(),
+            )
+            .await;
+        v.hooks.push(hook);
+        v.payee_change = Some(PayeeChange {
+            previous: describe_payee(&scouty, &previous, previous_identity),
+            current: describe_payee(&scouty, &payee, identity),
+        });
+    }
+
+    debug!("validators {:?}", validators);
+
+    // NOTE: Only send a message for the stashes that changed reward destination
+    validators.retain(|v| changes.contains(&v.stash));
+    if !validators.is_empty() {
+        // Prepare notification report
+        let data = RawData {
+            network,
+            validators,
+            session,
+            section: Section::PayeeChanged,
+            ..Default::default()
+        };
+
+        let report = Report::from(data);
+        scouty.send_report(&report).await?;
+    }
+
+    Ok(())
+}
+
+fn to_payee(
+    destination: node_runtime::runtime_types::pallet_staking::RewardDestination<
+        AccountId32,
+    >,
+) -> Payee {
+    use node_runtime::runtime_types::pallet_staking::RewardDestination;
+    let (destination, account) = match destination {
+        RewardDestination::Staked => ("Staked", None),
+        RewardDestination::Stash => ("Stash", None),
+        RewardDestination::Controller => ("Controller", None),
+        RewardDestination::Account(account) => ("Account", Some(account)),
+        RewardDestination::None => ("None", None),
+    };
+    Payee {
+        destination: destination.to_string(),
+        account,
+    }
+}
+
+/// Returns the identity of the account the rewards are paid to, if any
+async fn get_payee_identity(
+    scouty: &Scouty,
+    payee: &Payee,
+) -> Result<Option<String>, ScoutyError> {
+    match &payee.account {
+        Some(account) => Ok(get_identity(&scouty, account, None)
+            .await?
+            .map(|identity| identity.to_string())),
+        None => Ok(None),
+    }
+}
+
+/// Describe the reward destination with the account and its identity, e.g.
+/// `Account 15abc.. (Alice)`
+fn describe_payee(scouty: &Scouty, payee: &Payee, identity: Option<String>) -> String {
+    match &payee.account {
+        Some(account) => format!(
+            "{} {} ({})",
+            payee.destination,
+            scouty.to_ss58(account.clone()),
+            identity.unwrap_or("no identity".to_string())
+        ),
+        None => payee.destination.to_string(),
+    }
+}
+
+/// Verify the commission of the stashes predefined against the maximum commission
+/// configured and the minimum commission of the chain. The hook is only run when the
+/// commission falls outside the policy, and a message is only sent on transitions
+async fn try_run_commission_out_of_policy_hook(
+    scouty: &Scouty,
+    validator_prefs: &BTreeMap<AccountId32, Prefs>,
+    commission_out_of_policy: &mut BTreeMap<AccountId32, bool>,
+) -> Result<(), ScoutyError> {
+    let api = scouty.client().clone();
+    let config = CONFIG.current();
+
+    let min_commission_addr = node_runtime::storage().staking().min_commission();
+    let min_commission = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&min_commission_addr)
+        .await?
+        .map_or(0, |perbill| {
+            let node_runtime::runtime_types::sp_arithmetic::per_things::Perbill(
+                min_commission,
+            ) = perbill;
+            min_commission
+        });
+    let policy = commission_policy(min_commission, config.max_commission);
+
+    // Collect validators info based on config stashes
+    let mut validators = collect_validators_data(&scouty).await?;
+
+    let session = collect_session_data(&scouty, 0).await?;
+
+    let network = Network::load(scouty.rpc()).await?;
+    debug!("network {:?}", network);
+
+    let mut transitions: Vec<AccountId32> = Vec::new();
+    for v in validators.iter_mut() {
+        // Stashes not intending to validate are verified by the chilled hook
+        let commission = match validator_prefs.get(&v.stash) {
+            Some(prefs) => prefs.commission,
+            None => continue,
+        };
+        let is_out_of_policy = !policy.contains(commission);
+
+        // NOTE: stashes not verified yet are expected to be within the policy
+        let previous = commission_out_of_policy.insert(v.stash.clone(), is_out_of_policy);
+        if previous.unwrap_or(false) == is_out_of_policy {
+            continue;
+        }
+        transitions.push(v.stash.clone());
+        v.commission_policy = Some((commission, policy));
+
+        // Only run the hook when the commission falls outside the policy
+        if !is_out_of_policy {
+            continue;
+        }
+
+        // Try HOOK_COMMISSION_OUT_OF_POLICY
+        let mut args = vec![
+            scouty.to_ss58(v.stash.clone()),
+            v.name.to_string(),
+            session.active_era_index.to_string(),
+            session.current_session_index.to_string(),
+            commission.to_string(),
+            policy.min.to_string(),
+            policy.max.to_string(),
+        ];
+
+        let mut payload = HookPayload::new()
+            .validator(
+                scouty.to_ss58(v.stash.clone()),
+                v.name.to_string(),
+                format!("0x{:?}", HexDisplay::from(&v.queued_session_keys)),
+                v.is_active,
+                v.is_queued,
+            )
+            .session(
+                session.active_era_index,
+                session.current_session_index,
+                Some(session.eras_session_index),
+            )
+            .with("commission", commission)
+            .with("min_commission", policy.min)
+            .with("max_commission", policy.max);
+
+        if config.expose_network || config.expose_all {
+            args.push(network.name.to_string());
+            args.push(network.token_symbol.to_string());
+            args.push(network.token_decimals.to_string());
+            payload = payload.network(&network);
+        } else {
+            args.push("-".to_string());
+            args.push("-".to_string());
+            args.push("-".to_string());
+        }
+
+        // Try run hook
+        let hook = scouty
+            .try_run_hook(
+                HOOK_COMMISSION_OUT_OF_POLICY,
+                &config.hook_commission_out_of_policy_path,
+                args.clone(),
+                payload.clone(),
+            )
+            .await;
+        v.hooks.push(hook);
+    }
+
+    debug!("validators {:?}", validators);
+
+    // NOTE: Only send a message for the stashes that changed state
+    validators.retain(|v| transitions.contains(&v.stash));
+    if !validators.is_empty() {
+        // Prepare notification report
+        let data = RawData {
+            network,
+            validators,
+            session,
+            section: Section::CommissionPolicy,
+            ..Default::default()
+        };
+
+        let report = Report::from(data);
+        scouty.send_report(&report).await?;
+    }
+
+    Ok(())
+}
+
+/// Verify the free balance of the accounts watched on the chain. The hook is only run
+/// when the free balance drops below the minimum defined, and a message is only sent on
+/// transitions, so that a low balance is alerted again only after it recovers
+async fn try_run_low_balance_hook(
+    scouty: &Scouty,
+    low_balances: &mut BTreeMap<AccountId32, bool>,
+) -> Result<(), ScoutyError> {
+    let api = scouty.client().clone();
+    let config = CONFIG.current();
+
+    let network = Network::load(scouty.rpc()).await?;
+    debug!("network {:?}", network);
+
+    let watched_accounts = config.watched_accounts(&network.name);
+    if watched_accounts.is_empty() {
+        return Ok(());
+    }
+
+    let session = collect_session_data(&scouty, 0).await?;
+
+    let mut balances: Vec<WatchedBalance> = Vec::new();
+    for watched in watched_accounts {
+        let account_addr = node_runtime::storage().system().account(&watched.account);
+        let account_info = api
+            .storage()
+            .at_latest()
+            .await?
+            .fetch_or_default(&account_addr)
+            .await?;
+
+        let mut balance = WatchedBalance {
+            account: scouty.to_ss58(watched.account.clone()),
+            identity: get_identity(&scouty, &watched.account, None)
+                .await?
+                .map(|identity| identity.to_string()),
+            free: account_info.data.free,
+            min_free_balance: network.to_plancks(watched.min_free_balance),
+            ..Default::default()
+        };
+
+        // NOTE: accounts not verified yet are expected to be above the minimum
+        let previous = low_balances.insert(watched.account.clone(), balance.is_low());
+        if previous.unwrap_or(false) == balance.is_low() {
+            continue;
+        }
+
+        // Only run the hook when the free balance is below the minimum
+        if balance.is_low() {
+            // Try HOOK_LOW_BALANCE
+            let mut args = vec![
+                balance.account.to_string(),
+                balance.identity.clone().unwrap_or("-".to_string()),
+                session.active_era_index.to_string(),
+                session.current_session_index.to_string(),
+                balance.free.to_string(),
+                balance.min_free_balance.to_string(),
+            ];
+
+            let mut payload = HookPayload::new()
+                .with("account", balance.account.to_string())
+                .session(
+                    session.active_era_index,
+                    session.current_session_index,
+                    Some(session.eras_session_index),
+                )
+                .with("free_balance", balance.free.to_string())
+                .with("min_free_balance", balance.min_free_balance.to_string());
+
+            if config.expose_network || config.expose_all {
+                args.push(network.name.to_string());
+                args.push(network.token_symbol.to_string());
+                args.push(network.token_decimals.to_string());
+                payload = payload.network(&network);
+            } else {
+                args.push("-".to_string());
+                args.push("-".to_string());
+                args.push("-".to_string());
+            }
+
+            // Try run hook
+            let hook = scouty
+                .try_run_hook(
+                    HOOK_LOW_BALANCE,
+                    &config.hook_low_balance_path,
+                    args.clone(),
+                    payload.clone(),
+                )
+                .await;
+            balance.hooks.push(hook);
+        }
+        balances.push(balance);
+    }
+
+    debug!("balances {:?}", balances);
+
+    // NOTE: Only send a message for the accounts that crossed the minimum
+    if !balances.is_empty() {
+        // Prepare notification report
+        let data = RawData {
+            network,
+            balances,
+            session,
+            section: Section::LowBalance,
+            ..Default::default()
+        };
+
+        let report = Report::from(data);
+        scouty.send_report(&report).await?;
+    }
+
+    Ok(())
+}
+
+/// Verify if the stashes predefined are in the disabled validators of the session. The
+/// hook is only run when a stash is disabled, and a message is only sent on transitions
+async fn try_run_validator_disabled_hook(
+    scouty: &Scouty,
+    disabled_validators: &mut BTreeMap<AccountId32, bool>,
+) -> Result<(), ScoutyError> {
+    let api = scouty.client().clone();
+    let config = CONFIG.current();
+
+    let storage = api.storage().at_latest().await?;
+    let validators_addr = node_runtime::storage().session().validators();
+    let active_validators = storage.fetch(&validators_addr).await?.unwrap_or_default();
+    let disabled_validators_addr =
+        node_runtime::storage().session().disabled_validators();
+    let disabled_indices = storage
+        .fetch(&disabled_validators_addr)
+        .await?
+        .unwrap_or_default();
+
+    // Collect validators info based on config stashes
+    let mut validators = collect_validators_data(&scouty).await?;
+
+    let session = collect_session_data(&scouty, 0).await?;
+
+    let network = Network::load(scouty.rpc()).await?;
+    debug!("network {:?}", network);
+
+    let mut transitions: Vec<AccountId32> = Vec::new();
+    for v in validators.iter_mut() {
+        // Disabled validators are indexed by the position in the active set
+        let authority_index = active_validators
+            .iter()
+            .position(|a| a == &v.stash)
+            .map(|index| index as u32);
+        v.is_disabled =
+            authority_index.map_or(false, |index| disabled_indices.contains(&index));
+
+        // NOTE: stashes not verified yet are expected to be enabled
+        let previous = disabled_validators.insert(v.stash.clone(), v.is_disabled);
+        if previous.unwrap_or(false) == v.is_disabled {
+            continue;
+        }
+        transitions.push(v.stash.clone());
+
+        // Only run the hook when the stash is disabled
+        let authority_index = match authority_index {
+            Some(index) if v.is_disabled => index,
+            _ => continue,
+        };
+
+        // Try HOOK_VALIDATOR_DISABLED
+        let mut args = vec![
+            scouty.to_ss58(v.stash.clone()),
+            v.name.to_string(),
+            session.active_era_index.to_string(),
+            session.current_session_index.to_string(),
+            authority_index.to_string(),
+        ];
+
+        let mut payload = HookPayload::new()
+            .validator(
+                scouty.to_ss58(v.stash.clone()),
+                v.name.to_string(),
+                format!("0x{:?}", HexDisplay::from(&v.queued_session_keys)),
+                v.is_active,
+                v.is_queued,
+            )
+            .session(
+                session.active_era_index,
+                session.current_session_index,
+                Some(session.eras_session_index),
+            )
+            .with("authority_index", authority_index);
+
+        if config.expose_network || config.expose_all {
+            args.push(network.name.to_string());
+            args.push(network.token_symbol.to_string());
+            args.push(network.token_decimals.to_string());
+            payload = payload.network(&network);
+        } else {
+            args.push("-".to_string());
+            args.push("-".to_string());
+            args.push("-".to_string());
+        }
+
+        // Try run hook
+        let hook = scouty
+            .try_run_hook(
+                HOOK_VALIDATOR_DISABLED,
+                &config.hook_validator_disabled_path,
+                args.clone(),
+                payload.clone(),
+            )
+            .await;
+        v.hooks.push(hook);
+    }
+
+    debug!("validators {:?}", validators);
+
+    // NOTE: Only send a message for the stashes that changed state
+    validators.retain(|v| transitions.contains(&v.stash));
+    if !validators.is_empty() {
+        // Prepare notification report
+        let data = RawData {
+            network,
+            validators,
+            session,
+            section: Section::ValidatorDisabled,
+            ..Default::default()
+        };
+
+        let report = Report::from(data);
+        scouty.send_report(&report).await?;
+    }
+
+    Ok(())
+}
+
+/// Returns the range of the commission allowed (in parts per billion), from the
+/// minimum commission of the chain up to the maximum commission (in percentage)
+/// configured
+fn commission_policy(min_commission: u32, max_commission: f64) -> CommissionPolicy {
+    let max = (max_commission.clamp(0.0, 100.0) * 10_000_000.0).round() as u32;
+    CommissionPolicy {
+        min: min_commission,
+        max: max.max(min_commission),
+    }
+}
+
+/// Run the dispute hook for every dispute vote of the stashes predefined included in
+/// the parachains inherent of the block
+async fn try_run_dispute_hooks(
+    scouty: &Scouty,
+    block: &Block<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+) -> Result<(), ScoutyError> {
+    let config = CONFIG.current();
+
+    let extrinsics = block.extrinsics().await?;
+    let enter = match extrinsics.find_first::<Enter>()? {
+        Some(enter) => enter.value,
+        None => return Ok(()),
+    };
+
+    // Collect the votes of the stashes predefined
+    let mut votes: BTreeMap<AccountId32, Vec<DisputeVote>> = BTreeMap::new();
+    let mut para_validators: BTreeMap<u32, BTreeMap<u32, AccountId32>> = BTreeMap::new();
+    for set in enter.data.disputes.iter() {
+        if !para_validators.contains_key(&set.session) {
+            let stashes = get_para_validator_stashes(&scouty, set.session).await?;
+            para_validators.insert(set.session, stashes);
+        }
+        let stashes = &para_validators[&set.session];
+        for (statement, ValidatorIndex(index), _) in set.statements.iter() {
+            if let Some(stash) = stashes.get(index) {
+                votes.entry(stash.clone()).or_default().push(DisputeVote {
+                    candidate_hash: format!("{:?}", set.candidate_hash.0),
+                    session_index: set.session,
+                    is_valid: matches!(statement, DisputeStatement::Valid(_)),
+                });
+            }
+        }
+    }
+
+    if votes.is_empty() {
+        return Ok(());
+    }
+
+    // Collect validators info based on config stashes
+    let mut validators = collect_validators_data(&scouty).await?;
+
+    let session = collect_session_data(&scouty, 0).await?;
+
+    let network = Network::load(scouty.rpc()).await?;
+    debug!("network {:?}", network);
+
+    for v in validators.iter_mut() {
+        let stash_votes = match votes.remove(&v.stash) {
+            Some(stash_votes) => stash_votes,
+            None => continue,
+        };
+
+        for vote in stash_votes.iter() {
+            // Try HOOK_DISPUTE
+            let mut args = vec![
+                scouty.to_ss58(v.stash.clone()),
+                v.name.to_string(),
+                session.active_era_index.to_string(),
+                session.current_session_index.to_string(),
+                vote.session_index.to_string(),
+                vote.candidate_hash.to_string(),
+                vote.direction().to_string(),
+            ];
+
+            let mut payload = HookPayload::new()
+                .validator(
+                    scouty.to_ss58(v.stash.clone()),
+                    v.name.to_string(),
+                    format!("0x{:?}", HexDisplay::from(&v.queued_session_keys)),
+                    v.is_active,
+                    v.is_queued,
+                )
+                .session(
+                    session.active_era_index,
+                    session.current_session_index,
+                    Some(session.eras_session_index),
+                )
+                .with("dispute_session", vote.session_index)
+                .with("candidate_hash", vote.candidate_hash.to_string())
+                .with("vote", vote.direction());
+
+            if config.expose_network || config.expose_all {
+                args.push(network.name.to_string());
+                args.push(network.token_symbol.to_string());
+                args.push(network.token_decimals.to_string());
+                payload = payload.network(&network);
+            } else {
+                args.push("-".to_string());
+                args.push("-".to_string());
+                args.push("-".to_string());
+            }
+
+            // Try run hook
+            let hook = scouty
+                .try_run_hook(
+                    HOOK_DISPUTE,
+                    &config.hook_dispute_path,
+                    args.clone(),
+                    payload.clone(),
+                )
+                .await;
+            v.hooks.push(hook);
+        }
+        v.dispute_votes = stash_votes;
+    }
+
+    debug!("validators {:?}", validators);
+
+    // NOTE: Only send a message for the stashes that voted in a dispute
+    validators.retain(|v| !v.dispute_votes.is_empty());
+    if !validators.is_empty() {
+        // Prepare notification report
+        let data = RawData {
+            network,
+            validators,
+            session,
+            section: Section::Dispute,
+            ..Default::default()
+        };
+
+        let report = Report::from(data);
+        scouty.send_report(&report).await?;
+    }
+
+    Ok(())
+}
+
+/// Returns the stashes predefined by their parachain validator index in the session,
+/// the index used by the dispute statements
+async fn get_para_validator_stashes(
+    scouty: &Scouty,
+    session_index: u32,
+) -> Result<BTreeMap<u32, AccountId32>, ScoutyError> {
+    let api = scouty.client().clone();
+
+    let account_keys_addr = node_runtime::storage()
+        .para_session_info()
+        .account_keys(&session_index);
+    let session_info_addr = node_runtime::storage()
+        .para_session_info()
+        .sessions(&session_index);
+    let storage = api.storage().at_latest().await?;
+
+    let mut stashes = BTreeMap::new();
+    if let (Some(account_keys), Some(session_info)) = (
+        storage.fetch(&account_keys_addr).await?,
+        storage.fetch(&session_info_addr).await?,
+    ) {
+        for (para_index, ValidatorIndex(index)) in
+            session_info.active_validator_indices.iter().enumerate()
+        {
+            if let Some(account) = account_keys.get(*index as usize) {
+                if is_stash_predefined(account) {
+                    stashes.insert(para_index as u32, account.clone());
+                }
+            }
+        }
+    }
+    Ok(stashes)
+}
+
+/// Returns true if the backing votes can be tracked in the chain, which requires the
+/// para inclusion and para scheduler pallets (e.g. not available in every Westend runtime)
+fn is_backing_available(scouty: &Scouty) -> bool {
+    let metadata = scouty.client().metadata();
+    metadata.pallet_by_name("ParaInclusion").is_some()
+        && metadata.pallet_by_name("ParaScheduler").is_some()
+}
+
+/// Assign the stashes predefined to their para validator group in the new session, and
+/// returns their backing votes in the previous session
+async fn start_backing_session(
+    scouty: &Scouty,
+    session_index: u32,
+    backing_records: &mut BackingRecords,
+) -> Result<BTreeMap<AccountId32, SessionBacking>, ScoutyError> {
+    if !is_backing_available(&scouty) {
+        return Ok(BTreeMap::new());
+    }
+    let api = scouty.client().clone();
+
+    let validator_groups_addr =
+        node_runtime::storage().para_scheduler().validator_groups();
+    let validator_groups = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&validator_groups_addr)
+        .await?
+        .unwrap_or_default();
+
+    let mut assignments: BTreeMap<AccountId32, ParaAssignment> = BTreeMap::new();
+    let stashes = get_para_validator_stashes(&scouty, session_index).await?;
+    for (para_index, stash) in stashes.into_iter() {
+        for (group_index, group) in validator_groups.iter().enumerate() {
+            if let Some(group_position) = group
+                .iter()
+                .position(|ValidatorIndex(index)| *index == para_index)
+            {
+                assignments.insert(
+                    stash.clone(),
+                    ParaAssignment {
+                        para_index,
+                        group_index: group_index as u32,
+                        group_position: group_position as u32,
+                    },
+                );
+            }
+        }
+    }
+    debug!("para assignments {:?}", assignments);
+
+    Ok(backing_records.start_session(session_index, assignments))
+}
+
+/// Track the backing votes of the stashes predefined in the candidates backed in the
+/// block, the group that backed every candidate is given by the CandidateBacked events
+async fn track_backed_candidates(
+    block: &Block<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+    events: &Events<PolkadotConfig>,
+    backing_records: &mut BackingRecords,
+) -> Result<(), ScoutyError> {
+    // Groups by para id, in the order the candidates have been backed
+    let mut groups: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+    for event in events.find::<CandidateBacked>() {
+        let event = event?;
+        groups
+            .entry(event.0.descriptor.para_id.0)
+            .or_default()
+            .push(event.3 .0);
+    }
+    if groups.is_empty() {
+        return Ok(());
+    }
+
+    let extrinsics = block.extrinsics().await?;
+    let enter = match extrinsics.find_first::<Enter>()? {
+        Some(enter) => enter.value,
+        None => return Ok(()),
+    };
+    for candidate in enter.data.backed_candidates.iter() {
+        // Candidates dropped by the runtime have no CandidateBacked event
+        let group_index = match groups.get_mut(&candidate.candidate.descriptor.para_id.0)
+        {
+            Some(groups) if !groups.is_empty() => groups.remove(0),
+            _ => continue,
+        };
+        let voted = backing_votes(&candidate.validator_indices);
+        let explicit: Vec<bool> = candidate
+            .validity_votes
+            .iter()
+            .map(|vote| matches!(vote, ValidityAttestation::Explicit(_)))
+            .collect();
+        backing_records.track_candidate(group_index, &voted, &explicit);
+    }
+    Ok(())
+}
+
+/// Returns for every validator of the backing group if it voted for the candidate, in
+/// the order of the group
+fn backing_votes(validator_indices: &DecodedBits<u8, Lsb0>) -> Vec<bool> {
+    validator_indices.as_bits().iter().collect()
+}
+
+async fn try_run_offline_risk_hook(
+    scouty: &Scouty,
+    checked_session_index: &mut Option<u32>,
+) -> Result<(), ScoutyError> {
+    let api = scouty.client().clone();
+    let config = CONFIG.current();
+
+    if config.offline_risk_threshold == 0 {
+        return Ok(());
+    }
+
+    // Only verify once per session
+    let current_session_index_addr = node_runtime::storage().session().current_index();
+    let current_session_index = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&current_session_index_addr)
+        .await?
+        .unwrap_or_default();
+    if *checked_session_index == Some(current_session_index) {
+        return Ok(());
+    }
+
+    let session_progress = get_session_progress(&scouty).await?;
+    if session_progress < config.offline_risk_threshold {
+        return Ok(());
+    }
+    *checked_session_index = Some(current_session_index);
+
+    // Collect session active validators
+    let validators_addr = node_runtime::storage().session().validators();
+    let active_validators = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&validators_addr)
+        .await?
+        .unwrap_or_default();
+
+    // Collect validators info based on config stashes
+    let mut validators = collect_validators_data(&scouty).await?;
+
+    let session = collect_session_data(&scouty, current_session_index).await?;
+
+    let network = Network::load(scouty.rpc()).await?;
+    debug!("network {:?}", network);
+
+    for v in validators.iter_mut() {
+        // Heartbeats are indexed by the position of the validator in the active set
+        let authority_index = match active_validators.iter().position(|a| a == &v.stash) {
+            Some(index) => index,
+            None => continue,
+        };
+
+        let storage = api.storage().at_latest().await?;
+        let heartbeat = storage
+            .fetch(&dynamic::storage(
+                "ImOnline",
+                "ReceivedHeartbeats",
+                vec![
+                    Value::u128(current_session_index as u128),
+                    Value::u128(authority_index as u128),
+                ],
+            ))
+            .await?;
+        let authored_blocks = storage
+            .fetch(&dynamic::storage(
+                "ImOnline",
+                "AuthoredBlocks",
+                vec![
+                    Value::u128(current_session_index as u128),
+                    Value::from_bytes(&v.stash),
+                ],
+            ))
+            .await?;
+        if heartbeat.is_some() || authored_blocks.is_some() {
+            continue;
+        }
+        v.is_offline = true;
+
+        // Try HOOK_VALIDATOR_OFFLINE_RISK
+        let hook = run_offline_risk_hook(
+            &scouty,
+            v,
+            &session,
+            session.current_session_index,
+            session_progress,
+            "No heartbeat nor authored blocks so far",
+            &network,
+        )
+        .await;
+        v.hooks.push(hook);
+    }
+
+    debug!("validators {:?}", validators);
+
+    if validators.iter().any(|v| v.is_offline) {
+        // Prepare notification report
+        let data = RawData {
+            network,
+            validators,
+            session,
+            offline_risk: OfflineRisk { session_progress },
+            section: Section::OfflineRisk,
+            ..Default::default()
+        };
+
+        let report = Report::from(data);
+        scouty.send_report(&report).await?;
+    }
+
+    Ok(())
+}
+
+/// Run the offline risk hook of the stash in the session given, at the session progress
+/// (in percentage) the risk has been detected
+async fn run_offline_risk_hook(
+    scouty: &Scouty,
+    v: &Validator,
+    session: &Session,
+    session_index: u32,
+    session_progress: u32,
+    reason: &str,
+    network: &Network,
+) -> Hook {
+    let config = CONFIG.current();
+
+    let mut args = vec![
+        scouty.to_ss58(v.stash.clone()),
+        v.name.to_string(),
+        format!("0x{:?}", HexDisplay::from(&v.queued_session_keys)),
+        v.is_active.to_string(),
+        v.is_queued.to_string(),
+        session.active_era_index.to_string(),
+        session_index.to_string(),
+        session_progress.to_string(),
+    ];
+
+    let eras_session_index = if session_index == session.current_session_index {
+        Some(session.eras_session_index)
+    } else {
+        None
+    };
+    let mut payload = HookPayload::new()
+        .validator(
+            scouty.to_ss58(v.stash.clone()),
+            v.name.to_string(),
+            format!("0x{:?}", HexDisplay::from(&v.queued_session_keys)),
+            v.is_active,
+            v.is_queued,
+        )
+        .session(session.active_era_index, session_index, eras_session_index)
+        .with("session_progress", session_progress)
+        .with("reason", reason);
+
+    if config.expose_network || config.expose_all {
+        args.push(network.name.to_string());
+        args.push(network.token_symbol.to_string());
+        args.push(network.token_decimals.to_string());
+        payload = payload.network(&network);
+    } else {
+        args.push("-".to_string());
+        args.push("-".to_string());
+        args.push("-".to_string());
+    }
+    args.push(reason.to_string());
+
+    // Try run hook
+    scouty
+        .try_run_hook(
+            HOOK_VALIDATOR_OFFLINE_RISK,
+            &config.hook_validator_offline_risk_path,
+            args,
+            payload,
+        )
+        .await
+}
+
+/// Returns the percentage of the current session elapsed, based on the babe slots
+async fn get_session_progress(scouty: &Scouty) -> Result<u32, ScoutyError> {
+    let api = scouty.client().clone();
+
+    let epoch_duration_addr = node_runtime::constants().babe().epoch_duration();
+    let epoch_duration = api.constants().at(&epoch_duration_addr)?;
+
+    let storage = api.storage().at_latest().await?;
+    let genesis_slot = storage
+        .fetch(&node_runtime::storage().babe().genesis_slot())
+        .await?
+        .map(|slot| slot.0)
+        .unwrap_or_default();
+    let current_slot = storage
+        .fetch(&node_runtime::storage().babe().current_slot())
+        .await?
+        .map(|slot| slot.0)
+        .unwrap_or_default();
+    let epoch_index = storage
+        .fetch(&node_runtime::storage().babe().epoch_index())
+        .await?
+        .unwrap_or_default();
+
+    Ok(session_progress(
+        current_slot,
+        genesis_slot,
+        epoch_index,
+        epoch_duration,
+    ))
+}
+
+// DEPRECATED with runtime 1002000
+//
+// async fn try_run_im_online_some_offline_hook(
+//     scouty: &Scouty,
+//     event: Option<SomeOffline>,
+// ) -> Result<(), ScoutyError> {
+//     if let Some(event) = event {
+//         let config = CONFIG.current();
+
+//         // Collect validators info based on config stashes
+//         let mut validators = collect_validators_data(&scouty).await?;
+
+//         let network = Network::load(scouty.rpc()).await?;
+//         debug!("network {:?}", network);
+
+//         // Try to run hooks for each stash
+//         for v in validators.iter_mut() {
+//             for (account_id, _exposure) in event.offline.iter() {
+//                 if account_id == &v.stash {
+//                     v.is_offline = true;
+
+//                     // Try HOOK_VALIDATOR_OFFLINE
+//                     let mut args = vec![
+//                         convert_account_id(v.stash.clone()).to_string(),
+//                         v.name.to_string(),
+//                         format!("0x{:?}", HexDisplay::from(&v.queued_session_keys)),
+//                         v.is_active.to_string(),
+//                         v.is_queued.to_string(),
+//                     ];
+
+//                     if config.expose_network || config.expose_all {
+//                         args.push(network.name.to_string());
+//                         args.push(network.token_symbol.to_string());
+//                         args.push(network.token_decimals.to_string());
+//                     } else {
+//                         args.push("-".to_string());
+//                         args.push("-".to_string());
+//                         args.push("-".to_string());
+//                     }
+
+//                     // Try run hook
+//                     let hook = Hook::try_run(
+//                         HOOK_VALIDATOR_OFFLINE,
+//                         &config.hook_validator_offline_path,
+//                         args.clone(),
+//                     )?;
+//                     v.hooks.push(hook);
+//                     break;
+//                 }
+//             }
+//         }
+
+//         debug!("validators {:?}", validators);
+
+//         // NOTE: Only send offline message if the offline account is
+//         // one of the stashes defined in config
+//         if validators.iter().any(|v| v.is_offline) {
+//             // Prepare notification report
+//             let data = RawData {
+//                 network,
+//                 validators,
+//                 section: Section::Offline,
+//                 ..Default::default()
+//             };
+
+//             let report = Report::from(data);
+//             scouty
+//                 .send_message(&report.message(), &report.formatted_message())
+//                 .await?;
+//         }
+//     }
+
+//     Ok(())
+// }
+
+/// Send a digest of the eras missed while scouty was down, since the era of the last
+/// session processed, and run the catch up hook of every stash if enabled
+async fn try_run_catch_up(
+    scouty: &Scouty,
+    chain_state: &ChainState,
+) -> Result<(), ScoutyError> {
+    let api = scouty.client().clone();
+    let config = CONFIG.current();
+
+    if config.catch_up_disabled {
+        return Ok(());
+    }
+
+    let active_era_addr = node_runtime::storage().staking().active_era();
+    let active_era_index = match api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&active_era_addr)
+        .await?
+    {
+        Some(info) => info.index,
+        None => return Ok(()),
+    };
+    let history_depth_addr = node_runtime::constants().staking().history_depth();
+    let history_depth = api.constants().at(&history_depth_addr)?;
+
+    let catch_up = CatchUp::new(
+        chain_state.era_index,
+        active_era_index,
+        config.catch_up_lookback_eras,
+        history_depth,
+    );
+    if catch_up.is_empty() {
+        return Ok(());
+    }
+    info!(
+        "Catch up eras {:?} missed, eras {:?} not available",
+        catch_up.eras, catch_up.pruned
+    );
+
+    // Collect validators info based on config stashes
+    let mut validators = collect_validators_data(&scouty).await?;
+
+    let network = Network::load(scouty.rpc()).await?;
+    debug!("network {:?}", network);
+
+    for era_index in catch_up.eras.clone() {
+        let era_reward_points_addr = node_runtime::storage()
+            .staking()
+            .eras_reward_points(&era_index);
+        let era_reward_points = api
+            .storage()
+            .at_latest()
+            .await?
+            .fetch(&era_reward_points_addr)
+            .await?;
+
+        let era_reward_addr = node_runtime::storage()
+            .staking()
+            .eras_validator_reward(&era_index);
+        let validator_payout = api
+            .storage()
+            .at_latest()
+            .await?
+            .fetch(&era_reward_addr)
+            .await?
+            .unwrap_or_default();
+
+        for v in validators.iter_mut() {
+            // NOTE: the era points might have been pruned already, in which case the
+            // era is only noted as not available
+            let missed_era = match &era_reward_points {
+                Some(era_points) => {
+                    let points = era_points
+                        .individual
+                        .iter()
+                        .find(|(stash, _)| stash == &v.stash)
+                        .map(|(_, points)| *points);
+                    let is_active = points.is_some()
+                        || get_stake(&scouty, era_index, &v.stash).await?.is_some();
+                    let slash_addr = node_runtime::storage()
+                        .staking()
+                        .validator_slash_in_era(&era_index, &v.stash);
+                    let slashed = api
+                        .storage()
+                        .at_latest()
+                        .await?
+                        .fetch(&slash_addr)
+                        .await?
+                        .map(|(_, amount)| amount);
+                    MissedEra {
+                        era_index,
+                        is_available: true,
+                        is_active,
+                        points: points.unwrap_or_default(),
+                        era_avg: era_points.total
+                            / (era_points.individual.len().max(1) as u32),
+                        rank: era_points_rank(&era_points.individual, &v.stash),
+                        reward: estimate_reward(
+                            validator_payout,
+                            points.unwrap_or_default(),
+                            era_points.total,
+                        ),
+                        slashed,
+                    }
+                }
+                None => MissedEra {
+                    era_index,
+                    ..Default::default()
+                },
+            };
+            v.missed_eras.push(missed_era);
+        }
+    }
+
+    // Hooks are not run by default, the conditions of the eras missed are stale
+    if config.catch_up_hooks_enabled {
+        for v in validators.iter_mut() {
+            let active_eras: Vec<&MissedEra> =
+                v.missed_eras.iter().filter(|era| era.is_active).collect();
+            let points: u32 = active_eras.iter().map(|era| era.points).sum();
+            let reward: u128 = active_eras.iter().map(|era| era.reward).sum();
+            let slashed_eras: Vec<String> = v
+                .missed_eras
+                .iter()
+                .filter(|era| era.slashed.is_some())
+                .map(|era| era.era_index.to_string())
+                .collect();
+            let slashed_eras = if slashed_eras.is_empty() {
+                "-".to_string()
+            } else {
+                slashed_eras.join(",")
+            };
+
+            // Try HOOK_CATCH_UP
+            let mut args = vec![
+                scouty.to_ss58(v.stash.clone()),
+                v.name.to_string(),
+                catch_up.pruned.start.to_string(),
+                (active_era_index - 1).to_string(),
+                active_eras.len().to_string(),
+                points.to_string(),
+                reward.to_string(),
+                slashed_eras.to_string(),
+                catch_up.pruned.len().to_string(),
+            ];
+
+            let mut payload = HookPayload::new()
+                .validator(
+                    scouty.to_ss58(v.stash.clone()),
+                    v.name.to_string(),
+                    format!("0x{:?}", HexDisplay::from(&v.queued_session_keys)),
+                    v.is_active,
+                    v.is_queued,
+                )
+                .with("first_era_missed", catch_up.pruned.start)
+                .with("last_era_missed", active_era_index - 1)
+                .with("eras_active", active_eras.len())
+                .with("era_points", points)
+                .with("estimated_reward", reward.to_string())
+                .with("eras_slashed", slashed_eras)
+                .with("eras_not_available", catch_up.pruned.len());
+
+            if config.expose_network || config.expose_all {
+                args.push(network.name.to_string());
+                args.push(network.token_symbol.to_string());
+                args.push(network.token_decimals.to_string());
+                payload = payload.network(&network);
+            } else {
+                args.push("-".to_string());
+                args.push("-".to_string());
+                args.push("-".to_string());
+            }
+
+            // Try run hook
+            let hook = scouty
+                .try_run_hook(HOOK_CATCH_UP, &config.hook_catch_up_path, args, payload)
+                .await;
+            v.hooks.push(hook);
+        }
+    }
+
+    // Prepare notification report
+    let data = RawData {
+        network,
+        validators,
+        catch_up,
+        section: Section::CatchUp,
+        ..Default::default()
+    };
+
+    let report = Report::from(data);
+    scouty.send_report(&report).await?;
+
+    Ok(())
+}
+
+async fn try_run_era_paid_hook(
+    scouty: &Scouty,
+    event: Option<EraPaid>,
+) -> Result<(), ScoutyError> {
+    if let Some(event) = event {
+        let api = scouty.client().clone();
+        let config = CONFIG.current();
+
+        // An era paid already notified before a restart is not notified again
+        let chain = scouty.runtime().to_string();
+        if state::get(&chain).is_era_paid_processed(event.era_index) {
+            info!(
+                "Era {} paid already processed -> skip notification and hooks",
+                event.era_index
+            );
+            return Ok(());
+        }
+
+        // Fetch era reward points from the era paid
+        let era_reward_points_addr = node_runtime::storage()
+            .staking()
+            .eras_reward_points(&event.era_index);
+        let era_reward_points = api
+            .storage()
+            .at_latest()
+            .await?
+            .fetch(&era_reward_points_addr)
+            .await?;
+
+        // Fetch the total validators payout of the era paid
+        let era_reward_addr = node_runtime::storage()
+            .staking()
+            .eras_validator_reward(&event.era_index);
+        let validator_payout = api
+            .storage()
+            .at_latest()
+            .await?
+            .fetch(&era_reward_addr)
+            .await?
+            .unwrap_or(event.validator_payout);
+
+        let total_points = era_reward_points
+            .as_ref()
+            .map(|points| points.total)
+            .unwrap_or_default();
+        let validator_count = era_reward_points
+            .as_ref()
+            .map(|points| points.individual.len() as u32)
+            .unwrap_or_default();
+
+        // Collect validators info based on config stashes
+        let mut validators = collect_validators_data(&scouty).await?;
+
+        let network = Network::load(scouty.rpc()).await?;
+        debug!("network {:?}", network);
+
+        // Eras paid of every stash accumulated for the weekly report
+        let mut weekly_eras = Vec::new();
+
+        // Try to run hooks for each stash
+        for v in validators.iter_mut() {
+            v.reward.points = era_reward_points
+                .as_ref()
+                .and_then(|points| {
+                    points
+                        .individual
+                        .iter()
+                        .find(|(stash, _)| stash == &v.stash)
+                        .map(|(_, points)| *points)
+                })
+                .unwrap_or_default();
+            v.reward.amount_value =
+                estimate_reward(validator_payout, v.reward.points, total_points);
+            v.reward.rank = era_reward_points
+                .as_ref()
+                .and_then(|points| era_points_rank(&points.individual, &v.stash));
+
+            // The stash earns the commission of the era paid and the share of its own
+            // stake in the exposure, the remaining reward goes to the nominators
+            let era_prefs_addr = node_runtime::storage()
+                .staking()
+                .eras_validator_prefs(&event.era_index, &v.stash);
+            let commission = api
+                .storage()
+                .at_latest()
+                .await?
+                .fetch(&era_prefs_addr)
+                .await?
+                .map(|prefs| {
+                    let node_runtime::runtime_types::sp_arithmetic::per_things::Perbill(
+                        c,
+                    ) = prefs.commission;
+                    c
+                })
+                .unwrap_or_default();
+            let stake = get_stake(&scouty, event.era_index, &v.stash)
+                .await?
+                .unwrap_or_default();
+            v.reward.stash_amount_value =
+                estimate_stash_reward(v.reward.amount_value, commission, &stake);
+            weekly_eras.push((
+                scouty.to_ss58(v.stash.clone()),
+                v.reward.points > 0 || stake.total > 0,
+                v.reward.points,
+                v.reward.amount_value,
+                v.reward.rank,
+            ));
+
+            // Verify if the reward has already been claimed
+            let claimed_rewards_addr = node_runtime::storage()
+                .staking()
+                .claimed_rewards(&event.era_index, &v.stash);
+            v.reward.is_claimed = api
+                .storage()
+                .at_latest()
+                .await?
+                .fetch(&claimed_rewards_addr)
+                .await?
+                .is_some_and(|pages| !pages.is_empty());
+
+            // Try HOOK_ERA_PAID
+            let mut args = vec![
+                scouty.to_ss58(v.stash.clone()),
+                v.name.to_string(),
+                event.era_index.to_string(),
+                v.reward.points.to_string(),
+                total_points.to_string(),
+                v.reward.amount_value.to_string(),
+                validator_payout.to_string(),
+                v.reward.is_claimed.to_string(),
+            ];
+
+            let mut payload = HookPayload::new()
+                .validator(
+                    scouty.to_ss58(v.stash.clone()),
+                    v.name.to_string(),
+                    format!("0x{:?}", HexDisplay::from(&v.queued_session_keys)),
+                    v.is_active,
+                    v.is_queued,
+                )
+                .with("era_paid_index", event.era_index)
+                .with("era_points", v.reward.points)
+                .with("total_era_points", total_points)
+                .with("estimated_reward", v.reward.amount_value.to_string())
+                .with("validator_payout", validator_payout.to_string())
+                .with("is_claimed", v.reward.is_claimed)
+                .with("stash_reward", v.reward.stash_amount_value.to_string())
+                .with(
+                    "stash_reward_formatted",
+                    network.format_tokens(v.reward.stash_amount_value),
+                )
+                .with("era_points_rank", v.reward.rank)
+                .with("validator_count", validator_count);
+
+            if config.expose_network || config.expose_all {
+                args.push(network.name.to_string());
+                args.push(network.token_symbol.to_string());
+                args.push(network.token_decimals.to_string());
+                payload = payload.network(&network);
+            } else {
+                args.push("-".to_string());
+                args.push("-".to_string());
+                args.push("-".to_string());
+            }
+
+            args.push(v.reward.stash_amount_value.to_string());
+            args.push(network.format_tokens(v.reward.stash_amount_value));
+            args.push(
+                v.reward
+                    .rank
+                    .map_or("-".to_string(), |rank| rank.to_string()),
+            );
+            args.push(validator_count.to_string());
+
+            // Try run hook
+            let hook = scouty
+                .try_run_hook(
+                    HOOK_ERA_PAID,
+                    &config.hook_era_paid_path,
+                    args.clone(),
+                    payload.clone(),
+                )
+                .await;
+            v.hooks.push(hook);
+        }
+
+        debug!("validators {:?}", validators);
+
+        // Prepare notification report
+        let data = RawData {
+            network,
+            validators,
+            era_paid: EraPayout {
+                era_index: event.era_index,
+                validator_payout,
+                total_points,
+                validator_count,
+            },
+            section: Section::EraPaid,
+            ..Default::default()
+        };
+
+        let report = Report::from(data);
+        scouty.send_report(&report).await?;
+
+        state::update(&chain, |state| {
+            state.era_paid_index = Some(event.era_index);
+            if !config.weekly_report.is_empty() {
+                for (stash, is_active, points, reward, rank) in weekly_eras {
+                    state
+                        .weekly
+                        .track_era(&stash, is_active, points, reward, rank);
+                }
+            }
+        });
+    }
+
+    Ok(())
+}
+
+/// Submit the payouts of the eras not claimed yet of the stashes predefined, if
+/// enabled for the chain. Every payout is waited to be finalized before the next one
+async fn try_run_payouts(scouty: &Scouty) -> Result<(), ScoutyError> {
+    let api = scouty.client().clone();
+    let config = CONFIG.current();
+
+    let network = Network::load(scouty.rpc()).await?;
+    if !config.is_payout_enabled(&network.name) {
+        return Ok(());
+    }
+
+    // NOTE: an invalid signer is reported as the failure of every payout due
+    let signer =
+        payouts::load_signer(&config.payout_seed_path).map_err(|e| e.to_string());
+
+    let session = collect_session_data(&scouty, 0).await?;
+    let history_depth_addr = node_runtime::constants().staking().history_depth();
+    let history_depth = api.constants().at(&history_depth_addr)?;
+    let eras = payouts::claimable_eras(
+        session.active_era_index,
+        config.payout_eras_depth,
+        history_depth,
+    );
+
+    // Collect validators info based on config stashes
+    let mut validators = collect_validators_data(&scouty).await?;
+
+    for v in validators.iter_mut() {
+        let unclaimed = get_unclaimed_eras(&scouty, &v.stash, eras.clone()).await?;
+        'eras: for era in payouts::select_eras(unclaimed, config.payout_max_eras) {
+            // NOTE: every payout claims the next exposure page of the era
+            for _ in 0..era.pages {
+                let payout = match &signer {
+                    Ok(signer) => {
+                        match submit_payout(&scouty, signer, &v.stash, era.era_index)
+                            .await
+                        {
+                            Ok(payout) => payout,
+                            Err(e) => Payout {
+                                era_index: era.era_index,
+                                error: Some(e.to_string()),
+                                ..Default::default()
+                            },
+                        }
+                    }
+                    Err(e) => Payout {
+                        era_index: era.era_index,
+                        error: Some(e.to_string()),
+                        ..Default::default()
+                    },
+                };
+                info!(
+                    "Payout of era {} of stash {} submitted -> {}",
+                    era.era_index,
+                    v.stash,
+                    payout.error.as_deref().unwrap_or("ok")
+                );
+
+                // Try HOOK_PAYOUT_SUBMITTED
+                let mut args = vec![
+                    scouty.to_ss58(v.stash.clone()),
+                    v.name.to_string(),
+                    payout.era_index.to_string(),
+                    payout.error.is_none().to_string(),
+                    if payout.block_hash.is_empty() {
+                        "-".to_string()
+                    } else {
+                        payout.block_hash.to_string()
+                    },
+                    payout.fee.to_string(),
+                    payout.amount.to_string(),
+                    payout.error.clone().unwrap_or("-".to_string()),
+                ];
+
+                let mut payload = HookPayload::new()
+                    .validator(
+                        scouty.to_ss58(v.stash.clone()),
+                        v.name.to_string(),
+                        format!("0x{:?}", HexDisplay::from(&v.queued_session_keys)),
+                        v.is_active,
+                        v.is_queued,
+                    )
+                    .with("payout_era_index", payout.era_index)
+                    .with("is_success", payout.error.is_none())
+                    .with("block_hash", payout.block_hash.to_string())
+                    .with("fee", payout.fee.to_string())
+                    .with("payout", payout.amount.to_string());
+                if let Some(error) = &payout.error {
+                    payload = payload.with("error", error.to_string());
+                }
+
+                if config.expose_network || config.expose_all {
+                    args.push(network.name.to_string());
+                    args.push(network.token_symbol.to_string());
+                    args.push(network.token_decimals.to_string());
+                    payload = payload.network(&network);
+                } else {
+                    args.push("-".to_string());
+                    args.push("-".to_string());
+                    args.push("-".to_string());
+                }
+
+                // Try run hook
+                let hook = scouty
+                    .try_run_hook(
+                        HOOK_PAYOUT_SUBMITTED,
+                        &config.hook_payout_submitted_path,
+                        args.clone(),
+                        payload.clone(),
+                    )
+                    .await;
+                v.hooks.push(hook);
+
+                // NOTE: the remaining payouts would fail for the same reason
+                let is_failed = payout.error.is_some();
+                v.payouts.push(payout);
+                if is_failed {
+                    break 'eras;
+                }
+            }
+        }
+    }
+
+    debug!("validators {:?}", validators);
+
+    // NOTE: Only send a message for the stashes with payouts submitted
+    validators.retain(|v| !v.payouts.is_empty());
+    if !validators.is_empty() {
+        // Prepare notification report
+        let data = RawData {
+            network,
+            validators,
+            session,
+            section: Section::Payout,
+            ..Default::default()
+        };
+
+        let report = Report::from(data);
+        scouty.send_report(&report).await?;
+    }
+
+    Ok(())
+}
+
+/// Returns the eras in which the stash was active with exposure pages not claimed yet.
+/// Eras exposed before the paged exposures are verified against the claimed rewards
+/// kept in the ledger, so that eras of both storage versions are handled
+async fn get_unclaimed_eras(
+    scouty: &Scouty,
+    stash: &AccountId32,
+    eras: Range<u32>,
+) -> Result<Vec<UnclaimedEra>, ScoutyError> {
+    let api = scouty.client().clone();
+
+    let storage = api.storage().at_latest().await?;
+
+    // Eras claimed before the paged exposures are kept in the ledger
+    let bonded_addr = node_runtime::storage().staking().bonded(stash);
+    let legacy_claimed_rewards = match storage.fetch(&bonded_addr).await? {
+        Some(controller) => {
+            let ledger_addr = node_runtime::storage().staking().ledger(&controller);
+            match storage.fetch(&ledger_addr).await? {
+                Some(ledger) => {
+                    let BoundedVec(eras) = ledger.legacy_claimed_rewards;
+                    eras
+                }
+                None => Vec::new(),
+            }
+        }
+        None => Vec::new(),
+    };
+
+    let mut unclaimed: Vec<UnclaimedEra> = Vec::new();
+    for era_index in eras {
+        if legacy_claimed_rewards.contains(&era_index) {
+            continue;
+        }
+
+        let eras_stakers_overview_addr = node_runtime::storage()
+            .staking()
+            .eras_stakers_overview(&era_index, stash);
+        let page_count = match storage.fetch(&eras_stakers_overview_addr).await? {
+            Some(exposure) => exposure.page_count,
+            None => {
+                // Eras exposed before the paged exposures are claimed at once
+                let eras_stakers_addr = node_runtime::storage()
+                    .staking()
+                    .eras_stakers(&era_index, stash);
+                match storage.fetch(&eras_stakers_addr).await? {
+                    Some(exposure) if exposure.total > 0 => 1,
+                    // Stash not active in the era
+                    _ => continue,
+                }
+            }
+        };
+
+        let claimed_rewards_addr = node_runtime::storage()
+            .staking()
+            .claimed_rewards(&era_index, stash);
+        let claimed_pages = storage
+            .fetch(&claimed_rewards_addr)
+            .await?
+            .map_or(0, |pages| pages.len() as u32);
+
+        unclaimed.push(UnclaimedEra {
+            era_index,
+            pages: page_count.saturating_sub(claimed_pages),
+            ..Default::default()
+        });
+    }
+
+    Ok(unclaimed)
+}
+
+/// Returns the estimated reward of the stash in the era, including nominators share
+async fn get_estimated_reward(
+    scouty: &Scouty,
+    era_index: u32,
+    stash: &AccountId32,
+) -> Result<u128, ScoutyError> {
+    let storage = scouty.client().storage().at_latest().await?;
+
+    let era_reward_addr = node_runtime::storage()
+        .staking()
+        .eras_validator_reward(&era_index);
+    let validator_payout = storage.fetch(&era_reward_addr).await?.unwrap_or_default();
+
+    let era_reward_points_addr = node_runtime::storage()
+        .staking()
+        .eras_reward_points(&era_index);
+    let (points, total_points) = match storage.fetch(&era_reward_points_addr).await? {
+        Some(era_reward_points) => (
+            era_reward_points
+                .individual
+                .iter()
+                .find(|(s, _)| s == stash)
+                .map_or(0, |(_, points)| *points),
+            era_reward_points.total,
+        ),
+        None => (0, 0),
+    };
+
+    Ok(estimate_reward(validator_payout, points, total_points))
+}
+
+/// Verify the eras not claimed yet of the stashes predefined, once per era, and send a
+/// message if the oldest unclaimed era is older than the threshold, before the rewards
+/// expire
+async fn try_run_unclaimed_eras_check(scouty: &Scouty) -> Result<(), ScoutyError> {
+    let api = scouty.client().clone();
+    let config = CONFIG.current();
+
+    if config.unclaimed_eras_threshold == 0 {
+        return Ok(());
+    }
+
+    let session = collect_session_data(&scouty, 0).await?;
+    let history_depth_addr = node_runtime::constants().staking().history_depth();
+    let history_depth = api.constants().at(&history_depth_addr)?;
+    let eras =
+        payouts::claimable_eras(session.active_era_index, history_depth, history_depth);
+
+    // Collect validators info based on config stashes
+    let mut validators = collect_validators_data(&scouty).await?;
+
+    for v in validators.iter_mut() {
+        let mut unclaimed = get_unclaimed_eras(&scouty, &v.stash, eras.clone()).await?;
+        unclaimed.retain(|era| era.pages > 0);
+        match payouts::oldest_unclaimed_age(session.active_era_index, &unclaimed) {
+            Some(age) if age > config.unclaimed_eras_threshold => (),
+            _ => continue,
+        }
+        for era in unclaimed.iter_mut() {
+            era.estimated_reward =
+                get_estimated_reward(&scouty, era.era_index, &v.stash).await?;
+        }
+        v.unclaimed_eras = unclaimed;
+    }
+
+    debug!("validators {:?}", validators);
+
+    // NOTE: Only send a message for the stashes with old unclaimed eras
+    validators.retain(|v| !v.unclaimed_eras.is_empty());
+    if !validators.is_empty() {
+        let network = Network::load(scouty.rpc()).await?;
+
+        // Prepare notification report
+        let data = RawData {
+            network,
+            validators,
+            session,
+            section: Section::UnclaimedEras,
+            ..Default::default()
+        };
+
+        let report = Report::from(data);
+        scouty.send_report(&report).await?;
+    }
+
+    Ok(())
+}
+
+/// Sign and submit the payout of the next exposure page of the era and wait for it to
+/// be finalized, returns the fee paid and the rewards paid out
+async fn submit_payout(
+    scouty: &Scouty,
+    signer: &PayoutSigner,
+    stash: &AccountId32,
+    era_index: u32,
+) -> Result<Payout, ScoutyError> {
+    let api = scouty.client().clone();
+
+    let tx = node_runtime::tx()
+        .staking()
+        .payout_stakers(stash.clone(), era_index);
+    let progress = api
+        .tx()
+        .sign_and_submit_then_watch_default(&tx, signer)
+        .await?;
+    let in_block = async_std::future::timeout(
+        std::time::Duration::from_secs(PAYOUT_FINALIZATION_TIMEOUT),
+        progress.wait_for_finalized(),
+    )
+    .await
+    .map_err(|_| {
+        ScoutyError::Other(format!(
+            "Payout not finalized after {} seconds",
+            PAYOUT_FINALIZATION_TIMEOUT
+        ))
+    })??;
+    let block_hash = in_block.block_hash();
+    let events = in_block.wait_for_success().await?;
+
+    let fee = events
+        .find_first::<TransactionFeePaid>()?
+        .map_or(0, |event| event.actual_fee);
+    let amount = events
+        .find::<Rewarded>()
+        .filter_map(|event| event.ok())
+        .map(|event| event.amount)
+        .sum();
+
+    Ok(Payout {
+        era_index,
+        block_hash: format!("{:?}", block_hash),
+        fee,
+        amount,
+        error: None,
+    })
+}
+
+async fn try_run_staking_slashed_hooks(
+    scouty: &Scouty,
+    slashes: Vec<Slashed>,
+    offences: Vec<Offence>,
+) -> Result<(), ScoutyError> {
+    if slashes.is_empty() && offences.is_empty() {
+        return Ok(());
+    }
+    let config = CONFIG.current();
+
+    let session = collect_session_data(&scouty, 0).await?;
+
+    // Run the slashed hook for every slash on the stashes predefined
+    let mut others_slashed = 0;
+    for event in slashes {
+        if is_stash_predefined(&event.staker) {
+            try_run_staking_slashed_hook(&scouty, event, &session).await?;
+        } else {
+            others_slashed += 1;
+        }
+    }
+
+    // Slashes are usually deferred, so alert as soon as an offence is reported
+    // against any of the stashes predefined
+    let mut offenders: Vec<AccountId32> = Vec::new();
+    let mut kinds: Vec<String> = Vec::new();
+    let mut reported: Vec<(AccountId32, String, Vec<u8>)> = Vec::new();
+    for event in offences.iter() {
+        let kind = parse_offence_kind(&event.kind);
+        for offender in get_offenders(&scouty, event).await? {
+            if is_stash_predefined(&offender) {
+                reported.push((offender.clone(), kind.clone(), event.timeslot.clone()));
+            }
+            offenders.push(offender);
+        }
+        kinds.push(kind);
+    }
+
+    if !reported.is_empty() {
+        let mut validators = collect_validators_data(&scouty).await?;
+
+        let network = Network::load(scouty.rpc()).await?;
+        debug!("network {:?}", network);
+
+        for v in validators.iter_mut() {
+            v.is_offender = offenders.contains(&v.stash);
+            let offences: Vec<(&String, &Vec<u8>)> = reported
+                .iter()
+                .filter(|(stash, _, _)| stash == &v.stash)
+                .map(|(_, kind, timeslot)| (kind, timeslot))
+                .collect();
+            for (kind, timeslot) in offences {
+                let hook = run_offence_reported_hook(
+                    &scouty, v, &session, kind, timeslot, &network,
+                )
+                .await;
+                v.hooks.push(hook);
+            }
+        }
+
+        let data = RawData {
+            network,
+            validators,
+            session: session.clone(),
+            slash: Slash {
+                era_index: session.active_era_index,
+                offences: kinds.clone(),
+                ..Default::default()
+            },
+            section: Section::Offence,
+            ..Default::default()
+        };
+
+        let report = Report::from(data);
+        scouty.send_report(&report).await?;
+    }
+
+    // Optionally summarize the slashes and offences on the other validators
+    if config.slash_summary && (others_slashed > 0 || !kinds.is_empty()) {
+        let network = Network::load(scouty.rpc()).await?;
+        debug!("network {:?}", network);
+
+        let data = RawData {
+            network,
+            slash: Slash {
+                era_index: session.active_era_index,
+                others_slashed,
+                offences: kinds,
+                ..Default::default()
+            },
+            section: Section::SlashSummary,
+            ..Default::default()
+        };
+
+        let report = Report::from(data);
+        scouty.send_report(&report).await?;
+    }
+
+    Ok(())
+}
+
+async fn try_run_staking_slashed_hook(
+    scouty: &Scouty,
+    event: Slashed,
+    session: &Session,
+) -> Result<(), ScoutyError> {
+    let config = CONFIG.current();
+
+    // Collect validators info based on config stashes
+    let validators = collect_validators_data(&scouty).await?;
+
+    let network = Network::load(scouty.rpc()).await?;
+    debug!("network {:?}", network);
+
+    let mut args = vec![
+        event.staker.to_string(),
+        event.amount.to_string(),
+        session.active_era_index.to_string(),
+    ];
+
+    let mut payload = HookPayload::new()
+        .session(
+            session.active_era_index,
+            session.current_session_index,
+            Some(session.eras_session_index),
+        )
+        .with("staker", event.staker.to_string())
+        .with("amount", event.amount.to_string());
+
+    for v in validators.iter().filter(|v| event.staker == v.stash) {
+        payload = payload.validator(
+            scouty.to_ss58(v.stash.clone()),
+            v.name.to_string(),
+            format!("0x{:?}", HexDisplay::from(&v.queued_session_keys)),
+            v.is_active,
+            v.is_queued,
+        );
+    }
+
+    if config.expose_network || config.expose_all {
+        args.push(network.name.to_string());
+        args.push(network.token_symbol.to_string());
+        args.push(network.token_decimals.to_string());
+        payload = payload.network(&network);
+    } else {
+        args.push("-".to_string());
+        args.push("-".to_string());
+        args.push("-".to_string());
+    }
+
+    // Try run hook
+    let hook = scouty
+        .try_run_hook(
+            HOOK_VALIDATOR_SLASHED,
+            &config.hook_validator_slashed_path,
+            args.clone(),
+            payload.clone(),
+        )
+        .await;
+
+    // Prepare notification report
+    let data = slash_data(network, validators, event, session.active_era_index, hook);
+    debug!("validators {:?}", data.validators);
+
+    let report = Report::from(data);
+    scouty.send_report(&report).await?;
+
+    Ok(())
+}
+
+/// Returns the report data of the slash event given, the stashes predefined slashed
+/// are flagged so that they are listed in the report
+fn slash_data(
+    network: Network,
+    mut validators: Validators,
+    event: Slashed,
+    era_index: u32,
+    hook: Hook,
+) -> RawData {
+    for v in validators.iter_mut() {
+        if event.staker == v.stash {
+            v.is_slashed = true;
+        }
+    }
+
+    // Set slash info
+    let slash = Slash {
+        who: Some(event.staker),
+        amount_value: event.amount,
+        era_index,
+        hook,
+        ..Default::default()
+    };
+
+    RawData {
+        network,
+        validators,
+        slash,
+        section: Section::Slash,
+        ..Default::default()
+    }
+}
+
+/// Run the offence reported hook for the stash reported in the offence kind and time
+/// slot, the session index is the one in which the offence was reported
+async fn run_offence_reported_hook(
+    scouty: &Scouty,
+    v: &Validator,
+    session: &Session,
+    kind: &str,
+    timeslot: &[u8],
+    network: &Network,
+) -> Hook {
+    let config = CONFIG.current();
+
+    // Try HOOK_OFFENCE_REPORTED
+    let mut args = vec![
+        scouty.to_ss58(v.stash.clone()),
+        v.name.to_string(),
+        kind.to_string(),
+        session.active_era_index.to_string(),
+        session.current_session_index.to_string(),
+        format!("0x{:?}", HexDisplay::from(&timeslot)),
+    ];
+
+    let mut payload = HookPayload::new()
+        .validator(
+            scouty.to_ss58(v.stash.clone()),
+            v.name.to_string(),
+            format!("0x{:?}", HexDisplay::from(&v.queued_session_keys)),
+            v.is_active,
+            v.is_queued,
+        )
+        .session(
+            session.active_era_index,
+            session.current_session_index,
+            Some(session.eras_session_index),
+        )
+        .with("offence_kind", kind.to_string())
+        .with("timeslot", format!("0x{:?}", HexDisplay::from(&timeslot)));
+
+    if config.expose_network || config.expose_all {
+        args.push(network.name.to_string());
+        args.push(network.token_symbol.to_string());
+        args.push(network.token_decimals.to_string());
+        payload = payload.network(&network);
+    } else {
+        args.push("-".to_string());
+        args.push("-".to_string());
+        args.push("-".to_string());
+    }
+
+    // Try run hook
+    scouty
+        .try_run_hook(
+            HOOK_OFFENCE_REPORTED,
+            &config.hook_offence_reported_path,
+            args,
+            payload,
+        )
+        .await
+}
+
+/// Returns the offenders of all the reports of the same offence kind and time slot
+async fn get_offenders(
+    scouty: &Scouty,
+    event: &Offence,
+) -> Result<Vec<AccountId32>, ScoutyError> {
+    let api = scouty.client().clone();
+
+    let report_ids_addr = node_runtime::storage()
+        .offences()
+        .concurrent_reports_index(&event.kind, &*event.timeslot);
+    let report_ids = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&report_ids_addr)
+        .await?
+        .unwrap_or_default();
+
+    let mut offenders: Vec<AccountId32> = Vec::new();
+    for report_id in report_ids {
+        let report_addr = node_runtime::storage().offences().reports(&report_id);
+        if let Some(report) = api.storage().at_latest().await?.fetch(&report_addr).await?
+        {
+            offenders.push(report.offender.0);
+        }
+    }
+    Ok(offenders)
+}
+
+async fn try_run_referenda_submitted_hook(
+    scouty: &Scouty,
+    event: Option<Submitted>,
+) -> Result<(), ScoutyError> {
+    if let Some(event) = event {
+        let config = CONFIG.current();
+
+        let network = Network::load(scouty.rpc()).await?;
+        debug!("network {:?}", network);
+
+        let mut args = vec![event.index.to_string(), event.track.to_string()];
+
+        let mut payload = HookPayload::new()
+            .with("referendum_index", event.index)
+            .with("track", event.track);
+
+        if config.expose_network || config.expose_all {
+            args.push(network.name.to_string());
+            args.push(network.token_symbol.to_string());
+            args.push(network.token_decimals.to_string());
+            payload = payload.network(&network);
+        } else {
+            args.push("-".to_string());
+            args.push("-".to_string());
+            args.push("-".to_string());
+        }
+
+        // Try run hook
+        let hook = scouty
+            .try_run_hook(
+                HOOK_REFERENDA_SUBMITTED,
+                &config.hook_referenda_submitted_path,
+                args.clone(),
+                payload.clone(),
+            )
+            .await;
+
+        // Set referendum info
+        let referendum = Referendum {
+            index: event.index,
+            track: event.track,
+            hook,
+        };
+
+        // Prepare notification report
+        let data = RawData {
+            network,
+            referendum,
+            section: Section::Referenda,
+            ..Default::default()
+        };
+
+        let report = Report::from(data);
+        scouty.send_report(&report).await?;
+    }
+    Ok(())
+}
+
+async fn try_run_session_hooks(
+    scouty: &Scouty,
+    event: Option<NewSession>,
+    authority_records: &mut AuthorityRecords,
+    block_number: u32,
+    authority_index: AuthorityIndex,
+    para_records: &mut ParaRecords,
+    era_points_records: &mut EraPointsRecords,
+    chilled_stashes: &mut BTreeSet<AccountId32>,
+    nominator_records: &mut NominatorRecords,
+    waiting_records: &mut WaitingRecords,
+    backing_records: &mut BackingRecords,
+    era_digest_records: &mut EraDigestRecords,
+    session_keys_mismatches: &BTreeMap<AccountId32, bool>,
+) -> Result<(), ScoutyError> {
+    if let Some(event) = event {
+        run_session_checks(
+            &scouty,
+            event.session_index,
+            authority_records,
+            block_number,
+            Some(authority_index),
+            para_records,
+            era_points_records,
+            chilled_stashes,
+            nominator_records,
+            waiting_records,
+            backing_records,
+            era_digest_records,
+            session_keys_mismatches,
+        )
+        .await?;
+
+        // Let the uptime service know that the session has been processed
+        healthcheck::ping_success();
+    }
+    Ok(())
+}
+
+/// Run the checks of a new session for every stash predefined (e.g. active now, active
+/// next era and queued session keys), run the hooks and send the session report
+async fn run_session_checks(
+    scouty: &Scouty,
+    session_index: u32,
+    authority_records: &mut AuthorityRecords,
+    block_number: u32,
+    authority_index: Option<AuthorityIndex>,
+    para_records: &mut ParaRecords,
+    era_points_records: &mut EraPointsRecords,
+    chilled_stashes: &mut BTreeSet<AccountId32>,
+    nominator_records: &mut NominatorRecords,
+    waiting_records: &mut WaitingRecords,
+    backing_records: &mut BackingRecords,
+    era_digest_records: &mut EraDigestRecords,
+    session_keys_mismatches: &BTreeMap<AccountId32, bool>,
+) -> Result<(), ScoutyError> {
+    let api = scouty.client().clone();
+    let config = CONFIG.current();
+
+    // Collect session data
+    let session = collect_session_data(&scouty, session_index).await?;
+
+    // Collect session active validators
+    let validators_addr = node_runtime::storage().session().validators();
+    let active_validators = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&validators_addr)
+        .await?
+        .unwrap();
+
+    // Authority records -->
+    // Set a new authority set every new era in authority_records
+    if (session.eras_session_index) == 1 {
+        // Get current active authorities
+        authority_records.set_authorities(active_validators.clone());
+    }
+    // Only the chills of the active era explain why a stash is inactive next era
+    if (session.eras_session_index) == 1 {
+        chilled_stashes.clear();
+    }
+
+    // Set a new session in authority_records
+    authority_records.set_session(session.current_session_index);
+    // Track authority record with the new session updated
+    authority_records.insert_record(
+        &scouty.runtime().to_string(),
+        block_number,
+        authority_index,
+    )?;
+    // Authority records <--
+
+    // Para records -->
+    // Set a new validator index for config stashes every new era in para_records
+    if (session.eras_session_index) == 1 {
+        para_records.reset_config_stashes(
+            &scouty.runtime().to_string(),
+            active_validators.clone(),
+        )?;
+    }
+    // Track para record on a new session
+    track_para_records(&scouty, session.current_session_index, para_records).await?;
+    // Para records <--
+
+    // Backing records -->
+    // Assign the para validator groups of the new session and collect the backing votes
+    // of the previous session
+    let previous_backing =
+        start_backing_session(&scouty, session.current_session_index, backing_records)
+            .await?;
+    // Backing records <--
+
+    let network = Network::load(scouty.rpc()).await?;
+    debug!("network {:?}", network);
+
+    // Sync all nominators
+    let all_nominators_map = if config.expose_all_nominators || config.expose_all {
+        get_nominators(&scouty).await?
+    } else {
+        BTreeMap::new()
+    };
+
+    // Fetch era reward points from previous era
+    let era_reward_points_addr = node_runtime::storage()
+        .staking()
+        .eras_reward_points(&(session.active_era_index - 1));
+    let era_reward_points = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&era_reward_points_addr)
+        .await?
+        .unwrap();
+
+    // Collect previusly era reward
+    let era_reward_addr = node_runtime::storage()
+        .staking()
+        .eras_validator_reward(&(session.active_era_index - 1));
+    let era_reward = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&era_reward_addr)
+        .await?
+        .unwrap();
+
+    // Collect validators info based on config stashes
+    let mut validators = collect_validators_data(&scouty).await?;
+    update_status(&scouty, &session, &validators)?;
+
+    // A session already processed before a restart is not notified again, nor are its
+    // hooks run, unless checked on demand
+    let chain = scouty.runtime().to_string();
+    let chain_state = state::get(&chain);
+    if !config.oneshot && chain_state.is_session_processed(session.current_session_index)
+    {
+        info!(
+            "Session {} already processed -> skip notifications and hooks",
+            session.current_session_index
+        );
+        return Ok(());
+    }
+
+    // The era points so far of the active stashes are compared with the era average
+    // per active validator and with the points of the previous session
+    let era_reward_points_addr = node_runtime::storage()
+        .staking()
+        .eras_reward_points(&session.active_era_index);
+    let active_era_reward_points = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&era_reward_points_addr)
+        .await?;
+    let era_points_avg = active_era_reward_points
+        .as_ref()
+        .map(|points| points.total / (active_validators.len().max(1) as u32))
+        .unwrap_or_default();
+    for v in validators.iter_mut() {
+        v.era_points = active_era_reward_points
+            .as_ref()
+            .and_then(|points| {
+                points
+                    .individual
+                    .iter()
+                    .find(|(stash, _)| stash == &v.stash)
+            })
+            .map(|(_, points)| *points)
+            .unwrap_or_default();
+        if v.is_active {
+            v.era_points_trend = Some(era_points_records.track(
+                session.active_era_index,
+                &v.stash,
+                v.era_points,
+                era_points_avg,
+                config.era_points_threshold,
+            ));
+        }
+        v.authored_blocks = authority_records.previous_session_authored_blocks(&v.stash);
+        v.para_assignment = backing_records.assignment(&v.stash);
+        v.backing = previous_backing.get(&v.stash).cloned();
+        // The batched message also shows whether the node owns the session keys, as
+        // last verified
+        if config.matrix_batch_messages {
+            v.is_session_keys_mismatch = session_keys_mismatches
+                .get(&v.stash)
+                .copied()
+                .unwrap_or_default();
+        }
+    }
+
+    // The new era hook runs only once per era, even if scouty restarts mid-era
+    let is_new_era = is_new_era(
+        chain_state.new_era_index,
+        session.active_era_index,
+        session.eras_session_index,
+    );
+
+    // The state of every stash is compared with the last one notified, so that only
+    // its transitions are notified and hooked if configured
+    let mut stash_states: BTreeMap<String, StashState> = BTreeMap::new();
+    let mut changed_stashes: BTreeSet<AccountId32> = BTreeSet::new();
+    for v in validators.iter() {
+        let stash_state = StashState {
+            is_active: v.is_active,
+            is_active_next_era: v.is_queued,
+            queued_session_keys: format!(
+                "0x{:?}",
+                HexDisplay::from(&v.queued_session_keys)
+            ),
+        };
+        let stash = scouty.to_ss58(v.stash.clone());
+        if chain_state.is_changed(&stash, &stash_state) {
+            changed_stashes.insert(v.stash.clone());
+        }
+        stash_states.insert(stash, stash_state);
+    }
+
+    // The margin of the total backing above the minimum elected stake is verified once
+    // per era
+    let min_elected_stake = if is_new_era && config.stake_margin_threshold > 0 {
+        Some(get_min_elected_stake(&scouty, session.active_era_index).await?)
+    } else {
+        None
+    };
+
+    // The staking minimums are included in the summary of every new era
+    let minimums = if is_new_era {
+        Some(get_staking_minimums(&scouty, session.active_era_index).await?)
+    } else {
+        None
+    };
+
+    // The stashes waiting to be elected are given their position in the waiting queue
+    // at every new era
+    if is_new_era {
+        try_set_waiting_positions(
+            &scouty,
+            session.active_era_index,
+            &mut validators,
+            waiting_records,
+        )
+        .await?;
+    }
+
+    // Try to run hooks for each stash
+    for v in validators.iter_mut() {
+        // Try HOOK_NEW_SESSION
+        let mut args = vec![
+            scouty.to_ss58(v.stash.clone()),
+            v.name.to_string(),
+            format!("0x{:?}", HexDisplay::from(&v.queued_session_keys)),
+            v.is_active.to_string(),
+            v.is_queued.to_string(),
+            session.active_era_index.to_string(),
+            session.current_session_index.to_string(),
+            session.eras_session_index.to_string(),
+            block_number.to_string(),
+        ];
+
+        let mut payload = HookPayload::new()
+            .validator(
+                scouty.to_ss58(v.stash.clone()),
+                v.name.to_string(),
+                format!("0x{:?}", HexDisplay::from(&v.queued_session_keys)),
+                v.is_active,
+                v.is_queued,
+            )
+            .session(
+                session.active_era_index,
+                session.current_session_index,
+                Some(session.eras_session_index),
+            )
+            .with("block_number", block_number);
+
+        if config.expose_network || config.expose_all {
+            args.push(network.name.to_string());
+            args.push(network.token_symbol.to_string());
+            args.push(network.token_decimals.to_string());
+            payload = payload.network(&network);
+        } else {
+            args.push("-".to_string());
+            args.push("-".to_string());
+            args.push("-".to_string());
+        }
+
+        if v.is_active && (config.expose_nominators || config.expose_all) {
+            let (total_active_stake, own_stake, nominators, nominators_stake) =
+                get_active_nominators(&scouty, session.active_era_index, &v.stash)
+                    .await?;
+            // calculate APR
+            let apr = calculate_projected_apr(
+                &scouty,
+                &v.stash,
+                network.token_decimals,
+                total_active_stake,
+                era_reward,
+                active_validators.len().try_into().unwrap(),
+            )
+            .await?;
+            args.push(format!("{:.2}", apr * 100.0));
+            args.push(total_active_stake.to_string());
+            args.push(own_stake.to_string());
+            payload = payload
+                .with("projected_apr", format!("{:.2}", apr * 100.0))
+                .with("total_stake", total_active_stake.to_string())
+                .with("own_stake", own_stake.to_string());
+            args.push(nominators.join(",").to_string());
+            args.push(
+                nominators_stake
+                    .iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<String>>()
+                    .join(","),
+            );
+        } else {
+            args.push("-".to_string());
+            args.push("-".to_string());
+            args.push("-".to_string());
+            args.push("-".to_string());
+            args.push("-".to_string());
+        }
+
+        if v.is_active && (config.expose_authored_blocks || config.expose_all) {
+            let previous_session_total =
+                authority_records.previous_session_total(&v.stash);
+            let previous_six_sessions_total =
+                authority_records.previous_six_sessions_total(&v.stash);
+            args.push(previous_session_total.to_string());
+            args.push(previous_six_sessions_total.to_string());
+        } else {
+            args.push("-".to_string());
+            args.push("-".to_string());
+        }
+
+        if config.expose_all_nominators || config.expose_all {
+            if let Some(all_nominators) = all_nominators_map.get(&v.stash.to_string()) {
+                let all_nominators_stashes = all_nominators
+                    .iter()
+                    .map(|(x, _, _)| x.to_string())
+                    .collect::<Vec<String>>()
+                    .join(",");
+
+                let total_nominators_stake: u128 =
+                    all_nominators.iter().map(|(_, x, _)| x).sum();
+
+                let raw_nominees_stake: u128 =
+                    all_nominators.iter().map(|(_, x, y)| x / *y as u128).sum();
+                args.push(all_nominators_stashes.to_string());
+                args.push(format!("{},{}", total_nominators_stake, raw_nominees_stake));
+            }
+        } else {
+            args.push("-".to_string());
+            args.push("-".to_string());
+        }
+
+        if v.is_active && (config.expose_para_validator || config.expose_all) {
+            let is_para_validator = para_records.is_para_validator(&v.stash);
+            let previous_six_sessions_total =
+                para_records.previous_six_sessions_total(&v.stash);
+            args.push(is_para_validator.to_string());
+            args.push(previous_six_sessions_total.to_string());
+        } else {
+            args.push("-".to_string());
+            args.push("-".to_string());
+        }
+
+        // Expose the para validator group of the session and the backing votes of the
+        // previous session, only to the new session hook
+        let mut session_args = args.clone();
+        let mut session_payload = payload.clone();
+        if config.expose_para_validator || config.expose_all {
+            let to_arg = |value: Option<String>| value.unwrap_or("-".to_string());
+            session_args
+                .push(to_arg(v.para_assignment.map(|a| a.group_index.to_string())));
+            session_args.push(to_arg(
+                v.backing.as_ref().map(|b| b.votes.implicit.to_string()),
+            ));
+            session_args.push(to_arg(
+                v.backing.as_ref().map(|b| b.votes.explicit.to_string()),
+            ));
+            session_args.push(to_arg(
+                v.backing.as_ref().map(|b| b.votes.missed.to_string()),
+            ));
+            session_args.push(to_arg(
+                v.backing
+                    .as_ref()
+                    .and_then(|b| b.votes.missed_ratio())
+                    .map(|ratio| format!("{:.4}", ratio)),
+            ));
+            if let Some(assignment) = v.para_assignment {
+                session_payload =
+                    session_payload.with("para_group", assignment.group_index);
+            }
+            if let Some(backing) = &v.backing {
+                session_payload = session_payload
+                    .with("backing_votes_implicit", backing.votes.implicit)
+                    .with("backing_votes_explicit", backing.votes.explicit)
+                    .with("backing_votes_missed", backing.votes.missed);
+                if let Some(ratio) = backing.votes.missed_ratio() {
+                    session_payload = session_payload
+                        .with("missed_votes_ratio", format!("{:.4}", ratio));
+                }
+            }
+        } else {
+            for _ in 0..5 {
+                session_args.push("-".to_string());
+            }
+        }
+
+        // Try run hook
+        if !config.hooks_on_change_only || changed_stashes.contains(&v.stash) {
+            let hook = scouty
+                .try_run_hook(
+                    HOOK_NEW_SESSION,
+                    &config.hook_new_session_path,
+                    session_args,
+                    session_payload,
+                )
+                .await;
+            v.hooks.push(hook);
+        }
+
+        // A stash that authored no blocks during a whole session while active is at risk
+        // of being reported offline (e.g. wrong babe keys)
+        if let Some(session_index) = v
+            .authored_blocks
+            .as_ref()
+            .filter(|blocks| blocks.count == 0)
+            .map(|blocks| blocks.session_index)
+        {
+            let hook = run_offline_risk_hook(
+                &scouty,
+                v,
+                &session,
+                session_index,
+                100,
+                "No blocks authored during the whole session",
+                &network,
+            )
+            .await;
+            v.hooks.push(hook);
+        }
+
+        // Try HOOK_NEW_ERA
+        if is_new_era {
+            // Expose validator last era points
+            if config.expose_era_points || config.expose_all {
+                let points =
+                    get_validator_points_info(&v.stash, era_reward_points.clone())
+                        .await?;
+                args.push(points.validator.to_string());
+                args.push((points.era_avg as u32).to_string());
+                payload = payload
+                    .with("era_points", points.validator)
+                    .with("era_points_avg", points.era_avg as u32);
+            } else {
+                args.push("-".to_string());
+                args.push("-".to_string());
+            }
+
+            args.push(session.era_start_session_index.to_string());
+            args.push(session.era_start.to_string());
+            payload = payload
+                .with("era_start_session_index", session.era_start_session_index)
+                .with("era_start", session.era_start);
+
+            // Expose the stake backing the stash compared with the previous era, so
+            // that a slow bleed of nominators is noticed before missing an election
+            let stake_change = if v.is_active {
+                get_stake_change(&scouty, session.active_era_index, &v.stash)
+                    .await?
+                    .map(|change| StakeChange {
+                        min_elected_stake,
+                        ..change
+                    })
+            } else {
+                None
+            };
+            match &stake_change {
+                Some(change) => {
+                    let to_arg = |delta: Option<String>| delta.unwrap_or("-".to_string());
+                    args.push(change.current.own.to_string());
+                    args.push(change.current.total.to_string());
+                    args.push(change.current.nominator_count.to_string());
+                    args.push(to_arg(change.own_delta().map(|d| d.to_string())));
+                    args.push(to_arg(change.total_delta().map(|d| d.to_string())));
+                    args.push(to_arg(
+                        change.nominator_count_delta().map(|d| d.to_string()),
+                    ));
+                    payload = payload
+                        .with("era_own_stake", change.current.own.to_string())
+                        .with("era_total_stake", change.current.total.to_string())
+                        .with("era_nominator_count", change.current.nominator_count);
+                    if let Some(previous) = change.previous {
+                        payload = payload
+                            .with("previous_era_own_stake", previous.own.to_string())
+                            .with("previous_era_total_stake", previous.total.to_string())
+                            .with(
+                                "previous_era_nominator_count",
+                                previous.nominator_count,
+                            );
+                    }
+                }
+                None => {
+                    for _ in 0..6 {
+                        args.push("-".to_string());
+                    }
+                }
+            }
+            v.stake_change = stake_change;
+
+            // Try run hook
+            let hook = scouty
+                .try_run_hook(
+                    HOOK_NEW_ERA,
+                    &config.hook_new_era_path,
+                    args.clone(),
+                    payload.clone(),
+                )
+                .await;
+            v.hooks.push(hook);
+        }
+
+        if (session.eras_session_index) == 6 && session.queued_session_keys_changed {
+            let next_era_index = session.active_era_index + 1;
+            let next_session_index = session.current_session_index + 1;
+            let mut args = vec![
+                scouty.to_ss58(v.stash.clone()),
+                v.name.to_string(),
+                format!("0x{:?}", HexDisplay::from(&v.queued_session_keys)),
+                format!("{}", next_era_index),
+                format!("{}", next_session_index),
+            ];
+
+            let mut payload = HookPayload::new()
+                .validator(
+                    scouty.to_ss58(v.stash.clone()),
+                    v.name.to_string(),
+                    format!("0x{:?}", HexDisplay::from(&v.queued_session_keys)),
+                    v.is_active,
+                    v.is_queued,
+                )
+                .session(next_era_index, next_session_index, None);
+
+            if config.expose_network || config.expose_all {
+                args.push(network.name.to_string());
+                args.push(network.token_symbol.to_string());
+                args.push(network.token_decimals.to_string());
+                payload = payload.network(&network);
+            } else {
+                args.push("-".to_string());
+                args.push("-".to_string());
+                args.push("-".to_string());
+            }
+
+            // Try HOOK_VALIDATOR_STARTS_ACTIVE_NEXT_ERA
+            // If stash is not active and keys are queued for next Era -> trigger hook to get ready and warm up
+            if !v.is_active && v.is_queued {
+                // Try run hook
+                let hook = scouty
+                    .try_run_hook(
+                        HOOK_VALIDATOR_STARTS_ACTIVE_NEXT_ERA,
+                        &config.hook_validator_starts_active_next_era_path,
+                        args.clone(),
+                        payload.clone(),
+                    )
+                    .await;
+                v.hooks.push(hook);
+            }
+
+            // Try HOOK_VALIDATOR_INACTIVE_NEXT_ERA
+            // If stash is active and keys are not queued for next Era trigger hook to inform operator
+            if v.is_active && !v.is_queued {
+                // Explain why the stash is not going to be active in the next era
+                v.inactive_next_era_reason = get_inactive_next_era_reason(
+                    &scouty,
+                    &v.stash,
+                    session.active_era_index,
+                    chilled_stashes.contains(&v.stash),
+                    &network,
+                )
+                .await?;
+                args.push(v.inactive_next_era_reason.to_string());
+                payload = payload.with(
+                    "inactive_next_era_reason",
+                    v.inactive_next_era_reason.to_string(),
+                );
+
+                // Try run hook
+                let hook = scouty
+                    .try_run_hook(
+                        HOOK_VALIDATOR_STARTS_INACTIVE_NEXT_ERA,
+                        &config.hook_validator_starts_inactive_next_era_path,
+                        args.clone(),
+                        payload.clone(),
+                    )
+                    .await;
+                v.hooks.push(hook);
+            }
+        }
+    }
+
+    // The session is only kept as processed once notified, so that a report that
+    // failed to be sent is processed again after a restart
+    let processed_session_index = session.current_session_index;
+    let processed_era_index = session.active_era_index;
+    let reported = Some(stash_states).filter(|_| !changed_stashes.is_empty());
+    let result = notify_session_checks(
+        &scouty,
+        session,
+        network,
+        validators,
+        era_reward_points,
+        minimums,
+        is_new_era,
+        &changed_stashes,
+        nominator_records,
+        era_digest_records,
+    )
+    .await;
+    state::update_on_success(&chain, &result, |state| {
+        state.session_index = Some(processed_session_index);
+        state.era_index = Some(processed_era_index);
+        if is_new_era {
+            state.new_era_index = Some(processed_era_index);
+        }
+        if let Some(reported) = reported {
+            state.reported = reported;
+        }
+    });
+    result
+}
+
+/// Send the reports of the session checks, and run the era checks at a new era
+#[allow(clippy::too_many_arguments)]
+async fn notify_session_checks(
+    scouty: &Scouty,
+    session: Session,
+    network: Network,
+    mut validators: Validators,
+    era_reward_points: node_runtime::runtime_types::pallet_staking::EraRewardPoints<
+        AccountId32,
+    >,
+    minimums: Option<StakingMinimums>,
+    is_new_era: bool,
+    changed_stashes: &BTreeSet<AccountId32>,
+    nominator_records: &mut NominatorRecords,
+    era_digest_records: &mut EraDigestRecords,
+) -> Result<(), ScoutyError> {
+    let config = CONFIG.current();
+
+    // Nominators that left or arrived are reported on their own
+    let active_stashes: Vec<(AccountId32, String, bool)> = validators
+        .iter()
+        .filter(|v| v.is_active)
+        .map(|v| (v.stash.clone(), v.name.to_string(), v.has_identity))
+        .collect();
+
+    // In era report mode the sessions are summarized in a single digest at every new
+    // era, only the stashes that start inactive next era are notified straight away
+    if config.report_mode == ReportMode::Era {
+        let mut digests = BTreeMap::new();
+        for v in validators.iter() {
+            era_digest_records.track_previous_session(
+                &v.stash,
+                v.authored_blocks.as_ref().map(|blocks| blocks.count),
+                v.backing.as_ref().map(|backing| backing.votes),
+            );
+        }
+        if is_new_era {
+            digests = era_digest_records.take();
+        }
+        for v in validators.iter() {
+            era_digest_records.track_session(
+                session.active_era_index,
+                &v.stash,
+                v.is_active,
+                v.session_warnings(),
+            );
+        }
+
+        let digest_validators: Validators = validators
+            .iter()
+            .filter_map(|v| {
+                let mut digest = digests.remove(&v.stash)?;
+                if digest.era_index == session.active_era_index - 1 {
+                    digest.points = era_reward_points
+                        .individual
+                        .iter()
+                        .find(|(stash, _)| stash == &v.stash)
+                        .map(|(_, points)| *points)
+                        .unwrap_or_default();
+                    digest.rank =
+                        era_points_rank(&era_reward_points.individual, &v.stash);
+                    digest.validator_count = era_reward_points.individual.len() as u32;
+                }
+                let mut validator = Validator::new(v.stash.clone());
+                validator.name = v.name.to_string();
+                validator.has_identity = v.has_identity;
+                validator.is_active = v.is_active;
+                validator.is_queued = v.is_queued;
+                validator.stake_change = v.stake_change.clone();
+                validator.era_digest = Some(digest);
+                Some(validator)
+            })
+            .collect();
+        if !digest_validators.is_empty() {
+            let data = RawData {
+                network: network.clone(),
+                session: session.clone(),
+                validators: digest_validators,
+                minimums,
+                section: Section::EraDigest,
+                ..Default::default()
+            };
+            let report = Report::from(data);
+            scouty.send_report(&report).await?;
+        }
+
+        validators.retain(|v| {
+            v.hooks
+                .iter()
+                .any(|hook| hook.name == HOOK_VALIDATOR_STARTS_INACTIVE_NEXT_ERA)
+        });
+    }
+
+    // Only the stashes that changed, with a warning or with other hooks than the
+    // regular ones are notified, except for the summary of all the stashes sent once
+    // per era as a heartbeat, which the era digest already is in era report mode
+    let is_heartbeat = config.notify_on_change_only
+        && config.notify_era_heartbeat
+        && config.report_mode == ReportMode::Session
+        && is_new_era;
+    if config.notify_on_change_only && !is_heartbeat {
+        validators.retain(|v| {
+            changed_stashes.contains(&v.stash)
+                || v.has_session_warnings()
+                || v.hooks.iter().any(|hook| {
+                    hook.name != HOOK_NEW_SESSION && hook.name != HOOK_NEW_ERA
+                })
+        });
+    }
+
+    // Prepare notification report
+    debug!("validators {:?}", validators);
+
+    // The stashes notified straight away in era report mode are never batched
+    let is_batch =
+        config.matrix_batch_messages && config.report_mode == ReportMode::Session;
+    if is_batch {
+        // Stashes that start inactive next era are still notified on their own
+        let mut inactive_next_era: Validators = Vec::new();
+        for v in validators.iter_mut() {
+            if let Some(i) = v
+                .hooks
+                .iter()
+                .position(|hook| hook.name == HOOK_VALIDATOR_STARTS_INACTIVE_NEXT_ERA)
+            {
+                let mut validator = Validator::new(v.stash.clone());
+                validator.name = v.name.to_string();
+                validator.has_identity = v.has_identity;
+                validator.is_active = v.is_active;
+                validator.is_queued = v.is_queued;
+                validator.hooks.push(v.hooks.remove(i));
+                inactive_next_era.push(validator);
+            }
+        }
+        if !inactive_next_era.is_empty() {
+            let data = RawData {
+                network: network.clone(),
+                session: session.clone(),
+                validators: inactive_next_era,
+                section: Section::Session,
+                ..Default::default()
+            };
+            let report = Report::from(data);
+            scouty.send_report(&report).await?;
+        }
+    }
+
+    if !validators.is_empty() {
+        let data = RawData {
+            network: network.clone(),
+            session: session.clone(),
+            validators,
+            // In era report mode the minimums are already in the era digest
+            minimums: minimums.filter(|_| config.report_mode == ReportMode::Session),
+            section: if is_batch || is_heartbeat {
+                Section::SessionBatch
+            } else {
+                Section::Session
+            },
+            ..Default::default()
+        };
+
+        let report = Report::from(data);
+        scouty.send_report(&report).await?;
+    }
+
+    if is_new_era {
+        try_run_nomination_checks(&scouty, session.active_era_index).await?;
+        try_run_pool_checks(&scouty, session.active_era_index).await?;
+        try_run_nominator_changes(
+            &scouty,
+            network,
+            session,
+            active_stashes,
+            nominator_records,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Verify the nominations of the nominator accounts predefined in the active era, and
+/// send a summary with the warnings of the validators nominated. The nomination
+/// inactive hook is run for the accounts whose stake is not applied.
+async fn try_run_nomination_checks(
+    scouty: &Scouty,
+    era_index: u32,
+) -> Result<(), ScoutyError> {
+    let config = CONFIG.current();
+
+    let nominators = config.nominator_stashes(&scouty.runtime().to_string());
+    if nominators.is_empty() {
+        return Ok(());
+    }
+
+    let network = Network::load(scouty.rpc()).await?;
+
+    for stash_str in nominators.iter() {
+        let nominator = AccountId32::from_str(stash_str).map_err(|e| {
+            ScoutyError::Other(format!(
+                "Invalid SS58 format account: {:?} error: {e:?}",
+                stash_str
+            ))
+        })?;
+        let identity = get_cached_identity(&scouty, era_index, &nominator).await;
+        let name = identity::display_name(
+            identity.map(|identity| identity.to_string()).as_deref(),
+            &nominator,
+        );
+        let nomination = get_nomination(&scouty, era_index, &nominator, name).await?;
+
+        // Try HOOK_NOMINATION_INACTIVE
+        let mut lines = Vec::new();
+        if !nomination.is_stake_applied() {
+            let inactive_targets = nomination.inactive_targets();
+            let mut args = vec![
+                scouty.to_ss58(nominator.clone()),
+                nomination.name.to_string(),
+                era_index.to_string(),
+                nomination.bonded.to_string(),
+                nomination.targets.len().to_string(),
+                if inactive_targets.is_empty() {
+                    "-".to_string()
+                } else {
+                    inactive_targets.join(",")
+                },
+                nomination.warnings().join(";"),
+            ];
+
+            let mut payload = HookPayload::new()
+                .validator(
+                    scouty.to_ss58(nominator.clone()),
+                    nomination.name.to_string(),
+                    "-".to_string(),
+                    false,
+                    false,
+                )
+                .with("era_index", era_index)
+                .with("bonded", nomination.bonded.to_string())
+                .with("targets", nomination.targets.len())
+                .with("inactive_targets", inactive_targets)
+                .with("warnings", nomination.warnings());
+
+            if config.expose_network || config.expose_all {
+                args.push(network.name.to_string());
+                args.push(network.token_symbol.to_string());
+                args.push(network.token_decimals.to_string());
+                payload = payload.network(&network);
+            } else {
+                args.push("-".to_string());
+                args.push("-".to_string());
+                args.push("-".to_string());
+            }
+
+            let hook = scouty
+                .try_run_hook(
+                    HOOK_NOMINATION_INACTIVE,
+                    &config.hook_nomination_inactive_path,
+                    args,
+                    payload,
+                )
+                .await;
+            lines.extend(hook.matrix_lines());
+        }
+
+        let (message, formatted_message) = nomination.message(&network, &lines);
+        let severity = if nomination.is_critical() {
+            Severity::Critical
+        } else {
+            Severity::Info
+        };
+        if let Err(e) = scouty
+            .send_message(&message, &formatted_message, severity)
+            .await
+        {
+            warn!("Matrix message skipped! {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the nominations of the account in the era, with the stake of the account
+/// applied in the exposure of every validator nominated
+async fn get_nomination(
+    scouty: &Scouty,
+    era_index: u32,
+    nominator: &AccountId32,
+    name: String,
+) -> Result<Nomination, ScoutyError> {
+    let api = scouty.client().clone();
+
+    let max_exposure_page_size_addr =
+        node_runtime::constants().staking().max_exposure_page_size();
+    let max_exposure_page_size = api.constants().at(&max_exposure_page_size_addr)?;
+
+    let mut nomination = Nomination {
+        name,
+        era_index,
+        minimums: Some(get_staking_minimums(&scouty, era_index).await?),
+        ..Default::default()
+    };
+
+    // Only the active bonded stake is applied to the validators nominated
+    let bonded_addr = node_runtime::storage().staking().bonded(nominator);
+    if let Some(controller) = api.storage().at_latest().await?.fetch(&bonded_addr).await?
+    {
+        let ledger_addr = node_runtime::storage().staking().ledger(&controller);
+        if let Some(ledger) = api.storage().at_latest().await?.fetch(&ledger_addr).await?
+        {
+            nomination.bonded = ledger.active;
+        }
+    }
+
+    let nominations_addr = node_runtime::storage().staking().nominators(nominator);
+    let targets = match api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&nominations_addr)
+        .await?
+    {
+        Some(nominations) => {
+            let BoundedVec(targets) = nominations.targets;
+            targets
+        }
+        None => Vec::new(),
+    };
+
+    for target in targets.iter() {
+        let stake = get_stake(&scouty, era_index, target).await?;
+        // The stake of the nominator is applied if it is in one of the exposure pages
+        // of the validator
+        let exposed_stake = match stake {
+            Some(_) => get_backing(&scouty, era_index, target)
+                .await?
+                .nominators
+                .get(nominator)
+                .copied(),
+            None => None,
+        };
+        let slash_addr = node_runtime::storage()
+            .staking()
+            .validator_slash_in_era(&era_index.saturating_sub(1), target);
+        let is_slashed = era_index > 0
+            && api
+                .storage()
+                .at_latest()
+                .await?
+                .fetch(&slash_addr)
+                .await?
+                .is_some();
+        // Validators not elected have no preferences in the era, their commission is
+        // the one currently defined
+        let commission = match get_commission(&scouty, Some(era_index), target).await? {
+            Some(commission) => Some(commission),
+            None => get_commission(&scouty, None, target).await?,
+        };
+        let identity = get_cached_identity(&scouty, era_index, target).await;
+        nomination.targets.push(NominationTarget {
+            name: identity::display_name(
+                identity.map(|identity| identity.to_string()).as_deref(),
+                target,
+            ),
+            is_active: stake.is_some(),
+            exposed_stake,
+            is_oversubscribed: stake.as_ref().map_or(false, |stake| {
+                stake.nominator_count > max_exposure_page_size
+            }),
+            commission: commission.unwrap_or_default(),
+            previous_commission: match era_index {
+                0 => None,
+                _ => get_commission(&scouty, Some(era_index - 1), target).await?,
+            },
+            is_slashed,
+        });
+    }
+
+    Ok(nomination)
+}
+
+/// Verify the nomination pools predefined in the active era, if the nomination pools
+/// pallet is available, and send a summary of the nominations of the bonded account of
+/// every pool. The pools blocked, destroyed or with a commission changed since the last
+/// era verified are sent as critical messages.
+async fn try_run_pool_checks(scouty: &Scouty, era_index: u32) -> Result<(), ScoutyError> {
+    let api = scouty.client().clone();
+    let config = CONFIG.current();
+
+    let pools = config.pools();
+    if pools.is_empty() {
+        return Ok(());
+    }
+    if api.metadata().pallet_by_name("NominationPools").is_none() {
+        warn!("Nomination pools pallet not available -> pools not verified");
+        return Ok(());
+    }
+
+    let network = Network::load(scouty.rpc()).await?;
+    let pallet_id_addr = node_runtime::constants().nomination_pools().pallet_id();
+    let node_runtime::runtime_types::frame_support::PalletId(pallet_id) =
+        api.constants().at(&pallet_id_addr)?;
+
+    let chain = scouty.runtime().to_string();
+    let chain_state = state::get(&chain);
+
+    for monitored in pools.iter() {
+        let mut pool = Pool {
+            pool_id: monitored.pool_id,
+            previous: chain_state.pools.get(&monitored.pool_id).cloned(),
+            ..Default::default()
+        };
+
+        let metadata_addr = node_runtime::storage()
+            .nomination_pools()
+            .metadata(&monitored.pool_id);
+        if let Some(BoundedVec(metadata)) = api
+            .storage()
+            .at_latest()
+            .await?
+            .fetch(&metadata_addr)
+            .await?
+        {
+            pool.name = String::from_utf8_lossy(&metadata).to_string();
+        }
+
+        let bonded_pool_addr = node_runtime::storage()
+            .nomination_pools()
+            .bonded_pools(&monitored.pool_id);
+        match api
+            .storage()
+            .at_latest()
+            .await?
+            .fetch(&bonded_pool_addr)
+            .await?
+        {
+            Some(bonded_pool) => {
+                use node_runtime::runtime_types::pallet_nomination_pools::PoolState;
+                let commission = bonded_pool.commission.current.map(|(perbill, _)| {
+                    let node_runtime::runtime_types::sp_arithmetic::per_things::Perbill(
+                        c,
+                    ) = perbill;
+                    c
+                });
+                pool.member_count = bonded_pool.member_counter;
+                pool.snapshot = PoolSnapshot {
+                    state: match bonded_pool.state {
+                        PoolState::Open => POOL_STATE_OPEN,
+                        PoolState::Blocked => POOL_STATE_BLOCKED,
+                        PoolState::Destroying => POOL_STATE_DESTROYING,
+                    }
+                    .to_string(),
+                    commission,
+                };
+
+                // The nominations of the pool are the ones of its bonded account
+                let bonded_account =
+                    pool_account(pallet_id, POOL_BONDED_ACCOUNT, monitored.pool_id);
+                let name = identity::short_address(&bonded_account);
+                pool.nomination = Some(
+                    get_nomination(&scouty, era_index, &bonded_account, name).await?,
+                );
+            }
+            None => {
+                pool.snapshot = PoolSnapshot {
+                    state: POOL_STATE_DESTROYED.to_string(),
+                    commission: None,
+                };
+            }
+        }
+
+        if let Some(member) = &monitored.member {
+            let pending_rewards_addr = node_runtime::apis()
+                .nomination_pools_api()
+                .pending_rewards(member.clone());
+            match api
+                .runtime_api()
+                .at_latest()
+                .await?
+                .call(pending_rewards_addr)
+                .await
+            {
+                Ok(pending_rewards) => {
+                    let identity = get_cached_identity(&scouty, era_index, member).await;
+                    let name = identity::display_name(
+                        identity.map(|identity| identity.to_string()).as_deref(),
+                        member,
+                    );
+                    pool.member = Some((name, pending_rewards));
+                }
+                Err(e) => warn!(
+                    "Pending rewards of {} not available! {}",
+                    scouty.to_ss58(member.clone()),
+                    e
+                ),
+            }
+        }
+
+        let (message, formatted_message) = pool.message(&network);
+        let severity = if pool.is_critical() {
+            Severity::Critical
+        } else {
+            Severity::Info
+        };
+        if let Err(e) = scouty
+            .send_message(&message, &formatted_message, severity)
+            .await
+        {
+            warn!("Matrix message skipped! {}", e);
+        }
+
+        let snapshot = pool.snapshot.clone();
+        state::update(&chain, |state| {
+            state.pools.insert(monitored.pool_id, snapshot);
+        });
+    }
+
+    Ok(())
+}
+
+/// Returns the commission of the validator (in parts per billion) in the era, or the
+/// commission currently defined if no era is given
+async fn get_commission(
+    scouty: &Scouty,
+    era_index: Option<u32>,
+    stash: &AccountId32,
+) -> Result<Option<u32>, ScoutyError> {
+    let storage = scouty.client().storage().at_latest().await?;
+    let prefs = match era_index {
+        Some(era_index) => {
+            let era_prefs_addr = node_runtime::storage()
+                .staking()
+                .eras_validator_prefs(&era_index, stash);
+            storage.fetch(&era_prefs_addr).await?
+        }
+        None => {
+            let validator_prefs_addr =
+                node_runtime::storage().staking().validators(stash);
+            storage.fetch(&validator_prefs_addr).await?
+        }
+    };
+    Ok(prefs.map(|prefs| {
+        let node_runtime::runtime_types::sp_arithmetic::per_things::Perbill(c) =
+            prefs.commission;
+        c
+    }))
+}
+
+/// Compare the nominators backing the active stashes with the previous era and send a
+/// message if a large nominator left or arrived, or if the total backing dropped
+async fn try_run_nominator_changes(
+    scouty: &Scouty,
+    network: Network,
+    session: Session,
+    stashes: Vec<(AccountId32, String, bool)>,
+    nominator_records: &mut NominatorRecords,
+) -> Result<(), ScoutyError> {
+    let config = CONFIG.current();
+
+    let amount_threshold = network.to_plancks(config.nominator_amount_threshold);
+    if amount_threshold == 0 && config.backing_drop_threshold == 0 {
+        return Ok(());
+    }
+
+    let era_index = session.active_era_index;
+    let mut validators: Validators = Vec::new();
+    for (stash, name, has_identity) in stashes {
+        let backing = get_backing(&scouty, era_index, &stash).await?;
+        // NOTE: the backing of the previous era is only fetched if not kept in memory
+        // yet, e.g. on the first era after a restart
+        let previous = match nominator_records.insert(era_index, &stash, backing.clone())
+        {
+            Some(previous) => previous,
+            None if era_index > 0 => get_backing(&scouty, era_index - 1, &stash).await?,
+            None => continue,
+        };
+        // Stashes not active in the previous era have nothing to compare with
+        if previous.total == 0 {
+            continue;
+        }
+
+        let mut changes = NominatorChanges::between(
+            &previous,
+            &backing,
+            amount_threshold,
+            config.backing_drop_threshold,
+        );
+        if changes.is_empty() {
+            continue;
+        }
+        for nominator in changes.left.iter_mut().chain(changes.arrived.iter_mut()) {
+            nominator.name = get_display_name(&scouty, &nominator.stash).await?;
+        }
+        debug!("nominator changes {:?}", changes);
+
+        let mut v = Validator::new(stash);
+        v.name = name;
+        v.has_identity = has_identity;
+        v.is_active = true;
+        v.nominator_changes = Some(changes);
+        validators.push(v);
+    }
+
+    if !validators.is_empty() {
+        // Prepare notification report
+        let data = RawData {
+            network,
+            validators,
+            session,
+            section: Section::Nominators,
+            ..Default::default()
+        };
+
+        let report = Report::from(data);
+        scouty.send_report(&report).await?;
+    }
+
+    Ok(())
+}
+
+/// Run the checks of a new session once for the current session, as if the session had
+/// just started, so that the stashes can be checked without subscribing on-chain events
+pub async fn run_session_checks_once(scouty: &Scouty) -> Result<(), ScoutyError> {
+    let api = scouty.client().clone();
+
+    let mut authority_records = AuthorityRecords::new();
+    init_authority_records(&scouty, &mut authority_records).await?;
+
+    let mut para_records = ParaRecords::new();
+    init_para_records(&scouty, &mut para_records).await?;
+
+    let mut era_points_records = EraPointsRecords::new();
+
+    let mut chilled_stashes: BTreeSet<AccountId32> = BTreeSet::new();
+
+    let mut nominator_records = NominatorRecords::new();
+
+    let mut waiting_records = WaitingRecords::new();
+
+    let mut backing_records = BackingRecords::new();
+
+    let mut era_digest_records = EraDigestRecords::new();
+
+    let mut session_keys_mismatches: BTreeMap<AccountId32, bool> = BTreeMap::new();
+    try_run_session_keys_mismatch_hook(&scouty, &mut session_keys_mismatches).await?;
+
+    let current_session_index_addr = node_runtime::storage().session().current_index();
+    let current_session_index = match api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&current_session_index_addr)
+        .await?
+    {
+        Some(index) => index,
+        None => return Err("Current session index not defined".into()),
+    };
+    let block_number = api.blocks().at_latest().await?.number();
+    let authority_index = get_authority_index(&scouty, None).await?;
+
+    run_session_checks(
+        &scouty,
+        current_session_index,
+        &mut authority_records,
+        block_number,
+        authority_index,
+        &mut para_records,
+        &mut era_points_records,
+        &mut chilled_stashes,
+        &mut nominator_records,
+        &mut waiting_records,
+        &mut backing_records,
+        &mut era_digest_records,
+        &session_keys_mismatches,
+    )
+    .await?;
+
+    update_status_era_points(&scouty).await
+}
+
+async fn get_active_nominators(
+    scouty: &Scouty,
+    era_index: u32,
+    stash: &AccountId32,
+) -> Result<(u128, u128, Vec<String>, Vec<u128>), ScoutyError> {
+    let api = scouty.client().clone();
+
+    let mut exposure_total = 0;
+    let mut exposure_own = 0;
+    let mut nominators: Vec<String> = vec![];
+    let mut nominators_stake: Vec<u128> = vec![];
+
+    let eras_stakers_paged_addr = node_runtime::storage()
+        .staking()
+        .eras_stakers_paged_iter2(&era_index, stash);
+    let mut iter = api
+        .storage()
+        .at_latest()
+        .await?
+        .iter(eras_stakers_paged_addr)
+        .await?;
+
+    while let Some(Ok((_, exposure))) = iter.next().await {
+        debug!("__exposure: {:?}", exposure);
+        for other in exposure.others {
+            // NOTE: convert nominator account to specific chain format
+            nominators.push(scouty.to_ss58(other.who));
+            nominators_stake.push(other.value);
+        }
+    }
+
+    let eras_stakers_overview_addr = node_runtime::storage()
+        .staking()
+        .eras_stakers_overview(&era_index, stash);
+    if let Some(exposure) = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&eras_stakers_overview_addr)
+        .await?
+    {
+        debug!("__exposure: {:?}", exposure);
+        exposure_total = exposure.total;
+        exposure_own = exposure.own;
+    }
+
+    Ok((exposure_total, exposure_own, nominators, nominators_stake))
+}
+
+/// Returns the total stake backing the stash in the era, with the stake of the
+/// nominators of every exposure page
+async fn get_backing(
+    scouty: &Scouty,
+    era_index: u32,
+    stash: &AccountId32,
+) -> Result<Backing, ScoutyError> {
+    let api = scouty.client().clone();
+
+    let mut backing = Backing::default();
+
+    let eras_stakers_paged_addr = node_runtime::storage()
+        .staking()
+        .eras_stakers_paged_iter2(&era_index, stash);
+    let mut iter = api
+        .storage()
+        .at_latest()
+        .await?
+        .iter(eras_stakers_paged_addr)
+        .await?;
+    while let Some(Ok((_, exposure))) = iter.next().await {
+        for other in exposure.others {
+            *backing.nominators.entry(other.who).or_default() += other.value;
+        }
+    }
+
+    if let Some(stake) = get_stake(scouty, era_index, stash).await? {
+        backing.total = stake.total;
+    }
+
+    Ok(backing)
+}
+
+/// Returns the stake backing the stash in the era, from the exposure overview so that
+/// the nominators of every exposure page are counted
+async fn get_stake(
+    scouty: &Scouty,
+    era_index: u32,
+    stash: &AccountId32,
+) -> Result<Option<Stake>, ScoutyError> {
+    let eras_stakers_overview_addr = node_runtime::storage()
+        .staking()
+        .eras_stakers_overview(&era_index, stash);
+    let stake = scouty
+        .client()
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&eras_stakers_overview_addr)
+        .await?
+        .map(|exposure| Stake {
+            own: exposure.own,
+            total: exposure.total,
+            nominator_count: exposure.nominator_count,
+        });
+    Ok(stake)
+}
+
+/// Returns the stake backing the stash in the era compared with the previous era
+async fn get_stake_change(
+    scouty: &Scouty,
+    era_index: u32,
+    stash: &AccountId32,
+) -> Result<Option<StakeChange>, ScoutyError> {
+    let current = match get_stake(scouty, era_index, stash).await? {
+        Some(stake) => stake,
+        None => return Ok(None),
+    };
+    let previous = if era_index > 0 {
+        get_stake(scouty, era_index - 1, stash).await?
+    } else {
+        None
+    };
+    Ok(Some(StakeChange {
+        current,
+        previous,
+        ..Default::default()
+    }))
+}
+
+async fn get_nominators(
+    scouty: &Scouty,
+) -> Result<BTreeMap<String, Vec<(String, u128, u32)>>, ScoutyError> {
+    let api = scouty.client().clone();
+    let config = CONFIG.current();
+
+    // BTreeMap<String, Vec<(String, u128, u32)>> = validator_stash : [(nominator_stash, nominator_total_stake, number_of_nominations)]
+    let mut stashes_nominators: BTreeMap<String, Vec<(String, u128, u32)>> =
+        BTreeMap::new();
+    let stashes = config.validator_stashes(&scouty.runtime().to_string());
+    for stash_str in stashes.iter() {
+        let stash = AccountId32::from_str(stash_str).map_err(|e| {
+            ScoutyError::Other(format!(
+                "Invalid SS58 format account: {:?} error: {e:?}",
+                stash_str
+            ))
+        })?;
+        stashes_nominators.insert(stash.to_string(), vec![]);
+    }
+
+    info!("Starting syncing all nominators");
+    let storage_query = node_runtime::storage().staking().nominators_iter();
+    let mut results = api.storage().at_latest().await?.iter(storage_query).await?;
+    while let Some(Ok((key, nominations))) = results.next().await {
+        let nominator_stash = get_account_id_from_storage_key(key);
+        let bonded_addr = node_runtime::storage()
+            .staking()
+            .bonded(&nominator_stash.clone());
+        if let Some(controller) =
+            api.storage().at_latest().await?.fetch(&bonded_addr).await?
+        {
+            let ledger_addr = node_runtime::storage().staking().ledger(&controller);
+            let total_nominator_stake = if let Some(ledger) =
+                api.storage().at_latest().await?.fetch(&ledger_addr).await?
+            {
+                ledger.total
+            } else {
+                0
+            };
+
+            for stash_str in stashes.iter() {
+                let stash = AccountId32::from_str(stash_str).map_err(|e| {
+                    ScoutyError::Other(format!(
+                        "Invalid SS58 format account: {:?} error: {e:?}",
+                        stash_str
+                    ))
+                })?;
+                let BoundedVec(targets) = nominations.targets.clone();
+                if targets.contains(&stash) {
+                    if let Some(x) = stashes_nominators.get_mut(&stash.to_string()) {
+                        // NOTE: convert account to chain specific format
+                        x.push((
+                            scouty.to_ss58(nominator_stash.clone()),
+                            total_nominator_stake,
+                            targets.len().try_into().unwrap(),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    info!("Finished syncing all nominators");
+    Ok(stashes_nominators)
+}
+
+/// Set the position in the waiting queue of the stashes with validator intention that
+/// are not active, compared with the minimum stake elected in the active era
+async fn try_set_waiting_positions(
+    scouty: &Scouty,
+    active_era_index: u32,
+    validators: &mut Validators,
+    waiting_records: &mut WaitingRecords,
+) -> Result<(), ScoutyError> {
+    if validators.iter().all(|v| v.is_active) {
+        return Ok(());
+    }
+
+    let approvals = get_waiting_approvals(&scouty).await?;
+    if !validators.iter().any(|v| approvals.contains_key(&v.stash)) {
+        return Ok(());
+    }
+    let min_elected_stake = get_min_elected_stake(&scouty, active_era_index).await?;
+
+    for v in validators.iter_mut() {
+        if let Some(position) = waiting::waiting_position(&approvals, &v.stash) {
+            let approval_stake = approvals[&v.stash];
+            let gap = min_elected_stake.saturating_sub(approval_stake);
+            v.waiting_position = Some(WaitingPosition {
+                position,
+                waiting_count: approvals.len() as u32,
+                approval_stake,
+                min_elected_stake,
+                previous_gap: waiting_records.insert(active_era_index, &v.stash, gap),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Returns the approval stake of every validator waiting to be elected, the own stake
+/// plus the stake of every nominator that nominates it
+async fn get_waiting_approvals(
+    scouty: &Scouty,
+) -> Result<BTreeMap<AccountId32, u128>, ScoutyError> {
+    let api = scouty.client().clone();
+    let storage = api.storage().at_latest().await?;
+
+    let validators_addr = node_runtime::storage().session().validators();
+    let active_validators = storage.fetch(&validators_addr).await?.unwrap_or_default();
+
+    // Stake bonded by every stash
+    let mut stakes: BTreeMap<AccountId32, u128> = BTreeMap::new();
+    let mut iter = storage
+        .iter(node_runtime::storage().staking().ledger_iter())
+        .await?;
+    while let Some(Ok((_, ledger))) = iter.next().await {
+        stakes.insert(ledger.stash, ledger.active);
+    }
+
+    let mut approvals: BTreeMap<AccountId32, u128> = BTreeMap::new();
+    let mut iter = storage
+        .iter(node_runtime::storage().staking().validators_iter())
+        .await?;
+    while let Some(Ok((key, _))) = iter.next().await {
+        let stash = get_account_id_from_storage_key(key);
+        if !active_validators.contains(&stash) {
+            let own_stake = stakes.get(&stash).copied().unwrap_or_default();
+            approvals.insert(stash, own_stake);
+        }
+    }
+
+    let mut iter = storage
+        .iter(node_runtime::storage().staking().nominators_iter())
+        .await?;
+    while let Some(Ok((key, nominations))) = iter.next().await {
+        let nominator_stake = stakes
+            .get(&get_account_id_from_storage_key(key))
+            .copied()
+            .unwrap_or_default();
+        let BoundedVec(targets) = nominations.targets;
+        for target in targets.iter() {
+            if let Some(approval) = approvals.get_mut(target) {
+                *approval += nominator_stake;
+            }
+        }
+    }
+    Ok(approvals)
+}
+
+/// Returns the lowest total stake backing a validator elected in the era
+async fn get_min_elected_stake(
+    scouty: &Scouty,
+    era_index: u32,
+) -> Result<u128, ScoutyError> {
+    let api = scouty.client().clone();
+    let storage = api.storage().at_latest().await?;
+
+    let mut min_elected_stake: Option<u128> = None;
+    let eras_stakers_overview_addr = node_runtime::storage()
+        .staking()
+        .eras_stakers_overview_iter1(&era_index);
+    let mut iter = storage.iter(eras_stakers_overview_addr).await?;
+    while let Some(Ok((_, exposure))) = iter.next().await {
+        min_elected_stake =
+            Some(min_elected_stake.map_or(exposure.total, |min| min.min(exposure.total)));
+    }
+    Ok(min_elected_stake.unwrap_or_default())
+}
+
+/// Returns the staking minimums of the era, read only once per era
+async fn get_staking_minimums(
+    scouty: &Scouty,
+    era_index: u32,
+) -> Result<StakingMinimums, ScoutyError> {
+    if let Some(minimums) = scouty.minimums().get(era_index) {
+        return Ok(minimums);
+    }
+
+    let api = scouty.client().clone();
+    let storage = api.storage().at_latest().await?;
+
+    let minimum_active_stake_addr =
+        node_runtime::storage().staking().minimum_active_stake();
+    let min_nominator_bond_addr = node_runtime::storage().staking().min_nominator_bond();
+    let min_validator_bond_addr = node_runtime::storage().staking().min_validator_bond();
+    let min_validator_bond = storage.fetch_or_default(&min_validator_bond_addr).await?;
+
+    let minimums = StakingMinimums {
+        era_index,
+        minimum_active_stake: storage
+            .fetch_or_default(&minimum_active_stake_addr)
+            .await?,
+        min_nominator_bond: storage.fetch_or_default(&min_nominator_bond_addr).await?,
+        // A minimum validator bond of zero means that the chain does not define one
+        min_validator_bond: Some(min_validator_bond).filter(|bond| *bond > 0),
+    };
+    scouty.minimums().insert(minimums);
+    Ok(minimums)
+}
+
+/// Update the chain status shared with the matrix bot commands with the session
+/// processed
+fn update_status(
+    scouty: &Scouty,
+    session: &Session,
+    validators: &Validators,
+) -> Result<(), ScoutyError> {
+    let sessions_per_era_addr = node_runtime::constants().staking().sessions_per_era();
+    let sessions_per_era = scouty.client().constants().at(&sessions_per_era_addr)?;
+
+    scouty.status().update(|status| {
+        status.session_index = Some(session.current_session_index);
+        status.era_index = Some(session.active_era_index);
+        status.eras_session_index = session.eras_session_index;
+        status.sessions_per_era = sessions_per_era;
+        for v in validators.iter() {
+            let stash = status
+                .stashes
+                .entry(scouty.to_ss58(v.stash.clone()))
+                .or_default();
+            stash.name = v.name.to_string();
+            stash.is_active = v.is_active;
+            stash.is_queued = v.is_queued;
+        }
+    });
+    Ok(())
+}
+
+/// Update the era points collected so far by the stashes predefined
+async fn update_status_era_points(scouty: &Scouty) -> Result<(), ScoutyError> {
+    let era_index = match scouty.status().get().era_index {
+        Some(era_index) => era_index,
+        None => return Ok(()),
+    };
+    let era_reward_points_addr = node_runtime::storage()
+        .staking()
+        .eras_reward_points(&era_index);
+    let era_reward_points = scouty
+        .client()
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&era_reward_points_addr)
+        .await?;
+
+    scouty.status().update(|status| {
+        for (stash, s) in status.stashes.iter_mut() {
+            s.era_points = era_reward_points
+                .as_ref()
+                .and_then(|points| {
+                    points
+                        .individual
+                        .iter()
+                        .find(|(account, _)| &scouty.to_ss58(account.clone()) == stash)
+                })
+                .map(|(_, points)| *points)
+                .unwrap_or_default();
+        }
+    });
+    Ok(())
+}
+
+/// Returns true if the new era hook has not been run yet for the active era. Without
+/// any previous record the hook only runs on the first session of the era.
+fn is_new_era(
+    last_era_index: Option<u32>,
+    active_era_index: u32,
+    eras_session_index: u32,
+) -> bool {
+    match last_era_index {
+        Some(last_era_index) => active_era_index > last_era_index,
+        None => eras_session_index == 1,
+    }
+}
+
+async fn collect_session_data(
+    scouty: &Scouty,
+    _session_index: u32,
+) -> Result<Session, ScoutyError> {
+    let api = scouty.client().clone();
+
+    // Get Era index
+    let active_era_index_addr = node_runtime::storage().staking().active_era();
+    let (active_era_index, era_start) = match api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&active_era_index_addr)
+        .await?
+    {
+        Some(info) => (info.index, info.start.unwrap_or_default() / 1000),
+        None => return Err("Current era index not defined".into()),
+    };
+
+    // Get current session
+    let current_session_index_addr = node_runtime::storage().session().current_index();
+    let current_session_index = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&current_session_index_addr)
+        .await?
+        .unwrap();
+
+    // Get start session index
+    let start_session_index_addr = node_runtime::storage()
+        .staking()
+        .eras_start_session_index(&active_era_index);
+    let start_session_index = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&start_session_index_addr)
+        .await?
+        .unwrap();
+
+    // Eras session index
+    let eras_session_index = 1 + current_session_index - start_session_index;
+
+    // Get session keys queued status
+    let queued_changed_addr = node_runtime::storage().session().queued_changed();
+    let queued_session_keys_changed = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&queued_changed_addr)
+        .await?
+        .unwrap();
+
+    // Set network info
+    let session = Session {
+        active_era_index,
+        current_session_index,
+        eras_session_index,
+        queued_session_keys_changed,
+        era_start_session_index: start_session_index,
+        era_start,
+    };
+    debug!("session {:?}", session);
+
+    Ok(session)
+}
+
+async fn collect_validators_data(scouty: &Scouty) -> Result<Validators, ScoutyError> {
+    let api = scouty.client().clone();
+    let config = CONFIG.current();
+
+    // Verify session active validators
+    let validators_addr = node_runtime::storage().session().validators();
+    let active_validators = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&validators_addr)
+        .await?
+        .unwrap();
+
+    // Verify session queued keys
+    let queued_keys_addr = node_runtime::storage().session().queued_keys();
+    let queued_keys = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&queued_keys_addr)
+        .await?
+        .unwrap();
+    // let queued_keys = api.storage().session().queued_keys(None).await?;
+
+    // Identities are only resolved once per era
+    let active_era_addr = node_runtime::storage().staking().active_era();
+    let era_index = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&active_era_addr)
+        .await?
+        .map(|info| info.index)
+        .unwrap_or_default();
+
+    let mut validators: Validators = Vec::new();
+    for stash_str in config
+        .validator_stashes(&scouty.runtime().to_string())
+        .iter()
+    {
+        let stash = AccountId32::from_str(stash_str).map_err(|e| {
+            ScoutyError::Other(format!(
+                "Invalid SS58 format account: {:?} error: {e:?}",
+                stash_str
+            ))
+        })?;
+        let mut v = Validator::new(stash.clone());
+
+        // Get validator name, a failure querying the identity of one stash must not
+        // abort the processing of the others
+        let identity = get_cached_identity(&scouty, era_index, &stash).await;
+        v.has_identity = identity.is_some();
+        v.name = match identity {
+            Some(identity) => identity.to_string(),
+            None => identity::short_address(&stash),
+        };
+
+        // Check if validator is in active set
+        v.is_active = active_validators.contains(&v.stash);
+
+        // Check if validator session key is queued
+        for (account_id, session_keys) in &queued_keys {
+            if account_id == &v.stash {
+                v.is_queued = true;
+                v.queued_session_keys = session_keys.encode();
+                break;
+            }
+        }
+
+        validators.push(v);
+    }
+
+    debug!("validators {:?}", validators);
+    Ok(validators)
+}
+
+async fn get_display_name(
+    scouty: &Scouty,
+    stash: &AccountId32,
+) -> Result<String, ScoutyError> {
+    if let Some(identity) = get_identity(&scouty, &stash, None).await? {
+        return Ok(identity.to_string());
+    } else {
+        Ok(identity::short_address(stash))
+    }
+}
+
+/// Returns true if the identities can be resolved in the chain, the identity pallet
+/// has moved to the people system parachain in some chains
+fn is_identity_available(scouty: &Scouty) -> bool {
+    scouty
+        .client()
+        .metadata()
+        .pallet_by_name("Identity")
+        .is_some()
+}
+
+/// Returns the identity of the account, resolved once per era. A failure querying the
+/// identity of one account must not abort the processing of the others
+async fn get_cached_identity(
+    scouty: &Scouty,
+    era_index: u32,
+    account: &AccountId32,
+) -> Option<Identity> {
+    if let Some(identity) = scouty.identities().get(era_index, account) {
+        return identity;
+    }
+    match get_identity(&scouty, account, None).await {
+        Ok(identity) => {
+            scouty
+                .identities()
+                .insert(era_index, account.clone(), identity.clone());
+            identity
+        }
+        Err(e) => {
+            warn!(
+                "Identity of {} not available! {}",
+                scouty.to_ss58(account.clone()),
+                e
+            );
+            None
+        }
+    }
+}
+
+#[async_recursion]
+async fn get_identity(
+    scouty: &Scouty,
+    stash: &AccountId32,
+    sub_account_name: Option<String>,
+) -> Result<Option<Identity>, ScoutyError> {
+    // NOTE: accounts are only described by their address without the identity pallet
+    if !is_identity_available(&scouty) {
+        return Ok(None);
+    }
+    let api = scouty.client().clone();
+
+    let identity_of_addr = node_runtime::storage().identity().identity_of(stash);
+    match api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&identity_of_addr)
+        .await?
+    {
+        Some((identity, _)) => {
+            debug!("identity {:?}", identity);
+            let parent = parse_identity_data(identity.info.display);
+            let identity = match sub_account_name {
+                Some(child) => Identity::with_name_and_sub(parent, child),
+                None => Identity::with_name(parent),
+            };
+            Ok(Some(identity))
+        }
+        None => {
+            let super_of_addr = node_runtime::storage().identity().super_of(stash);
+            if let Some((parent_account, data)) = api
+                .storage()
+                .at_latest()
+                .await?
+                .fetch(&super_of_addr)
+                .await?
+            {
+                let sub_account_name = parse_identity_data(data);
+                return get_identity(
+                    &scouty,
+                    &parent_account,
+                    Some(sub_account_name.to_string()),
+                )
+                .await;
+            } else {
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Returns true if the account is one of the stashes predefined
+fn is_stash_predefined(account: &AccountId32) -> bool {
+    CONFIG
+        .current()
+        .stashes
+        .iter()
+        .any(|stash| AccountId32::from_str(stash).is_ok_and(|stash| &stash == account))
+}
+
+/// Returns true if the session keys state changed since the previous verification,
+/// stashes not verified yet are expected to own their session keys
+fn is_session_keys_transition(previous: Option<bool>, is_mismatch: bool) -> bool {
+    previous.unwrap_or(false) != is_mismatch
+}
+
+/// Estimate the validator reward (including the nominators share) of an era from
+/// the validator era points
+fn estimate_reward(validator_payout: u128, points: u32, total_points: u32) -> u128 {
+    if total_points == 0 {
+        return 0;
+    }
+    validator_payout * points as u128 / total_points as u128
+}
+
+/// Estimate the reward earned by the stash itself from the validator reward of an era,
+/// the commission (in parts per billion) plus the share of its own stake in the
+/// remaining reward
+fn estimate_stash_reward(validator_reward: u128, commission: u32, stake: &Stake) -> u128 {
+    let commission_reward =
+        validator_reward * commission.min(1_000_000_000) as u128 / 1_000_000_000;
+    if stake.total == 0 {
+        return commission_reward;
+    }
+    let own_reward = (validator_reward - commission_reward) * stake.own / stake.total;
+    commission_reward + own_reward
+}
+
+/// Rank of the stash by era points within the validators that earned points in the
+/// era, validators with the same points share the same rank
+fn era_points_rank(
+    individual: &[(AccountId32, u32)],
+    stash: &AccountId32,
+) -> Option<u32> {
+    let (_, points) = individual.iter().find(|(s, _)| s == stash)?;
+    Some(1 + individual.iter().filter(|(_, p)| p > points).count() as u32)
+}
+
+/// Percentage of the epoch elapsed, epochs start at genesis slot and
+/// last epoch duration slots
+fn session_progress(
+    current_slot: u64,
+    genesis_slot: u64,
+    epoch_index: u64,
+    epoch_duration: u64,
+) -> u32 {
+    if epoch_duration == 0 {
+        return 0;
+    }
+    let epoch_start_slot = genesis_slot + epoch_index * epoch_duration;
+    (current_slot.saturating_sub(epoch_start_slot) * 100 / epoch_duration) as u32
+}
+
+/// Describe the reason of a chill from the call that triggered it
+fn parse_chill_reason(pallet: &str, call: &str) -> String {
+    match (pallet, call) {
+        ("Staking", "chill") => "chill() called by the controller".to_string(),
+        ("Staking", "chill_other") => {
+            "chill_other() called by another account".to_string()
+        }
+        (pallet, call) => format!("{}.{} called", pallet, call),
+    }
+}
+
+/// Offence kinds are 16 bytes ids padded with zeros, e.g. im-online:offlin
+fn parse_offence_kind(kind: &[u8; 16]) -> String {
+    String::from_utf8_lossy(kind)
+        .trim_end_matches('\0')
+        .to_string()
+}
+
+//
+fn parse_identity_data(data: Data) -> String {
+    match data {
+        Data::Raw0(bytes) => str(bytes.to_vec()),
+        Data::Raw1(bytes) => str(bytes.to_vec()),
+        Data::Raw2(bytes) => str(bytes.to_vec()),
+        Data::Raw3(bytes) => str(bytes.to_vec()),
+        Data::Raw4(bytes) => str(bytes.to_vec()),
+        Data::Raw5(bytes) => str(bytes.to_vec()),
+        Data::Raw6(bytes) => str(bytes.to_vec()),
+        Data::Raw7(bytes) => str(bytes.to_vec()),
+        Data::Raw8(bytes) => str(bytes.to_vec()),
+        Data::Raw9(bytes) => str(bytes.to_vec()),
+        Data::Raw10(bytes) => str(bytes.to_vec()),
+        Data::Raw11(bytes) => str(bytes.to_vec()),
+        Data::Raw12(bytes) => str(bytes.to_vec()),
+        Data::Raw13(bytes) => str(bytes.to_vec()),
+        Data::Raw14(bytes) => str(bytes.to_vec()),
+        Data::Raw15(bytes) => str(bytes.to_vec()),
+        Data::Raw16(bytes) => str(bytes.to_vec()),
+        Data::Raw17(bytes) => str(bytes.to_vec()),
+        Data::Raw18(bytes) => str(bytes.to_vec()),
+        Data::Raw19(bytes) => str(bytes.to_vec()),
+        Data::Raw20(bytes) => str(bytes.to_vec()),
+        Data::Raw21(bytes) => str(bytes.to_vec()),
+        Data::Raw22(bytes) => str(bytes.to_vec()),
+        Data::Raw23(bytes) => str(bytes.to_vec()),
+        Data::Raw24(bytes) => str(bytes.to_vec()),
+        Data::Raw25(bytes) => str(bytes.to_vec()),
+        Data::Raw26(bytes) => str(bytes.to_vec()),
+        Data::Raw27(bytes) => str(bytes.to_vec()),
+        Data::Raw28(bytes) => str(bytes.to_vec()),
+        Data::Raw29(bytes) => str(bytes.to_vec()),
+        Data::Raw30(bytes) => str(bytes.to_vec()),
+        Data::Raw31(bytes) => str(bytes.to_vec()),
+        Data::Raw32(bytes) => str(bytes.to_vec()),
+        _ => format!("???"),
+    }
+}
+
+fn str(bytes: Vec<u8>) -> String {
+    format!("{}", String::from_utf8_lossy(&bytes))
+}
+
+async fn init_authority_records(
+    scouty: &Scouty,
+    authority_records: &mut AuthorityRecords,
+) -> Result<(), ScoutyError> {
+    let api = scouty.client().clone();
+    let _config = CONFIG.current();
+    // Get current block
+    let number_addr = node_runtime::storage().system().number();
+    let number = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&number_addr)
+        .await?
+        .unwrap();
+
+    authority_records.set_block(number);
+
+    // Get current session
+    let current_session_index_addr = node_runtime::storage().session().current_index();
+    let current_session_index = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&current_session_index_addr)
+        .await?
+        .unwrap();
+
+    authority_records.set_session(current_session_index);
+
+    // Get current active authorities
+    let validators_addr = node_runtime::storage().session().validators();
+    let active_validators = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&validators_addr)
+        .await?
+        .unwrap();
+
+    authority_records.set_authorities(active_validators);
+
+    // // DEPRECATED: Get blocks authored for each stash
+    //
+    // for stash_str in config.stashes.iter() {
+    //     let stash = AccountId32::from_str(stash_str).map_err(|e| {
+    //         ScoutyError::Other(format!(
+    //             "Invalid SS58 format account: {:?} error: {e:?}",
+    //             stash_str
+    //         ))
+    //     })?;
+    //     let key = format!("{}:{}", current_session_index, stash);
+    //     let blocks_addr = node_runtime::storage()
+    //         .im_online()
+    //         .authored_blocks(&current_session_index, &stash);
+    //     let blocks = if let Some(n) =
+    //         api.storage().at_latest().await?.fetch(&blocks_addr).await?
+    //     {
+    //         n
+    //     } else {
+    //         0
+    //     };
+
+    //     authority_records.records.insert(key, blocks);
+    // }
+    Ok(())
+}
+
+async fn init_para_records(
+    scouty: &Scouty,
+    para_records: &mut ParaRecords,
+) -> Result<(), ScoutyError> {
+    let api = scouty.client().clone();
+
+    // Get current active authorities
+    let validators_addr = node_runtime::storage().session().validators();
+    let active_validators = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&validators_addr)
+        .await?
+        .unwrap();
+
+    para_records
+        .reset_config_stashes(&scouty.runtime().to_string(), active_validators)?;
+
+    // Get current session
+    let current_session_index_addr = node_runtime::storage().session().current_index();
+    let current_session_index = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&current_session_index_addr)
+        .await?
+        .unwrap();
+
+    track_para_records(&scouty, current_session_index, para_records).await?;
+
+    Ok(())
+}
+
+async fn track_para_records(
+    scouty: &Scouty,
+    new_session_index: u32,
+    para_records: &mut ParaRecords,
+) -> Result<(), ScoutyError> {
+    let api = scouty.client().clone();
+
+    // Get para active validator indices
+    let para_validators_addr = node_runtime::storage()
+        .paras_shared()
+        .active_validator_indices();
+    let para_validators = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&para_validators_addr)
+        .await?
+        .unwrap();
+
+    // Parse Vec<ValidatorIndex> to Vec<u32>
+    let active_validator_indices: Vec<u32> = para_validators
+        .iter()
+        .map(|&ValidatorIndex(index)| index)
+        .collect();
+
+    // Insert record
+    para_records.insert_record(new_session_index, active_validator_indices);
+
+    Ok(())
+}
+
+async fn get_validator_points_info(
+    stash: &AccountId32,
+    era_reward_points: node_runtime::runtime_types::pallet_staking::EraRewardPoints<
+        AccountId32,
+    >,
+) -> Result<Points, ScoutyError> {
+    let stash_points = match era_reward_points
+        .individual
+        .iter()
+        .find(|(s, _)| s == stash)
+    {
+        Some((_, p)) => *p,
+        None => 0,
+    };
+
+    // Calculate average points
+    let mut points: Vec<u32> = era_reward_points
+        .individual
+        .into_iter()
+        .map(|(_, points)| points)
+        .collect();
+
+    let points_f64: Vec<f64> = points.iter().map(|points| *points as f64).collect();
+
+    let points = Points {
+        validator: stash_points,
+        era_avg: stats::mean(&points_f64),
+        ci99_9_interval: stats::confidence_interval_99_9(&points_f64),
+        outlier_limits: stats::iqr_interval(&mut points),
+    };
+
+    Ok(points)
+}
+
+async fn calculate_projected_apr(
+    scouty: &Scouty,
+    stash: &AccountId32,
+    token_decimals: u8,
+    stash_active_stake: u128,
+    era_reward: u128,
+    total_active_validators: u32,
+) -> Result<f64, ScoutyError> {
+    let api = scouty.client().clone();
+
+    // Get validator prefs
+    let validator_prefs_addr = node_runtime::storage().staking().validators(stash);
+    let validator_prefs = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&validator_prefs_addr)
+        .await?
+        .unwrap();
+
+    let node_runtime::runtime_types::sp_arithmetic::per_things::Perbill(c) =
+        validator_prefs.commission;
+    let commission = normalize_commission(c);
+
+    let avg_reward_per_validator_per_era =
+        from_plancks_to_ksm(token_decimals, era_reward) / total_active_validators as f64;
+
+    let nominators_reward = (1.0 - commission) * avg_reward_per_validator_per_era;
+    let nominator_reward_per_ksm = (1.0_f64
+        / from_plancks_to_ksm(token_decimals, stash_active_stake))
+        * nominators_reward;
+    let apr = nominator_reward_per_ksm * ERAS_PER_DAY as f64 * 365.0_f64;
+    Ok(apr)
+}
+
+// async fn calculate_current_apr(scouty: &Scouty) -> Result<f64, ScoutyError> {
+//     let client = scouty.client();
+//     let api = scouty.client().clone();
+
+//     // Get validator prefs
+//     info!("Starting validators sync");
+//     let history_depth: u32 = api.storage().staking().history_depth(None).await?;
+//     let prefs = api
+//         .storage()
+//         .staking()
+//         .validators(stash.clone(), None)
+//         .await?;
+
+//     let node_runtime::runtime_types::sp_arithmetic::per_things::Perbill(c) = prefs.commission;
+//     let commission = normalize_commission(c);
+
+//     let avg_reward_per_validator_per_era =
+//         from_plancks_to_ksm(token_decimals, era_reward) / total_active_validators as f64;
+
+//     let nominators_reward = (1.0 - commission) * avg_reward_per_validator_per_era;
+//     let nominator_reward_per_ksm =
+//         (1.0_f64 / from_plancks_to_ksm(token_decimals, stash_active_stake)) * nominators_reward;
+//     let apr = nominator_reward_per_ksm * ERAS_PER_DAY as f64 * 365.0_f64;
+//     Ok(apr)
+// }
+
+/// Normalize commission perbill between 0 - 1
+fn normalize_commission(commission: u32) -> f64 {
+    (commission as f64 / 10.0_f64.powi(9)) as f64
+}
+
+/// Convert Planks to KSM
+fn from_plancks_to_ksm(token_decimals: u8, plancks: u128) -> f64 {
+    (plancks as f64 / 10.0_f64.powi(token_decimals.into())) as f64
+}
+
+async fn get_authority_index(
+    scouty: &Scouty,
+    block_hash: Option<H256>,
+) -> Result<Option<AuthorityIndex>, ScoutyError> {
+    if let Some(header) = scouty.rpc().chain_get_header(block_hash).await? {
+        match header.digest {
+            Digest { logs } => {
+                for digests in logs.iter() {
+                    match digests {
+                        DigestItem::PreRuntime(_, data) => {
+                            if let Some(pre) = PreDigest::decode(&mut &data[..]).ok() {
+                                match pre {
+                                    PreDigest::Primary(e) => {
+                                        return Ok(Some(e.authority_index))
+                                    }
+                                    PreDigest::SecondaryPlain(e) => {
+                                        return Ok(Some(e.authority_index))
+                                    }
+                                    PreDigest::SecondaryVRF(e) => {
+                                        return Ok(Some(e.authority_index))
+                                    }
+                                }
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+            }
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_identity_data() {
+        assert_eq!(parse_identity_data(Data::Raw5(*b"scout")), "scout".to_string());
+        assert_eq!(parse_identity_data(Data::None), "???".to_string());
+    }
+
+    #[test]
+    fn it_parses_offence_kind() {
+        assert_eq!(parse_offence_kind(b"im-online:offlin"), "im-online:offlin");
+        assert_eq!(parse_offence_kind(b"babe:equivocatio"), "babe:equivocatio");
+        assert_eq!(parse_offence_kind(b"kind\0\0\0\0\0\0\0\0\0\0\0\0"), "kind");
+    }
+
+    #[test]
+    fn it_infers_the_reason_of_a_stash_inactive_next_era() {
+        let network = Network {
+            name: "Polkadot".to_string(),
+            token_symbol: "DOT".to_string(),
+            token_decimals: 10,
+        };
+        let context = InactiveNextEra {
+            has_intention: true,
+            has_next_keys: true,
+            backing_stake: Some(123_000_000_000_000),
+            min_elected_stake: Some(141_000_000_000_000),
+            ..Default::default()
+        };
+        assert_eq!(
+            context.reason(&network),
+            "backing stake 12.3k DOT below minimum elected 14.1k DOT"
+        );
+        assert_eq!(
+            InactiveNextEra {
+                has_next_keys: false,
+                ..context
+            }
+            .reason(&network),
+            "session keys not set (session.NextKeys)"
+        );
+        assert_eq!(
+            InactiveNextEra::default().reason(&network),
+            "validator intention not set (staking.Validators)"
+        );
+        assert_eq!(
+            InactiveNextEra {
+                is_chilled: true,
+                ..Default::default()
+            }
+            .reason(&network),
+            "validator chilled this era"
+        );
+    }
+
+    #[test]
+    fn it_parses_chill_reason() {
+        assert_eq!(
+            parse_chill_reason("Staking", "chill"),
+            "chill() called by the controller"
+        );
+        assert_eq!(
+            parse_chill_reason("Utility", "batch_all"),
+            "Utility.batch_all called"
+        );
+    }
+
+    #[test]
+    fn it_detects_session_keys_transitions() {
+        assert!(is_session_keys_transition(None, true));
+        assert!(!is_session_keys_transition(None, false));
+        assert!(!is_session_keys_transition(Some(true), true));
+        assert!(is_session_keys_transition(Some(true), false));
+        assert!(is_session_keys_transition(Some(false), true));
+    }
+
+    #[test]
+    fn it_defines_the_commission_policy() {
+        let policy = commission_policy(0, 15.0);
+        assert_eq!(policy.max, 150_000_000);
+        assert!(policy.contains(150_000_000));
+        assert!(!policy.contains(150_000_001));
+
+        // The minimum commission of the chain takes precedence
+        let policy = commission_policy(50_000_000, 2.5);
+        assert_eq!(policy.max, 50_000_000);
+        assert!(!policy.contains(0));
+        assert_eq!(commission_policy(0, 120.0).max, 1_000_000_000);
+    }
+
+    #[test]
+    fn it_detects_new_eras() {
+        assert!(is_new_era(None, 1234, 1));
+        assert!(!is_new_era(None, 1234, 3));
+        assert!(is_new_era(Some(1233), 1234, 3));
+        assert!(!is_new_era(Some(1234), 1234, 1));
+    }
+
+    #[test]
+    fn it_estimates_reward() {
+        assert_eq!(estimate_reward(1_000_000, 20, 100), 200_000);
+        assert_eq!(estimate_reward(1_000_000, 0, 100), 0);
+        assert_eq!(estimate_reward(1_000_000, 20, 0), 0);
+    }
+
+    #[test]
+    fn it_estimates_stash_reward() {
+        let stake = Stake {
+            own: 100,
+            total: 1_000,
+            nominator_count: 10,
+        };
+        // 10% commission and 10% of the remaining reward for the own stake
+        assert_eq!(
+            estimate_stash_reward(1_000_000, 100_000_000, &stake),
+            190_000
+        );
+        assert_eq!(estimate_stash_reward(1_000_000, 0, &stake), 100_000);
+        assert_eq!(
+            estimate_stash_reward(1_000_000, 1_000_000_000, &stake),
+            1_000_000
+        );
+        assert_eq!(
+            estimate_stash_reward(1_000_000, 100_000_000, &Stake::default()),
+            100_000
+        );
+    }
+
+    #[test]
+    fn it_ranks_era_points() {
+        let alice = AccountId32::from([1u8; 32]);
+        let bob = AccountId32::from([2u8; 32]);
+        let charlie = AccountId32::from([3u8; 32]);
+        let individual = vec![(alice.clone(), 80), (bob.clone(), 100), (charlie, 80)];
+        assert_eq!(era_points_rank(&individual, &bob), Some(1));
+        assert_eq!(era_points_rank(&individual, &alice), Some(2));
+        assert_eq!(
+            era_points_rank(&individual, &AccountId32::from([4u8; 32])),
+            None
+        );
+    }
+
+    #[test]
+    fn it_calculates_session_progress() {
+        assert_eq!(session_progress(1000, 1000, 0, 600), 0);
+        assert_eq!(session_progress(1480, 1000, 0, 600), 80);
+        assert_eq!(session_progress(2200, 1000, 1, 600), 100);
+        assert_eq!(session_progress(1900, 1000, 1, 600), 50);
+        assert_eq!(session_progress(1900, 1000, 1, 0), 0);
+    }
+
+    #[test]
+    fn it_normalizes_commission() {
+        assert_eq!(normalize_commission(50_000_000), 0.05);
+        assert_eq!(normalize_commission(1_000_000_000), 1.0);
+    }
+
+    #[test]
+    fn it_converts_plancks() {
+        assert_eq!(from_plancks_to_ksm(12, 1_500_000_000_000), 1.5);
+        assert_eq!(from_plancks_to_ksm(10, 10_000_000_000), 1.0);
+    }
+
+    // NOTE: The tests below are run against the types generated for each runtime
+    // module including this file (e.g. polkadot::tests and kusama::tests)
+
+    #[test]
+    fn it_builds_the_slash_report_data() {
+        let alice = AccountId32::from([1u8; 32]);
+        let bob = AccountId32::from([2u8; 32]);
+        let network = Network {
+            name: "Polkadot".to_string(),
+            token_symbol: "DOT".to_string(),
+            token_decimals: 10,
+        };
+        let event = Slashed {
+            staker: alice.clone(),
+            amount: 1_000_000_000_000,
+        };
+        let data = slash_data(
+            network,
+            vec![Validator::new(alice.clone()), Validator::new(bob)],
+            event,
+            1234,
+            Hook::default(),
+        );
+        assert_eq!(data.section, Section::Slash);
+        assert_eq!(data.slash.who, Some(alice));
+        assert_eq!(data.slash.amount_value, 1_000_000_000_000);
+        assert_eq!(data.slash.era_index, 1234);
+        assert!(data.validators[0].is_slashed);
+        assert!(!data.validators[1].is_slashed);
+        assert!(!data.is_suppressed());
+    }
+
+    #[test]
+    fn it_decodes_the_backing_votes() {
+        // 5 validators in the group, the 2nd and the 5th did not vote
+        let encoded = vec![5 << 2, 0b0000_1101];
+        let validator_indices =
+            DecodedBits::<u8, Lsb0>::decode(&mut &encoded[..]).unwrap();
+        assert_eq!(
+            backing_votes(&validator_indices),
+            vec![true, false, true, true, false]
+        );
+    }
+
+    #[test]
+    fn it_converts_the_reward_destination() {
+        use node_runtime::runtime_types::pallet_staking::RewardDestination;
+        let alice = AccountId32::from([1u8; 32]);
+        let payee = to_payee(RewardDestination::Account(alice.clone()));
+        assert_eq!(payee.destination, "Account");
+        assert_eq!(payee.account, Some(alice));
+        let payee = to_payee(RewardDestination::Staked);
+        assert_eq!(payee.destination, "Staked");
+        assert_eq!(payee.account, None);
+    }
+}
@@ -19,6 +19,10 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+use crate::errors::ScoutyError;
+use log::warn;
+use std::convert::TryFrom;
+
 pub type ChainPrefix = u16;
 pub type ChainTokenSymbol = String;
 
@@ -27,26 +31,111 @@ pub enum SupportedRuntime {
     Polkadot,
     Kusama,
     Westend,
+    Generic,
+}
+
+impl SupportedRuntime {
+    /// Returns the ss58 prefix of the runtime
+    pub fn chain_prefix(&self) -> ChainPrefix {
+        match self {
+            Self::Polkadot => 0,
+            Self::Kusama => 2,
+            Self::Westend => 42,
+            Self::Generic => 42,
+        }
+    }
+
+    /// Returns true if the runtime module of the chain is compiled in this build
+    pub fn is_available(&self) -> bool {
+        matches!(self, Self::Polkadot | Self::Kusama | Self::Generic)
+    }
+
+    /// Returns the runtime matching the `spec_name` of the runtime version, if any
+    pub fn from_spec_name(spec_name: &str) -> Option<Self> {
+        match spec_name {
+            "polkadot" => Some(Self::Polkadot),
+            "kusama" => Some(Self::Kusama),
+            "westend" => Some(Self::Westend),
+            _ => None,
+        }
+    }
+
+    /// Detect the runtime from the `spec_name` of the runtime version of the connected
+    /// chain. If the `spec_name` is unknown fallback to the chain name, ss58 prefix and
+    /// token symbol, all of them need to match, since several chains share the
+    /// same ss58 prefix (e.g. Polkadot and Paseo). Runtimes without a compiled module
+    /// are reported as unsupported.
+    pub fn detect(
+        spec_name: &str,
+        chain: &str,
+        chain_prefix: ChainPrefix,
+        chain_token_symbol: ChainTokenSymbol,
+    ) -> Result<Self, ScoutyError> {
+        if let Some(runtime) = Self::from_spec_name(spec_name) {
+            if runtime.chain_prefix() != chain_prefix {
+                warn!(
+                    "Runtime {} detected from spec name {} but chain {} reports prefix {} instead of {}",
+                    runtime,
+                    spec_name,
+                    chain,
+                    chain_prefix,
+                    runtime.chain_prefix()
+                );
+            }
+            return runtime.check_available(chain);
+        }
+        let runtime = Self::try_from(chain_token_symbol)?;
+        if runtime.chain_prefix() != chain_prefix
+            || !chain.starts_with(&runtime.to_string())
+        {
+            return Err(ScoutyError::UnsupportedChain(format!(
+                "{} chain with prefix {} is not supported",
+                chain, chain_prefix
+            )));
+        }
+        runtime.check_available(chain)
+    }
+
+    /// Returns the runtime if its module is compiled in this build
+    pub fn check_available(self, chain: &str) -> Result<Self, ScoutyError> {
+        if !self.is_available() {
+            return Err(ScoutyError::UnsupportedChain(format!(
+                "{} chain is not supported, {} runtime is not available in this build",
+                chain, self
+            )));
+        }
+        Ok(self)
+    }
 }
 
-impl From<ChainPrefix> for SupportedRuntime {
-    fn from(v: ChainPrefix) -> Self {
+impl TryFrom<ChainPrefix> for SupportedRuntime {
+    type Error = ScoutyError;
+
+    fn try_from(v: ChainPrefix) -> Result<Self, Self::Error> {
         match v {
-            0 => Self::Polkadot,
-            2 => Self::Kusama,
-            42 => Self::Westend,
-            _ => unimplemented!("Chain prefix not supported"),
+            0 => Ok(Self::Polkadot),
+            2 => Ok(Self::Kusama),
+            42 => Ok(Self::Westend),
+            _ => Err(ScoutyError::UnsupportedChain(format!(
+                "chain with prefix {} is not supported",
+                v
+            ))),
         }
     }
 }
 
-impl From<ChainTokenSymbol> for SupportedRuntime {
-    fn from(v: ChainTokenSymbol) -> Self {
+impl TryFrom<ChainTokenSymbol> for SupportedRuntime {
+    type Error = ScoutyError;
+
+    fn try_from(v: ChainTokenSymbol) -> Result<Self, Self::Error> {
         match v.as_str() {
-            "DOT" => Self::Polkadot,
-            "KSM" => Self::Kusama,
-            "WND" => Self::Westend,
-            _ => unimplemented!("Chain unit not supported"),
+            "DOT" => Ok(Self::Polkadot),
+            "KSM" => Ok(Self::Kusama),
+            "WND" => Ok(Self::Westend),
+            _ => Err(ScoutyError::UnsupportedChain(format!(
+                "chain with token {} is not supported",
+                v
+            ))),
         }
     }
 }
@@ -57,6 +146,65 @@ impl std::fmt::Display for SupportedRuntime {
             Self::Polkadot => write!(f, "Polkadot"),
             Self::Kusama => write!(f, "Kusama"),
             Self::Westend => write!(f, "Westend"),
+            Self::Generic => write!(f, "Generic"),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_detects_supported_runtimes() {
+        assert_eq!(
+            SupportedRuntime::detect("", "Polkadot", 0, "DOT".to_string()).unwrap(),
+            SupportedRuntime::Polkadot
+        );
+        assert_eq!(
+            SupportedRuntime::detect("", "Kusama", 2, "KSM".to_string()).unwrap(),
+            SupportedRuntime::Kusama
+        );
+    }
+
+    #[test]
+    fn it_detects_runtimes_by_spec_name_first() {
+        assert_eq!(
+            SupportedRuntime::detect("kusama", "Kusama", 2, "KSM".to_string()).unwrap(),
+            SupportedRuntime::Kusama
+        );
+        // Spec name wins even if the prefix reported disagrees
+        assert_eq!(
+            SupportedRuntime::detect("polkadot", "Polkadot", 42, "DOT".to_string())
+                .unwrap(),
+            SupportedRuntime::Polkadot
+        );
+    }
+
+    #[test]
+    fn it_rejects_chains_sharing_the_same_prefix() {
+        assert!(SupportedRuntime::detect("", "Polkadot", 63, "DOT".to_string()).is_err());
+        assert!(
+            SupportedRuntime::detect("acala", "Acala", 0, "ACA".to_string()).is_err()
+        );
+        assert!(
+            SupportedRuntime::detect("astar", "Astar", 0, "DOT".to_string()).is_err()
+        );
+        assert!(
+            SupportedRuntime::detect("node", "Development", 42, "UNIT".to_string())
+                .is_err()
+        );
+        assert!(
+            SupportedRuntime::detect("paseo", "Paseo Testnet", 0, "PAS".to_string())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn it_rejects_runtimes_not_available_in_this_build() {
+        assert!(
+            SupportedRuntime::detect("westend", "Westend", 42, "WND".to_string())
+                .is_err()
+        );
+    }
+}
@@ -19,30 +19,79 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use crate::config::{Config, CONFIG};
+use crate::backoff::Backoff;
+use crate::check::{self, Check};
+use crate::commands::{self, Command};
+use crate::config::{ChainEndpoints, Config, CONFIG};
+use crate::discord::Discord;
 use crate::errors::ScoutyError;
+use crate::events_file::EventsFile;
+use crate::finality::{FinalityRecords, FinalityStatus};
+use crate::health::{HealthRecords, HealthStatus};
+use crate::healthcheck;
 use crate::hooks::{
-    Hook, HOOK_INIT, HOOK_NEW_ERA, HOOK_NEW_SESSION, HOOK_REFERENDA_SUBMITTED,
-    HOOK_VALIDATOR_CHILLED, HOOK_VALIDATOR_OFFLINE, HOOK_VALIDATOR_SLASHED,
-    HOOK_VALIDATOR_STARTS_ACTIVE_NEXT_ERA, HOOK_VALIDATOR_STARTS_INACTIVE_NEXT_ERA,
+    self, Hook, HookPayload, HookResult, HookRunner, HooksDir, SyntheticEvent,
+    HOOKS_MUTE_EXEMPT, HOOK_FINALITY_STALLED, HOOK_NODE_UNHEALTHY, HOOK_RUNTIME_UPGRADE,
+    HOOK_STASH_PLACEHOLDER, HOOK_WEEKLY_REPORT,
+};
+use crate::identity::IdentityCache;
+use crate::matrix::{self, Matrix, MessageQueue, Severity};
+use crate::metrics;
+use crate::minimums::MinimumsCache;
+use crate::notifier::{self, Notification, Notifier, EVENT_ERROR, EVENT_MESSAGE};
+use crate::pagerduty::PagerDuty;
+use crate::quiet::QuietDigest;
+use crate::reload;
+use crate::report::{
+    Finality, Health, Network, RawData, Report, RuntimeUpgrade, Section,
 };
-use crate::matrix::Matrix;
 use crate::runtimes::{
+    generic,
     kusama,
     polkadot,
     support::{ChainPrefix, ChainTokenSymbol, SupportedRuntime},
     // westend,
 };
+use crate::shutdown;
+use crate::smtp::Smtp;
+use crate::stashes;
+use crate::state;
+use crate::status::SharedStatus;
+use crate::telegram::Telegram;
+use crate::templates::{self, Variables, TEMPLATE_ERROR_HOLD, TEMPLATE_SHUTDOWN};
+use crate::webhook::Webhook;
+use crate::weekly::WeeklyStats;
 
-use async_std::task;
-use log::{error, info, warn};
-use std::{convert::TryInto, result::Result, str::FromStr, thread, time};
+use async_std::{channel::Receiver, task};
+use chrono::Utc;
+use futures::{
+    future::{self, Either},
+    stream, Future, StreamExt,
+};
+use jsonrpsee::{
+    client_transport::ws::{Url, WsTransportClientBuilder},
+    core::client::Client,
+};
+use log::{debug, error, info, warn};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    convert::TryInto,
+    path::Path,
+    process,
+    result::Result,
+    str::FromStr,
+    time,
+};
 use subxt::{
     backend::{
         legacy::{rpc_methods::StorageKey, LegacyRpcMethods},
         rpc::RpcClient,
     },
-    ext::sp_core::crypto,
+    error::RpcError,
+    ext::sp_core::{
+        crypto::{self, Ss58Codec},
+        H256,
+    },
     utils::{validate_url_is_secure, AccountId32},
     OnlineClient, PolkadotConfig,
 };
@@ -50,16 +99,40 @@ use subxt::{
 pub async fn _create_substrate_node_client(
     config: Config,
 ) -> Result<OnlineClient<PolkadotConfig>, subxt::Error> {
-    OnlineClient::<PolkadotConfig>::from_url(config.substrate_ws_url).await
+    OnlineClient::<PolkadotConfig>::from_url(&config.substrate_ws_urls()[0]).await
 }
 
-pub async fn create_substrate_rpc_client_from_config(
-    config: Config,
+// Interval (in seconds) between node syncing checks
+const NODE_SYNCING_WAIT_INTERVAL: u64 = 30;
+
+// Time (in milliseconds) the homeserver holds a matrix sync request while there are
+// no new events
+const MATRIX_SYNC_TIMEOUT: u64 = 30000;
+
+// Interval (in seconds) at which the uptime is accumulated and the weekly report is
+// verified to be due
+const WEEKLY_REPORT_INTERVAL: u64 = 60;
+
+pub async fn create_substrate_rpc_client_from_url(
+    url: &str,
 ) -> Result<RpcClient, subxt::Error> {
-    if let Err(_) = validate_url_is_secure(config.substrate_ws_url.as_ref()) {
-        warn!("Insecure URL provided: {}", config.substrate_ws_url);
+    let config = CONFIG.current();
+    if validate_url_is_secure(url).is_err() {
+        warn!("Insecure URL provided: {}", url);
     };
-    RpcClient::from_insecure_url(config.substrate_ws_url).await
+    let url = Url::parse(url).map_err(|e| RpcError::ClientError(Box::new(e)))?;
+    let (sender, receiver) = WsTransportClientBuilder::default()
+        .connection_timeout(time::Duration::from_secs(config.connection_timeout))
+        .build(url)
+        .await
+        .map_err(|e| RpcError::ClientError(Box::new(e)))?;
+    // Requests without response within the timeout are returned as errors, so that
+    // a hung connection is handled exactly as a failed one
+    let client = Client::builder()
+        .max_buffer_capacity_per_subscription(4096)
+        .request_timeout(time::Duration::from_secs(config.request_timeout))
+        .build_with_tokio(sender, receiver);
+    Ok(RpcClient::new(client))
 }
 
 pub async fn create_substrate_client_from_rpc_client(
@@ -68,18 +141,67 @@ pub async fn create_substrate_client_from_rpc_client(
     OnlineClient::<PolkadotConfig>::from_rpc_client(rpc_client).await
 }
 
+/// Connect to one of the endpoints available, if `expected_chain` is defined only
+/// endpoints connected to the same chain are accepted, and an error is returned once
+/// every endpoint has been found connected to another chain
 pub async fn create_or_await_substrate_node_client(
     config: Config,
-) -> (
-    OnlineClient<PolkadotConfig>,
-    LegacyRpcMethods<PolkadotConfig>,
-    SupportedRuntime,
-) {
+    urls: &[String],
+    expected_chain: Option<&str>,
+) -> Result<
+    (
+        OnlineClient<PolkadotConfig>,
+        LegacyRpcMethods<PolkadotConfig>,
+        SupportedRuntime,
+        ChainPrefix,
+        String,
+        String,
+    ),
+    ScoutyError,
+> {
+    let mut index: usize = 0;
+    let mut attempts: u32 = 0;
+    let mut failures: usize = 0;
+    let mut mismatched: BTreeSet<String> = BTreeSet::new();
+    let mut backoff = Backoff::new(
+        config.reconnect_initial_interval,
+        config.reconnect_max_interval,
+    );
     loop {
-        match create_substrate_rpc_client_from_config(config.clone()).await {
+        let url = &urls[index % urls.len()];
+        match create_substrate_rpc_client_from_url(url).await {
             Ok(rpc_client) => {
                 let rpc = LegacyRpcMethods::<PolkadotConfig>::new(rpc_client.clone());
                 let chain = rpc.system_chain().await.unwrap_or_default();
+
+                // Verify that the endpoint is connected to the expected chain
+                if let Some(expected_chain) = expected_chain {
+                    if chain != expected_chain {
+                        warn!(
+                            "Endpoint {} is connected to {} network instead of {} -> skipped",
+                            url, chain, expected_chain
+                        );
+                        index += 1;
+                        attempts = 0;
+                        failures += 1;
+                        // The chain of an endpoint is not expected to change, so the
+                        // endpoints are not retried once all of them are mismatched
+                        if !chain.is_empty() {
+                            mismatched.insert(url.to_string());
+                        }
+                        if (config.oneshot && failures >= urls.len())
+                            || urls.iter().all(|url| mismatched.contains(url))
+                        {
+                            return Err(ScoutyError::Other(format!(
+                                "No endpoint connected to {} network",
+                                expected_chain
+                            )));
+                        }
+                        task::sleep(backoff.next_delay()).await;
+                        continue;
+                    }
+                }
+
                 let name = rpc.system_name().await.unwrap_or_default();
                 let version = rpc.system_version().await.unwrap_or_default();
                 let properties = rpc.system_properties().await.unwrap_or_default();
@@ -92,10 +214,6 @@ pub async fn create_or_await_substrate_node_client(
                         0
                     };
 
-                crypto::set_default_ss58_version(crypto::Ss58AddressFormat::custom(
-                    chain_prefix,
-                ));
-
                 let chain_token_symbol: ChainTokenSymbol =
                     if let Some(token_symbol) = properties.get("tokenSymbol") {
                         use serde_json::Value::String;
@@ -109,59 +227,289 @@ pub async fn create_or_await_substrate_node_client(
 
                 info!(
                     "Connected to {} network using {} * Substrate node {} v{}",
-                    chain, config.substrate_ws_url, name, version
+                    chain, url, name, version
                 );
 
+                // Runtime spec name is used as the primary discriminator of the
+                // runtime, since several chains share the same ss58 prefix
+                let spec_name = match rpc.state_get_runtime_version(None).await {
+                    Ok(runtime_version) => runtime_version
+                        .other
+                        .get("specName")
+                        .and_then(|spec_name| spec_name.as_str())
+                        .unwrap_or_default()
+                        .to_string(),
+                    Err(e) => {
+                        warn!("Runtime version not available: {}", e);
+                        String::default()
+                    }
+                };
+
+                let runtime = match SupportedRuntime::detect(
+                    &spec_name,
+                    &chain,
+                    chain_prefix,
+                    chain_token_symbol,
+                ) {
+                    Ok(runtime) => runtime,
+                    Err(e) if config.generic_runtime => {
+                        warn!("{} -> fallback to generic runtime", e);
+                        SupportedRuntime::Generic
+                    }
+                    Err(e) => {
+                        notify_unsupported_chain(&chain, &e).await;
+                        return Err(e);
+                    }
+                };
+
                 match create_substrate_client_from_rpc_client(rpc_client.clone()).await {
                     Ok(client) => {
-                        break (client, rpc, SupportedRuntime::from(chain_token_symbol));
+                        // Client has been built, so start over the backoff delays
+                        backoff.reset();
+                        break Ok((
+                            client,
+                            rpc,
+                            runtime,
+                            chain_prefix,
+                            url.to_string(),
+                            chain,
+                        ));
                     }
                     Err(e) => {
                         error!("{}", e);
-                        info!(
-                            "Awaiting for connection using {}",
-                            config.substrate_ws_url
-                        );
-                        thread::sleep(time::Duration::from_secs(6));
                     }
                 }
             }
             Err(e) => {
                 error!("{}", e);
-                info!("Awaiting for connection using {}", config.substrate_ws_url);
-                thread::sleep(time::Duration::from_secs(6));
             }
         }
+
+        // One-shot checks try every endpoint once instead of awaiting for a connection
+        failures += 1;
+        if config.oneshot {
+            if failures >= urls.len() {
+                return Err(ScoutyError::Other(format!(
+                    "Connection failed using {}",
+                    urls.join(",")
+                )));
+            }
+            index += 1;
+            continue;
+        }
+
+        // Rotate to the next endpoint after too many failed attempts
+        attempts += 1;
+        if urls.len() > 1 && attempts >= config.substrate_ws_url_max_attempts {
+            index += 1;
+            attempts = 0;
+            warn!(
+                "Endpoint {} failed too many times -> rotate to {}",
+                url,
+                urls[index % urls.len()]
+            );
+        }
+        let delay = backoff.next_delay();
+        info!(
+            "Awaiting for connection using {} * attempt {} -> next retry in {:.1}s",
+            urls[index % urls.len()],
+            backoff.attempt(),
+            delay.as_secs_f64()
+        );
+        task::sleep(delay).await;
+    }
+}
+
+/// Send a matrix warning to the room of the chain connected, so that the operator
+/// knows that 'scouty' has stopped
+async fn notify_unsupported_chain(chain: &str, error: &ScoutyError) {
+    let mut matrix: Matrix = Matrix::new();
+    if let Err(e) = matrix.authenticate(chain).await {
+        warn!("Matrix message skipped! {}", e);
+        return;
+    }
+    let message = format!("{} -> scouty stopped!", error);
+    let formatted_message = format!(
+        "<br/>⛔ {} -> <code>scouty</code> stopped!<br/><br/>",
+        error
+    );
+    if let Err(e) = matrix
+        .send_message(&message, &formatted_message, Severity::Critical)
+        .await
+    {
+        warn!("Matrix message skipped! {}", e);
     }
 }
 
+/// Returns the notifiers the messages are delivered to, matrix with the messages
+/// queued to be retried unless matrix is disabled, and the webhook, telegram, discord,
+/// pagerduty, email and events file if defined
+fn build_notifiers(messages: &MessageQueue) -> Vec<Box<dyn Notifier>> {
+    let mut notifiers: Vec<Box<dyn Notifier>> = Vec::new();
+    if !CONFIG.current().matrix_disabled {
+        notifiers.push(Box::new(messages.clone()));
+    }
+    if !CONFIG.current().webhook_url.is_empty() {
+        notifiers.push(Box::new(Webhook::new()));
+    }
+    if !CONFIG.current().telegram_bot_token.is_empty()
+        && !CONFIG.current().telegram_chat_id.is_empty()
+    {
+        notifiers.push(Box::new(Telegram::new()));
+    }
+    if !CONFIG.current().discord_webhook_url.is_empty() {
+        notifiers.push(Box::new(Discord::new()));
+    }
+    if !CONFIG.current().pagerduty_routing_key.is_empty() {
+        notifiers.push(Box::new(PagerDuty::new()));
+    }
+    if !CONFIG.current().smtp_host.is_empty() {
+        notifiers.push(Box::new(Smtp::new()));
+    }
+    if !CONFIG.current().events_file_path.is_empty() {
+        notifiers.push(Box::new(EventsFile::new()));
+    }
+    notifiers
+}
+
 pub struct Scouty {
     runtime: SupportedRuntime,
+    chain_prefix: ChainPrefix,
+    chain: String,
+    urls: Vec<String>,
+    url: String,
     client: OnlineClient<PolkadotConfig>,
     rpc: LegacyRpcMethods<PolkadotConfig>,
     matrix: Matrix,
+    messages: MessageQueue,
+    notifiers: Vec<Box<dyn Notifier>>,
+    hooks: HookRunner,
+    hook_results: Receiver<HookResult>,
+    status: SharedStatus,
+    /// Informational messages suppressed during quiet hours
+    quiet_digest: QuietDigest,
+    /// Identities of the stashes resolved in the active era
+    identities: IdentityCache,
+    /// Staking minimums read in the active era
+    minimums: MinimumsCache,
 }
 
 impl Scouty {
-    async fn new() -> Scouty {
-        let (client, rpc, runtime) =
-            create_or_await_substrate_node_client(CONFIG.clone()).await;
+    async fn new(
+        urls: Vec<String>,
+        expected_chain: Option<&str>,
+    ) -> Result<Scouty, ScoutyError> {
+        let (client, rpc, runtime, chain_prefix, url, chain) =
+            create_or_await_substrate_node_client(
+                Config::clone(&CONFIG.current()),
+                &urls,
+                expected_chain,
+            )
+            .await?;
+
+        // Stashes of another chain would be silently reported as never active
+        if !CONFIG.current().stash_prefix_mismatch_allowed {
+            let mismatches = CONFIG
+                .current()
+                .stash_prefix_mismatches(&runtime.to_string(), chain_prefix);
+            if !mismatches.is_empty() {
+                return Err(ScoutyError::ConfigInvalid(mismatches));
+            }
+        }
+
+        info!(
+            "Configuration derived -> chain: {}, runtime: {}, ss58 prefix: {}, url: {}",
+            chain, runtime, chain_prefix, url
+        );
 
         // Initialize matrix client
         let mut matrix: Matrix = Matrix::new();
-        matrix.authenticate(runtime).await.unwrap_or_else(|e| {
-            error!("{}", e);
-            Default::default()
-        });
+        matrix
+            .authenticate(&runtime.to_string())
+            .await
+            .unwrap_or_else(|e| {
+                error!("{}", e);
+                Default::default()
+            });
 
-        Scouty {
+        // Messages are only retried if the matrix client has been authenticated
+        let messages = MessageQueue::new(
+            matrix.clone(),
+            if matrix.is_authenticated() {
+                CONFIG.current().matrix_retry_queue_size
+            } else {
+                0
+            },
+            CONFIG.current().matrix_retry_max_age,
+        );
+        let notifiers = build_notifiers(&messages);
+
+        // The status is also exported to the prometheus metrics
+        let status = SharedStatus::default();
+        metrics::register_chain(&runtime.to_string(), &status);
+
+        // Initialize hooks runner
+        let (hooks, hook_results) = HookRunner::new(
+            runtime,
+            CONFIG.current().max_concurrent_hooks,
+            CONFIG.current().hook_overlap_skip,
+        );
+
+        let scouty = Scouty {
             runtime,
+            chain_prefix,
+            chain,
+            urls,
+            url,
             client,
             rpc,
             matrix,
+            messages,
+            notifiers,
+            hooks,
+            hook_results,
+            status,
+            quiet_digest: QuietDigest::default(),
+            identities: IdentityCache::default(),
+            minimums: MinimumsCache::default(),
+        };
+        scouty.log_stashes();
+        Ok(scouty)
+    }
+
+    pub fn runtime(&self) -> SupportedRuntime {
+        self.runtime
+    }
+
+    /// Log every stash monitored on the connected chain with its SS58 address rendered
+    /// in the prefix of the chain
+    fn log_stashes(&self) {
+        let config = CONFIG.current();
+        let stashes = config.chain_stashes(&self.runtime.to_string());
+        info!(
+            "Monitoring {} stash(es) on {} network",
+            stashes.len(),
+            self.runtime
+        );
+        for (i, stash_str) in stashes.iter().enumerate() {
+            if let Ok(stash) = AccountId32::from_str(stash_str) {
+                info!(
+                    "  {}. {} ({})",
+                    i + 1,
+                    self.to_ss58(stash),
+                    CONFIG.current().account_type(stash_str)
+                );
+            }
         }
     }
 
+    /// Returns the SS58 address of the account based on the connected chain
+    pub fn to_ss58(&self, acc: AccountId32) -> String {
+        convert_account_id(acc).to_ss58check_with_version(
+            crypto::Ss58AddressFormat::custom(self.chain_prefix),
+        )
+    }
+
     pub fn client(&self) -> &OnlineClient<PolkadotConfig> {
         &self.client
     }
@@ -175,86 +523,1551 @@ impl Scouty {
         &self.matrix
     }
 
+    /// Returns the chain status shared with the matrix bot commands
+    pub fn status(&self) -> &SharedStatus {
+        &self.status
+    }
+
+    /// Returns the identities of the stashes resolved in the active era
+    pub fn identities(&self) -> &IdentityCache {
+        &self.identities
+    }
+
+    /// Returns the staking minimums read in the active era
+    pub fn minimums(&self) -> &MinimumsCache {
+        &self.minimums
+    }
+
+    /// Identify the chain in the message if more than one is being monitored
+    fn with_chain_prefix(
+        &self,
+        message: &str,
+        formatted_message: &str,
+    ) -> (String, String) {
+        if CONFIG.current().is_multi_chain() {
+            (
+                format!("[{}] {}", self.runtime, message),
+                format!("<b>[{}]</b> {}", self.runtime, formatted_message),
+            )
+        } else {
+            (message.to_string(), formatted_message.to_string())
+        }
+    }
+
+    /// Returns true if alerts are muted with the matrix bot commands, events exempt
+    /// are only muted if `mute_all` is set
+    fn is_muted(&self, is_mute_exempt: bool) -> bool {
+        (CONFIG.current().mute_all || !is_mute_exempt)
+            && self.status.get().is_muted(Utc::now())
+    }
+
+    /// Returns true if informational messages are within the quiet hours defined
+    fn is_quiet_hours(&self) -> bool {
+        match CONFIG.current().quiet_hours() {
+            Ok(Some(quiet_hours)) => quiet_hours.contains(Utc::now()),
+            _ => false,
+        }
+    }
+
+    /// Send the message to every notifier configured, e.g. to the matrix room of the
+    /// severity, where messages that fail to be delivered are queued to be retried
     pub async fn send_message(
         &self,
         message: &str,
         formatted_message: &str,
+        severity: Severity,
     ) -> Result<(), ScoutyError> {
-        self.matrix()
-            .send_message(message, formatted_message)
-            .await?;
+        let event = match severity {
+            Severity::Critical => EVENT_ERROR,
+            Severity::Info => EVENT_MESSAGE,
+        };
+        let mut notification = Notification::new(
+            event,
+            &self.runtime.to_string(),
+            severity,
+            message,
+            formatted_message,
+        );
+        let status = self.status.get();
+        notification.session_index = status.session_index;
+        notification.era_index = status.era_index;
+        self.deliver_message(notification, false).await
+    }
+
+    /// Send the message, unless alerts are muted and the message is not exempt
+    async fn deliver_message(
+        &self,
+        mut notification: Notification,
+        is_mute_exempt: bool,
+    ) -> Result<(), ScoutyError> {
+        if CONFIG.current().dry_run {
+            info!(
+                "Dry run: [{}] matrix message -> {}",
+                self.runtime, notification.message
+            );
+            return Ok(());
+        }
+        // Nothing to deliver to, e.g. matrix is disabled and scouty only runs the hooks
+        if self.notifiers.is_empty() {
+            debug!(
+                "No notifiers: [{}] message -> {}",
+                self.runtime, notification.message
+            );
+            return Ok(());
+        }
+        if CONFIG.current().oneshot_quiet {
+            info!(
+                "Quiet: [{}] matrix message -> {}",
+                self.runtime, notification.message
+            );
+            return Ok(());
+        }
+        if notification.severity == Severity::Info && self.is_quiet_hours() {
+            info!(
+                "Quiet hours: [{}] matrix message -> {}",
+                self.runtime, notification.message
+            );
+            self.quiet_digest.add(&notification.event);
+            return Ok(());
+        }
+        if self.is_muted(is_mute_exempt) {
+            info!(
+                "Muted: [{}] matrix message -> {}",
+                self.runtime, notification.message
+            );
+            return Ok(());
+        }
+        let _in_flight = shutdown::InFlight::start();
+        let (message, formatted_message) = self
+            .with_chain_prefix(&notification.message, &notification.formatted_message);
+        notification.message = message;
+        notification.formatted_message = formatted_message;
+        notifier::notify_all(&self.notifiers, &notification).await
+    }
+
+    /// Send the report to matrix, unless the hook scripts have suppressed it
+    pub async fn send_report(&self, report: &Report) -> Result<(), ScoutyError> {
+        if report.is_suppressed().await {
+            if self.notifiers.is_empty() {
+                debug!("Matrix message suppressed by hook scripts");
+            } else {
+                info!("Matrix message suppressed by hook scripts");
+            }
+            return Ok(());
+        }
+        let notification = report.notification(&self.runtime.to_string());
+        // Critical notifications are counted as alerts of the stashes in the weekly report
+        if notification.severity == Severity::Critical
+            && !CONFIG.current().weekly_report.is_empty()
+        {
+            let stashes: Vec<String> = notification
+                .stashes
+                .iter()
+                .filter_map(|stash_str| AccountId32::from_str(stash_str).ok())
+                .map(|stash| self.to_ss58(stash))
+                .collect();
+            state::update(&self.runtime.to_string(), |state| {
+                for stash in stashes.iter() {
+                    state.weekly.track_alert(stash);
+                }
+            });
+        }
+        self.deliver_message(notification, report.is_mute_exempt())
+            .await
+    }
+
+    /// Run the hook script, in the background unless `hooks_blocking` is set
+    fn run_hook(
+        &self,
+        name: &str,
+        filename: &str,
+        args: Vec<String>,
+        payload: HookPayload,
+    ) -> Result<Hook, ScoutyError> {
+        let filename = self.hook_path(name, filename, &payload);
+        if CONFIG.current().dry_run {
+            let stash_desc = payload
+                .stash
+                .as_ref()
+                .map_or(String::new(), |stash| format!(" ({})", stash));
+            info!(
+                "Dry run: [{}] {}{} -> {} {}",
+                self.runtime,
+                name,
+                stash_desc,
+                filename,
+                args.join(" ")
+            );
+            return Ok(Hook {
+                name: name.to_string(),
+                filename_exists: Path::new(&filename).exists(),
+                filename,
+                stdout: vec![],
+                is_suppressed: false,
+                suppression: None,
+            });
+        }
+        if CONFIG.current().mute_hooks && self.is_muted(HOOKS_MUTE_EXEMPT.contains(&name))
+        {
+            info!("Muted: [{}] {} -> {} not run", self.runtime, name, filename);
+            return Ok(Hook {
+                name: name.to_string(),
+                filename_exists: Path::new(&filename).exists(),
+                filename,
+                stdout: vec![],
+                is_suppressed: false,
+                suppression: None,
+            });
+        }
+        // One-shot checks exit once done, so hooks can not run in the background
+        if CONFIG.current().hooks_blocking || CONFIG.current().oneshot {
+            return Hook::try_run(self.runtime, name, &filename, args, payload);
+        }
+        Ok(self.hooks.spawn(name, &filename, args, payload))
+    }
+
+    /// Run the hook script like `run_hook`, a hook script that fails is logged and
+    /// notified instead of interrupting the processing of the event
+    pub async fn try_run_hook(
+        &self,
+        name: &str,
+        filename: &str,
+        args: Vec<String>,
+        payload: HookPayload,
+    ) -> Hook {
+        let path = self.hook_path(name, filename, &payload);
+        match self.run_hook(name, filename, args, payload) {
+            Ok(hook) => hook,
+            Err(e) => {
+                self.notify_hook_failed(&e).await;
+                Hook {
+                    name: name.to_string(),
+                    filename_exists: Path::new(&path).exists(),
+                    filename: path,
+                    stdout: vec![],
+                    is_suppressed: false,
+                    suppression: None,
+                }
+            }
+        }
+    }
+
+    /// Log the hook script failure and send it to the matrix room
+    async fn notify_hook_failed(&self, e: &ScoutyError) {
+        error!("{}", e);
+        let message = format!("{}", e);
+        let formatted_message = format!("<br/>⚠️ {}<br/><br/>", e);
+        if let Err(e) = self
+            .send_message(&message, &formatted_message, Severity::Critical)
+            .await
+        {
+            warn!("Matrix message skipped! {}", e);
+        }
+    }
+
+    /// Returns the path of the hook script run for the payload given
+    fn hook_path(&self, name: &str, filename: &str, payload: &HookPayload) -> String {
+        let filename = self.chain_hook_path(name, filename);
+        // Validator hooks might run a different script for every stash
+        match &payload.stash {
+            Some(stash) => Hook::resolve_path(&filename, stash),
+            None => filename,
+        }
+    }
+
+    /// Returns the hook script discovered in the hooks dir of the connected chain, if
+    /// defined, otherwise the hook script defined for every chain
+    fn chain_hook_path(&self, name: &str, filename: &str) -> String {
+        CONFIG
+            .current()
+            .chain_hooks_dir(&self.runtime.to_string())
+            .and_then(|hooks_dir| HooksDir::discover(hooks_dir).ok())
+            .and_then(|hooks_dir| hooks_dir.discovered.get(name).cloned())
+            .unwrap_or_else(|| filename.to_string())
+    }
+
+    /// Verify the hook script of every stash predefined, if hooks strict is set the
+    /// hooks that are not defined, not a file or not executable are returned as errors,
+    /// otherwise they are only logged as warnings
+    fn verify_hook(&self, name: &str, filename: &str) -> Result<(), ScoutyError> {
+        let mut filenames = Vec::new();
+        if !filename.contains(HOOK_STASH_PLACEHOLDER) {
+            filenames.push((name.to_string(), filename.to_string()));
+        } else {
+            for stash_str in CONFIG
+                .current()
+                .chain_stashes(&self.runtime.to_string())
+                .iter()
+            {
+                match AccountId32::from_str(stash_str) {
+                    Ok(stash) => {
+                        let stash = self.to_ss58(stash);
+                        filenames.push((
+                            format!("{} ({})", name, stash),
+                            Hook::resolve_path(filename, &stash),
+                        ));
+                    }
+                    Err(e) => {
+                        warn!("Invalid SS58 format account: {} error: {:?}", stash_str, e)
+                    }
+                }
+            }
+        }
+
+        let mut errors = Vec::new();
+        for (name, filename) in filenames {
+            if let Err(e) = Hook::verify(&name, &filename) {
+                // Hooks without a filename configured are not considered broken
+                if CONFIG.current().hooks_strict && !filename.is_empty() {
+                    errors.push(format!("{}", e));
+                } else {
+                    warn!("{}", e);
+                }
+            }
+        }
+        if !errors.is_empty() {
+            return Err(ScoutyError::HooksVerificationFailed(errors));
+        }
         Ok(())
     }
 
+    /// Verify all the hook scripts and return every broken hook at once
+    fn verify_hooks(&self) -> Result<(), ScoutyError> {
+        let mut config = Config::clone(&CONFIG.current());
+        let mut errors = Vec::new();
+
+        let hooks_dirs: Vec<String> = vec![
+            Some(config.hooks_dir.as_str()).filter(|hooks_dir| !hooks_dir.is_empty()),
+            config.chain_hooks_dir(&self.runtime.to_string()),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|hooks_dir| hooks_dir.to_string())
+        .collect();
+        for hooks_dir_path in hooks_dirs.iter() {
+            match HooksDir::discover(hooks_dir_path) {
+                Ok(hooks_dir) => {
+                    for (name, filename) in hooks_dir.discovered.iter() {
+                        info!(
+                            "Hook script - {} - discovered in hooks dir ({})",
+                            name, filename
+                        );
+                    }
+                    for file_name in hooks_dir.unknown.iter() {
+                        warn!(
+                            "Hooks dir ({}) file {} does not map to any known hook or is a duplicate",
+                            hooks_dir_path, file_name
+                        );
+                    }
+                }
+                Err(e) => {
+                    let message = format!(
+                        "Hooks dir ({}) could not be read -> {}",
+                        hooks_dir_path, e
+                    );
+                    warn!("{}", message);
+                    if config.hooks_strict {
+                        errors.push(message);
+                    }
+                }
+            }
+        }
+
+        for (name, filename) in config.hook_paths_mut() {
+            if let Err(ScoutyError::HooksVerificationFailed(e)) =
+                self.verify_hook(name, &self.chain_hook_path(name, filename))
+            {
+                errors.extend(e);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(ScoutyError::HooksVerificationFailed(errors));
+        }
+        Ok(())
+    }
+
+    /// Notify the output of the hook scripts run in the background
+    async fn report_hook_results(&self) -> Result<(), ScoutyError> {
+        while let Ok(result) = self.hook_results.recv().await {
+            match result {
+                Ok(hook) => {
+                    let lines = hook.matrix_lines();
+                    if lines.is_empty() || hook.is_suppressed {
+                        continue;
+                    }
+                    let message = format!("{} -> {}", hook.name, lines.join("\n"));
+                    let formatted_message = format!(
+                        "<br/>🪝 <code>{}</code><br/>{}<br/><br/>",
+                        hook.filename,
+                        lines
+                            .iter()
+                            .map(|line| format!("‣ {}", line))
+                            .collect::<Vec<String>>()
+                            .join("<br/>")
+                    );
+                    if let Err(e) = self
+                        .send_message(&message, &formatted_message, Severity::Info)
+                        .await
+                    {
+                        warn!("Matrix message skipped! {}", e);
+                    }
+                }
+                Err(e) => self.notify_hook_failed(&e).await,
+            }
+        }
+        Err(ScoutyError::SubscriptionFinished)
+    }
+
     /// Spawn and restart subscription on error
     pub fn subscribe() {
         spawn_and_restart_subscription_on_error();
     }
 
+    /// Run the checks of a new session once for every chain, print the results to
+    /// stdout and exit with the worst exit code of the checks
+    pub fn check() {
+        stashes::listen();
+        let tasks: Vec<_> = CONFIG
+            .current()
+            .substrate_ws_urls_by_chain()
+            .into_iter()
+            .map(check_chain)
+            .collect();
+        let checks = task::block_on(future::join_all(tasks));
+        println!("{}", check::render(&checks, CONFIG.current().oneshot_json));
+        process::exit(check::exit_code(&checks));
+    }
+
+    /// Print the fully-resolved configuration with the secrets redacted, followed by the
+    /// values derived from the endpoints connected, e.g. the chain and ss58 prefix
+    pub fn print_config() {
+        for line in CONFIG.current().dump() {
+            println!("{}", line);
+        }
+        let tasks: Vec<_> = CONFIG
+            .current()
+            .substrate_ws_urls_by_chain()
+            .into_iter()
+            .map(derived_config)
+            .collect();
+        for lines in task::block_on(future::join_all(tasks)) {
+            for line in lines {
+                println!("{}", line);
+            }
+        }
+        process::exit(0);
+    }
+
+    /// Send a test message to the matrix rooms of every chain, print the response of
+    /// the homeserver for each room and exit with 1 if any room failed
+    pub fn test_matrix() {
+        let tasks: Vec<_> = CONFIG
+            .current()
+            .substrate_ws_urls_by_chain()
+            .into_iter()
+            .map(test_matrix_chain)
+            .collect();
+        let results = task::block_on(future::join_all(tasks));
+        let (succeeded, total) =
+            results.iter().fold((0, 0), |(s, t), (succeeded, total)| {
+                (s + succeeded, t + total)
+            });
+        println!("{} of {} matrix rooms succeeded", succeeded, total);
+        process::exit(if total > 0 && succeeded == total {
+            0
+        } else {
+            1
+        });
+    }
+
+    /// Run the hook script configured for the hook defined with a synthetic event, the
+    /// path is resolved as for a real event, and exit with the exit code of the script
+    pub fn test_hook() {
+        let name = match hooks::hook_by_id(&CONFIG.current().test_hook) {
+            Some(name) => name,
+            None => {
+                println!(
+                    "Hook {} is not known, use one of: {}",
+                    CONFIG.current().test_hook,
+                    hooks::HOOK_SCRIPT_NAMES
+                        .iter()
+                        .map(|(_, id)| *id)
+                        .collect::<Vec<&str>>()
+                        .join(", ")
+                );
+                process::exit(1);
+            }
+        };
+        let event = match SyntheticEvent::with_overrides(&CONFIG.current().test_hook_args)
+        {
+            Ok(event) => event,
+            Err(e) => {
+                println!("{}", e);
+                process::exit(1);
+            }
+        };
+        let mut config = Config::clone(&CONFIG.current());
+        let filename = config
+            .hook_paths_mut()
+            .into_iter()
+            .find(|(hook, _)| *hook == name)
+            .map(|(_, filename)| Hook::resolve_path(filename, &event.stash))
+            .unwrap_or_default();
+        if !Path::new(&filename).exists() {
+            println!(
+                "Hook script - {} - filename ({}) not defined",
+                name, filename
+            );
+            process::exit(1);
+        }
+        println!(
+            "Hook script - {} - run with: {}",
+            name,
+            event.args().join(" ")
+        );
+        match Hook::try_run(event.chain, name, &filename, event.args(), event.payload()) {
+            Ok(hook) if hook.is_suppressed => {
+                println!(
+                    "Hook script - {} - exit code 10 -> matrix message suppressed",
+                    name
+                );
+                process::exit(10);
+            }
+            Ok(_) => {
+                println!("Hook script - {} - exit code 0 -> success", name);
+                process::exit(0);
+            }
+            Err(ScoutyError::HookFailed(e, exit_code)) => {
+                println!("{}", e);
+                process::exit(exit_code.unwrap_or(1));
+            }
+            Err(e) => {
+                println!("{}", e);
+                process::exit(1);
+            }
+        }
+    }
+
+    /// Returns the SS58 addresses of the stashes predefined based on the connected chain
+    fn stashes(&self) -> Vec<String> {
+        CONFIG
+            .current()
+            .chain_stashes(&self.runtime.to_string())
+            .iter()
+            .filter_map(|stash_str| AccountId32::from_str(stash_str).ok())
+            .map(|stash| self.to_ss58(stash))
+            .collect()
+    }
+
+    async fn run_session_checks_once(&self) -> Result<(), ScoutyError> {
+        // Verify if hooks scripts are available
+        self.verify_hooks()?;
+
+        match self.runtime {
+            SupportedRuntime::Polkadot => polkadot::run_session_checks_once(self).await,
+            SupportedRuntime::Kusama => kusama::run_session_checks_once(self).await,
+            SupportedRuntime::Generic => generic::run_session_checks_once(self).await,
+            _ => self.runtime.check_available(&self.chain).map(|_| ()),
+        }
+    }
+
     async fn subscribe_on_chain_events(&self) -> Result<(), ScoutyError> {
-        let config = CONFIG.clone();
+        let config = CONFIG.current();
 
         // Verify if hooks scripts are available
-        Hook::exists(HOOK_INIT, &config.hook_init_path);
-        Hook::exists(HOOK_NEW_SESSION, &config.hook_new_session_path);
-        Hook::exists(HOOK_NEW_ERA, &config.hook_new_era_path);
-        Hook::exists(
-            HOOK_VALIDATOR_STARTS_ACTIVE_NEXT_ERA,
-            &config.hook_validator_starts_active_next_era_path,
-        );
-        Hook::exists(
-            HOOK_VALIDATOR_STARTS_INACTIVE_NEXT_ERA,
-            &config.hook_validator_starts_inactive_next_era_path,
+        self.verify_hooks()?;
+
+        if !config.node_syncing_wait_disabled {
+            self.await_node_synced().await?;
+        }
+
+        let subscription = async {
+            match self.runtime {
+                SupportedRuntime::Polkadot => {
+                    polkadot::init_and_subscribe_on_chain_events(self).await
+                }
+                SupportedRuntime::Kusama => {
+                    kusama::init_and_subscribe_on_chain_events(self).await
+                }
+                SupportedRuntime::Generic => {
+                    generic::init_and_subscribe_on_chain_events(self).await
+                }
+                // SupportedRuntime::Westend => {
+                //     westend::init_and_subscribe_on_chain_events(self).await
+                // }
+                _ => self.runtime.check_available(&self.chain).map(|_| ()),
+            }
+        };
+
+        // Watch runtime upgrades alongside the subscription, so that the subscription
+        // is restarted with the refreshed metadata
+        let subscription =
+            select_first(subscription, self.subscribe_on_runtime_upgrades());
+
+        // Notify the output of the hooks run in the background alongside the subscription
+        let subscription = select_first(subscription, self.report_hook_results());
+
+        // Reply to the matrix bot commands alongside the subscription
+        let subscription = async {
+            if !config.matrix_commands_enabled || !self.matrix().is_authenticated() {
+                return subscription.await;
+            }
+            select_first(subscription, self.subscribe_on_matrix_commands()).await
+        };
+
+        // Announce the configuration reloads alongside the subscription
+        let subscription = async {
+            if !config.config_reload_announce {
+                return subscription.await;
+            }
+            select_first(subscription, self.subscribe_on_config_reloads()).await
+        };
+
+        // Announce the changes of the stashes fetched or read alongside the subscription
+        let subscription = async {
+            if config.stashes_url.is_empty() && config.stashes_path.is_empty() {
+                return subscription.await;
+            }
+            select_first(subscription, self.subscribe_on_stashes_changes()).await
+        };
+
+        // Send the digest of the quiet hours alongside the subscription
+        let subscription = async {
+            if config.quiet_hours.is_empty() {
+                return subscription.await;
+            }
+            select_first(subscription, self.subscribe_on_quiet_hours()).await
+        };
+
+        // Accumulate the uptime and send the weekly report alongside the subscription
+        let subscription = async {
+            if config.weekly_report.is_empty() {
+                return subscription.await;
+            }
+            select_first(subscription, self.subscribe_on_weekly_report()).await
+        };
+
+        // Track finality lag alongside the subscription
+        let subscription = async {
+            if config.finality_max_lag == 0 {
+                return subscription.await;
+            }
+            select_first(subscription, self.subscribe_on_finality_lag()).await
+        };
+
+        if config.health_check_interval == 0 {
+            return subscription.await;
+        }
+
+        // Run node health checks alongside the subscription, if the subscription
+        // finishes the health checks are dropped as well
+        select_first(subscription, self.run_health_checks()).await
+    }
+
+    /// Send a message with the result of every configuration reload
+    async fn subscribe_on_config_reloads(&self) -> Result<(), ScoutyError> {
+        let mut generation = reload::generation();
+        loop {
+            task::sleep(time::Duration::from_secs(1)).await;
+            if reload::generation() == generation {
+                continue;
+            }
+            generation = reload::generation();
+            let (message, formatted_message) = reload::last_message();
+            if let Err(e) = self
+                .send_message(&message, &formatted_message, Severity::Info)
+                .await
+            {
+                warn!("Matrix message skipped! {}", e);
+            }
+        }
+    }
+
+    /// Stop tracking the stashes removed and send a message with the stashes added and
+    /// removed on every change of the stashes fetched or read from the stashes file
+    async fn subscribe_on_stashes_changes(&self) -> Result<(), ScoutyError> {
+        let mut generation = stashes::generation();
+        loop {
+            task::sleep(time::Duration::from_secs(1)).await;
+            if stashes::generation() == generation {
+                continue;
+            }
+            generation = stashes::generation();
+            let monitored = self.stashes();
+            self.status.update(|status| {
+                status.stashes.retain(|stash, _| monitored.contains(stash))
+            });
+            let (message, formatted_message) = stashes::last_changes().message();
+            if let Err(e) = self
+                .send_message(&message, &formatted_message, Severity::Info)
+                .await
+            {
+                warn!("Matrix message skipped! {}", e);
+            }
+        }
+    }
+
+    /// Send a digest of the informational messages suppressed once the quiet hours end
+    async fn subscribe_on_quiet_hours(&self) -> Result<(), ScoutyError> {
+        loop {
+            task::sleep(time::Duration::from_secs(60)).await;
+            let quiet_hours = match CONFIG.current().quiet_hours() {
+                Ok(Some(quiet_hours)) => quiet_hours,
+                _ => continue,
+            };
+            if quiet_hours.contains(Utc::now()) {
+                continue;
+            }
+            let suppressed = self.quiet_digest.take();
+            if suppressed.is_empty() {
+                continue;
+            }
+            let (message, formatted_message) =
+                QuietDigest::message(&quiet_hours, &suppressed);
+            if let Err(e) = self
+                .send_message(&message, &formatted_message, Severity::Info)
+                .await
+            {
+                warn!("Matrix message skipped! {}", e);
+            }
+        }
+    }
+
+    /// Accumulate the uptime of scouty and send the weekly report once it is due. The
+    /// first report is only due after a full period, so that it never covers less than
+    /// the stats accumulated since the weekly report was enabled.
+    async fn subscribe_on_weekly_report(&self) -> Result<(), ScoutyError> {
+        let chain = self.runtime.to_string();
+        loop {
+            task::sleep(time::Duration::from_secs(WEEKLY_REPORT_INTERVAL)).await;
+            let schedule = match CONFIG.current().weekly_report() {
+                Ok(Some(schedule)) => schedule,
+                _ => continue,
+            };
+            let now = Utc::now();
+            let due = schedule.last_due(now).timestamp();
+            let mut stats = None;
+            state::update(&chain, |state| {
+                state.weekly.track_uptime(now, WEEKLY_REPORT_INTERVAL);
+                match state.weekly_report_at {
+                    Some(weekly_report_at) if weekly_report_at < due => {
+                        stats = Some(state.weekly.take(now));
+                        state.weekly_report_at = Some(due);
+                    }
+                    Some(_) => {}
+                    None => state.weekly_report_at = Some(due),
+                }
+            });
+            if let Some(stats) = stats {
+                if let Err(e) = self.send_weekly_report(stats, now).await {
+                    warn!("Weekly report skipped! {}", e);
+                }
+            }
+        }
+    }
+
+    /// Send the weekly report and run the weekly report hook for every stash
+    async fn send_weekly_report(
+        &self,
+        mut stats: WeeklyStats,
+        now: chrono::DateTime<Utc>,
+    ) -> Result<(), ScoutyError> {
+        let schedule = match CONFIG.current().weekly_report() {
+            Ok(Some(schedule)) => schedule,
+            _ => return Ok(()),
+        };
+        let network = Network::load(self.rpc()).await?;
+        let status = self.status.get();
+        // The validator stashes predefined are always reported, even without eras paid
+        for stash in CONFIG
+            .current()
+            .validator_stashes(&self.runtime.to_string())
+            .iter()
+            .filter_map(|stash_str| AccountId32::from_str(stash_str).ok())
+        {
+            stats.stashes.entry(self.to_ss58(stash)).or_default();
+        }
+        let names: BTreeMap<String, String> = status
+            .stashes
+            .iter()
+            .map(|(stash, stash_status)| {
+                (stash.to_string(), stash_status.name.to_string())
+            })
+            .collect();
+        let uptime = stats.uptime_percentage(now);
+        let since = stats.started_at(now).timestamp();
+
+        let mut lines = Vec::new();
+        for (stash, stash_stats) in stats.stashes.iter() {
+            let stash_status = status.stashes.get(stash).cloned().unwrap_or_default();
+            let average_rank = stash_stats
+                .average_rank()
+                .map_or("-".to_string(), |rank| format!("{:.1}", rank));
+            let mut args = vec![
+                stash.to_string(),
+                stash_status.name.to_string(),
+                stash_stats.eras_active.to_string(),
+                stash_stats.eras.to_string(),
+                stash_stats.points.to_string(),
+                stash_stats.reward.to_string(),
+                average_rank.to_string(),
+                stash_stats.alerts.to_string(),
+                format!("{:.1}", uptime),
+            ];
+            let mut payload = HookPayload::new()
+                .validator(
+                    stash.to_string(),
+                    stash_status.name.to_string(),
+                    "-".to_string(),
+                    stash_status.is_active,
+                    stash_status.is_queued,
+                )
+                .with("eras_active", stash_stats.eras_active)
+                .with("eras", stash_stats.eras)
+                .with("era_points", stash_stats.points)
+                .with("estimated_reward", stash_stats.reward.to_string())
+                .with("average_rank", stash_stats.average_rank())
+                .with("alerts", stash_stats.alerts)
+                .with("uptime", uptime)
+                .with("since", since)
+                .with("until", now.timestamp());
+            if CONFIG.current().expose_network || CONFIG.current().expose_all {
+                args.push(network.name.to_string());
+                args.push(network.token_symbol.to_string());
+                args.push(network.token_decimals.to_string());
+                payload = payload.network(&network);
+            } else {
+                args.push("-".to_string());
+                args.push("-".to_string());
+                args.push("-".to_string());
+            }
+            args.push(since.to_string());
+            args.push(now.timestamp().to_string());
+
+            let hook = self
+                .try_run_hook(
+                    HOOK_WEEKLY_REPORT,
+                    &CONFIG.current().hook_weekly_report_path,
+                    args,
+                    payload,
+                )
+                .await;
+            lines.extend(hook.matrix_lines());
+        }
+
+        let (mut message, mut formatted_message) =
+            stats.message(&schedule, &names, &network, now);
+        for line in lines {
+            message.push_str(&format!("\n{}", line));
+            formatted_message.push_str(&format!("‣ {}<br/>", line));
+        }
+        if let Err(e) = self
+            .send_message(&message, &formatted_message, Severity::Info)
+            .await
+        {
+            warn!("Matrix message skipped! {}", e);
+        }
+        Ok(())
+    }
+
+    /// Reply to the commands sent to the matrix rooms by the users allowed, commands
+    /// from other users are ignored silently
+    async fn subscribe_on_matrix_commands(&self) -> Result<(), ScoutyError> {
+        let config = CONFIG.current();
+        let allowlist = config.matrix_commands_allowlist();
+        let mut backoff = Backoff::new(
+            config.reconnect_initial_interval,
+            config.reconnect_max_interval,
         );
-        Hook::exists(HOOK_VALIDATOR_SLASHED, &config.hook_validator_slashed_path);
-        Hook::exists(HOOK_VALIDATOR_CHILLED, &config.hook_validator_chilled_path);
-        Hook::exists(HOOK_VALIDATOR_OFFLINE, &config.hook_validator_offline_path);
-        Hook::exists(
-            HOOK_REFERENDA_SUBMITTED,
-            &config.hook_referenda_submitted_path,
+        let mut since: Option<String> = None;
+        loop {
+            let messages = match self
+                .matrix()
+                .sync(since.as_deref(), MATRIX_SYNC_TIMEOUT)
+                .await
+            {
+                Ok((next_batch, messages)) => {
+                    backoff.reset();
+                    since = Some(next_batch);
+                    messages
+                }
+                Err(e) => {
+                    let delay = backoff.next_delay();
+                    warn!(
+                        "Matrix sync failed! {} -> next sync in {:.1}s",
+                        e,
+                        delay.as_secs_f64()
+                    );
+                    task::sleep(delay).await;
+                    continue;
+                }
+            };
+            // Announce once the alerts muted are notified again
+            let muted_until = self.status.get().muted_until;
+            if muted_until.is_some_and(|until| until <= Utc::now()) {
+                self.status.update(|status| status.muted_until = None);
+                let message = "Mute expired -> alerts are notified again";
+                let formatted_message =
+                    "<br/>🔔 Mute expired -> alerts are notified again<br/><br/>";
+                if let Err(e) = self
+                    .send_message(message, formatted_message, Severity::Info)
+                    .await
+                {
+                    warn!("Matrix message skipped! {}", e);
+                }
+            }
+            for message in messages {
+                let command = match Command::parse(&message.body) {
+                    Some(command) => command,
+                    None => continue,
+                };
+                if !commands::is_allowed(&message.sender, &allowlist) {
+                    debug!("Matrix command from {} ignored", message.sender);
+                    continue;
+                }
+                info!("Matrix command {:?} from {}", command, message.sender);
+                let report =
+                    command.run(&self.chain, &self.url, &self.status, Utc::now());
+                let (reply, formatted_reply) = self
+                    .with_chain_prefix(&report.message(), &report.formatted_message());
+                if let Err(e) = self
+                    .matrix()
+                    .send_message_to_room(&message.room_id, &reply, &formatted_reply)
+                    .await
+                {
+                    warn!("Matrix command reply not sent! {}", e);
+                }
+            }
+        }
+    }
+
+    /// Track the lag between best and finalized blocks and notify on state transitions
+    async fn subscribe_on_finality_lag(&self) -> Result<(), ScoutyError> {
+        let config = CONFIG.current();
+        let mut finality_records = FinalityRecords::new(
+            config.finality_max_lag,
+            config.finality_max_lag_duration,
         );
 
-        match self.runtime {
-            SupportedRuntime::Polkadot => {
-                polkadot::init_and_subscribe_on_chain_events(self).await
+        let best_blocks = self
+            .client()
+            .blocks()
+            .subscribe_best()
+            .await?
+            .map(|block| block.map(|block| (false, block.number(), block.hash())));
+        let finalized_blocks = self
+            .client()
+            .blocks()
+            .subscribe_finalized()
+            .await?
+            .map(|block| block.map(|block| (true, block.number(), block.hash())));
+        let mut blocks = stream::select(best_blocks, finalized_blocks);
+
+        let mut best_block = (0, H256::default());
+        let mut finalized_block = (0, H256::default());
+        while let Some(block) = blocks.next().await {
+            let (is_finalized, block_number, block_hash) = block?;
+            if is_finalized {
+                finalized_block = (block_number, block_hash);
+            } else {
+                best_block = (block_number, block_hash);
             }
-            SupportedRuntime::Kusama => {
-                kusama::init_and_subscribe_on_chain_events(self).await
+            // Wait for both subscriptions to deliver their first block
+            if best_block.0 == 0 || finalized_block.0 == 0 {
+                continue;
+            }
+
+            let now = time::SystemTime::now()
+                .duration_since(time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+
+            if let Some(status) =
+                finality_records.update(best_block.0, finalized_block.0, now)
+            {
+                // Only stream errors stop the subscription, notification and hook errors
+                // are logged so that the subscriptions alongside keep running
+                if let Err(e) = self
+                    .try_run_finality_stalled_hook(status, best_block, finalized_block)
+                    .await
+                {
+                    warn!("Matrix message skipped! {}", e);
+                }
             }
-            // SupportedRuntime::Westend => {
-            //     westend::init_and_subscribe_on_chain_events(self).await
-            // }
-            _ => unreachable!(),
         }
+        Err(ScoutyError::SubscriptionFinished)
+    }
+
+    async fn try_run_finality_stalled_hook(
+        &self,
+        status: FinalityStatus,
+        best_block: (u32, H256),
+        finalized_block: (u32, H256),
+    ) -> Result<(), ScoutyError> {
+        let config = CONFIG.current();
+
+        let network = Network::load(self.rpc()).await?;
+        debug!("network {:?}", network);
+
+        let best_block_hash = format!("{:?}", best_block.1);
+        let finalized_block_hash = format!("{:?}", finalized_block.1);
+
+        // Try HOOK_FINALITY_STALLED
+        let hook = if status.is_stalled() {
+            let mut args = vec![
+                best_block.0.to_string(),
+                best_block_hash.to_string(),
+                finalized_block.0.to_string(),
+                finalized_block_hash.to_string(),
+            ];
+
+            let mut payload = HookPayload::new()
+                .with("best_block_number", best_block.0)
+                .with("best_block_hash", best_block_hash.to_string())
+                .with("finalized_block_number", finalized_block.0)
+                .with("finalized_block_hash", finalized_block_hash.to_string());
+
+            if config.expose_network || config.expose_all {
+                args.push(network.name.to_string());
+                args.push(network.token_symbol.to_string());
+                args.push(network.token_decimals.to_string());
+                payload = payload.network(&network);
+            } else {
+                args.push("-".to_string());
+                args.push("-".to_string());
+                args.push("-".to_string());
+            }
+
+            self.try_run_hook(
+                HOOK_FINALITY_STALLED,
+                &config.hook_finality_stalled_path,
+                args,
+                payload,
+            )
+            .await
+        } else {
+            Hook::default()
+        };
+
+        // Prepare notification report
+        let data = RawData {
+            network,
+            finality: Finality {
+                best_block_number: best_block.0,
+                best_block_hash,
+                finalized_block_number: finalized_block.0,
+                finalized_block_hash,
+                status,
+                hook,
+            },
+            section: Section::Finality,
+            ..Default::default()
+        };
+
+        let report = Report::from(data);
+        self.send_report(&report).await?;
+
+        Ok(())
+    }
+
+    /// Refresh the client metadata on every runtime upgrade
+    async fn subscribe_on_runtime_upgrades(&self) -> Result<(), ScoutyError> {
+        let updater = self.client().updater();
+        let mut updates = updater.runtime_updates().await?;
+        while let Some(update) = updates.next().await {
+            let update = update?;
+            let spec_version = update.runtime_version().spec_version;
+            let previous_spec_version = self.client().runtime_version().spec_version;
+            // The current runtime version is always sent when subscribing, skip it
+            if updater.apply_update(update).is_err() {
+                continue;
+            }
+            info!(
+                "Runtime upgraded from {} to {}",
+                previous_spec_version, spec_version
+            );
+
+            // Notification errors must not stop the runtime upgrade from being validated
+            if let Err(e) = self
+                .try_run_runtime_upgrade_hook(previous_spec_version, spec_version)
+                .await
+            {
+                warn!("Runtime upgrade hook skipped! {}", e);
+            }
+
+            let validation = match self.runtime {
+                SupportedRuntime::Polkadot => polkadot::validate_codegen(self),
+                SupportedRuntime::Kusama => kusama::validate_codegen(self),
+                SupportedRuntime::Generic => generic::validate_codegen(self),
+                _ => self.runtime.check_available(&self.chain).map(|_| ()),
+            };
+            if let Err(e) = validation {
+                error!("{}", e);
+                let e = ScoutyError::IncompatibleRuntime(spec_version);
+                let message = format!("{}", e);
+                let formatted_message = format!("<br/>⛔ {}<br/><br/>", e);
+                self.send_message(&message, &formatted_message, Severity::Critical)
+                    .await?;
+                return Err(e);
+            }
+
+            return Err(ScoutyError::RuntimeUpgraded(spec_version));
+        }
+        Err(ScoutyError::SubscriptionFinished)
+    }
+
+    async fn try_run_runtime_upgrade_hook(
+        &self,
+        previous_spec_version: u32,
+        spec_version: u32,
+    ) -> Result<(), ScoutyError> {
+        let config = CONFIG.current();
+
+        let network = Network::load(self.rpc()).await?;
+        debug!("network {:?}", network);
+
+        let block_number = self
+            .rpc()
+            .chain_get_header(None)
+            .await?
+            .map(|header| header.number)
+            .unwrap_or_default();
+
+        // Try HOOK_RUNTIME_UPGRADE
+        let mut args = vec![
+            previous_spec_version.to_string(),
+            spec_version.to_string(),
+            block_number.to_string(),
+        ];
+
+        let mut payload = HookPayload::new()
+            .with("previous_spec_version", previous_spec_version)
+            .with("spec_version", spec_version)
+            .with("block_number", block_number);
+
+        if config.expose_network || config.expose_all {
+            args.push(network.name.to_string());
+            args.push(network.token_symbol.to_string());
+            args.push(network.token_decimals.to_string());
+            payload = payload.network(&network);
+        } else {
+            args.push("-".to_string());
+            args.push("-".to_string());
+            args.push("-".to_string());
+        }
+
+        let hook = self
+            .try_run_hook(
+                HOOK_RUNTIME_UPGRADE,
+                &config.hook_runtime_upgrade_path,
+                args,
+                payload,
+            )
+            .await;
+
+        // Prepare notification report
+        let data = RawData {
+            network,
+            runtime_upgrade: RuntimeUpgrade {
+                previous_spec_version,
+                spec_version,
+                block_number,
+                hook,
+            },
+            section: Section::RuntimeUpgrade,
+            ..Default::default()
+        };
+
+        let report = Report::from(data);
+        self.send_report(&report).await?;
+
+        Ok(())
+    }
+
+    /// Wait until the substrate node is no longer syncing
+    async fn await_node_synced(&self) -> Result<(), ScoutyError> {
+        loop {
+            let health = self.rpc().system_health().await?;
+            if !health.is_syncing {
+                return Ok(());
+            }
+            let best = self
+                .rpc()
+                .chain_get_header(None)
+                .await?
+                .map(|header| header.number)
+                .unwrap_or_default();
+            info!("Node syncing, current best #{}", best);
+            task::sleep(time::Duration::from_secs(NODE_SYNCING_WAIT_INTERVAL)).await;
+        }
+    }
+
+    /// Periodically verify the substrate node health and notify on state transitions
+    async fn run_health_checks(&self) -> Result<(), ScoutyError> {
+        let config = CONFIG.current();
+        let mut health_records = HealthRecords::new(
+            config.health_min_peers,
+            config.health_syncing_grace_period,
+        );
+        loop {
+            task::sleep(time::Duration::from_secs(config.health_check_interval)).await;
+
+            let health = match self.rpc().system_health().await {
+                Ok(health) => health,
+                Err(e) => {
+                    warn!("Node health check failed: {}", e);
+                    continue;
+                }
+            };
+            debug!("node health {:?}", health);
+
+            let now = time::SystemTime::now()
+                .duration_since(time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+
+            if let Some(status) = health_records.update(
+                health.peers,
+                health.is_syncing,
+                health.should_have_peers,
+                now,
+            ) {
+                // Notification errors must not stop the chain subscription that runs
+                // alongside the health checks
+                if let Err(e) = self
+                    .try_run_node_health_hook(status, health.peers, health.is_syncing)
+                    .await
+                {
+                    warn!("Matrix message skipped! {}", e);
+                }
+            }
+        }
+    }
+
+    async fn try_run_node_health_hook(
+        &self,
+        status: HealthStatus,
+        peers: usize,
+        is_syncing: bool,
+    ) -> Result<(), ScoutyError> {
+        let config = CONFIG.current();
+
+        let network = Network::load(self.rpc()).await?;
+        debug!("network {:?}", network);
+
+        // Try HOOK_NODE_UNHEALTHY
+        let hook = if !status.is_healthy() {
+            let mut args = vec![
+                self.url.to_string(),
+                peers.to_string(),
+                is_syncing.to_string(),
+                status.to_string(),
+            ];
+
+            let mut payload = HookPayload::new()
+                .with("url", self.url.to_string())
+                .with("peers", peers)
+                .with("is_syncing", is_syncing)
+                .with("status", status.to_string());
+
+            if config.expose_network || config.expose_all {
+                args.push(network.name.to_string());
+                args.push(network.token_symbol.to_string());
+                args.push(network.token_decimals.to_string());
+                payload = payload.network(&network);
+            } else {
+                args.push("-".to_string());
+                args.push("-".to_string());
+                args.push("-".to_string());
+            }
+
+            self.try_run_hook(
+                HOOK_NODE_UNHEALTHY,
+                &config.hook_node_unhealthy_path,
+                args,
+                payload,
+            )
+            .await
+        } else {
+            Hook::default()
+        };
+
+        // Prepare notification report
+        let data = RawData {
+            network,
+            health: Health {
+                url: self.url.to_string(),
+                peers,
+                is_syncing,
+                status,
+                hook,
+            },
+            section: Section::Health,
+            ..Default::default()
+        };
+
+        let report = Report::from(data);
+        self.send_report(&report).await?;
+
+        Ok(())
+    }
+}
+
+/// Run both futures and return the result of the first one to finish
+async fn select_first<A, B>(a: A, b: B) -> Result<(), ScoutyError>
+where
+    A: Future<Output = Result<(), ScoutyError>>,
+    B: Future<Output = Result<(), ScoutyError>>,
+{
+    match future::select(Box::pin(a), Box::pin(b)).await {
+        Either::Left((result, _)) => result,
+        Either::Right((result, _)) => result,
     }
 }
 
+/// Spawn one subscription for each chain, so that each chain keeps its own restart loop
 fn spawn_and_restart_subscription_on_error() {
-    let t = task::spawn(async {
-        let config = CONFIG.clone();
-        loop {
-            let c: Scouty = Scouty::new().await;
-            if let Err(e) = c.subscribe_on_chain_events().await {
-                match e {
-                    ScoutyError::SubscriptionFinished => warn!("{}", e),
-                    ScoutyError::MatrixError(_) => warn!("Matrix message skipped!"),
-                    _ => {
+    let config = CONFIG.current();
+    shutdown::listen();
+    reload::listen();
+    stashes::listen();
+    let tasks: Vec<_> = config
+        .substrate_ws_urls_by_chain()
+        .into_iter()
+        .map(|endpoints| task::spawn(restart_subscription_on_error(endpoints)))
+        .collect();
+    task::block_on(future::join_all(tasks));
+}
+
+/// Send a test message to the matrix rooms of a single chain and return the number of
+/// rooms that succeeded and the number of rooms tested. The chain connected is only
+/// needed to find the private room of the chain.
+async fn test_matrix_chain(endpoints: ChainEndpoints) -> (usize, usize) {
+    let mut config = Config::clone(&CONFIG.current());
+    config.oneshot = true;
+    let runtime = match create_or_await_substrate_node_client(
+        config,
+        &endpoints.urls,
+        endpoints.chain.as_deref(),
+    )
+    .await
+    {
+        Ok((_, _, runtime, _, _, _)) => runtime,
+        Err(e) => {
+            println!("{} -> matrix not tested: {}", endpoints.urls.join(","), e);
+            return (0, 1);
+        }
+    };
+    let mut matrix: Matrix = Matrix::new();
+    if let Err(e) = matrix.authenticate(&runtime.to_string()).await {
+        println!("[{}] Matrix authentication failed -> {}", runtime, e);
+        return (0, 1);
+    }
+    let (message, formatted_message) =
+        matrix::test_message(&matrix::hostname(), Utc::now());
+    let tests = match matrix
+        .send_test_messages(&message, &formatted_message)
+        .await
+    {
+        Ok(tests) => tests,
+        Err(e) => {
+            println!("[{}] Matrix test failed -> {}", runtime, e);
+            return (0, 1);
+        }
+    };
+    for test in tests.iter() {
+        match &test.result {
+            Ok(event_id) => {
+                println!("[{}] {} room -> sent: {}", runtime, test.target, event_id)
+            }
+            Err(e) => println!("[{}] {} room -> failed: {}", runtime, test.target, e),
+        }
+    }
+    let succeeded = tests.iter().filter(|test| test.result.is_ok()).count();
+    (succeeded, tests.len())
+}
+
+/// Returns the values derived from the endpoint connected of a single chain as
+/// `key=value` lines, e.g. the chain, the runtime and the ss58 prefix
+async fn derived_config(endpoints: ChainEndpoints) -> Vec<String> {
+    let mut config = Config::clone(&CONFIG.current());
+    config.oneshot = true;
+    match create_or_await_substrate_node_client(
+        config,
+        &endpoints.urls,
+        endpoints.chain.as_deref(),
+    )
+    .await
+    {
+        Ok((_, _, runtime, chain_prefix, url, chain)) => vec![
+            format!("derived_chain={}", chain),
+            format!("derived_runtime={}", runtime),
+            format!("derived_ss58_prefix={}", chain_prefix),
+            format!("derived_substrate_ws_url={}", url),
+        ],
+        Err(e) => vec![format!(
+            "derived_chain=unknown ({} not connected: {})",
+            endpoints.urls.join(","),
+            e
+        )],
+    }
+}
+
+/// Run the checks of a new session once for a single chain
+async fn check_chain(endpoints: ChainEndpoints) -> Check {
+    let url = endpoints.urls.join(",");
+    let c: Scouty = match Scouty::new(endpoints.urls, endpoints.chain.as_deref()).await {
+        Ok(c) => c,
+        Err(e) => {
+            error!("{}", e);
+            return Check::failed(&url, e);
+        }
+    };
+    let result = c.run_session_checks_once().await;
+    if let Err(e) = &result {
+        error!("{}", e);
+    }
+    Check::new(
+        &c.runtime.to_string(),
+        &c.stashes(),
+        &c.status.get(),
+        result.err(),
+    )
+}
+
+/// Subscribe on-chain events of a single chain and restart the subscription on error
+async fn restart_subscription_on_error(endpoints: ChainEndpoints) {
+    let config = CONFIG.current();
+    // Stop retrying if the chain connected is not supported or is not the chain the
+    // endpoints are defined for
+    let mut c: Scouty =
+        match Scouty::new(endpoints.urls, endpoints.chain.as_deref()).await {
+            Ok(c) => c,
+            Err(e) => {
+                error!("{}", e);
+                return;
+            }
+        };
+    while !shutdown::is_requested() {
+        // The subscription is only dropped on shutdown once any in-flight
+        // matrix message has been delivered
+        let result = select_first(c.subscribe_on_chain_events(), async {
+            shutdown::wait().await;
+            Ok(())
+        })
+        .await;
+        if let Err(e) = result {
+            // Runtime upgrades are expected, the subscription is just restarted
+            if !matches!(e, ScoutyError::RuntimeUpgraded(_)) {
+                healthcheck::ping_fail();
+            }
+            match e {
+                ScoutyError::SubscriptionFinished => {
+                    warn!("{}", e);
+                    shutdown::sleep(time::Duration::from_secs(1)).await;
+                }
+                ScoutyError::RuntimeUpgraded(_) => info!("{} -> resubscribing", e),
+                // The hook has been killed, let the operator know and subscribe
+                // again straight away
+                ScoutyError::HookTimeout(_) => {
+                    warn!("{}", e);
+                    let message = format!("{} -> resubscribing", e);
+                    let formatted_message =
+                        format!("<br/>⏱️ {} -> resubscribing<br/><br/>", e);
+                    if let Err(e) = c
+                        .send_message(&message, &formatted_message, Severity::Critical)
+                        .await
+                    {
+                        warn!("Matrix message skipped! {}", e);
+                    }
+                }
+                // Hooks strict is set and some hooks are broken, stop straight away
+                ScoutyError::HooksVerificationFailed(_) => {
+                    error!("{}", e);
+                    let message = format!("{} -> scouty stopped", e);
+                    let formatted_message =
+                        format!("<br/>🚨 {} -> <code>scouty</code> stopped<br/><br/>", e);
+                    if let Err(e) = c
+                        .send_message(&message, &formatted_message, Severity::Critical)
+                        .await
+                    {
+                        warn!("Matrix message skipped! {}", e);
+                    }
+                    process::exit(1);
+                }
+                ScoutyError::MatrixError(_) => {
+                    warn!("Matrix message skipped!");
+                    shutdown::sleep(time::Duration::from_secs(1)).await;
+                }
+                _ => {
+                    error!("{}", e);
+                    let mut variables = Variables::new();
+                    variables.insert("chain", c.runtime.to_string());
+                    variables.insert("error", e.to_string());
+                    variables.insert("error_interval", config.error_interval.to_string());
+                    let (message, formatted_message) = templates::templates().render_or(
+                        TEMPLATE_ERROR_HOLD,
+                        &variables,
+                        &format!("{} -> On hold for {} min!", e, config.error_interval),
+                        &format!("<br/>🚨 An error was raised -> <code>scouty</code> on hold for {} min while rescue is on the way 🚁 🚒 🚑 🚓<br/><br/><code>{}</code><br/><br/>", config.error_interval, e),
+                    );
+                    if let Err(e) = c
+                        .send_message(&message, &formatted_message, Severity::Critical)
+                        .await
+                    {
+                        warn!("Matrix message skipped! {}", e);
+                    }
+                    shutdown::sleep(time::Duration::from_secs(
+                        60 * config.error_interval,
+                    ))
+                    .await;
+                }
+            }
+            // Only recreate the node and matrix clients if the connection was lost,
+            // otherwise just subscribe again with the same clients
+            if e.is_connection_error() && !shutdown::is_requested() {
+                match Scouty::new(c.urls.clone(), Some(&c.chain)).await {
+                    Ok(mut scouty) => {
+                        // Keep the messages still to be retried
+                        if c.messages.is_enabled() {
+                            scouty.messages = c.messages.clone();
+                            scouty.notifiers = build_notifiers(&scouty.messages);
+                        }
+                        // Keep the status until the runtime updates it again
+                        scouty.status = c.status.clone();
+                        metrics::register_chain(
+                            &scouty.runtime.to_string(),
+                            &scouty.status,
+                        );
+                        metrics::RPC_RECONNECTS
+                            .inc(&[("chain", &scouty.runtime.to_string())]);
+                        c = scouty;
+                    }
+                    Err(e) => {
                         error!("{}", e);
-                        let message =
-                            format!("On hold for {} min!", config.error_interval);
-                        let formatted_message = format!("<br/>🚨 An error was raised -> <code>scouty</code> on hold for {} min while rescue is on the way 🚁 🚒 🚑 🚓<br/><br/>", config.error_interval);
-                        c.send_message(&message, &formatted_message).await.unwrap();
-                        thread::sleep(time::Duration::from_secs(
-                            60 * config.error_interval,
-                        ));
-                        continue;
+                        return;
                     }
                 }
-                thread::sleep(time::Duration::from_secs(1));
-            };
-        }
-    });
-    task::block_on(t);
+            }
+        };
+    }
+
+    // Let the operator know that monitoring has been stopped on purpose
+    let mut variables = Variables::new();
+    variables.insert("chain", c.runtime.to_string());
+    let (message, formatted_message) = templates::templates().render_or(
+        TEMPLATE_SHUTDOWN,
+        &variables,
+        "scouty shutting down",
+        "<br/>👋 <code>scouty</code> shutting down<br/><br/>",
+    );
+    if let Err(e) = c
+        .send_message(&message, &formatted_message, Severity::Info)
+        .await
+    {
+        warn!("Matrix message skipped! {}", e);
+    }
+    info!("{}", message);
 }
 
 pub fn get_account_id_from_storage_key(key: StorageKey) -> AccountId32 {
@@ -0,0 +1,89 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use async_signal::{Signal, Signals};
+use async_std::task;
+use futures::{future, StreamExt};
+use log::{error, warn};
+use std::{
+    process,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    time::Duration,
+};
+
+static SHUTDOWN: AtomicBool = AtomicBool::new(false);
+static IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
+
+/// Keeps track of an operation (e.g. matrix message) that should not be interrupted
+/// by a shutdown, the operation is considered done once the guard is dropped
+pub struct InFlight;
+
+impl InFlight {
+    pub fn start() -> Self {
+        IN_FLIGHT.fetch_add(1, Ordering::SeqCst);
+        InFlight
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        IN_FLIGHT.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Returns true if a termination signal has been received
+pub fn is_requested() -> bool {
+    SHUTDOWN.load(Ordering::SeqCst)
+}
+
+/// Listen for SIGTERM/SIGINT. The first signal requests a graceful shutdown,
+/// a second one exits straight away.
+pub fn listen() {
+    let mut signals = match Signals::new([Signal::Term, Signal::Int]) {
+        Ok(signals) => signals,
+        Err(e) => {
+            error!("Signal handler could not be registered: {}", e);
+            return;
+        }
+    };
+    task::spawn(async move {
+        while let Some(signal) = signals.next().await {
+            if is_requested() {
+                warn!("{:?} received again -> exit now!", signal);
+                process::exit(1);
+            }
+            warn!("{:?} received -> shutting down..", signal);
+            SHUTDOWN.store(true, Ordering::SeqCst);
+        }
+    });
+}
+
+/// Resolves once shutdown has been requested and no operation is in flight
+pub async fn wait() {
+    while !is_requested() || IN_FLIGHT.load(Ordering::SeqCst) > 0 {
+        task::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+/// Sleep for the given duration or until shutdown has been requested
+pub async fn sleep(duration: Duration) {
+    future::select(Box::pin(task::sleep(duration)), Box::pin(wait())).await;
+}
@@ -0,0 +1,270 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::config::CONFIG;
+use crate::errors::ScoutyError;
+use crate::matrix::Severity;
+use crate::notifier::{Notification, Notifier};
+use async_std::task;
+use futures::future::BoxFuture;
+use lettre::{
+    message::{Mailbox, MultiPart},
+    transport::smtp::{
+        authentication::Credentials,
+        client::{Tls, TlsParameters},
+    },
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use std::{fmt, time};
+
+// Delay (in seconds) before the email that failed is retried
+const SMTP_RETRY_INTERVAL: u64 = 5;
+
+/// How the connection to the SMTP server is secured
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpTls {
+    /// Connect in plain text and upgrade the connection with STARTTLS
+    #[default]
+    Starttls,
+    /// Connect with implicit TLS, usually on port 465
+    Tls,
+}
+
+impl fmt::Display for SmtpTls {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Starttls => write!(f, "starttls"),
+            Self::Tls => write!(f, "tls"),
+        }
+    }
+}
+
+/// Returns the comma separated addresses as mailboxes
+fn parse_mailboxes(addresses: &str) -> Result<Vec<Mailbox>, ScoutyError> {
+    addresses
+        .split(',')
+        .map(|a| a.trim())
+        .filter(|a| !a.is_empty())
+        .map(|a| {
+            a.parse::<Mailbox>().map_err(|e| {
+                ScoutyError::SmtpError(format!("invalid address {}: {}", a, e))
+            })
+        })
+        .collect()
+}
+
+/// Returns the subject of the email of the notification
+fn subject(notification: &Notification) -> String {
+    match notification.severity {
+        Severity::Critical => format!(
+            "[Scouty] {} {} (critical)",
+            notification.chain, notification.event
+        ),
+        Severity::Info => {
+            format!("[Scouty] {} {}", notification.chain, notification.event)
+        }
+    }
+}
+
+/// Sends an email for every event at or above the minimum severity, with an HTML part
+/// of the formatted message and a plain part of the message
+#[derive(Debug, Clone)]
+pub struct Smtp {
+    host: String,
+    port: u16,
+    tls: SmtpTls,
+    username: String,
+    password: String,
+    from: String,
+    to: String,
+    min_severity: Severity,
+}
+
+impl Smtp {
+    pub fn new() -> Self {
+        let config = CONFIG.current();
+        Smtp {
+            host: config.smtp_host.clone(),
+            port: config.smtp_port,
+            tls: config.smtp_tls,
+            username: config.smtp_username.clone(),
+            password: config.smtp_password.clone(),
+            from: config.smtp_from.clone(),
+            to: config.smtp_to.clone(),
+            min_severity: config.smtp_min_severity,
+        }
+    }
+
+    fn message(&self, notification: &Notification) -> Result<Message, ScoutyError> {
+        let from = parse_mailboxes(&self.from)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| ScoutyError::SmtpError("from address not defined".into()))?;
+        let to = parse_mailboxes(&self.to)?;
+        if to.is_empty() {
+            return Err(ScoutyError::SmtpError("to address not defined".into()));
+        }
+        let mut builder = Message::builder().from(from).subject(subject(notification));
+        for mailbox in to {
+            builder = builder.to(mailbox);
+        }
+        builder
+            .multipart(MultiPart::alternative_plain_html(
+                notification.message.clone(),
+                notification.formatted_message.clone(),
+            ))
+            .map_err(|e| ScoutyError::SmtpError(e.to_string()))
+    }
+
+    fn transport(&self) -> Result<AsyncSmtpTransport<Tokio1Executor>, ScoutyError> {
+        let parameters = TlsParameters::new(self.host.clone())
+            .map_err(|e| ScoutyError::SmtpError(e.to_string()))?;
+        let tls = match self.tls {
+            SmtpTls::Starttls => Tls::Required(parameters),
+            SmtpTls::Tls => Tls::Wrapper(parameters),
+        };
+        let mut builder =
+            AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&self.host)
+                .port(self.port)
+                .tls(tls)
+                .timeout(Some(time::Duration::from_secs(
+                    CONFIG.current().request_timeout,
+                )));
+        if !self.username.is_empty() {
+            builder = builder.credentials(Credentials::new(
+                self.username.clone(),
+                self.password.clone(),
+            ));
+        }
+        Ok(builder.build())
+    }
+
+    async fn deliver(&self, message: &Message) -> Result<(), ScoutyError> {
+        let res = self
+            .transport()?
+            .send(message.clone())
+            .await
+            .map_err(|e| ScoutyError::SmtpError(e.to_string()))?;
+        debug!("response {:?}", res);
+        Ok(())
+    }
+
+    /// Send the email, retrying once if the connection or the authentication fails
+    async fn deliver_with_retry(self, message: Message) {
+        if let Err(e) = self.deliver(&message).await {
+            warn!(
+                "Email message not sent! {} -> next retry in {}s",
+                e, SMTP_RETRY_INTERVAL
+            );
+            task::sleep(time::Duration::from_secs(SMTP_RETRY_INTERVAL)).await;
+            if let Err(e) = self.deliver(&message).await {
+                warn!("Email message dropped after retry! {}", e);
+            }
+        }
+    }
+}
+
+/// Emails are sent in the background, so that a slow or unreachable SMTP server does
+/// not hold the processing of the session
+impl Notifier for Smtp {
+    fn name(&self) -> &str {
+        "Email"
+    }
+
+    fn send<'a>(
+        &'a self,
+        notification: &'a Notification,
+    ) -> BoxFuture<'a, Result<(), ScoutyError>> {
+        Box::pin(async move {
+            if notification.severity < self.min_severity {
+                return Ok(());
+            }
+            let message = self.message(notification)?;
+            task::spawn(self.clone().deliver_with_retry(message));
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn smtp(to: &str) -> Smtp {
+        Smtp {
+            host: "smtp.example.com".to_string(),
+            port: 587,
+            tls: SmtpTls::Starttls,
+            username: String::new(),
+            password: String::new(),
+            from: "Scouty <scouty@example.com>".to_string(),
+            to: to.to_string(),
+            min_severity: Severity::Critical,
+        }
+    }
+
+    #[test]
+    fn it_parses_comma_separated_addresses() {
+        let mailboxes =
+            parse_mailboxes("ops@example.com, Alice <alice@example.com>,").unwrap();
+        assert_eq!(mailboxes.len(), 2);
+        assert_eq!(mailboxes[1].name, Some("Alice".to_string()));
+        assert!(parse_mailboxes("not an address").is_err());
+    }
+
+    #[test]
+    fn it_builds_the_subject_of_the_notification() {
+        let notification = Notification::new(
+            "slash",
+            "Polkadot",
+            Severity::Critical,
+            "Slash!",
+            "Slash!",
+        );
+        assert_eq!(subject(&notification), "[Scouty] Polkadot slash (critical)");
+        let notification =
+            Notification::new("new_era", "Kusama", Severity::Info, "Era 10", "Era 10");
+        assert_eq!(subject(&notification), "[Scouty] Kusama new_era");
+    }
+
+    #[test]
+    fn it_builds_the_message_with_plain_and_html_parts() {
+        let notification = Notification::new(
+            "new_era",
+            "Polkadot",
+            Severity::Critical,
+            "Era 10",
+            "<b>Era 10</b>",
+        );
+        let message = smtp("ops@example.com, dev@example.com")
+            .message(&notification)
+            .unwrap();
+        assert_eq!(message.envelope().to().len(), 2);
+        let formatted = String::from_utf8(message.formatted()).unwrap();
+        assert!(formatted.contains("text/plain"));
+        assert!(formatted.contains("text/html"));
+        assert!(formatted.contains("<b>Era 10</b>"));
+        assert!(smtp("").message(&notification).is_err());
+    }
+}
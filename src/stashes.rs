@@ -0,0 +1,344 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::config::CONFIG;
+use crate::errors::ScoutyError;
+use async_std::task;
+use lazy_static::lazy_static;
+use log::{info, warn};
+use std::{
+    fs,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    time,
+};
+use subxt::utils::AccountId32;
+
+// Interval (in seconds) to verify if the stashes file was modified
+const STASHES_PATH_POLL_INTERVAL: u64 = 5;
+
+// Sources of the stashes monitored alongside the stashes defined locally
+const SOURCE_URL: &str = "stashes_url";
+const SOURCE_PATH: &str = "stashes_path";
+
+static GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+lazy_static! {
+    /// Stashes added and removed by the last change of the stashes monitored
+    static ref LAST_CHANGES: Mutex<StashesChanges> = Mutex::new(Default::default());
+}
+
+/// Stashes added and removed between two lists of stashes monitored
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct StashesChanges {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl StashesChanges {
+    pub fn between(before: &[String], after: &[String]) -> Self {
+        Self {
+            added: after
+                .iter()
+                .filter(|stash| !before.contains(stash))
+                .cloned()
+                .collect(),
+            removed: before
+                .iter()
+                .filter(|stash| !after.contains(stash))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+
+    /// Returns the plain and formatted message of the stashes added and removed
+    pub fn message(&self) -> (String, String) {
+        let mut message = "Stashes monitored changed".to_string();
+        let mut formatted_message = "<br/>🔭 Stashes monitored changed".to_string();
+        if !self.added.is_empty() {
+            message.push_str(&format!(" -> added: {}", self.added.join(", ")));
+            formatted_message.push_str(&format!(
+                " -> added: <code>{}</code>",
+                self.added.join(", ")
+            ));
+        }
+        if !self.removed.is_empty() {
+            message.push_str(&format!(" -> removed: {}", self.removed.join(", ")));
+            formatted_message.push_str(&format!(
+                " -> removed: <code>{}</code>",
+                self.removed.join(", ")
+            ));
+        }
+        formatted_message.push_str("<br/><br/>");
+        (message, formatted_message)
+    }
+}
+
+/// Returns the number of changes of the stashes monitored since startup
+pub fn generation() -> usize {
+    GENERATION.load(Ordering::SeqCst)
+}
+
+/// Returns the stashes added and removed by the last change
+pub fn last_changes() -> StashesChanges {
+    LAST_CHANGES.lock().unwrap().clone()
+}
+
+/// Returns the stash addresses of the list, either a JSON array of addresses, a JSON
+/// array of objects with a `stash` field (e.g. Thousand Validators candidates) or one
+/// address per line. Entries that are not a valid SS58 address are skipped.
+pub fn parse(body: &str) -> Vec<String> {
+    let entries: Vec<String> = match serde_json::from_str::<serde_json::Value>(body) {
+        Ok(serde_json::Value::Array(entries)) => entries
+            .iter()
+            .filter_map(|entry| match entry {
+                serde_json::Value::String(stash) => Some(stash.to_string()),
+                serde_json::Value::Object(entry) => entry
+                    .get("stash")
+                    .and_then(|stash| stash.as_str())
+                    .map(|stash| stash.to_string()),
+                _ => None,
+            })
+            .collect(),
+        _ => body.lines().map(|line| line.to_string()).collect(),
+    };
+    let mut stashes: Vec<String> = Vec::new();
+    for stash in entries.iter().map(|stash| stash.trim()) {
+        if stash.is_empty() || stashes.iter().any(|s| s == stash) {
+            continue;
+        }
+        if AccountId32::from_str(stash).is_err() {
+            warn!("Invalid SS58 format account: {} -> skipped", stash);
+            continue;
+        }
+        stashes.push(stash.to_string());
+    }
+    stashes
+}
+
+/// Returns the stash addresses of the file, one address per line, and the line numbers
+/// of the lines that are not a valid SS58 address. Empty lines and lines starting with
+/// `#` are ignored.
+pub fn parse_lines(body: &str) -> (Vec<String>, Vec<(usize, String)>) {
+    let mut stashes: Vec<String> = Vec::new();
+    let mut malformed: Vec<(usize, String)> = Vec::new();
+    for (i, line) in body.lines().enumerate() {
+        let stash = line.trim();
+        if stash.is_empty() || stash.starts_with('#') {
+            continue;
+        }
+        if AccountId32::from_str(stash).is_err() {
+            malformed.push((i + 1, stash.to_string()));
+            continue;
+        }
+        if !stashes.iter().any(|s| s == stash) {
+            stashes.push(stash.to_string());
+        }
+    }
+    (stashes, malformed)
+}
+
+fn read_file(path: &str) -> Result<Vec<String>, ScoutyError> {
+    let body = fs::read_to_string(path).map_err(|e| ScoutyError::Other(e.to_string()))?;
+    let (stashes, malformed) = parse_lines(&body);
+    for (line, stash) in malformed.iter() {
+        warn!(
+            "Stashes file ({}) line {}: invalid SS58 format account: {} -> skipped",
+            path, line, stash
+        );
+    }
+    Ok(stashes)
+}
+
+/// Returns the time the file was last modified
+fn modified(path: &str) -> Option<time::SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+async fn fetch(url: &str) -> Result<Vec<String>, ScoutyError> {
+    let client = reqwest::Client::new();
+    let res = client
+        .get(url)
+        .timeout(time::Duration::from_secs(CONFIG.current().request_timeout))
+        .send()
+        .await
+        .map_err(|e| ScoutyError::Other(e.to_string()))?;
+    if !res.status().is_success() {
+        return Err(ScoutyError::Other(format!("status {}", res.status())));
+    }
+    let body = res
+        .text()
+        .await
+        .map_err(|e| ScoutyError::Other(e.to_string()))?;
+    Ok(parse(&body))
+}
+
+/// Fetch the stashes url and monitor the stashes fetched alongside the stashes defined
+/// locally. The last list fetched is kept if the url fails or returns no stashes.
+async fn refresh() {
+    let url = CONFIG.current().stashes_url.clone();
+    let stashes = match fetch(&url).await {
+        Ok(stashes) if stashes.is_empty() => {
+            warn!(
+                "Stashes url ({}) returned no stashes -> last list kept",
+                url
+            );
+            return;
+        }
+        Ok(stashes) => stashes,
+        Err(e) => {
+            warn!("Stashes url ({}) not fetched -> last list kept: {}", url, e);
+            return;
+        }
+    };
+    info!(
+        "Stashes url ({}) fetched -> {} stash(es)",
+        url,
+        stashes.len()
+    );
+    apply(SOURCE_URL, stashes);
+}
+
+/// Read the stashes file and monitor the stashes of the file alongside the stashes
+/// defined locally. The last list read is kept if the file can not be read.
+fn reload_file() {
+    let path = CONFIG.current().stashes_path.clone();
+    let stashes = match read_file(&path) {
+        Ok(stashes) => stashes,
+        Err(e) => {
+            warn!("Stashes file ({}) not read -> last list kept: {}", path, e);
+            return;
+        }
+    };
+    info!(
+        "Stashes file ({}) read -> {} stash(es)",
+        path,
+        stashes.len()
+    );
+    apply(SOURCE_PATH, stashes);
+}
+
+/// Replace the stashes of the source and keep the changes of the stashes monitored, so
+/// that the subscriptions stop tracking the stashes removed and announce the changes
+fn apply(source: &str, stashes: Vec<String>) {
+    let (before, after) = CONFIG.set_remote_stashes(source, stashes);
+    let changes = StashesChanges::between(&before, &after);
+    if changes.is_empty() {
+        return;
+    }
+    info!("{}", changes.message().0);
+    *LAST_CHANGES.lock().unwrap() = changes;
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Fetch the stashes url and read the stashes file at startup, before the
+/// subscriptions start. The stashes url is fetched again on every interval and the
+/// stashes file is read again every time it is modified.
+pub fn listen() {
+    if !CONFIG.current().stashes_url.is_empty() {
+        task::block_on(refresh());
+        if CONFIG.current().stashes_url_interval > 0 {
+            task::spawn(async {
+                loop {
+                    task::sleep(time::Duration::from_secs(
+                        CONFIG.current().stashes_url_interval,
+                    ))
+                    .await;
+                    refresh().await;
+                }
+            });
+        }
+    }
+    if !CONFIG.current().stashes_path.is_empty() {
+        let mut last_modified = modified(&CONFIG.current().stashes_path);
+        reload_file();
+        task::spawn(async move {
+            loop {
+                task::sleep(time::Duration::from_secs(STASHES_PATH_POLL_INTERVAL)).await;
+                let modified = modified(&CONFIG.current().stashes_path);
+                if modified.is_none() || modified == last_modified {
+                    continue;
+                }
+                last_modified = modified;
+                reload_file();
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+    const BOB: &str = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+
+    #[test]
+    fn it_parses_the_stashes_fetched() {
+        let expected = vec![ALICE.to_string(), BOB.to_string()];
+        let body = format!(r#"["{}", "{}", "not_a_stash", "{}"]"#, ALICE, BOB, ALICE);
+        assert_eq!(parse(&body), expected);
+        let body = format!(
+            r#"[{{"name": "alice", "stash": "{}"}}, {{"stash": "{}"}}, {{"name": "x"}}]"#,
+            ALICE, BOB
+        );
+        assert_eq!(parse(&body), expected);
+        let body = format!("{}\n\n  {}  \n", ALICE, BOB);
+        assert_eq!(parse(&body), expected);
+        assert!(parse("{}").is_empty());
+    }
+
+    #[test]
+    fn it_parses_the_lines_of_the_stashes_file() {
+        let body = format!(
+            "# validators\n{}\n\nnot_a_stash\n  {}\n{}\n",
+            ALICE, BOB, ALICE
+        );
+        let (stashes, malformed) = parse_lines(&body);
+        assert_eq!(stashes, vec![ALICE.to_string(), BOB.to_string()]);
+        assert_eq!(malformed, vec![(4, "not_a_stash".to_string())]);
+        assert_eq!(parse_lines(""), (Vec::new(), Vec::new()));
+    }
+
+    #[test]
+    fn it_builds_the_changes_between_stashes() {
+        let before = vec![ALICE.to_string()];
+        let after = vec![BOB.to_string()];
+        let changes = StashesChanges::between(&before, &after);
+        assert_eq!(changes.added, vec![BOB.to_string()]);
+        assert_eq!(changes.removed, vec![ALICE.to_string()]);
+        assert_eq!(
+            changes.message().0,
+            format!(
+                "Stashes monitored changed -> added: {} -> removed: {}",
+                BOB, ALICE
+            )
+        );
+        assert!(StashesChanges::between(&before, &before).is_empty());
+    }
+}
@@ -0,0 +1,282 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::config::CONFIG;
+use crate::errors::ScoutyError;
+use crate::pools::PoolSnapshot;
+use crate::weekly::WeeklyStats;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, io, sync::Mutex};
+
+// Serializes the read and write of the state file, which is shared by all the chains
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// State of a chain that is kept between restarts
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct ChainState {
+    /// Last era for which the new era hook has been run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_era_index: Option<u32>,
+    /// Last session for which the session checks have been run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_index: Option<u32>,
+    /// Active era of the last session processed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub era_index: Option<u32>,
+    /// Last era for which the era paid hook has been run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub era_paid_index: Option<u32>,
+    /// Last state notified of every stash, keyed by the stash address
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub reported: BTreeMap<String, StashState>,
+    /// Last weekly report due, in seconds since the epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weekly_report_at: Option<i64>,
+    /// Last snapshot verified of every pool, keyed by the pool id
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub pools: BTreeMap<u32, PoolSnapshot>,
+    /// Stats accumulated since the last weekly report
+    #[serde(default, skip_serializing_if = "WeeklyStats::is_empty")]
+    pub weekly: WeeklyStats,
+}
+
+impl ChainState {
+    /// Returns true if the session has already been processed, e.g. before a restart
+    pub fn is_session_processed(&self, session_index: u32) -> bool {
+        self.session_index
+            .is_some_and(|last_session_index| session_index <= last_session_index)
+    }
+
+    /// Returns true if the era paid has already been notified, e.g. before a restart
+    pub fn is_era_paid_processed(&self, era_index: u32) -> bool {
+        self.era_paid_index
+            .is_some_and(|last_era_index| era_index <= last_era_index)
+    }
+
+    /// Returns true if the state of the stash differs from the last one notified, or if
+    /// it has never been notified
+    pub fn is_changed(&self, stash: &str, state: &StashState) -> bool {
+        self.reported.get(stash) != Some(state)
+    }
+}
+
+/// State of a stash compared between sessions, to only notify its transitions
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct StashState {
+    pub is_active: bool,
+    pub is_active_next_era: bool,
+    pub queued_session_keys: String,
+}
+
+type State = BTreeMap<String, ChainState>;
+
+/// Returns the state of the chain, or the default state if the state file does not
+/// exist or can not be read
+pub fn get(chain: &str) -> ChainState {
+    get_from(&CONFIG.current().state_path, chain).unwrap_or_else(|e| {
+        warn!(
+            "State file ({}) could not be read: {}",
+            CONFIG.current().state_path,
+            e
+        );
+        ChainState::default()
+    })
+}
+
+/// Update the state of the chain, failing to write the state file is only logged.
+/// Nothing is written in dry run mode.
+pub fn update<F: FnOnce(&mut ChainState)>(chain: &str, f: F) {
+    if CONFIG.current().dry_run {
+        return;
+    }
+    if let Err(e) = update_in(&CONFIG.current().state_path, chain, f) {
+        warn!(
+            "State file ({}) could not be written: {}",
+            CONFIG.current().state_path,
+            e
+        );
+    }
+}
+
+/// Update the state of the chain only once the processing of the event succeeded, so
+/// that an event not notified (e.g. the matrix message failed to be sent) is
+/// processed again after a restart
+pub fn update_on_success<T, F: FnOnce(&mut ChainState)>(
+    chain: &str,
+    result: &Result<T, ScoutyError>,
+    f: F,
+) {
+    if CONFIG.current().dry_run {
+        return;
+    }
+    if let Err(e) = update_in_on_success(&CONFIG.current().state_path, chain, result, f) {
+        warn!(
+            "State file ({}) could not be written: {}",
+            CONFIG.current().state_path,
+            e
+        );
+    }
+}
+
+fn get_from(path: &str, chain: &str) -> Result<ChainState, ScoutyError> {
+    let _lock = LOCK.lock().unwrap();
+    Ok(load(path)?.get(chain).cloned().unwrap_or_default())
+}
+
+fn update_in<F: FnOnce(&mut ChainState)>(
+    path: &str,
+    chain: &str,
+    f: F,
+) -> Result<(), ScoutyError> {
+    let _lock = LOCK.lock().unwrap();
+    let mut state = load(path)?;
+    f(state.entry(chain.to_string()).or_default());
+    // Write a temporary file first, so that the state file is never left half written
+    let tmp_path = format!("{}.tmp", path);
+    fs::write(&tmp_path, serde_json::to_vec_pretty(&state)?)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+fn update_in_on_success<T, F: FnOnce(&mut ChainState)>(
+    path: &str,
+    chain: &str,
+    result: &Result<T, ScoutyError>,
+    f: F,
+) -> Result<(), ScoutyError> {
+    if result.is_err() {
+        return Ok(());
+    }
+    update_in(path, chain, f)
+}
+
+fn load(path: &str) -> Result<State, ScoutyError> {
+    match fs::read_to_string(path) {
+        // A corrupted state file is treated as a cold start, and overwritten by the
+        // next update
+        Ok(data) => match serde_json::from_str(&data) {
+            Ok(state) => Ok(state),
+            Err(e) => {
+                warn!(
+                    "State file ({}) is corrupted, starting from a cold state: {}",
+                    path, e
+                );
+                Ok(State::new())
+            }
+        },
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(State::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn it_keeps_the_state_of_every_chain() {
+        let path = env::temp_dir().join("scouty_state.json");
+        let path = path.to_string_lossy().to_string();
+        let _ = fs::remove_file(&path);
+        assert_eq!(get_from(&path, "polkadot").unwrap(), ChainState::default());
+
+        update_in(&path, "polkadot", |state| state.new_era_index = Some(1234)).unwrap();
+        update_in(&path, "kusama", |state| state.new_era_index = Some(5678)).unwrap();
+        assert_eq!(
+            get_from(&path, "polkadot").unwrap().new_era_index,
+            Some(1234)
+        );
+        assert_eq!(get_from(&path, "kusama").unwrap().new_era_index, Some(5678));
+    }
+
+    #[test]
+    fn it_starts_cold_from_a_corrupted_state() {
+        let path = env::temp_dir().join("scouty_corrupted_state.json");
+        let path = path.to_string_lossy().to_string();
+        fs::write(&path, "{\"polkadot\": {\"new_era_").unwrap();
+        assert_eq!(get_from(&path, "polkadot").unwrap(), ChainState::default());
+
+        update_in(&path, "polkadot", |state| state.session_index = Some(42)).unwrap();
+        assert_eq!(get_from(&path, "polkadot").unwrap().session_index, Some(42));
+    }
+
+    #[test]
+    fn it_only_keeps_the_state_of_the_events_processed() {
+        let path = env::temp_dir().join("scouty_processed_state.json");
+        let path = path.to_string_lossy().to_string();
+        let _ = fs::remove_file(&path);
+
+        let failed: Result<(), ScoutyError> =
+            Err(ScoutyError::Other("matrix message not sent".to_string()));
+        update_in_on_success(&path, "polkadot", &failed, |state| {
+            state.session_index = Some(42)
+        })
+        .unwrap();
+        assert_eq!(get_from(&path, "polkadot").unwrap().session_index, None);
+
+        update_in_on_success(&path, "polkadot", &Ok(()), |state| {
+            state.session_index = Some(42)
+        })
+        .unwrap();
+        assert_eq!(get_from(&path, "polkadot").unwrap().session_index, Some(42));
+    }
+
+    #[test]
+    fn it_skips_the_sessions_and_eras_already_processed() {
+        let mut state = ChainState::default();
+        assert!(!state.is_session_processed(10));
+        assert!(!state.is_era_paid_processed(2));
+
+        state.session_index = Some(10);
+        state.era_paid_index = Some(2);
+        assert!(state.is_session_processed(9));
+        assert!(state.is_session_processed(10));
+        assert!(!state.is_session_processed(11));
+        assert!(state.is_era_paid_processed(2));
+        assert!(!state.is_era_paid_processed(3));
+    }
+
+    #[test]
+    fn it_detects_the_stash_state_changes() {
+        let mut state = ChainState::default();
+        let stash_state = StashState {
+            is_active: true,
+            is_active_next_era: true,
+            queued_session_keys: "0x00".to_string(),
+        };
+        assert!(state.is_changed("stash", &stash_state));
+
+        state
+            .reported
+            .insert("stash".to_string(), stash_state.clone());
+        assert!(!state.is_changed("stash", &stash_state));
+        assert!(state.is_changed(
+            "stash",
+            &StashState {
+                is_active_next_era: false,
+                ..stash_state.clone()
+            }
+        ));
+        assert!(state.is_changed("other", &stash_state));
+    }
+}
@@ -0,0 +1,121 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use chrono::{DateTime, Utc};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, RwLock},
+};
+
+/// State of a predefined stash, as last observed by the runtime modules
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StashStatus {
+    pub name: String,
+    pub is_active: bool,
+    pub is_queued: bool,
+    /// Points collected so far in the active era
+    pub era_points: u32,
+}
+
+/// Latest state observed by the runtime modules while processing on-chain events
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChainStatus {
+    /// Last finalized block processed
+    pub block_number: u32,
+    /// Last session processed
+    pub session_index: Option<u32>,
+    /// Active era of the last session processed
+    pub era_index: Option<u32>,
+    /// Session of the era (starting at 1) of the last session processed
+    pub eras_session_index: u32,
+    pub sessions_per_era: u32,
+    /// Predefined stashes by their SS58 address
+    pub stashes: BTreeMap<String, StashStatus>,
+    /// Alerts are muted until the deadline, set with the matrix bot commands
+    pub muted_until: Option<DateTime<Utc>>,
+}
+
+impl ChainStatus {
+    /// Returns the era progress as a percentage, based on the sessions already started
+    pub fn era_progress(&self) -> u32 {
+        if self.sessions_per_era == 0 {
+            return 0;
+        }
+        (self.eras_session_index.saturating_sub(1) * 100 / self.sessions_per_era).min(100)
+    }
+
+    /// Returns true if alerts are muted at the time defined
+    pub fn is_muted(&self, now: DateTime<Utc>) -> bool {
+        self.muted_until.is_some_and(|until| now < until)
+    }
+}
+
+/// Chain status shared between the runtime modules, which update it, and the matrix
+/// bot commands, which read it
+#[derive(Debug, Clone, Default)]
+pub struct SharedStatus(Arc<RwLock<ChainStatus>>);
+
+impl SharedStatus {
+    pub fn get(&self) -> ChainStatus {
+        self.0.read().unwrap().clone()
+    }
+
+    pub fn update<F: FnOnce(&mut ChainStatus)>(&self, f: F) {
+        f(&mut self.0.write().unwrap());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_calculates_the_era_progress() {
+        let mut status = ChainStatus {
+            sessions_per_era: 6,
+            eras_session_index: 1,
+            ..Default::default()
+        };
+        assert_eq!(status.era_progress(), 0);
+        status.eras_session_index = 4;
+        assert_eq!(status.era_progress(), 50);
+        status.sessions_per_era = 0;
+        assert_eq!(status.era_progress(), 0);
+    }
+
+    #[test]
+    fn it_is_muted_until_the_deadline() {
+        let now = Utc::now();
+        let mut status = ChainStatus::default();
+        assert!(!status.is_muted(now));
+        status.muted_until = Some(now + chrono::Duration::hours(2));
+        assert!(status.is_muted(now));
+        assert!(!status.is_muted(now + chrono::Duration::hours(2)));
+    }
+
+    #[test]
+    fn it_shares_the_updates_between_clones() {
+        let status = SharedStatus::default();
+        let clone = status.clone();
+        status.update(|s| s.block_number = 1234);
+        assert_eq!(clone.get().block_number, 1234);
+    }
+}
@@ -0,0 +1,211 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::config::CONFIG;
+use crate::errors::ScoutyError;
+use crate::notifier::{Notification, Notifier};
+use futures::future::BoxFuture;
+use lazy_static::lazy_static;
+use log::debug;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use std::time;
+
+const TELEGRAM_URL: &str = "https://api.telegram.org";
+
+// Maximum number of characters of a telegram message
+const TELEGRAM_MAX_MESSAGE_LENGTH: usize = 4096;
+
+// Tags of the matrix formatted messages supported by the telegram HTML parse mode
+const TELEGRAM_HTML_TAGS: [&str; 8] =
+    ["a", "b", "i", "u", "s", "code", "pre", "blockquote"];
+
+lazy_static! {
+    static ref HTML_TAG: Regex = Regex::new(r"</?([a-zA-Z]+)[^>]*>").unwrap();
+}
+
+#[derive(Debug, Serialize)]
+struct SendMessageRequest<'a> {
+    chat_id: &'a str,
+    text: &'a str,
+    parse_mode: &'a str,
+    disable_web_page_preview: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct SendMessageResponse {
+    ok: bool,
+    #[serde(default)]
+    description: String,
+}
+
+/// Convert the matrix formatted message into the HTML subset supported by telegram,
+/// line breaks and table rows become new lines and unsupported tags are dropped
+fn sanitize(formatted_message: &str) -> String {
+    let text = HTML_TAG.replace_all(formatted_message, |caps: &Captures| {
+        let tag = caps[1].to_lowercase();
+        let is_closing = caps[0].starts_with("</");
+        match tag.as_str() {
+            "br" => "\n".to_string(),
+            "tr" if is_closing => "\n".to_string(),
+            "td" | "th" if is_closing => " | ".to_string(),
+            _ if TELEGRAM_HTML_TAGS.contains(&tag.as_str()) => caps[0].to_string(),
+            _ => "".to_string(),
+        }
+    });
+    text.replace(" | \n", "\n").trim().to_string()
+}
+
+/// Split the message by lines into messages within the telegram limit, lines longer
+/// than the limit are split on their own
+fn split_message(message: &str, max_length: usize) -> Vec<String> {
+    let mut messages: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut current_length = 0;
+    for line in message.lines() {
+        let line_length = line.chars().count();
+        // Include the new line that joins the line to the current message
+        if current_length > 0 && current_length + 1 + line_length > max_length {
+            messages.push(current);
+            current = String::new();
+            current_length = 0;
+        }
+        if line_length > max_length {
+            let chars: Vec<char> = line.chars().collect();
+            for chunk in chars.chunks(max_length) {
+                messages.push(chunk.iter().collect());
+            }
+            continue;
+        }
+        if current_length > 0 {
+            current.push('\n');
+            current_length += 1;
+        }
+        current.push_str(line);
+        current_length += line_length;
+    }
+    if current_length > 0 {
+        messages.push(current);
+    }
+    messages
+}
+
+/// Sends every message to the telegram chat defined with the Bot API
+#[derive(Debug, Clone)]
+pub struct Telegram {
+    client: reqwest::Client,
+    bot_token: String,
+    chat_id: String,
+}
+
+impl Telegram {
+    pub fn new() -> Self {
+        let config = CONFIG.current();
+        Telegram {
+            client: reqwest::Client::new(),
+            bot_token: config.telegram_bot_token.clone(),
+            chat_id: config.telegram_chat_id.clone(),
+        }
+    }
+
+    async fn send_message(&self, text: &str) -> Result<(), ScoutyError> {
+        let req = SendMessageRequest {
+            chat_id: &self.chat_id,
+            text,
+            parse_mode: "HTML",
+            disable_web_page_preview: true,
+        };
+        let res = self
+            .client
+            .post(format!(
+                "{}/bot{}/sendMessage",
+                TELEGRAM_URL, self.bot_token
+            ))
+            .timeout(time::Duration::from_secs(CONFIG.current().request_timeout))
+            .json(&req)
+            .send()
+            .await
+            .map_err(|e| ScoutyError::TelegramError(e.without_url().to_string()))?;
+        debug!("response {:?}", res.status());
+        let status = res.status();
+        let response = res
+            .json::<SendMessageResponse>()
+            .await
+            .map_err(|e| ScoutyError::TelegramError(e.without_url().to_string()))?;
+        if !response.ok {
+            return Err(ScoutyError::TelegramError(format!(
+                "{} ({})",
+                response.description, status
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Notifier for Telegram {
+    fn name(&self) -> &str {
+        "Telegram"
+    }
+
+    fn send<'a>(
+        &'a self,
+        notification: &'a Notification,
+    ) -> BoxFuture<'a, Result<(), ScoutyError>> {
+        Box::pin(async move {
+            let text = sanitize(&notification.formatted_message);
+            for message in split_message(&text, TELEGRAM_MAX_MESSAGE_LENGTH) {
+                self.send_message(&message).await?;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_sanitizes_the_matrix_formatted_message() {
+        assert_eq!(
+            sanitize("<br/>🤖 <code>scouty v0.6.1</code><br/><b>New session</b> <i>100</i><br/><br/>"),
+            "🤖 <code>scouty v0.6.1</code>\n<b>New session</b> <i>100</i>"
+        );
+        assert_eq!(
+            sanitize("<table><tr><th>Stash</th><th>Active</th></tr><tr><td><a href=\"https://polkadot.subscan.io/validator/5G\">Alice</a></td><td>✅</td></tr></table>"),
+            "Stash | Active\n<a href=\"https://polkadot.subscan.io/validator/5G\">Alice</a> | ✅"
+        );
+    }
+
+    #[test]
+    fn it_splits_long_messages_by_lines() {
+        assert_eq!(
+            split_message("first line\nsecond line\nthird", 22),
+            vec!["first line\nsecond line", "third"]
+        );
+        assert_eq!(
+            split_message("abcdefgh\nij", 3),
+            vec!["abc", "def", "gh", "ij"]
+        );
+        assert_eq!(split_message("short", 4096), vec!["short"]);
+        assert!(split_message("", 4096).is_empty());
+    }
+}
@@ -0,0 +1,291 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::config::CONFIG;
+use crate::errors::ScoutyError;
+use serde::Deserialize;
+use std::{collections::BTreeMap, fs, sync::OnceLock};
+
+pub const TEMPLATE_INIT: &str = "init";
+pub const TEMPLATE_NEW_SESSION: &str = "new_session";
+pub const TEMPLATE_ACTIVE_NEXT_ERA: &str = "active_next_era";
+pub const TEMPLATE_INACTIVE_NEXT_ERA: &str = "inactive_next_era";
+pub const TEMPLATE_ERA_PAID: &str = "era_paid";
+pub const TEMPLATE_SLASH: &str = "slash";
+pub const TEMPLATE_SLASH_SUMMARY: &str = "slash_summary";
+pub const TEMPLATE_OFFENCE: &str = "offence";
+pub const TEMPLATE_CHILL: &str = "chill";
+pub const TEMPLATE_OFFLINE: &str = "offline";
+pub const TEMPLATE_OFFLINE_RISK: &str = "offline_risk";
+pub const TEMPLATE_SESSION_KEYS: &str = "session_keys";
+pub const TEMPLATE_VALIDATOR_PREFS: &str = "validator_prefs";
+pub const TEMPLATE_COMMISSION_POLICY: &str = "commission_policy";
+pub const TEMPLATE_VALIDATOR_DISABLED: &str = "validator_disabled";
+pub const TEMPLATE_PAYEE_CHANGED: &str = "payee_changed";
+pub const TEMPLATE_LOW_BALANCE: &str = "low_balance";
+pub const TEMPLATE_CATCH_UP: &str = "catch_up";
+pub const TEMPLATE_ERA_DIGEST: &str = "era_digest";
+pub const TEMPLATE_NOMINATORS: &str = "nominators";
+pub const TEMPLATE_PAYOUT: &str = "payout";
+pub const TEMPLATE_UNCLAIMED_ERAS: &str = "unclaimed_eras";
+pub const TEMPLATE_DISPUTE: &str = "dispute";
+pub const TEMPLATE_REFERENDA: &str = "referenda";
+pub const TEMPLATE_NODE_HEALTH: &str = "node_health";
+pub const TEMPLATE_FINALITY: &str = "finality";
+pub const TEMPLATE_RUNTIME_UPGRADE: &str = "runtime_upgrade";
+pub const TEMPLATE_ERROR_HOLD: &str = "error_hold";
+pub const TEMPLATE_SHUTDOWN: &str = "shutdown";
+
+/// Variables available in the templates of every event reported
+pub const REPORT_VARIABLES: [&str; 5] =
+    ["version", "chain", "era", "session", "validators"];
+
+/// Variables available in the templates rendered for every stash of an event reported
+pub const VALIDATOR_VARIABLES: [&str; 8] = [
+    "stash",
+    "identity",
+    "display_name",
+    "explorer_url",
+    "is_active",
+    "is_queued",
+    "inactive_next_era_reason",
+    "hook_output",
+];
+
+/// Templates that can be defined, with the variables available to each of them
+const TEMPLATE_VARIABLES: [(&str, &[&str]); 29] = [
+    (TEMPLATE_INIT, &REPORT_VARIABLES),
+    (TEMPLATE_NEW_SESSION, &REPORT_VARIABLES),
+    (TEMPLATE_ACTIVE_NEXT_ERA, &REPORT_VARIABLES),
+    (TEMPLATE_INACTIVE_NEXT_ERA, &REPORT_VARIABLES),
+    (TEMPLATE_ERA_PAID, &REPORT_VARIABLES),
+    (TEMPLATE_SLASH, &REPORT_VARIABLES),
+    (TEMPLATE_SLASH_SUMMARY, &REPORT_VARIABLES),
+    (TEMPLATE_OFFENCE, &REPORT_VARIABLES),
+    (TEMPLATE_CHILL, &REPORT_VARIABLES),
+    (TEMPLATE_OFFLINE, &REPORT_VARIABLES),
+    (TEMPLATE_OFFLINE_RISK, &REPORT_VARIABLES),
+    (TEMPLATE_SESSION_KEYS, &REPORT_VARIABLES),
+    (TEMPLATE_VALIDATOR_PREFS, &REPORT_VARIABLES),
+    (TEMPLATE_COMMISSION_POLICY, &REPORT_VARIABLES),
+    (TEMPLATE_VALIDATOR_DISABLED, &REPORT_VARIABLES),
+    (TEMPLATE_PAYEE_CHANGED, &REPORT_VARIABLES),
+    (TEMPLATE_LOW_BALANCE, &REPORT_VARIABLES),
+    (TEMPLATE_CATCH_UP, &REPORT_VARIABLES),
+    (TEMPLATE_ERA_DIGEST, &REPORT_VARIABLES),
+    (TEMPLATE_NOMINATORS, &REPORT_VARIABLES),
+    (TEMPLATE_PAYOUT, &REPORT_VARIABLES),
+    (TEMPLATE_UNCLAIMED_ERAS, &REPORT_VARIABLES),
+    (TEMPLATE_DISPUTE, &REPORT_VARIABLES),
+    (TEMPLATE_REFERENDA, &REPORT_VARIABLES),
+    (TEMPLATE_NODE_HEALTH, &REPORT_VARIABLES),
+    (TEMPLATE_FINALITY, &REPORT_VARIABLES),
+    (TEMPLATE_RUNTIME_UPGRADE, &REPORT_VARIABLES),
+    (TEMPLATE_ERROR_HOLD, &["chain", "error", "error_interval"]),
+    (TEMPLATE_SHUTDOWN, &["chain"]),
+];
+
+static TEMPLATES: OnceLock<Templates> = OnceLock::new();
+
+/// Template of the matrix messages of an event, variants not defined fall back to the
+/// built-in messages
+#[derive(Debug, Deserialize, Default, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Template {
+    pub message: Option<String>,
+    pub formatted_message: Option<String>,
+    /// Rendered for every stash and joined into the `{validators}` variable
+    pub validator: Option<String>,
+    pub formatted_validator: Option<String>,
+}
+
+pub type Variables = BTreeMap<&'static str, String>;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Templates(BTreeMap<String, Template>);
+
+impl Templates {
+    /// Parse and verify the templates, so that a bad template fails at startup
+    fn parse(data: &str) -> Result<Templates, ScoutyError> {
+        let templates: BTreeMap<String, Template> = serde_json::from_str(data)
+            .map_err(|e| ScoutyError::TemplateInvalid(e.to_string()))?;
+        for (key, template) in templates.iter() {
+            let variables = TEMPLATE_VARIABLES
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, variables)| *variables)
+                .ok_or_else(|| {
+                    ScoutyError::TemplateInvalid(format!("{} is not a known event", key))
+                })?;
+            for (variant, text, variables) in [
+                ("message", &template.message, variables),
+                ("formatted_message", &template.formatted_message, variables),
+                ("validator", &template.validator, &VALIDATOR_VARIABLES[..]),
+                (
+                    "formatted_validator",
+                    &template.formatted_validator,
+                    &VALIDATOR_VARIABLES[..],
+                ),
+            ] {
+                if let Some(text) = text {
+                    verify(text, variables).map_err(|e| {
+                        ScoutyError::TemplateInvalid(format!("{}.{} {}", key, variant, e))
+                    })?;
+                }
+            }
+        }
+        Ok(Templates(templates))
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Template> {
+        self.0.get(key)
+    }
+
+    /// Render the message and formatted message of the event, or return the built-in
+    /// messages if the template is not defined
+    pub fn render_or(
+        &self,
+        key: &str,
+        variables: &Variables,
+        message: &str,
+        formatted_message: &str,
+    ) -> (String, String) {
+        let template = self.get(key).cloned().unwrap_or_default();
+        (
+            template
+                .message
+                .map_or(message.to_string(), |t| render(&t, variables)),
+            template
+                .formatted_message
+                .map_or(formatted_message.to_string(), |t| render(&t, variables)),
+        )
+    }
+}
+
+/// Load the templates defined by `templates_path`, no templates are loaded if the
+/// path is not defined
+pub fn init() -> Result<(), ScoutyError> {
+    let templates = if CONFIG.current().templates_path.is_empty() {
+        Templates::default()
+    } else {
+        let data = fs::read_to_string(&CONFIG.current().templates_path).map_err(|e| {
+            ScoutyError::TemplateInvalid(format!(
+                "{} {}",
+                CONFIG.current().templates_path,
+                e
+            ))
+        })?;
+        Templates::parse(&data)?
+    };
+    let _ = TEMPLATES.set(templates);
+    Ok(())
+}
+
+/// Returns the templates loaded at startup
+pub fn templates() -> &'static Templates {
+    TEMPLATES.get_or_init(Templates::default)
+}
+
+/// Verify that the braces are balanced and that every variable is available
+fn verify(template: &str, variables: &[&str]) -> Result<(), String> {
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        if rest[..start].contains('}') {
+            return Err("has an unopened '}'".to_string());
+        }
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| "has an unclosed '{'".to_string())?;
+        let name = &rest[start + 1..start + end];
+        if !variables.contains(&name) {
+            return Err(format!(
+                "has an unknown variable {{{}}}, available: {}",
+                name,
+                variables.join(", ")
+            ));
+        }
+        rest = &rest[start + end + 1..];
+    }
+    if rest.contains('}') {
+        return Err("has an unopened '}'".to_string());
+    }
+    Ok(())
+}
+
+/// Replace every `{variable}` of the template by its value
+pub fn render(template: &str, variables: &Variables) -> String {
+    variables
+        .iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), value)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_renders_the_variables() {
+        let mut variables = Variables::new();
+        variables.insert("chain", "Polkadot".to_string());
+        variables.insert("era", "1234".to_string());
+        assert_eq!(
+            render("{chain} -> era {era} {era}", &variables),
+            "Polkadot -> era 1234 1234"
+        );
+    }
+
+    #[test]
+    fn it_falls_back_to_the_built_in_messages() {
+        let templates =
+            Templates::parse(r#"{ "shutdown": { "message": "{chain} bot stopped" } }"#)
+                .unwrap();
+        let mut variables = Variables::new();
+        variables.insert("chain", "Kusama".to_string());
+        assert_eq!(
+            templates.render_or(TEMPLATE_SHUTDOWN, &variables, "bye", "<b>bye</b>"),
+            ("Kusama bot stopped".to_string(), "<b>bye</b>".to_string())
+        );
+        assert_eq!(
+            templates.render_or(TEMPLATE_ERROR_HOLD, &variables, "hold", "<b>hold</b>"),
+            ("hold".to_string(), "<b>hold</b>".to_string())
+        );
+    }
+
+    #[test]
+    fn it_rejects_the_bad_templates() {
+        assert!(Templates::parse(r#"{ "new_sesion": { "message": "" } }"#).is_err());
+        assert!(Templates::parse(r#"{ "new_session": { "mesage": "" } }"#).is_err());
+        assert!(
+            Templates::parse(r#"{ "new_session": { "message": "{foo}" } }"#).is_err()
+        );
+        assert!(Templates::parse(r#"{ "new_session": { "message": "{era" } }"#).is_err());
+        assert!(Templates::parse(r#"{ "new_session": { "message": "era}" } }"#).is_err());
+        assert!(
+            Templates::parse(r#"{ "new_session": { "validator": "{era}" } }"#).is_err()
+        );
+        assert!(Templates::parse(
+            r#"{ "new_session": { "message": "{era}\n{validators}", "validator": "{identity}" } }"#
+        )
+        .is_ok());
+    }
+}
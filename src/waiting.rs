@@ -0,0 +1,161 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::BTreeMap;
+use subxt::utils::AccountId32;
+
+/// Position of a stash in the validators waiting to be elected, ordered by approval
+/// stake (own stake plus the stake of every nominator that nominates the stash),
+/// compared with the minimum stake elected in the active era
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct WaitingPosition {
+    pub position: u32,
+    pub waiting_count: u32,
+    pub approval_stake: u128,
+    pub min_elected_stake: u128,
+    /// Gap to the minimum elected stake in the previous era, if the stash was waiting
+    pub previous_gap: Option<u128>,
+}
+
+impl WaitingPosition {
+    /// Returns the stake missing to reach the minimum elected stake
+    pub fn gap(&self) -> u128 {
+        self.min_elected_stake.saturating_sub(self.approval_stake)
+    }
+
+    /// Returns the difference of the gap with the previous era, negative if it shrank
+    pub fn gap_delta(&self) -> Option<i128> {
+        self.previous_gap
+            .map(|previous| self.gap() as i128 - previous as i128)
+    }
+
+    /// Returns the eras estimated to close the gap, if it keeps shrinking at the pace
+    /// of the previous era
+    pub fn estimated_eras(&self) -> Option<u32> {
+        match self.gap_delta() {
+            Some(delta) if delta < 0 && self.gap() > 0 => {
+                let shrink = delta.unsigned_abs();
+                Some(self.gap().div_ceil(shrink) as u32)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Returns the position (starting at 1) of the stash in the validators waiting, by
+/// approval stake in descending order
+pub fn waiting_position(
+    approvals: &BTreeMap<AccountId32, u128>,
+    stash: &AccountId32,
+) -> Option<u32> {
+    let approval_stake = approvals.get(stash)?;
+    let ahead = approvals
+        .iter()
+        .filter(|(other, stake)| {
+            *stake > approval_stake || (*stake == approval_stake && *other < stash)
+        })
+        .count();
+    Some(ahead as u32 + 1)
+}
+
+/// Gap to the minimum elected stake of the stashes predefined waiting in the last era
+/// seen, kept to be compared with the gap of the next era
+#[derive(Debug, Default)]
+pub struct WaitingRecords {
+    gaps: BTreeMap<AccountId32, (u32, u128)>,
+}
+
+impl WaitingRecords {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Keep the gap of the stash in the era and return the gap kept for the previous
+    /// era, if any
+    pub fn insert(
+        &mut self,
+        era_index: u32,
+        stash: &AccountId32,
+        gap: u128,
+    ) -> Option<u128> {
+        match self.gaps.insert(stash.clone(), (era_index, gap)) {
+            Some((previous_era_index, previous))
+                if previous_era_index + 1 == era_index =>
+            {
+                Some(previous)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_returns_the_waiting_position_by_approval_stake() {
+        let approvals: BTreeMap<AccountId32, u128> =
+            [(1, 300), (2, 500), (3, 300), (4, 100)]
+                .iter()
+                .map(|(id, stake)| (AccountId32([*id; 32]), *stake))
+                .collect();
+
+        assert_eq!(waiting_position(&approvals, &AccountId32([2; 32])), Some(1));
+        assert_eq!(waiting_position(&approvals, &AccountId32([1; 32])), Some(2));
+        assert_eq!(waiting_position(&approvals, &AccountId32([3; 32])), Some(3));
+        assert_eq!(waiting_position(&approvals, &AccountId32([4; 32])), Some(4));
+        assert_eq!(waiting_position(&approvals, &AccountId32([5; 32])), None);
+    }
+
+    #[test]
+    fn it_compares_the_gap_with_the_previous_era() {
+        let stash = AccountId32([1; 32]);
+        let mut records = WaitingRecords::new();
+
+        let mut position = WaitingPosition {
+            position: 3,
+            waiting_count: 10,
+            approval_stake: 700,
+            min_elected_stake: 1000,
+            previous_gap: records.insert(10, &stash, 300),
+        };
+        assert_eq!(position.gap(), 300);
+        assert_eq!(position.gap_delta(), None);
+
+        position.approval_stake = 900;
+        position.previous_gap = records.insert(11, &stash, position.gap());
+        assert_eq!(position.gap_delta(), Some(-200));
+        assert_eq!(position.estimated_eras(), Some(1));
+
+        position.previous_gap = Some(150);
+        assert_eq!(position.estimated_eras(), Some(2));
+        position.previous_gap = Some(50);
+        assert_eq!(position.estimated_eras(), None);
+
+        // Above the minimum elected stake there is no gap
+        position.approval_stake = 1200;
+        assert_eq!(position.gap(), 0);
+
+        // Only consecutive eras are compared
+        assert_eq!(records.insert(13, &stash, 0), None);
+    }
+}
@@ -0,0 +1,220 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::backoff::Backoff;
+use crate::config::CONFIG;
+use crate::errors::ScoutyError;
+use crate::matrix::Severity;
+use crate::notifier::{Notification, Notifier};
+use async_std::task;
+use futures::future::BoxFuture;
+use hmac::{Hmac, Mac};
+use log::{debug, warn};
+use serde::Serialize;
+use sha2::Sha256;
+use std::time;
+
+pub const WEBHOOK_PAYLOAD_VERSION: u32 = 1;
+
+// Header with the HMAC-SHA256 signature of the body, if a webhook secret is defined
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-Scouty-Signature";
+
+// Number of times a webhook request that failed is retried
+const WEBHOOK_MAX_RETRIES: u32 = 2;
+
+// Backoff intervals (in seconds) between webhook retries
+const WEBHOOK_RETRY_INITIAL_INTERVAL: u64 = 1;
+const WEBHOOK_RETRY_MAX_INTERVAL: u64 = 10;
+
+/// JSON body posted to the webhook for every event reported
+#[derive(Serialize, Debug)]
+struct WebhookPayload<'a> {
+    version: u32,
+    event: &'a str,
+    chain: &'a str,
+    stashes: &'a [String],
+    session_index: Option<u32>,
+    era_index: Option<u32>,
+    severity: &'static str,
+    message: &'a str,
+    timestamp: String,
+}
+
+impl<'a> From<&'a Notification> for WebhookPayload<'a> {
+    fn from(notification: &'a Notification) -> Self {
+        WebhookPayload {
+            version: WEBHOOK_PAYLOAD_VERSION,
+            event: &notification.event,
+            chain: &notification.chain,
+            stashes: &notification.stashes,
+            session_index: notification.session_index,
+            era_index: notification.era_index,
+            severity: match notification.severity {
+                Severity::Info => "info",
+                Severity::Critical => "critical",
+            },
+            message: &notification.message,
+            timestamp: notification.timestamp.to_rfc3339(),
+        }
+    }
+}
+
+/// Returns the hex encoded HMAC-SHA256 signature of the body
+fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC can take key of any size");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Posts a JSON body for every event reported to the webhook url defined
+#[derive(Debug, Clone)]
+pub struct Webhook {
+    client: reqwest::Client,
+    url: String,
+    token: String,
+    secret: String,
+}
+
+impl Webhook {
+    pub fn new() -> Self {
+        let config = CONFIG.current();
+        Webhook {
+            client: reqwest::Client::new(),
+            url: config.webhook_url.clone(),
+            token: config.webhook_token.clone(),
+            secret: config.webhook_secret.clone(),
+        }
+    }
+
+    async fn post(&self, body: &[u8]) -> Result<(), ScoutyError> {
+        let mut req = self
+            .client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .timeout(time::Duration::from_secs(CONFIG.current().request_timeout))
+            .body(body.to_vec());
+        if !self.token.is_empty() {
+            req = req.bearer_auth(&self.token);
+        }
+        if !self.secret.is_empty() {
+            req = req.header(WEBHOOK_SIGNATURE_HEADER, sign(&self.secret, body));
+        }
+        let res = req
+            .send()
+            .await
+            .map_err(|e| ScoutyError::WebhookError(e.to_string()))?;
+        debug!("response {:?}", res);
+        if !res.status().is_success() {
+            return Err(ScoutyError::WebhookError(format!(
+                "responded with status {}",
+                res.status()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Post the body, retrying a couple of times with backoff if the request fails
+    async fn post_with_retries(self, body: Vec<u8>) {
+        let mut backoff =
+            Backoff::new(WEBHOOK_RETRY_INITIAL_INTERVAL, WEBHOOK_RETRY_MAX_INTERVAL);
+        loop {
+            match self.post(&body).await {
+                Ok(()) => return,
+                Err(e) if backoff.attempt() < WEBHOOK_MAX_RETRIES => {
+                    let delay = backoff.next_delay();
+                    warn!(
+                        "Webhook message not sent! {} -> next retry in {:.1}s",
+                        e,
+                        delay.as_secs_f64()
+                    );
+                    task::sleep(delay).await;
+                }
+                Err(e) => {
+                    warn!(
+                        "Webhook message dropped after {} retries! {}",
+                        WEBHOOK_MAX_RETRIES, e
+                    );
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Webhook messages are posted in the background, so that retries do not hold the
+/// processing of the session
+impl Notifier for Webhook {
+    fn name(&self) -> &str {
+        "Webhook"
+    }
+
+    fn send<'a>(
+        &'a self,
+        notification: &'a Notification,
+    ) -> BoxFuture<'a, Result<(), ScoutyError>> {
+        Box::pin(async move {
+            let body = serde_json::to_vec(&WebhookPayload::from(notification))?;
+            task::spawn(self.clone().post_with_retries(body));
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_serializes_the_payload_of_the_notification() {
+        let mut notification = Notification::new(
+            "new_session",
+            "Polkadot",
+            Severity::Critical,
+            "Session 100",
+            "<b>Session 100</b>",
+        );
+        notification.stashes = vec!["5GrwvaEF".to_string()];
+        notification.session_index = Some(100);
+        notification.era_index = Some(10);
+        let payload: serde_json::Value =
+            serde_json::to_value(WebhookPayload::from(&notification)).unwrap();
+        assert_eq!(payload["version"], WEBHOOK_PAYLOAD_VERSION);
+        assert_eq!(payload["event"], "new_session");
+        assert_eq!(payload["chain"], "Polkadot");
+        assert_eq!(payload["stashes"][0], "5GrwvaEF");
+        assert_eq!(payload["session_index"], 100);
+        assert_eq!(payload["era_index"], 10);
+        assert_eq!(payload["severity"], "critical");
+        assert_eq!(payload["message"], "Session 100");
+        assert!(payload.get("formatted_message").is_none());
+        assert_eq!(payload["timestamp"], notification.timestamp.to_rfc3339());
+    }
+
+    #[test]
+    fn it_signs_the_body_with_the_secret() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
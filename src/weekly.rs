@@ -0,0 +1,372 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::report::Network;
+use chrono::{DateTime, Datelike, Duration, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Weekday and time, in the local time of the timezone defined, at which the weekly
+/// report is sent
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeeklySchedule {
+    weekday: Weekday,
+    time: NaiveTime,
+    timezone: Tz,
+}
+
+impl WeeklySchedule {
+    /// Parse the weekday and time (e.g. mon 09:00) in the timezone defined (e.g.
+    /// Europe/Lisbon)
+    pub fn parse(schedule: &str, timezone: &str) -> Result<Self, String> {
+        let (weekday, time) = schedule.trim().split_once(' ').ok_or_else(|| {
+            format!("{} is not a weekday and time (e.g. mon 09:00)", schedule)
+        })?;
+        let weekday = weekday
+            .trim()
+            .parse::<Weekday>()
+            .map_err(|_| format!("{} is not a weekday (e.g. mon)", weekday.trim()))?;
+        let time = NaiveTime::parse_from_str(time.trim(), "%H:%M")
+            .map_err(|_| format!("{} is not a time (e.g. 09:00)", time.trim()))?;
+        let timezone = timezone.trim().parse::<Tz>().map_err(|_| {
+            format!("{} is not a timezone (e.g. Europe/Lisbon)", timezone)
+        })?;
+        Ok(Self {
+            weekday,
+            time,
+            timezone,
+        })
+    }
+
+    /// Returns the last time the weekly report was due, at or before the time given.
+    /// A time skipped by a DST transition is taken as if it was UTC.
+    pub fn last_due(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let local = now.with_timezone(&self.timezone);
+        let days = (7 + local.weekday().num_days_from_monday()
+            - self.weekday.num_days_from_monday())
+            % 7;
+        let mut date = local.date_naive() - Duration::days(days.into());
+        loop {
+            let naive = date.and_time(self.time);
+            let due = self
+                .timezone
+                .from_local_datetime(&naive)
+                .earliest()
+                .unwrap_or_else(|| self.timezone.from_utc_datetime(&naive))
+                .with_timezone(&Utc);
+            if due <= now {
+                return due;
+            }
+            date -= Duration::days(7);
+        }
+    }
+}
+
+impl std::fmt::Display for WeeklySchedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.weekday.to_string().to_lowercase(),
+            self.time.format("%H:%M"),
+            self.timezone
+        )
+    }
+}
+
+/// Stats of a stash accumulated since the last weekly report
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct StashWeeklyStats {
+    /// Eras paid since the last weekly report
+    pub eras: u32,
+    pub eras_active: u32,
+    pub points: u32,
+    /// Estimated rewards of the stash, the nominators share included
+    pub reward: u128,
+    /// Sum of the era points ranks, to average the rank of the eras ranked
+    pub rank_sum: u32,
+    pub ranked_eras: u32,
+    /// Critical notifications raised
+    pub alerts: u32,
+}
+
+impl StashWeeklyStats {
+    /// Returns the average rank by era points of the eras ranked
+    pub fn average_rank(&self) -> Option<f64> {
+        if self.ranked_eras == 0 {
+            return None;
+        }
+        Some(self.rank_sum as f64 / self.ranked_eras as f64)
+    }
+}
+
+/// Stats of the stashes accumulated since the last weekly report, kept in the state
+/// file so that a restart mid-week does not start over the accumulation
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct WeeklyStats {
+    /// Start of the accumulation, in seconds since the epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<i64>,
+    /// Seconds scouty has been running since the start of the accumulation
+    #[serde(default)]
+    pub uptime: u64,
+    /// Stats by the stash address
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub stashes: BTreeMap<String, StashWeeklyStats>,
+}
+
+impl WeeklyStats {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Track the era paid of the stash
+    pub fn track_era(
+        &mut self,
+        stash: &str,
+        is_active: bool,
+        points: u32,
+        reward: u128,
+        rank: Option<u32>,
+    ) {
+        let stats = self.stashes.entry(stash.to_string()).or_default();
+        stats.eras += 1;
+        if is_active {
+            stats.eras_active += 1;
+        }
+        stats.points += points;
+        stats.reward += reward;
+        if let Some(rank) = rank {
+            stats.rank_sum += rank;
+            stats.ranked_eras += 1;
+        }
+    }
+
+    /// Track a critical notification of the stash
+    pub fn track_alert(&mut self, stash: &str) {
+        self.stashes.entry(stash.to_string()).or_default().alerts += 1;
+    }
+
+    /// Track the seconds scouty has been running until the time given, the
+    /// accumulation starts with the first seconds tracked
+    pub fn track_uptime(&mut self, now: DateTime<Utc>, seconds: u64) {
+        if self.since.is_none() {
+            self.since = Some(now.timestamp() - seconds as i64);
+        }
+        self.uptime += seconds;
+    }
+
+    /// Returns the percentage of time scouty has been running since the start of the
+    /// accumulation, restarts and downtime included
+    pub fn uptime_percentage(&self, now: DateTime<Utc>) -> f64 {
+        let since = self.since.unwrap_or_else(|| now.timestamp());
+        let elapsed = (now.timestamp() - since).max(0) as f64;
+        if elapsed == 0.0 {
+            return 100.0;
+        }
+        (self.uptime as f64 * 100.0 / elapsed).min(100.0)
+    }
+
+    /// Returns the stats accumulated so far and start over the accumulation
+    pub fn take(&mut self, now: DateTime<Utc>) -> WeeklyStats {
+        std::mem::replace(
+            self,
+            WeeklyStats {
+                since: Some(now.timestamp()),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Returns the start of the accumulation, or the time given if not started yet
+    pub fn started_at(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        self.since
+            .and_then(|since| Utc.timestamp_opt(since, 0).single())
+            .unwrap_or(now)
+    }
+
+    /// Returns the plain and formatted message summarizing the stats of every stash,
+    /// the stashes are displayed by the names given or by their address otherwise
+    pub fn message(
+        &self,
+        schedule: &WeeklySchedule,
+        names: &BTreeMap<String, String>,
+        network: &Network,
+        now: DateTime<Utc>,
+    ) -> (String, String) {
+        let period = format!(
+            "{} - {}",
+            self.started_at(now).format("%Y-%m-%d %H:%M"),
+            now.format("%Y-%m-%d %H:%M UTC")
+        );
+        let uptime = format!("{:.1}%", self.uptime_percentage(now));
+        let lines = self
+            .stashes
+            .iter()
+            .map(|(stash, stats)| {
+                let name = names
+                    .get(stash)
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or(stash);
+                let rank = stats
+                    .average_rank()
+                    .map_or("-".to_string(), |rank| format!("#{:.1}", rank));
+                (
+                    name.to_string(),
+                    format!(
+                        "active {}/{} eras, {} points, ~{} rewards, average rank {}, {} alert(s)",
+                        stats.eras_active,
+                        stats.eras,
+                        stats.points,
+                        network.format_amount(stats.reward),
+                        rank,
+                        stats.alerts
+                    ),
+                )
+            })
+            .collect::<Vec<(String, String)>>();
+        let mut message = format!(
+            "Weekly report ({}) -> {}, scouty uptime {}",
+            schedule, period, uptime
+        );
+        let mut formatted_message = format!(
+            "<br/>🗓️ Weekly report (<code>{}</code>) -> {}, scouty uptime <b>{}</b><br/>",
+            schedule, period, uptime
+        );
+        if lines.is_empty() {
+            message.push_str(" -> no eras paid");
+            formatted_message.push_str("‣ No eras paid<br/>");
+        }
+        for (name, line) in lines.iter() {
+            message.push_str(&format!("\n{} -> {}", name, line));
+            formatted_message.push_str(&format!("‣ <b>{}</b> -> {}<br/>", name, line));
+        }
+        formatted_message.push_str("<br/>");
+        (message, formatted_message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(time)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn it_parses_the_weekly_schedule() {
+        let schedule = WeeklySchedule::parse("mon 09:00", "Europe/Lisbon").unwrap();
+        assert_eq!(schedule.to_string(), "mon 09:00 Europe/Lisbon");
+        let schedule = WeeklySchedule::parse("Friday 18:30", "UTC").unwrap();
+        assert_eq!(schedule.to_string(), "fri 18:30 UTC");
+        assert!(WeeklySchedule::parse("mon", "UTC").is_err());
+        assert!(WeeklySchedule::parse("someday 09:00", "UTC").is_err());
+        assert!(WeeklySchedule::parse("mon 9h", "UTC").is_err());
+        assert!(WeeklySchedule::parse("mon 09:00", "Europe/Nowhere").is_err());
+    }
+
+    #[test]
+    fn it_returns_the_last_time_due() {
+        // 2024-01-15 is a monday
+        let schedule = WeeklySchedule::parse("mon 09:00", "UTC").unwrap();
+        assert_eq!(
+            schedule.last_due(utc("2024-01-15T09:00:00Z")),
+            utc("2024-01-15T09:00:00Z")
+        );
+        assert_eq!(
+            schedule.last_due(utc("2024-01-15T08:59:00Z")),
+            utc("2024-01-08T09:00:00Z")
+        );
+        assert_eq!(
+            schedule.last_due(utc("2024-01-18T12:00:00Z")),
+            utc("2024-01-15T09:00:00Z")
+        );
+    }
+
+    #[test]
+    fn it_follows_the_dst_transitions() {
+        let schedule = WeeklySchedule::parse("mon 09:00", "Europe/Lisbon").unwrap();
+        // Winter time is UTC+0 and summer time is UTC+1
+        assert_eq!(
+            schedule.last_due(utc("2024-01-16T00:00:00Z")),
+            utc("2024-01-15T09:00:00Z")
+        );
+        assert_eq!(
+            schedule.last_due(utc("2024-07-16T00:00:00Z")),
+            utc("2024-07-15T08:00:00Z")
+        );
+    }
+
+    #[test]
+    fn it_accumulates_the_weekly_stats() {
+        let mut stats = WeeklyStats::default();
+        assert!(stats.is_empty());
+        stats.track_uptime(utc("2024-01-15T09:01:00Z"), 60);
+        stats.track_era("stash", true, 1200, 1_000, Some(10));
+        stats.track_era("stash", true, 800, 500, Some(20));
+        stats.track_era("stash", false, 0, 0, None);
+        stats.track_alert("stash");
+        let stash = stats.stashes.get("stash").unwrap();
+        assert_eq!((stash.eras_active, stash.eras), (2, 3));
+        assert_eq!((stash.points, stash.reward, stash.alerts), (2000, 1_500, 1));
+        assert_eq!(stash.average_rank(), Some(15.0));
+        assert_eq!(stats.uptime_percentage(utc("2024-01-15T09:02:00Z")), 50.0);
+
+        let taken = stats.take(utc("2024-01-22T09:00:00Z"));
+        assert_eq!(taken.stashes.len(), 1);
+        assert!(stats.stashes.is_empty());
+        assert_eq!(stats.since, Some(utc("2024-01-22T09:00:00Z").timestamp()));
+    }
+
+    #[test]
+    fn it_keeps_the_weekly_stats_between_restarts() {
+        let mut stats = WeeklyStats::default();
+        stats.track_uptime(utc("2024-01-15T09:01:00Z"), 60);
+        stats.track_era("stash", true, 1200, 1_000, Some(10));
+        let data = serde_json::to_string(&stats).unwrap();
+        assert_eq!(serde_json::from_str::<WeeklyStats>(&data).unwrap(), stats);
+    }
+
+    #[test]
+    fn it_summarizes_the_weekly_stats() {
+        let schedule = WeeklySchedule::parse("mon 09:00", "UTC").unwrap();
+        let network = Network {
+            name: "polkadot".to_string(),
+            token_symbol: "DOT".to_string(),
+            token_decimals: 10,
+        };
+        let mut stats = WeeklyStats::default();
+        stats.track_uptime(utc("2024-01-15T09:00:00Z"), 60);
+        stats.track_era("stash", true, 1200, 15_000_000_000_000, Some(10));
+        let mut names = BTreeMap::new();
+        names.insert("stash".to_string(), "Validator".to_string());
+        let (message, _) =
+            stats.message(&schedule, &names, &network, utc("2024-01-15T09:01:00Z"));
+        assert_eq!(
+            message,
+            "Weekly report (mon 09:00 UTC) -> 2024-01-15 08:59 - 2024-01-15 09:01 UTC, scouty uptime 50.0%\nValidator -> active 1/1 eras, 1200 points, ~1.5k DOT rewards, average rank #10.0, 0 alert(s)"
+        );
+    }
+}